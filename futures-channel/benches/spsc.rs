@@ -0,0 +1,108 @@
+#![feature(test)]
+
+extern crate test;
+use crate::test::Bencher;
+
+use {
+    futures::{
+        channel::{mpsc, spsc},
+        executor::block_on,
+        sink::SinkExt,
+        stream::StreamExt,
+        task::Poll,
+    },
+    futures_test::task::noop_context,
+    std::thread,
+};
+
+/// Send and receive 1000 items on a bounded mpsc channel, as a baseline
+#[bench]
+fn mpsc_uncontended(b: &mut Bencher) {
+    let mut cx = noop_context();
+    b.iter(|| {
+        let (mut tx, mut rx) = mpsc::channel(1000);
+
+        for i in 0..1000 {
+            tx.try_send(i).unwrap();
+        }
+        for i in 0..1000 {
+            assert_eq!(Poll::Ready(Some(i)), rx.poll_next_unpin(&mut cx));
+        }
+    })
+}
+
+/// Send and receive 1000 items on a spsc channel
+#[bench]
+fn spsc_uncontended(b: &mut Bencher) {
+    let mut cx = noop_context();
+    b.iter(|| {
+        let (mut tx, mut rx) = spsc::channel(1000);
+
+        for i in 0..1000 {
+            tx.try_send(i).unwrap();
+        }
+        for i in 0..1000 {
+            assert_eq!(Poll::Ready(Some(i)), rx.poll_next_unpin(&mut cx));
+        }
+    })
+}
+
+/// Alternate between parking the receiver and sending an item, on mpsc
+#[bench]
+fn mpsc_ping_pong(b: &mut Bencher) {
+    let mut cx = noop_context();
+    b.iter(|| {
+        let (mut tx, mut rx) = mpsc::channel(0);
+
+        for i in 0..1000 {
+            assert_eq!(Poll::Pending, rx.poll_next_unpin(&mut cx));
+            tx.try_send(i).unwrap();
+            assert_eq!(Poll::Ready(Some(i)), rx.poll_next_unpin(&mut cx));
+        }
+    })
+}
+
+/// Alternate between parking the receiver and sending an item, on spsc
+#[bench]
+fn spsc_ping_pong(b: &mut Bencher) {
+    let mut cx = noop_context();
+    b.iter(|| {
+        let (mut tx, mut rx) = spsc::channel(1);
+
+        for i in 0..1000 {
+            assert_eq!(Poll::Pending, rx.poll_next_unpin(&mut cx));
+            tx.try_send(i).unwrap();
+            assert_eq!(Poll::Ready(Some(i)), rx.poll_next_unpin(&mut cx));
+        }
+    })
+}
+
+/// Stream 10000 items from another thread through a bounded mpsc channel
+#[bench]
+fn mpsc_threaded(b: &mut Bencher) {
+    b.iter(|| {
+        let (mut tx, rx) = mpsc::channel(64);
+        let handle = thread::spawn(move || block_on(async {
+            for i in 0..10_000u32 {
+                tx.send(i).await.unwrap();
+            }
+        }));
+        assert_eq!(block_on(rx.fold(0, |n, _| async move { n + 1 })), 10_000);
+        handle.join().unwrap();
+    })
+}
+
+/// Stream 10000 items from another thread through a spsc channel
+#[bench]
+fn spsc_threaded(b: &mut Bencher) {
+    b.iter(|| {
+        let (mut tx, rx) = spsc::channel(64);
+        let handle = thread::spawn(move || block_on(async {
+            for i in 0..10_000u32 {
+                tx.send(i).await.unwrap();
+            }
+        }));
+        assert_eq!(block_on(rx.fold(0, |n, _| async move { n + 1 })), 10_000);
+        handle.join().unwrap();
+    })
+}
@@ -0,0 +1,368 @@
+//! A multi-producer, multi-consumer broadcast channel where every receiver
+//! sees every message.
+//!
+//! Messages are kept in a buffer of fixed capacity until all receivers have
+//! seen them. Sending never waits: when the buffer is full, the oldest
+//! message is overwritten, and receivers which did not see it yet report how
+//! many messages they missed with a [`Lagged`] error before continuing with
+//! the oldest message still available.
+//!
+//! New receivers are created with [`Sender::subscribe`], which starts at the
+//! next message sent, or by cloning a [`Receiver`], which starts at the same
+//! position as the original.
+//!
+//! When all [`Sender`]s have been dropped, receivers still get the messages
+//! that are buffered, after which their stream ends.
+
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// The transmission end of a broadcast channel.
+///
+/// This value is created by the [`channel`](channel) function.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The receiving end of a broadcast channel.
+///
+/// This value is created by the [`channel`](channel) function or the
+/// [`Sender::subscribe`](Sender::subscribe) method.
+#[must_use = "streams do nothing unless polled"]
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    // Position of the next message to receive.
+    pos: u64,
+    id: u64,
+    terminated: bool,
+}
+
+// The channel never projects Pin to the inner T
+impl<T> Unpin for Sender<T> {}
+impl<T> Unpin for Receiver<T> {}
+
+/// The error returned by [`Sender::send`](Sender::send) when there are no
+/// receivers left, containing the message that could not be sent.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+/// The error yielded by a [`Receiver`](Receiver) which fell behind the
+/// sender, containing the number of messages it missed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lagged(pub u64);
+
+/// The error returned by [`Receiver::try_recv`](Receiver::try_recv).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// No message is available right now.
+    Empty,
+    /// All senders have been dropped and all messages have been received.
+    Closed,
+    /// The receiver fell behind the sender and missed this many messages.
+    Lagged(u64),
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    capacity: usize,
+}
+
+struct State<T> {
+    queue: VecDeque<Slot<T>>,
+    // Position of the first message in `queue`.
+    head: u64,
+    num_senders: usize,
+    num_receivers: usize,
+    next_id: u64,
+    wakers: HashMap<u64, Waker>,
+}
+
+struct Slot<T> {
+    value: T,
+    // Number of receivers which did not receive this message yet.
+    remaining: usize,
+}
+
+/// Creates a bounded broadcast channel for sending values to any number of
+/// receivers.
+///
+/// Up to `capacity` messages are buffered for receivers which have not seen
+/// them yet, see the [module documentation](self) for what happens to
+/// receivers which fall further behind.
+///
+/// # Panics
+///
+/// This function panics if `capacity` is zero.
+pub fn channel<T: Clone>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "broadcast channel capacity must be non-zero");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::with_capacity(capacity),
+            head: 0,
+            num_senders: 1,
+            num_receivers: 1,
+            next_id: 1,
+            wakers: HashMap::new(),
+        }),
+        capacity,
+    });
+    let rx = Receiver {
+        shared: shared.clone(),
+        pos: 0,
+        id: 0,
+        terminated: false,
+    };
+    (Sender { shared }, rx)
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl<T> State<T> {
+    // Drops messages from the front of the queue which every receiver has seen.
+    fn pop_received(&mut self) {
+        while self.queue.front().map(|slot| slot.remaining == 0).unwrap_or(false) {
+            self.queue.pop_front();
+            self.head += 1;
+        }
+    }
+
+    fn wake_all(&mut self) {
+        for (_, waker) in self.wakers.drain() {
+            waker.wake();
+        }
+    }
+
+    fn new_receiver(&mut self, shared: Arc<Shared<T>>, pos: u64) -> Receiver<T> {
+        let skip = pos.saturating_sub(self.head) as usize;
+        for slot in self.queue.iter_mut().skip(skip) {
+            slot.remaining += 1;
+        }
+        self.num_receivers += 1;
+        let id = self.next_id;
+        self.next_id += 1;
+        Receiver { shared, pos, id, terminated: false }
+    }
+}
+
+impl<T> Sender<T> {
+    /// Sends a message to all receivers.
+    ///
+    /// This never waits. If the buffer is full, the oldest message is
+    /// dropped and receivers which have not seen it yet will report a
+    /// [`Lagged`] error.
+    ///
+    /// An error containing the message is returned if there are no
+    /// receivers.
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        let mut state = self.shared.lock();
+        if state.num_receivers == 0 {
+            return Err(SendError(msg));
+        }
+        if state.queue.len() == self.shared.capacity {
+            state.queue.pop_front();
+            state.head += 1;
+        }
+        let remaining = state.num_receivers;
+        state.queue.push_back(Slot { value: msg, remaining });
+        state.wake_all();
+        Ok(())
+    }
+
+    /// Creates a new receiver which receives all messages sent after this
+    /// call.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let mut state = self.shared.lock();
+        let pos = state.head + state.queue.len() as u64;
+        state.new_receiver(self.shared.clone(), pos)
+    }
+
+    /// Returns the number of receivers of this channel.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.lock().num_receivers
+    }
+
+    /// Returns whether the senders send to the same receivers.
+    pub fn same_channel(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.shared, &other.shared)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        self.shared.lock().num_senders += 1;
+        Sender { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.num_senders -= 1;
+        if state.num_senders == 0 {
+            state.wake_all();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender")
+            .field("capacity", &self.shared.capacity)
+            .finish()
+    }
+}
+
+impl<T: Clone> Receiver<T> {
+    /// Attempts to receive the next message without waiting.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        match self.recv_inner(None) {
+            Poll::Ready(Some(Ok(msg))) => Ok(msg),
+            Poll::Ready(Some(Err(Lagged(n)))) => Err(TryRecvError::Lagged(n)),
+            Poll::Ready(None) => Err(TryRecvError::Closed),
+            Poll::Pending => Err(TryRecvError::Empty),
+        }
+    }
+
+    fn recv_inner(&mut self, cx: Option<&mut Context<'_>>) -> Poll<Option<Result<T, Lagged>>> {
+        let mut state = self.shared.lock();
+        if self.pos < state.head {
+            let missed = state.head - self.pos;
+            self.pos = state.head;
+            return Poll::Ready(Some(Err(Lagged(missed))));
+        }
+
+        let idx = (self.pos - state.head) as usize;
+        if idx < state.queue.len() {
+            self.pos += 1;
+            let slot = &mut state.queue[idx];
+            slot.remaining -= 1;
+            let msg = if idx == 0 && slot.remaining == 0 {
+                state.head += 1;
+                let msg = state.queue.pop_front().unwrap().value;
+                state.pop_received();
+                msg
+            } else {
+                slot.value.clone()
+            };
+            return Poll::Ready(Some(Ok(msg)));
+        }
+
+        if state.num_senders == 0 {
+            return Poll::Ready(None);
+        }
+        if let Some(cx) = cx {
+            state.wakers.insert(self.id, cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Returns the number of messages this receiver has not received yet.
+    pub fn len(&self) -> usize {
+        let state = self.shared.lock();
+        (state.head + state.queue.len() as u64).saturating_sub(self.pos) as usize
+    }
+
+    /// Returns `true` if this receiver has received all buffered messages.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Clone> Stream for Receiver<T> {
+    type Item = Result<T, Lagged>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+        let item = self.recv_inner(Some(cx));
+        if let Poll::Ready(None) = item {
+            self.terminated = true;
+        }
+        item
+    }
+}
+
+impl<T: Clone> FusedStream for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    /// Creates a new receiver which receives the same messages as this one,
+    /// starting at the same position.
+    fn clone(&self) -> Receiver<T> {
+        let mut state = self.shared.lock();
+        let mut rx = state.new_receiver(self.shared.clone(), self.pos);
+        rx.terminated = self.terminated;
+        rx
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        let skip = self.pos.saturating_sub(state.head) as usize;
+        for slot in state.queue.iter_mut().skip(skip) {
+            slot.remaining -= 1;
+        }
+        state.pop_received();
+        state.num_receivers -= 1;
+        state.wakers.remove(&self.id);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("pos", &self.pos)
+            .field("terminated", &self.terminated)
+            .finish()
+    }
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SendError").finish()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "send failed because there are no receivers")
+    }
+}
+
+impl<T> Error for SendError<T> {}
+
+impl fmt::Display for Lagged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "receiver lagged behind and missed {} messages", self.0)
+    }
+}
+
+impl Error for Lagged {}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => write!(f, "receive failed because channel is empty"),
+            TryRecvError::Closed => write!(f, "receive failed because channel is closed"),
+            TryRecvError::Lagged(n) => write!(f, "receiver lagged behind and missed {} messages", n),
+        }
+    }
+}
+
+impl Error for TryRecvError {}
@@ -36,10 +36,18 @@ cfg_target_has_atomic! {
     #[cfg(feature = "alloc")]
     extern crate alloc;
 
+    #[cfg(feature = "std")]
+    pub mod broadcast;
     #[cfg(feature = "alloc")]
     mod lock;
     #[cfg(feature = "std")]
+    pub mod mpmc;
+    #[cfg(feature = "std")]
     pub mod mpsc;
     #[cfg(feature = "alloc")]
     pub mod oneshot;
+    #[cfg(feature = "std")]
+    pub mod spsc;
+    #[cfg(feature = "std")]
+    pub mod watch;
 }
@@ -0,0 +1,406 @@
+//! A multi-producer, multi-consumer queue for distributing values among
+//! asynchronous tasks.
+//!
+//! Unlike a [`broadcast`](crate::broadcast) channel, each value is delivered
+//! to exactly one [`Receiver`]: receivers compete for the queued values,
+//! which makes this channel a simple work queue for a set of worker tasks.
+//! Both halves can be cloned.
+//!
+//! The channel is bounded: once `buffer` values are queued, senders wait for
+//! a receiver to take one. When all senders have been dropped, receivers
+//! still get the queued values, after which their streams end. When all
+//! receivers have been dropped, sending fails.
+//!
+//! The error types are shared with the [`mpsc`](crate::mpsc) module.
+
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub use crate::mpsc::{SendError, TryRecvError, TrySendError};
+use crate::mpsc::SendErrorKind;
+
+/// The transmission end of a mpmc channel.
+///
+/// This value is created by the [`channel`](channel) function.
+pub struct Sender<T> {
+    shared: Option<Arc<Shared<T>>>,
+    id: u64,
+    // Whether this sender holds a slot reserved by `poll_ready`.
+    reserved: bool,
+}
+
+/// The receiving end of a mpmc channel.
+///
+/// This value is created by the [`channel`](channel) function.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    id: u64,
+    terminated: bool,
+}
+
+// The channel never projects Pin to the inner T
+impl<T> Unpin for Sender<T> {}
+impl<T> Unpin for Receiver<T> {}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    buffer: usize,
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    // Number of slots reserved by `poll_ready`.
+    reserved: usize,
+    is_open: bool,
+    num_senders: usize,
+    num_receivers: usize,
+    next_id: u64,
+    recv_tasks: HashMap<u64, Waker>,
+    send_tasks: HashMap<u64, Waker>,
+}
+
+/// Creates a bounded mpmc channel for distributing values among tasks.
+///
+/// # Panics
+///
+/// This function panics if `buffer` is zero.
+pub fn channel<T>(buffer: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(buffer > 0, "mpmc channel buffer must be non-zero");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::with_capacity(buffer),
+            reserved: 0,
+            is_open: true,
+            num_senders: 1,
+            num_receivers: 1,
+            next_id: 2,
+            recv_tasks: HashMap::new(),
+            send_tasks: HashMap::new(),
+        }),
+        buffer,
+    });
+    let rx = Receiver { shared: shared.clone(), id: 1, terminated: false };
+    (Sender { shared: Some(shared), id: 0, reserved: false }, rx)
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl<T> State<T> {
+    fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    // Each value is only taken by one receiver, so only one waiting receiver
+    // is woken per value.
+    fn wake_receiver(&mut self) {
+        let id = self.recv_tasks.keys().next().cloned();
+        if let Some(task) = id.and_then(|id| self.recv_tasks.remove(&id)) {
+            task.wake();
+        }
+    }
+
+    fn wake_all_receivers(&mut self) {
+        for (_, task) in self.recv_tasks.drain() {
+            task.wake();
+        }
+    }
+
+    fn wake_senders(&mut self) {
+        for (_, task) in self.send_tasks.drain() {
+            task.wake();
+        }
+    }
+}
+
+fn disconnected() -> SendError {
+    SendError { kind: SendErrorKind::Disconnected }
+}
+
+impl<T> Sender<T> {
+    /// Attempts to send a message on this `Sender`, returning the message
+    /// if there was an error.
+    pub fn try_send(&mut self, msg: T) -> Result<(), TrySendError<T>> {
+        let shared = match &self.shared {
+            Some(shared) => shared,
+            None => return Err(TrySendError { err: disconnected(), val: msg }),
+        };
+        let mut state = shared.lock();
+        if !state.is_open {
+            return Err(TrySendError { err: disconnected(), val: msg });
+        }
+        if self.reserved {
+            self.reserved = false;
+            state.reserved -= 1;
+        } else if state.queue.len() + state.reserved >= shared.buffer {
+            return Err(TrySendError {
+                err: SendError { kind: SendErrorKind::Full },
+                val: msg,
+            });
+        }
+
+        state.queue.push_back(msg);
+        state.wake_receiver();
+        Ok(())
+    }
+
+    /// Send a message on the channel.
+    ///
+    /// This function should only be called after
+    /// [`poll_ready`](Sender::poll_ready) has reported that the channel is
+    /// ready to receive a message.
+    pub fn start_send(&mut self, msg: T) -> Result<(), SendError> {
+        self.try_send(msg).map_err(|e| e.err)
+    }
+
+    /// Polls the channel to determine if there is capacity to send a message.
+    ///
+    /// Once this returns `Poll::Ready(Ok(()))`, a slot is reserved for this
+    /// sender until it sends a message or is dropped.
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SendError>> {
+        let shared = self.shared.as_ref().ok_or_else(disconnected)?;
+        let mut state = shared.lock();
+        if !state.is_open {
+            return Poll::Ready(Err(disconnected()));
+        }
+        if self.reserved {
+            return Poll::Ready(Ok(()));
+        }
+        if state.queue.len() + state.reserved < shared.buffer {
+            state.reserved += 1;
+            self.reserved = true;
+            state.send_tasks.remove(&self.id);
+            Poll::Ready(Ok(()))
+        } else {
+            state.send_tasks.insert(self.id, cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    /// Returns whether this channel is closed without needing a context.
+    pub fn is_closed(&self) -> bool {
+        self.shared.as_ref().map(|shared| !shared.lock().is_open).unwrap_or(true)
+    }
+
+    /// Returns the number of messages queued in the channel.
+    pub fn len(&self) -> usize {
+        self.shared.as_ref().map(|shared| shared.lock().queue.len()).unwrap_or(0)
+    }
+
+    /// Returns `true` if no messages are queued in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Disconnects this sender from the channel, closing it if there are no
+    /// more senders left.
+    pub fn disconnect(&mut self) {
+        if let Some(shared) = self.shared.take() {
+            let mut state = shared.lock();
+            state.send_tasks.remove(&self.id);
+            if self.reserved {
+                self.reserved = false;
+                state.reserved -= 1;
+                state.wake_senders();
+            }
+            state.num_senders -= 1;
+            if state.num_senders == 0 {
+                state.wake_all_receivers();
+            }
+        }
+    }
+
+    /// Returns whether the senders send to the same channel.
+    pub fn same_channel(&self, other: &Self) -> bool {
+        match (&self.shared, &other.shared) {
+            (Some(shared), Some(other)) => Arc::ptr_eq(shared, other),
+            _ => false,
+        }
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        match &self.shared {
+            Some(shared) => {
+                let mut state = shared.lock();
+                state.num_senders += 1;
+                let id = state.next_id();
+                Sender { shared: Some(shared.clone()), id, reserved: false }
+            }
+            None => Sender { shared: None, id: 0, reserved: false },
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Closes the channel for all receivers, without dropping them.
+    ///
+    /// This prevents any further messages from being sent on the channel while
+    /// still enabling the receivers to drain messages that are buffered.
+    pub fn close(&mut self) {
+        let mut state = self.shared.lock();
+        state.is_open = false;
+        state.wake_senders();
+    }
+
+    /// Tries to receive the next message without notifying a context if
+    /// empty.
+    ///
+    /// Returns `Ok(None)` once all senders have been dropped and all messages
+    /// have been received.
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        match self.next_message(None) {
+            Poll::Ready(msg) => Ok(msg),
+            Poll::Pending => Err(TryRecvError { _inner: () }),
+        }
+    }
+
+    /// Returns the number of messages queued in the channel.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// Returns `true` if no messages are queued in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of receivers of this channel.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.lock().num_receivers
+    }
+
+    fn next_message(&mut self, cx: Option<&mut Context<'_>>) -> Poll<Option<T>> {
+        let mut state = self.shared.lock();
+        if let Some(msg) = state.queue.pop_front() {
+            state.recv_tasks.remove(&self.id);
+            state.wake_senders();
+            // Pass the wakeup on if more messages are waiting.
+            if !state.queue.is_empty() {
+                state.wake_receiver();
+            }
+            return Poll::Ready(Some(msg));
+        }
+        if state.num_senders == 0 {
+            return Poll::Ready(None);
+        }
+        if let Some(cx) = cx {
+            state.recv_tasks.insert(self.id, cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+        let msg = self.next_message(Some(cx));
+        if let Poll::Ready(None) = msg {
+            self.terminated = true;
+        }
+        msg
+    }
+}
+
+impl<T> FusedStream for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        let mut state = self.shared.lock();
+        state.num_receivers += 1;
+        let id = state.next_id();
+        Receiver { shared: self.shared.clone(), id, terminated: self.terminated }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.num_receivers -= 1;
+        if state.num_receivers == 0 {
+            state.is_open = false;
+            state.wake_senders();
+        } else {
+            state.recv_tasks.remove(&self.id);
+            // This receiver may have been woken for a message it will never
+            // take, so hand the wakeup over to another one.
+            if !state.queue.is_empty() {
+                state.wake_receiver();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "sink")]
+impl<T> futures_sink::Sink<T> for Sender<T> {
+    type Error = SendError;
+
+    fn poll_ready(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        (*self).poll_ready(cx)
+    }
+
+    fn start_send(
+        mut self: Pin<&mut Self>,
+        msg: T,
+    ) -> Result<(), Self::Error> {
+        (*self).start_send(msg)
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.disconnect();
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender")
+            .field("reserved", &self.reserved)
+            .finish()
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("terminated", &self.terminated)
+            .finish()
+    }
+}
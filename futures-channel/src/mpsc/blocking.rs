@@ -0,0 +1,52 @@
+use futures_core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::mem;
+use std::sync::Arc;
+use std::thread::{self, Thread};
+
+// A waker which unparks the thread that created it. The data pointer is an
+// `Arc<Thread>` turned into a raw pointer.
+fn thread_waker() -> Waker {
+    let thread = Arc::new(thread::current());
+    unsafe { Waker::from_raw(raw_waker(thread)) }
+}
+
+fn raw_waker(thread: Arc<Thread>) -> RawWaker {
+    RawWaker::new(Arc::into_raw(thread) as *const (), &VTABLE)
+}
+
+static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+
+unsafe fn clone(data: *const ()) -> RawWaker {
+    let thread = Arc::from_raw(data as *const Thread);
+    let clone = thread.clone();
+    mem::forget(thread);
+    raw_waker(clone)
+}
+
+unsafe fn wake(data: *const ()) {
+    Arc::from_raw(data as *const Thread).unpark();
+}
+
+unsafe fn wake_by_ref(data: *const ()) {
+    (*(data as *const Thread)).unpark();
+}
+
+unsafe fn drop(data: *const ()) {
+    mem::drop(Arc::from_raw(data as *const Thread));
+}
+
+/// Repeatedly calls `f` until it is ready, parking the current thread in
+/// between.
+///
+/// `thread::park` can return spuriously, which only costs another call to
+/// `f` as it registers the waker again every time.
+pub(super) fn block_on_poll<R>(mut f: impl FnMut(&mut Context<'_>) -> Poll<R>) -> R {
+    let waker = thread_waker();
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(r) = f(&mut cx) {
+            return r;
+        }
+        thread::park();
+    }
+}
@@ -0,0 +1,286 @@
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::{SendError, SendErrorKind, TryRecvError, TrySendError};
+
+/// The transmission end of a coalescing mpsc channel.
+///
+/// This value is created by the [`coalescing_channel`](coalescing_channel)
+/// function.
+pub struct CoalescingSender<K, T> {
+    shared: Option<Arc<Shared<K, T>>>,
+}
+
+/// The receiving end of a coalescing mpsc channel.
+///
+/// This value is created by the [`coalescing_channel`](coalescing_channel)
+/// function.
+pub struct CoalescingReceiver<K, T> {
+    shared: Arc<Shared<K, T>>,
+    terminated: bool,
+}
+
+// The channel never projects Pin to the inner T
+impl<K, T> Unpin for CoalescingSender<K, T> {}
+impl<K, T> Unpin for CoalescingReceiver<K, T> {}
+
+type Merge<T> = Box<dyn Fn(&mut T, T) + Send + Sync>;
+
+struct Shared<K, T> {
+    state: Mutex<State<K, T>>,
+    merge: Merge<T>,
+}
+
+struct State<K, T> {
+    // Keys in the order their values are received, each key appears at most
+    // once.
+    keys: VecDeque<K>,
+    values: HashMap<K, T>,
+    // Total number of messages merged into a queued one.
+    coalesced: u64,
+    is_open: bool,
+    num_senders: usize,
+    recv_task: Option<Waker>,
+}
+
+/// Creates an unbounded mpsc channel which keeps at most one message per key.
+///
+/// Sending a message for a key which already has a message queued replaces
+/// the queued message, which keeps its place in the queue. This suits
+/// propagating the latest state of a set of entities: the channel never holds
+/// more messages than there are distinct keys, however far the receiver
+/// falls behind.
+///
+/// See [`coalescing_channel_with`](coalescing_channel_with) to combine
+/// messages instead of replacing them.
+pub fn coalescing_channel<K, T>() -> (CoalescingSender<K, T>, CoalescingReceiver<K, T>)
+where
+    K: Hash + Eq + Clone,
+{
+    coalescing_channel_with(|old, new| *old = new)
+}
+
+/// Creates an unbounded mpsc channel which keeps at most one message per key,
+/// combining messages with `merge`.
+///
+/// Sending a message for a key which already has a message queued calls
+/// `merge` with the queued message and the new one, for example to add up
+/// counters or to apply a partial update. See
+/// [`coalescing_channel`](coalescing_channel).
+pub fn coalescing_channel_with<K, T, F>(merge: F) -> (CoalescingSender<K, T>, CoalescingReceiver<K, T>)
+where
+    K: Hash + Eq + Clone,
+    F: Fn(&mut T, T) + Send + Sync + 'static,
+{
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            keys: VecDeque::new(),
+            values: HashMap::new(),
+            coalesced: 0,
+            is_open: true,
+            num_senders: 1,
+            recv_task: None,
+        }),
+        merge: Box::new(merge),
+    });
+    let rx = CoalescingReceiver { shared: shared.clone(), terminated: false };
+    (CoalescingSender { shared: Some(shared) }, rx)
+}
+
+impl<K, T> Shared<K, T> {
+    fn lock(&self) -> MutexGuard<'_, State<K, T>> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl<K: Hash + Eq + Clone, T> CoalescingSender<K, T> {
+    /// Sends a message for `key` on the channel without waiting.
+    ///
+    /// If a message for `key` is already queued, the new message is merged
+    /// into it. An error containing the key and the message is returned if
+    /// the receiver has been dropped or closed.
+    pub fn send(&self, key: K, msg: T) -> Result<(), TrySendError<(K, T)>> {
+        let shared = match &self.shared {
+            Some(shared) => shared,
+            None => return Err(disconnected(key, msg)),
+        };
+        let mut state = shared.lock();
+        if !state.is_open {
+            return Err(disconnected(key, msg));
+        }
+        match state.values.get_mut(&key) {
+            Some(queued) => {
+                (shared.merge)(queued, msg);
+                state.coalesced += 1;
+            }
+            None => {
+                state.keys.push_back(key.clone());
+                state.values.insert(key, msg);
+                if let Some(task) = state.recv_task.take() {
+                    task.wake();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn disconnected<K, T>(key: K, msg: T) -> TrySendError<(K, T)> {
+    TrySendError {
+        err: SendError { kind: SendErrorKind::Disconnected },
+        val: (key, msg),
+    }
+}
+
+impl<K, T> CoalescingSender<K, T> {
+    /// Returns the total number of messages merged into a queued message so
+    /// far.
+    pub fn coalesced_count(&self) -> u64 {
+        self.shared.as_ref().map(|shared| shared.lock().coalesced).unwrap_or(0)
+    }
+
+    /// Returns whether this channel is closed without needing a context.
+    pub fn is_closed(&self) -> bool {
+        self.shared.as_ref().map(|shared| !shared.lock().is_open).unwrap_or(true)
+    }
+
+    /// Disconnects this sender from the channel, closing it if there are no
+    /// more senders left.
+    pub fn disconnect(&mut self) {
+        if let Some(shared) = self.shared.take() {
+            let mut state = shared.lock();
+            state.num_senders -= 1;
+            if state.num_senders == 0 {
+                if let Some(task) = state.recv_task.take() {
+                    task.wake();
+                }
+            }
+        }
+    }
+
+    /// Returns whether the senders send to the same receiver.
+    pub fn same_receiver(&self, other: &Self) -> bool {
+        match (&self.shared, &other.shared) {
+            (Some(shared), Some(other)) => Arc::ptr_eq(shared, other),
+            _ => false,
+        }
+    }
+}
+
+impl<K, T> Clone for CoalescingSender<K, T> {
+    fn clone(&self) -> CoalescingSender<K, T> {
+        if let Some(shared) = &self.shared {
+            shared.lock().num_senders += 1;
+        }
+        CoalescingSender { shared: self.shared.clone() }
+    }
+}
+
+impl<K, T> Drop for CoalescingSender<K, T> {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+impl<K, T> CoalescingReceiver<K, T> {
+    /// Closes the receiving half of a channel, without dropping it.
+    ///
+    /// This prevents any further messages from being sent on the channel while
+    /// still enabling the receiver to drain messages that are buffered.
+    pub fn close(&mut self) {
+        self.shared.lock().is_open = false;
+    }
+
+    /// Returns the total number of messages merged into a queued message so
+    /// far.
+    pub fn coalesced_count(&self) -> u64 {
+        self.shared.lock().coalesced
+    }
+
+    /// Returns the number of messages queued in the channel.
+    pub fn len(&self) -> usize {
+        self.shared.lock().keys.len()
+    }
+
+    /// Returns `true` if no messages are queued in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K: Hash + Eq, T> CoalescingReceiver<K, T> {
+    /// Tries to receive the next message without notifying a context if
+    /// empty.
+    ///
+    /// Returns `Ok(None)` once all senders have been dropped and all messages
+    /// have been received.
+    pub fn try_next(&mut self) -> Result<Option<(K, T)>, TryRecvError> {
+        match self.next_message(None) {
+            Poll::Ready(msg) => Ok(msg),
+            Poll::Pending => Err(TryRecvError { _inner: () }),
+        }
+    }
+
+    fn next_message(&mut self, cx: Option<&mut Context<'_>>) -> Poll<Option<(K, T)>> {
+        let mut state = self.shared.lock();
+        if let Some(key) = state.keys.pop_front() {
+            let msg = state.values.remove(&key).unwrap();
+            return Poll::Ready(Some((key, msg)));
+        }
+        if state.num_senders == 0 {
+            return Poll::Ready(None);
+        }
+        if let Some(cx) = cx {
+            state.recv_task = Some(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+impl<K: Hash + Eq, T> Stream for CoalescingReceiver<K, T> {
+    type Item = (K, T);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<(K, T)>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+        let msg = self.next_message(Some(cx));
+        if let Poll::Ready(None) = msg {
+            self.terminated = true;
+        }
+        msg
+    }
+}
+
+impl<K: Hash + Eq, T> FusedStream for CoalescingReceiver<K, T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<K, T> Drop for CoalescingReceiver<K, T> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl<K, T> fmt::Debug for CoalescingSender<K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CoalescingSender")
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+impl<K, T> fmt::Debug for CoalescingReceiver<K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CoalescingReceiver")
+            .field("terminated", &self.terminated)
+            .finish()
+    }
+}
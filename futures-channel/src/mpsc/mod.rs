@@ -10,7 +10,13 @@
 //! the task will be notified when additional capacity is available. In other
 //! words, the channel provides backpressure.
 //!
-//! Unbounded channels are also available using the `unbounded` constructor.
+//! Unbounded channels are also available using the `unbounded` constructor,
+//! channels which deliver the greatest queued message first using the
+//! `priority_channel` constructor, channels which drop the oldest message
+//! instead of applying backpressure using the `ring_channel` constructor,
+//! channels bounded by the total weight of their messages, such as their size
+//! in bytes, using the `weighted_channel` constructor, and channels which keep
+//! only the latest message per key using the `coalescing_channel` constructor.
 //!
 //! # Disconnection
 //!
@@ -21,7 +27,8 @@
 //!
 //! If the [`Receiver`] handle is dropped, then messages can no longer
 //! be read out of the channel. In this case, all further attempts to send will
-//! result in an error.
+//! result in an error. Messages which were still queued are dropped, unless
+//! the senders asked to recover them with [`Sender::keep_rejected`].
 //!
 //! # Clean Shutdown
 //!
@@ -33,6 +40,7 @@
 //! completion, at which point the receiver can be dropped.
 //!
 //! [`Sender`]: struct.Sender.html
+//! [`Sender::keep_rejected`]: struct.Sender.html#method.keep_rejected
 //! [`Receiver`]: struct.Receiver.html
 //! [`Stream`]: ../../futures_core/stream/trait.Stream.html
 //! [`Receiver::poll_next`]:
@@ -78,20 +86,42 @@
 // happens-before semantics required for the acquire / release semantics used
 // by the queue structure.
 
+use futures_core::future::Future;
 use futures_core::stream::{FusedStream, Stream};
 use futures_core::task::{Context, Poll, Waker};
 use futures_core::task::__internal::AtomicWaker;
 use std::any::Any;
 use std::error::Error;
 use std::fmt;
+use std::mem;
 use std::pin::Pin;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, Weak};
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering::SeqCst;
 
 use crate::mpsc::queue::Queue;
 
+mod blocking;
+
+mod coalesce;
+pub use self::coalesce::{
+    coalescing_channel, coalescing_channel_with, CoalescingReceiver, CoalescingSender,
+};
+
+mod priority;
+pub use self::priority::{priority_channel, PriorityReceiver, PrioritySender};
+
 mod queue;
+
+mod receiver_set;
+pub use self::receiver_set::ReceiverSet;
+
+mod ring;
+pub use self::ring::{ring_channel, RingReceiver, RingSender};
+
+mod weighted;
+pub use self::weighted::{weighted_channel, WeightedReceiver, WeightedSender};
+
 #[cfg(feature = "sink")]
 mod sink_impl;
 
@@ -125,6 +155,21 @@ pub struct Sender<T>(Option<SenderInner<T>>);
 #[derive(Debug)]
 pub struct UnboundedSender<T>(Option<SenderInner<T>>);
 
+/// A bounded mpsc sender which does not keep the channel open.
+///
+/// This value is created by the [`Sender::downgrade`](Sender::downgrade)
+/// method. Once all [`Sender`](Sender)s are dropped, the receiver observes
+/// the channel as disconnected regardless of any `WeakSender`s left.
+#[derive(Debug)]
+pub struct WeakSender<T>(Weak<Inner<T>>);
+
+/// An unbounded mpsc sender which does not keep the channel open.
+///
+/// This value is created by the
+/// [`UnboundedSender::downgrade`](UnboundedSender::downgrade) method.
+#[derive(Debug)]
+pub struct WeakUnboundedSender<T>(Weak<Inner<T>>);
+
 trait AssertKinds: Send + Sync + Clone {}
 impl AssertKinds for UnboundedSender<u32> {}
 
@@ -148,25 +193,25 @@ impl<T> Unpin for UnboundedReceiver<T> {}
 /// The error type for [`Sender`s](Sender) used as `Sink`s.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SendError {
-    kind: SendErrorKind,
+    pub(crate) kind: SendErrorKind,
 }
 
 /// The error type returned from [`try_send`](Sender::try_send).
 #[derive(Clone, PartialEq, Eq)]
 pub struct TrySendError<T> {
-    err: SendError,
-    val: T,
+    pub(crate) err: SendError,
+    pub(crate) val: T,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-enum SendErrorKind {
+pub(crate) enum SendErrorKind {
     Full,
     Disconnected,
 }
 
 /// The error type returned from [`try_next`](Receiver::try_next).
 pub struct TryRecvError {
-    _inner: (),
+    pub(crate) _inner: (),
 }
 
 impl fmt::Display for SendError {
@@ -276,6 +321,10 @@ struct Inner<T> {
 
     // Handle to the receiver's task.
     recv_task: AtomicWaker,
+
+    // Messages which were still queued when the receiver was dropped, if
+    // the senders asked to keep them.
+    rejected: Mutex<Option<Vec<T>>>,
 }
 
 // Struct representation of `Inner::state`.
@@ -368,6 +417,7 @@ fn channel2<T>(buffer: Option<usize>) -> (SenderInner<T>, Receiver<T>) {
         parked_queue: Queue::new(),
         num_senders: AtomicUsize::new(1),
         recv_task: AtomicWaker::new(),
+        rejected: Mutex::new(None),
     });
 
     let tx = SenderInner {
@@ -558,6 +608,34 @@ impl<T> SenderInner<T> {
         !decode_state(self.inner.state.load(SeqCst)).is_open
     }
 
+    /// Creates a new sender from a weak handle, as long as another sender
+    /// still exists.
+    fn upgrade(inner: &Weak<Inner<T>>) -> Option<Self> {
+        let inner = inner.upgrade()?;
+        let mut curr = inner.num_senders.load(SeqCst);
+
+        loop {
+            // Once the last sender is gone, the channel is closed for good.
+            if curr == 0 {
+                return None;
+            }
+            if curr == inner.max_senders() {
+                panic!("cannot upgrade `WeakSender` -- too many outstanding senders");
+            }
+
+            match inner.num_senders.compare_exchange(curr, curr + 1, SeqCst, SeqCst) {
+                Ok(_) => {
+                    return Some(SenderInner {
+                        inner,
+                        sender_task: Arc::new(Mutex::new(SenderTask::new())),
+                        maybe_parked: false,
+                    })
+                }
+                Err(actual) => curr = actual,
+            }
+        }
+    }
+
     /// Closes this channel from the sender side, preventing any new messages.
     fn close_channel(&self) {
         // There's no need to park this sender, its dropping,
@@ -667,6 +745,166 @@ impl<T> Sender<T> {
             _ => false,
         }
     }
+
+    /// Returns the number of messages queued in the channel.
+    ///
+    /// Other senders and the receiver may change the number at any time, so
+    /// the returned value can be out of date as soon as it is returned. It is
+    /// meant for metrics and load-shedding heuristics, not synchronization.
+    ///
+    /// Returns 0 if this sender has been disconnected.
+    pub fn len(&self) -> usize {
+        self.0.as_ref().map(|inner| inner.inner.num_messages()).unwrap_or(0)
+    }
+
+    /// Returns `true` if no messages are queued in the channel.
+    ///
+    /// See [`len`](Sender::len) for the caveats of this method.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the buffer size the channel was created with.
+    ///
+    /// On top of this buffer, each sender has one guaranteed slot, so
+    /// [`len`](Sender::len) can exceed the capacity by up to the number of
+    /// senders. Returns 0 if this sender has been disconnected.
+    pub fn capacity(&self) -> usize {
+        self.0.as_ref().map(|inner| inner.inner.capacity()).unwrap_or(0)
+    }
+
+    /// Returns `true` if the buffer of the channel is used up, in which case
+    /// the next message sent by any sender parks that sender until the
+    /// receiver catches up.
+    ///
+    /// See [`len`](Sender::len) for the caveats of this method.
+    pub fn is_full(&self) -> bool {
+        self.0.as_ref().map(|inner| inner.inner.is_full()).unwrap_or(false)
+    }
+
+    /// Creates a [`WeakSender`](WeakSender) for this channel.
+    ///
+    /// A weak sender cannot send messages and does not keep the channel
+    /// open, but it can be turned back into a `Sender` as long as other
+    /// senders still exist. This is useful for auxiliary holders that should
+    /// not delay the receiver observing the end of the channel.
+    ///
+    /// Downgrading a disconnected sender returns a weak sender which never
+    /// upgrades.
+    pub fn downgrade(&self) -> WeakSender<T> {
+        let inner = self.0.as_ref().map(|inner| Arc::downgrade(&inner.inner));
+        WeakSender(inner.unwrap_or_default())
+    }
+
+    /// Makes the channel keep the messages which are still queued when the
+    /// receiver is dropped, instead of dropping them.
+    ///
+    /// The kept messages will never be delivered and can be recovered with
+    /// [`take_rejected`](Sender::take_rejected) by any sender of the channel,
+    /// which allows resending them elsewhere. Messages sent after the
+    /// receiver has been dropped are returned by the send methods as usual.
+    pub fn keep_rejected(&self) {
+        if let Some(inner) = &self.0 {
+            inner.inner.keep_rejected();
+        }
+    }
+
+    /// Takes the messages which were still queued when the receiver was
+    /// dropped.
+    ///
+    /// Returns an empty vector unless [`keep_rejected`](Sender::keep_rejected)
+    /// has been called before the receiver was dropped, or if another sender
+    /// has already taken the messages.
+    pub fn take_rejected(&self) -> Vec<T> {
+        self.0.as_ref().map(|inner| inner.inner.take_rejected()).unwrap_or_default()
+    }
+
+    /// Waits for capacity to send one message, returning a [`Permit`] to
+    /// send it with.
+    ///
+    /// Sending through the permit can neither fail nor wait, so this allows
+    /// checking for capacity before doing the work of producing the message.
+    /// The returned future resolves to an error if the receiver has been
+    /// dropped.
+    pub fn reserve(&mut self) -> Reserve<'_, T> {
+        Reserve { sender: Some(self) }
+    }
+
+    /// Attempts to reserve capacity to send one message without waiting.
+    ///
+    /// See [`reserve`](Sender::reserve). If the channel is at capacity, an
+    /// error for which [`is_full`](SendError::is_full) returns `true` is
+    /// returned.
+    pub fn try_reserve(&mut self) -> Result<Permit<'_, T>, SendError> {
+        let inner = self.0.as_mut().ok_or(SendError {
+            kind: SendErrorKind::Disconnected,
+        })?;
+        if inner.is_closed() {
+            return Err(SendError {
+                kind: SendErrorKind::Disconnected,
+            });
+        }
+        if inner.poll_unparked(None).is_pending() {
+            return Err(SendError {
+                kind: SendErrorKind::Full,
+            });
+        }
+        Ok(Permit { inner })
+    }
+
+    /// Sends a message on the channel, blocking the current thread until
+    /// there is capacity for it.
+    ///
+    /// This is meant for sending from synchronous code to asynchronous
+    /// tasks. An error is returned if the receiver has been dropped.
+    ///
+    /// This function must not be called from an asynchronous task: blocking
+    /// the thread of an executor can keep the receiver from ever making
+    /// room, deadlocking the program.
+    pub fn blocking_send(&mut self, msg: T) -> Result<(), SendError> {
+        blocking::block_on_poll(|cx| self.poll_ready(cx))?;
+        self.start_send(msg)
+    }
+}
+
+/// Future for the [`reserve`](Sender::reserve) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Reserve<'a, T> {
+    sender: Option<&'a mut Sender<T>>,
+}
+
+impl<T> Unpin for Reserve<'_, T> {}
+
+impl<'a, T> Future for Reserve<'a, T> {
+    type Output = Result<Permit<'a, T>, SendError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let sender = self.sender.as_mut().expect("Reserve polled after completion");
+        futures_core::ready!(sender.poll_ready(cx))?;
+        let inner = self.sender.take().unwrap().0.as_mut().unwrap();
+        Poll::Ready(Ok(Permit { inner }))
+    }
+}
+
+/// Capacity to send one message on a bounded channel.
+///
+/// This value is created by the [`reserve`](Sender::reserve) and
+/// [`try_reserve`](Sender::try_reserve) methods. Dropping it without sending
+/// a message gives the capacity back.
+#[derive(Debug)]
+pub struct Permit<'a, T> {
+    inner: &'a mut SenderInner<T>,
+}
+
+impl<T> Permit<'_, T> {
+    /// Sends a message using the reserved capacity.
+    ///
+    /// This never waits for capacity. If the receiver has been dropped or
+    /// closed since the capacity was reserved, the message is dropped.
+    pub fn send(self, msg: T) {
+        let _ = self.inner.do_send_b(msg);
+    }
 }
 
 impl<T> UnboundedSender<T> {
@@ -724,6 +962,33 @@ impl<T> UnboundedSender<T> {
             .map_err(|e| e.err)
     }
 
+    /// Creates a [`WeakUnboundedSender`](WeakUnboundedSender) for this
+    /// channel.
+    ///
+    /// See [`Sender::downgrade`](Sender::downgrade).
+    pub fn downgrade(&self) -> WeakUnboundedSender<T> {
+        let inner = self.0.as_ref().map(|inner| Arc::downgrade(&inner.inner));
+        WeakUnboundedSender(inner.unwrap_or_default())
+    }
+
+    /// Makes the channel keep the messages which are still queued when the
+    /// receiver is dropped, instead of dropping them.
+    ///
+    /// See [`Sender::keep_rejected`](Sender::keep_rejected).
+    pub fn keep_rejected(&self) {
+        if let Some(inner) = &self.0 {
+            inner.inner.keep_rejected();
+        }
+    }
+
+    /// Takes the messages which were still queued when the receiver was
+    /// dropped.
+    ///
+    /// See [`Sender::take_rejected`](Sender::take_rejected).
+    pub fn take_rejected(&self) -> Vec<T> {
+        self.0.as_ref().map(|inner| inner.inner.take_rejected()).unwrap_or_default()
+    }
+
     /// Sends a message along this channel.
     ///
     /// This is an unbounded sender, so this function differs from `Sink::send`
@@ -754,6 +1019,37 @@ impl<T> Clone for UnboundedSender<T> {
     }
 }
 
+impl<T> WeakSender<T> {
+    /// Attempts to turn this weak sender into a [`Sender`](Sender).
+    ///
+    /// Returns `None` if all senders of the channel have been dropped.
+    pub fn upgrade(&self) -> Option<Sender<T>> {
+        SenderInner::upgrade(&self.0).map(|inner| Sender(Some(inner)))
+    }
+}
+
+impl<T> WeakUnboundedSender<T> {
+    /// Attempts to turn this weak sender into an
+    /// [`UnboundedSender`](UnboundedSender).
+    ///
+    /// Returns `None` if all senders of the channel have been dropped.
+    pub fn upgrade(&self) -> Option<UnboundedSender<T>> {
+        SenderInner::upgrade(&self.0).map(|inner| UnboundedSender(Some(inner)))
+    }
+}
+
+impl<T> Clone for WeakSender<T> {
+    fn clone(&self) -> WeakSender<T> {
+        WeakSender(self.0.clone())
+    }
+}
+
+impl<T> Clone for WeakUnboundedSender<T> {
+    fn clone(&self) -> WeakUnboundedSender<T> {
+        WeakUnboundedSender(self.0.clone())
+    }
+}
+
 impl<T> Clone for SenderInner<T> {
     fn clone(&self) -> SenderInner<T> {
         // Since this atomic op isn't actually guarding any memory and we don't
@@ -838,21 +1134,178 @@ impl<T> Receiver<T> {
         }
     }
 
-    fn next_message(&mut self) -> Poll<Option<T>> {
-        let inner = self.inner.as_mut().expect("Receiver::next_message called after `None`");
-        // Pop off a message
-        match unsafe { inner.message_queue.pop_spin() } {
-            Some(msg) => {
-                // If there are any parked task handles in the parked queue,
-                // pop one and unpark it.
-                self.unpark_one();
+    /// Receives the next message, blocking the current thread until one is
+    /// available.
+    ///
+    /// This is meant for receiving in synchronous code from asynchronous
+    /// tasks. Returns `None` once all senders have been dropped and all
+    /// messages have been received.
+    ///
+    /// This function must not be called from an asynchronous task: blocking
+    /// the thread of an executor can keep the senders from ever sending,
+    /// deadlocking the program.
+    pub fn blocking_recv(&mut self) -> Option<T> {
+        blocking::block_on_poll(|cx| Pin::new(&mut *self).poll_next(cx))
+    }
 
-                // Decrement number of messages
-                self.dec_num_messages();
+    /// Polls for the next message without removing it from the channel.
+    ///
+    /// Returns `Poll::Ready(Some(_))` with a reference to the message which
+    /// the next call to [`poll_next`](futures_core::stream::Stream::poll_next)
+    /// or [`try_next`](Receiver::try_next) will return, allowing decisions to
+    /// be made before committing to take it. `Poll::Ready(None)` is returned
+    /// once the channel has ended. If no message is queued, the current task
+    /// is notified once one arrives.
+    pub fn poll_peek(&mut self, cx: &mut Context<'_>) -> Poll<Option<&T>> {
+        match self.poll_peek_ready(cx) {
+            Poll::Ready(true) => Poll::Ready(self.peek_ref()),
+            Poll::Ready(false) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
 
-                Poll::Ready(Some(msg))
+    /// Waits for the next message without removing it from the channel.
+    ///
+    /// See [`poll_peek`](Receiver::poll_peek).
+    pub fn peek(&mut self) -> Peek<'_, T> {
+        Peek { receiver: Some(self) }
+    }
+
+    // Returns whether a message is available to peek at, or `false` if the
+    // channel has ended.
+    fn poll_peek_ready(&mut self, cx: &mut Context<'_>) -> Poll<bool> {
+        let inner = match &self.inner {
+            Some(inner) => inner,
+            None => return Poll::Ready(false),
+        };
+        for i in 0..2 {
+            if unsafe { inner.message_queue.peek_spin() }.is_some() {
+                return Poll::Ready(true);
+            }
+            let state = decode_state(inner.state.load(SeqCst));
+            if !state.is_open && state.num_messages == 0 {
+                return Poll::Ready(false);
+            }
+            if i == 0 {
+                // Check again after registering, see `poll_next`.
+                inner.recv_task.register(cx.waker());
             }
+        }
+        Poll::Pending
+    }
+
+    fn peek_ref(&self) -> Option<&T> {
+        let inner = self.inner.as_ref()?;
+        unsafe { inner.message_queue.peek_spin() }
+    }
+
+    /// Returns an iterator over the messages which are currently queued,
+    /// removing them from the channel.
+    ///
+    /// The iterator ends as soon as no message is immediately available, it
+    /// never waits. This is meant for shutdown paths: after calling
+    /// [`close`](Receiver::close), draining the receiver returns the backlog
+    /// of unprocessed messages so that it can be persisted or logged.
+    ///
+    /// Draining does not end the stream, a message may still be in the middle
+    /// of being sent when the iterator ends, in which case it can be received
+    /// afterwards.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain { receiver: self }
+    }
+
+    /// Returns the number of messages queued in the channel.
+    ///
+    /// Senders may add messages at any time, so the returned value can be
+    /// out of date as soon as it is returned. It is meant for metrics and
+    /// load-shedding heuristics, not synchronization.
+    ///
+    /// Returns 0 once the channel has terminated.
+    pub fn len(&self) -> usize {
+        self.inner.as_ref().map(|inner| inner.num_messages()).unwrap_or(0)
+    }
+
+    /// Returns `true` if no messages are queued in the channel.
+    ///
+    /// See [`len`](Receiver::len) for the caveats of this method.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the buffer size the channel was created with.
+    ///
+    /// See [`Sender::capacity`](Sender::capacity). Returns 0 once the channel
+    /// has terminated.
+    pub fn capacity(&self) -> usize {
+        self.inner.as_ref().map(|inner| inner.capacity()).unwrap_or(0)
+    }
+
+    /// Returns `true` if the buffer of the channel is used up, in which case
+    /// senders are parked until messages are received.
+    ///
+    /// See [`len`](Receiver::len) for the caveats of this method.
+    pub fn is_full(&self) -> bool {
+        self.inner.as_ref().map(|inner| inner.is_full()).unwrap_or(false)
+    }
+
+    /// Receives up to `limit` messages at once, appending them to `buf`.
+    ///
+    /// All messages which are already queued are moved into `buf` in one
+    /// call, up to `limit` of them, and the number of messages received is
+    /// returned. If no message is queued, the current task is notified once
+    /// one arrives, the same as for
+    /// [`poll_next`](futures_core::stream::Stream::poll_next).
+    ///
+    /// `Poll::Ready(0)` is returned once the channel is closed and all
+    /// messages have been received, or if `limit` is zero.
+    pub fn poll_recv_many(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut Vec<T>,
+        limit: usize,
+    ) -> Poll<usize> {
+        let mut received = 0;
+        while received < limit && self.inner.is_some() {
+            let msg = match self.next_message() {
+                Poll::Ready(msg) => msg,
+                Poll::Pending if received > 0 => break,
+                Poll::Pending => {
+                    self.inner.as_ref().unwrap().recv_task.register(cx.waker());
+                    // Check the queue again after registering, see `poll_next`.
+                    match self.next_message() {
+                        Poll::Ready(msg) => msg,
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+            };
+            match msg {
+                Some(msg) => {
+                    buf.push(msg);
+                    received += 1;
+                }
+                None => break,
+            }
+        }
+        Poll::Ready(received)
+    }
+
+    /// Receives up to `limit` messages at once, appending them to `buf`.
+    ///
+    /// The returned future resolves to the number of messages received, see
+    /// [`poll_recv_many`](Receiver::poll_recv_many) for details. Draining a
+    /// busy channel this way takes far fewer wakeups than receiving one
+    /// message at a time.
+    pub fn recv_many<'a>(&'a mut self, buf: &'a mut Vec<T>, limit: usize) -> RecvMany<'a, T> {
+        RecvMany { receiver: self, buf, limit }
+    }
+
+    fn next_message(&mut self) -> Poll<Option<T>> {
+        assert!(self.inner.is_some(), "Receiver::next_message called after `None`");
+        // Pop off a message
+        match self.pop_message() {
+            Some(msg) => Poll::Ready(Some(msg)),
             None => {
+                let inner = self.inner.as_ref().unwrap();
                 let state = decode_state(inner.state.load(SeqCst));
                 if state.is_open || state.num_messages != 0 {
                     // If queue is open, we need to return Pending
@@ -873,6 +1326,22 @@ impl<T> Receiver<T> {
         }
     }
 
+    // Pop a message off the queue, without checking whether the channel has
+    // ended if there is none.
+    fn pop_message(&mut self) -> Option<T> {
+        let inner = self.inner.as_mut()?;
+        let msg = unsafe { inner.message_queue.pop_spin() }?;
+
+        // If there are any parked task handles in the parked queue,
+        // pop one and unpark it.
+        self.unpark_one();
+
+        // Decrement number of messages
+        self.dec_num_messages();
+
+        Some(msg)
+    }
+
     // Unpark a single task handle if there is one pending in the parked queue
     fn unpark_one(&mut self) {
         if let Some(inner) = &mut self.inner {
@@ -932,9 +1401,16 @@ impl<T> Drop for Receiver<T> {
     fn drop(&mut self) {
         // Drain the channel of all pending messages
         self.close();
-        if self.inner.is_some() {
-            while let Poll::Ready(Some(..)) = self.next_message() {
-                // ...
+        if let Some(inner) = self.inner.clone() {
+            let keep = inner.rejected.lock().unwrap().is_some();
+            let mut rejected = Vec::new();
+            while let Poll::Ready(Some(msg)) = self.next_message() {
+                if keep {
+                    rejected.push(msg);
+                }
+            }
+            if let Some(kept) = &mut *inner.rejected.lock().unwrap() {
+                kept.append(&mut rejected);
             }
         }
     }
@@ -960,6 +1436,55 @@ impl<T> UnboundedReceiver<T> {
     pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
         self.0.try_next()
     }
+
+    /// Receives the next message, blocking the current thread until one is
+    /// available.
+    ///
+    /// See [`Receiver::blocking_recv`](Receiver::blocking_recv).
+    pub fn blocking_recv(&mut self) -> Option<T> {
+        self.0.blocking_recv()
+    }
+
+    /// Polls for the next message without removing it from the channel.
+    ///
+    /// See [`Receiver::poll_peek`](Receiver::poll_peek).
+    pub fn poll_peek(&mut self, cx: &mut Context<'_>) -> Poll<Option<&T>> {
+        self.0.poll_peek(cx)
+    }
+
+    /// Waits for the next message without removing it from the channel.
+    ///
+    /// See [`Receiver::peek`](Receiver::peek).
+    pub fn peek(&mut self) -> Peek<'_, T> {
+        self.0.peek()
+    }
+
+    /// Returns an iterator over the messages which are currently queued,
+    /// removing them from the channel.
+    ///
+    /// See [`Receiver::drain`](Receiver::drain).
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        self.0.drain()
+    }
+
+    /// Receives up to `limit` messages at once, appending them to `buf`.
+    ///
+    /// See [`Receiver::poll_recv_many`](Receiver::poll_recv_many).
+    pub fn poll_recv_many(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut Vec<T>,
+        limit: usize,
+    ) -> Poll<usize> {
+        self.0.poll_recv_many(cx, buf, limit)
+    }
+
+    /// Receives up to `limit` messages at once, appending them to `buf`.
+    ///
+    /// See [`Receiver::recv_many`](Receiver::recv_many).
+    pub fn recv_many<'a>(&'a mut self, buf: &'a mut Vec<T>, limit: usize) -> RecvMany<'a, T> {
+        self.0.recv_many(buf, limit)
+    }
 }
 
 impl<T> FusedStream for UnboundedReceiver<T> {
@@ -979,6 +1504,60 @@ impl<T> Stream for UnboundedReceiver<T> {
     }
 }
 
+/// Future for the [`recv_many`](Receiver::recv_many) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RecvMany<'a, T> {
+    receiver: &'a mut Receiver<T>,
+    buf: &'a mut Vec<T>,
+    limit: usize,
+}
+
+impl<T> Unpin for RecvMany<'_, T> {}
+
+impl<T> Future for RecvMany<'_, T> {
+    type Output = usize;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<usize> {
+        let this = &mut *self;
+        this.receiver.poll_recv_many(cx, this.buf, this.limit)
+    }
+}
+
+/// Future for the [`peek`](Receiver::peek) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Peek<'a, T> {
+    receiver: Option<&'a mut Receiver<T>>,
+}
+
+impl<T> Unpin for Peek<'_, T> {}
+
+impl<'a, T> Future for Peek<'a, T> {
+    type Output = Option<&'a T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let receiver = self.receiver.as_mut().expect("Peek polled after completion");
+        let ready = futures_core::ready!(receiver.poll_peek_ready(cx));
+        let receiver = self.receiver.take().unwrap();
+        Poll::Ready(if ready { receiver.peek_ref() } else { None })
+    }
+}
+
+/// Iterator for the [`drain`](Receiver::drain) method.
+#[derive(Debug)]
+pub struct Drain<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.receiver.pop_message()
+    }
+}
+
 /*
  *
  * ===== impl Inner =====
@@ -986,6 +1565,17 @@ impl<T> Stream for UnboundedReceiver<T> {
  */
 
 impl<T> Inner<T> {
+    fn keep_rejected(&self) {
+        self.rejected.lock().unwrap().get_or_insert_with(Vec::new);
+    }
+
+    fn take_rejected(&self) -> Vec<T> {
+        match self.rejected.lock().unwrap().as_mut() {
+            Some(rejected) => mem::replace(rejected, Vec::new()),
+            None => Vec::new(),
+        }
+    }
+
     // The return value is such that the total number of messages that can be
     // enqueued into the channel will never exceed MAX_CAPACITY
     fn max_senders(&self) -> usize {
@@ -995,6 +1585,21 @@ impl<T> Inner<T> {
         }
     }
 
+    fn num_messages(&self) -> usize {
+        decode_state(self.state.load(SeqCst)).num_messages
+    }
+
+    fn capacity(&self) -> usize {
+        self.buffer.unwrap_or(MAX_BUFFER)
+    }
+
+    fn is_full(&self) -> bool {
+        match self.buffer {
+            Some(buffer) => self.num_messages() >= buffer,
+            None => false,
+        }
+    }
+
     // Clear `open` flag in the state, keep `num_messages` intact.
     fn set_closed(&self) {
         let curr = self.state.load(SeqCst);
@@ -0,0 +1,357 @@
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll, Waker};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::{SendError, SendErrorKind, TryRecvError, TrySendError};
+
+/// The transmission end of a priority mpsc channel.
+///
+/// This value is created by the [`priority_channel`](priority_channel)
+/// function.
+pub struct PrioritySender<T> {
+    shared: Option<Arc<Shared<T>>>,
+    id: u64,
+    // Whether this sender holds a slot reserved by `poll_ready`.
+    reserved: bool,
+}
+
+/// The receiving end of a priority mpsc channel.
+///
+/// This value is created by the [`priority_channel`](priority_channel)
+/// function.
+pub struct PriorityReceiver<T> {
+    shared: Arc<Shared<T>>,
+    terminated: bool,
+}
+
+// The channel never projects Pin to the inner T
+impl<T> Unpin for PrioritySender<T> {}
+impl<T> Unpin for PriorityReceiver<T> {}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    buffer: usize,
+}
+
+struct State<T> {
+    heap: BinaryHeap<Entry<T>>,
+    // Sequence number of the next message, keeps messages of equal priority
+    // in the order they were sent.
+    next_seq: u64,
+    // Number of slots reserved by `poll_ready`.
+    reserved: usize,
+    is_open: bool,
+    num_senders: usize,
+    next_id: u64,
+    recv_task: Option<Waker>,
+    send_tasks: HashMap<u64, Waker>,
+}
+
+struct Entry<T> {
+    msg: T,
+    seq: u64,
+}
+
+impl<T: Ord> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.msg.cmp(&other.msg).then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl<T: Ord> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Ord> Eq for Entry<T> {}
+
+/// Creates a bounded mpsc channel which delivers the greatest queued message
+/// first.
+///
+/// Messages are ordered by their [`Ord`] implementation, messages which
+/// compare equal are received in the order they were sent. Wrapping messages
+/// in a type which orders by an explicit priority allows separating control
+/// messages from bulk data without a second channel.
+///
+/// Unlike [`channel`](super::channel), senders have no guaranteed slot, so
+/// at most `buffer` messages are queued at any time.
+///
+/// # Panics
+///
+/// This function panics if `buffer` is zero.
+pub fn priority_channel<T: Ord>(buffer: usize) -> (PrioritySender<T>, PriorityReceiver<T>) {
+    assert!(buffer > 0, "priority channel buffer must be non-zero");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            heap: BinaryHeap::with_capacity(buffer),
+            next_seq: 0,
+            reserved: 0,
+            is_open: true,
+            num_senders: 1,
+            next_id: 1,
+            recv_task: None,
+            send_tasks: HashMap::new(),
+        }),
+        buffer,
+    });
+    let rx = PriorityReceiver { shared: shared.clone(), terminated: false };
+    (PrioritySender { shared: Some(shared), id: 0, reserved: false }, rx)
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl<T> State<T> {
+    fn wake_senders(&mut self) {
+        for (_, task) in self.send_tasks.drain() {
+            task.wake();
+        }
+    }
+}
+
+fn disconnected() -> SendError {
+    SendError { kind: SendErrorKind::Disconnected }
+}
+
+impl<T: Ord> PrioritySender<T> {
+    /// Attempts to send a message on this `PrioritySender`, returning the
+    /// message if there was an error.
+    pub fn try_send(&mut self, msg: T) -> Result<(), TrySendError<T>> {
+        let shared = match &self.shared {
+            Some(shared) => shared,
+            None => return Err(TrySendError { err: disconnected(), val: msg }),
+        };
+        let mut state = shared.lock();
+        if !state.is_open {
+            return Err(TrySendError { err: disconnected(), val: msg });
+        }
+        if self.reserved {
+            self.reserved = false;
+            state.reserved -= 1;
+        } else if state.heap.len() + state.reserved >= shared.buffer {
+            return Err(TrySendError {
+                err: SendError { kind: SendErrorKind::Full },
+                val: msg,
+            });
+        }
+
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.heap.push(Entry { msg, seq });
+        if let Some(task) = state.recv_task.take() {
+            task.wake();
+        }
+        Ok(())
+    }
+
+    /// Send a message on the channel.
+    ///
+    /// This function should only be called after
+    /// [`poll_ready`](PrioritySender::poll_ready) has reported that the
+    /// channel is ready to receive a message.
+    pub fn start_send(&mut self, msg: T) -> Result<(), SendError> {
+        self.try_send(msg).map_err(|e| e.err)
+    }
+}
+
+impl<T> PrioritySender<T> {
+    /// Polls the channel to determine if there is capacity to send a message.
+    ///
+    /// Once this returns `Poll::Ready(Ok(()))`, a slot is reserved for this
+    /// sender until it sends a message or is dropped.
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SendError>> {
+        let shared = self.shared.as_ref().ok_or_else(disconnected)?;
+        let mut state = shared.lock();
+        if !state.is_open {
+            return Poll::Ready(Err(disconnected()));
+        }
+        if self.reserved {
+            return Poll::Ready(Ok(()));
+        }
+        if state.heap.len() + state.reserved < shared.buffer {
+            state.reserved += 1;
+            self.reserved = true;
+            state.send_tasks.remove(&self.id);
+            Poll::Ready(Ok(()))
+        } else {
+            state.send_tasks.insert(self.id, cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    /// Returns whether this channel is closed without needing a context.
+    pub fn is_closed(&self) -> bool {
+        self.shared.as_ref().map(|shared| !shared.lock().is_open).unwrap_or(true)
+    }
+
+    /// Returns the number of messages queued in the channel.
+    ///
+    /// The number can change at any time, so it is only meant for metrics.
+    pub fn len(&self) -> usize {
+        self.shared.as_ref().map(|shared| shared.lock().heap.len()).unwrap_or(0)
+    }
+
+    /// Returns `true` if no messages are queued in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Disconnects this sender from the channel, closing it if there are no
+    /// more senders left.
+    pub fn disconnect(&mut self) {
+        if let Some(shared) = self.shared.take() {
+            let mut state = shared.lock();
+            state.send_tasks.remove(&self.id);
+            if self.reserved {
+                self.reserved = false;
+                state.reserved -= 1;
+                state.wake_senders();
+            }
+            state.num_senders -= 1;
+            if state.num_senders == 0 {
+                if let Some(task) = state.recv_task.take() {
+                    task.wake();
+                }
+            }
+        }
+    }
+
+    /// Returns whether the senders send to the same receiver.
+    pub fn same_receiver(&self, other: &Self) -> bool {
+        match (&self.shared, &other.shared) {
+            (Some(shared), Some(other)) => Arc::ptr_eq(shared, other),
+            _ => false,
+        }
+    }
+}
+
+impl<T> Clone for PrioritySender<T> {
+    fn clone(&self) -> PrioritySender<T> {
+        match &self.shared {
+            Some(shared) => {
+                let mut state = shared.lock();
+                state.num_senders += 1;
+                let id = state.next_id;
+                state.next_id += 1;
+                PrioritySender { shared: Some(shared.clone()), id, reserved: false }
+            }
+            None => PrioritySender { shared: None, id: 0, reserved: false },
+        }
+    }
+}
+
+impl<T> Drop for PrioritySender<T> {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+impl<T> PriorityReceiver<T> {
+    /// Closes the receiving half of a channel, without dropping it.
+    ///
+    /// This prevents any further messages from being sent on the channel while
+    /// still enabling the receiver to drain messages that are buffered.
+    pub fn close(&mut self) {
+        let mut state = self.shared.lock();
+        state.is_open = false;
+        state.wake_senders();
+    }
+
+    /// Returns the number of messages queued in the channel.
+    ///
+    /// The number can change at any time, so it is only meant for metrics.
+    pub fn len(&self) -> usize {
+        self.shared.lock().heap.len()
+    }
+
+    /// Returns `true` if no messages are queued in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Ord> PriorityReceiver<T> {
+    /// Tries to receive the greatest queued message without notifying a
+    /// context if empty.
+    ///
+    /// Returns `Ok(None)` once the channel is closed and all messages have
+    /// been received.
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        match self.next_message(None) {
+            Poll::Ready(msg) => Ok(msg),
+            Poll::Pending => Err(TryRecvError { _inner: () }),
+        }
+    }
+
+    fn next_message(&mut self, cx: Option<&mut Context<'_>>) -> Poll<Option<T>> {
+        let mut state = self.shared.lock();
+        if let Some(entry) = state.heap.pop() {
+            state.wake_senders();
+            return Poll::Ready(Some(entry.msg));
+        }
+        if state.num_senders == 0 {
+            return Poll::Ready(None);
+        }
+        if let Some(cx) = cx {
+            state.recv_task = Some(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+impl<T: Ord> Stream for PriorityReceiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+        let msg = self.next_message(Some(cx));
+        if let Poll::Ready(None) = msg {
+            self.terminated = true;
+        }
+        msg
+    }
+}
+
+impl<T: Ord> FusedStream for PriorityReceiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<T> Drop for PriorityReceiver<T> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl<T> fmt::Debug for PrioritySender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrioritySender")
+            .field("reserved", &self.reserved)
+            .finish()
+    }
+}
+
+impl<T> fmt::Debug for PriorityReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PriorityReceiver")
+            .field("terminated", &self.terminated)
+            .finish()
+    }
+}
@@ -1,52 +1,29 @@
-/* Copyright (c) 2010-2011 Dmitry Vyukov. All rights reserved.
- * Redistribution and use in source and binary forms, with or without
- * modification, are permitted provided that the following conditions are met:
- *
- *    1. Redistributions of source code must retain the above copyright notice,
- *       this list of conditions and the following disclaimer.
- *
- *    2. Redistributions in binary form must reproduce the above copyright
- *       notice, this list of conditions and the following disclaimer in the
- *       documentation and/or other materials provided with the distribution.
- *
- * THIS SOFTWARE IS PROVIDED BY DMITRY VYUKOV "AS IS" AND ANY EXPRESS OR IMPLIED
- * WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF
- * MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT
- * SHALL DMITRY VYUKOV OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT,
- * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
- * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
- * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
- * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE
- * OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF
- * ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
- *
- * The views and conclusions contained in the software and documentation are
- * those of the authors and should not be interpreted as representing official
- * policies, either expressed or implied, of Dmitry Vyukov.
- */
-
 //! A mostly lock-free multi-producer, single consumer queue for sending
 //! messages between asynchronous tasks.
 //!
-//! The queue implementation is essentially the same one used for mpsc channels
-//! in the standard library.
+//! Messages are stored in blocks of `BLOCK_CAP` slots which are linked
+//! together as the queue grows. A producer claims a slot by advancing the tail
+//! index and then writes its message into it, so pushing only allocates once
+//! per block instead of once per message, and consecutive messages share
+//! cache lines. Blocks which have been fully consumed are reset and linked in
+//! again after the tail, so a queue which stays within a few blocks stops
+//! allocating altogether.
+//!
+//! The design follows the list based channel of `crossbeam-channel`.
 //!
 //! Note that the current implementation of this queue has a caveat of the `pop`
 //! method, and see the method for more information about it. Due to this
 //! caveat, this queue may not be appropriate for all use-cases.
 
-// http://www.1024cores.net/home/lock-free-algorithms
-//                         /queues/non-intrusive-mpsc-node-based-queue
-
-// NOTE: this implementation is lifted from the standard library and only
-//       slightly modified
-
 pub(super) use self::PopResult::*;
 
-use std::thread;
+use std::alloc::{self, Layout};
 use std::cell::UnsafeCell;
+use std::fmt;
+use std::mem::MaybeUninit;
 use std::ptr;
-use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+use std::thread;
 
 /// A result of the `pop` function.
 pub(super) enum PopResult<T> {
@@ -61,50 +38,133 @@ pub(super) enum PopResult<T> {
     Inconsistent,
 }
 
-#[derive(Debug)]
-struct Node<T> {
-    next: AtomicPtr<Node<T>>,
-    value: Option<T>,
+// Indices count slots across all blocks, each block taking up `LAP` of them.
+// The last index of every block has no slot: a producer which finds the tail
+// index there knows that the next block is being installed and waits for it.
+const LAP: usize = 32;
+const BLOCK_CAP: usize = LAP - 1;
+
+// How many blocks a consumed block may be linked in after before it is freed
+// instead.
+const MAX_REUSE_ATTEMPTS: usize = 3;
+
+struct Slot<T> {
+    msg: UnsafeCell<MaybeUninit<T>>,
+    // Set once the message has been written.
+    ready: AtomicBool,
+}
+
+struct Block<T> {
+    next: AtomicPtr<Block<T>>,
+    slots: [Slot<T>; BLOCK_CAP],
+}
+
+impl<T> Block<T> {
+    fn new() -> *mut Block<T> {
+        // All zeroes is an empty block: a null `next` pointer and slots with
+        // uninitialized messages which are not ready.
+        let layout = Layout::new::<Block<T>>();
+        let block = unsafe { alloc::alloc_zeroed(layout) } as *mut Block<T>;
+        if block.is_null() {
+            alloc::handle_alloc_error(layout);
+        }
+        block
+    }
+
+    /// Frees a block, which must have been created by `Block::new`, without
+    /// dropping any messages.
+    unsafe fn free(block: *mut Block<T>) {
+        drop(Box::from_raw(block));
+    }
+
+    /// Returns the block after `block`, installing a new one unless a
+    /// consumed block is already linked in for reuse.
+    unsafe fn next(block: *mut Block<T>) -> *mut Block<T> {
+        let next = (*block).next.load(Ordering::Acquire);
+        if !next.is_null() {
+            return next;
+        }
+        let new = Block::new();
+        match (*block).next.compare_exchange(
+            ptr::null_mut(), new, Ordering::AcqRel, Ordering::Acquire,
+        ) {
+            Ok(_) => new,
+            Err(next) => {
+                Block::free(new);
+                next
+            }
+        }
+    }
 }
 
 /// The multi-producer single-consumer structure. This is not cloneable, but it
 /// may be safely shared so long as it is guaranteed that there is only one
 /// popper at a time (many pushers are allowed).
-#[derive(Debug)]
 pub(super) struct Queue<T> {
-    head: AtomicPtr<Node<T>>,
-    tail: UnsafeCell<*mut Node<T>>,
+    // Index of the next slot to push to, and the block containing it.
+    tail: AtomicUsize,
+    tail_block: AtomicPtr<Block<T>>,
+
+    // Index of the next slot to pop from, and the block containing it. Only
+    // accessed by the consumer.
+    head: UnsafeCell<usize>,
+    head_block: UnsafeCell<*mut Block<T>>,
 }
 
 unsafe impl<T: Send> Send for Queue<T> { }
 unsafe impl<T: Send> Sync for Queue<T> { }
 
-impl<T> Node<T> {
-    unsafe fn new(v: Option<T>) -> *mut Node<T> {
-        Box::into_raw(Box::new(Node {
-            next: AtomicPtr::new(ptr::null_mut()),
-            value: v,
-        }))
-    }
-}
-
 impl<T> Queue<T> {
     /// Creates a new queue that is safe to share among multiple producers and
     /// one consumer.
     pub(super) fn new() -> Queue<T> {
-        let stub = unsafe { Node::new(None) };
+        let block = Block::new();
         Queue {
-            head: AtomicPtr::new(stub),
-            tail: UnsafeCell::new(stub),
+            tail: AtomicUsize::new(0),
+            tail_block: AtomicPtr::new(block),
+            head: UnsafeCell::new(0),
+            head_block: UnsafeCell::new(block),
         }
     }
 
     /// Pushes a new value onto this queue.
     pub(super) fn push(&self, t: T) {
-        unsafe {
-            let n = Node::new(Some(t));
-            let prev = self.head.swap(n, Ordering::AcqRel);
-            (*prev).next.store(n, Ordering::Release);
+        let mut tail = self.tail.load(Ordering::Acquire);
+        let mut block = self.tail_block.load(Ordering::Acquire);
+        loop {
+            let offset = tail % LAP;
+            if offset == BLOCK_CAP {
+                // Another producer is installing the next block.
+                thread::yield_now();
+                tail = self.tail.load(Ordering::Acquire);
+                block = self.tail_block.load(Ordering::Acquire);
+                continue;
+            }
+
+            // `block` was loaded after `tail`, so it is the block containing
+            // `tail` if the index has not moved on since.
+            match self.tail.compare_exchange_weak(
+                tail, tail.wrapping_add(1), Ordering::SeqCst, Ordering::Acquire,
+            ) {
+                Ok(_) => unsafe {
+                    // Whoever claims the last slot moves the tail to the next
+                    // block, which has to be linked in before the message is
+                    // written so the consumer can follow it.
+                    if offset + 1 == BLOCK_CAP {
+                        let next = Block::next(block);
+                        self.tail_block.store(next, Ordering::Release);
+                        self.tail.store(tail.wrapping_add(2), Ordering::Release);
+                    }
+                    let slot = &(*block).slots[offset];
+                    (*slot.msg.get()).as_mut_ptr().write(t);
+                    slot.ready.store(true, Ordering::Release);
+                    return;
+                },
+                Err(current) => {
+                    tail = current;
+                    block = self.tail_block.load(Ordering::Acquire);
+                }
+            }
         }
     }
 
@@ -114,26 +174,38 @@ impl<T> Queue<T> {
     /// return `Option<T>`. It is possible for this queue to be in an
     /// inconsistent state where many pushes have succeeded and completely
     /// finished, but pops cannot return `Some(t)`. This inconsistent state
-    /// happens when a pusher is preempted at an inopportune moment.
+    /// happens when a pusher is preempted between claiming a slot and writing
+    /// its message.
     ///
     /// This inconsistent state means that this queue does indeed have data, but
     /// it does not currently have access to it at this time.
     ///
     /// This function is unsafe because only one thread can call it at a time.
     pub(super) unsafe fn pop(&self) -> PopResult<T> {
-        let tail = *self.tail.get();
-        let next = (*tail).next.load(Ordering::Acquire);
+        let head = *self.head.get();
+        let block = *self.head_block.get();
+        let offset = head % LAP;
+        let slot = &(*block).slots[offset];
 
-        if !next.is_null() {
-            *self.tail.get() = next;
-            assert!((*tail).value.is_none());
-            assert!((*next).value.is_some());
-            let ret = (*next).value.take().unwrap();
-            drop(Box::from_raw(tail));
-            return Data(ret);
+        if !slot.ready.load(Ordering::Acquire) {
+            return if self.tail.load(Ordering::Acquire) == head {
+                Empty
+            } else {
+                Inconsistent
+            };
         }
 
-        if self.head.load(Ordering::Acquire) == tail {Empty} else {Inconsistent}
+        let ret = (*slot.msg.get()).as_ptr().read();
+        if offset + 1 == BLOCK_CAP {
+            // The producer of this message linked in the next block before
+            // writing it.
+            *self.head_block.get() = (*block).next.load(Ordering::Acquire);
+            *self.head.get() = head.wrapping_add(2);
+            self.reuse(block);
+        } else {
+            *self.head.get() = head.wrapping_add(1);
+        }
+        Data(ret)
     }
 
     /// Pop an element similarly to `pop` function, but spin-wait on inconsistent
@@ -162,17 +234,87 @@ impl<T> Queue<T> {
             }
         }
     }
+
+    /// Returns a reference to the next element without popping it,
+    /// spin-waiting on inconsistent queue state like `pop_spin`.
+    ///
+    /// This function is unsafe because only one thread can call it or pop at
+    /// a time, and no element may be popped while the reference is alive.
+    pub(super) unsafe fn peek_spin(&self) -> Option<&T> {
+        let head = *self.head.get();
+        let slot = &(**self.head_block.get()).slots[head % LAP];
+        loop {
+            if slot.ready.load(Ordering::Acquire) {
+                return Some(&*(*slot.msg.get()).as_ptr());
+            }
+            if self.tail.load(Ordering::Acquire) == head {
+                return None;
+            }
+            thread::yield_now();
+        }
+    }
+
+    /// Resets a fully consumed block and links it in after the tail, or frees
+    /// it if other blocks keep getting in the way.
+    ///
+    /// This function is unsafe because only the consumer can call it.
+    unsafe fn reuse(&self, block: *mut Block<T>) {
+        (*block).next.store(ptr::null_mut(), Ordering::Relaxed);
+        for slot in (*block).slots.iter() {
+            slot.ready.store(false, Ordering::Relaxed);
+        }
+
+        // Blocks from the tail onwards are never freed by anyone but the
+        // consumer, so they can be followed safely.
+        let mut last = self.tail_block.load(Ordering::Acquire);
+        for _ in 0..MAX_REUSE_ATTEMPTS {
+            match (*last).next.compare_exchange(
+                ptr::null_mut(), block, Ordering::AcqRel, Ordering::Acquire,
+            ) {
+                Ok(_) => return,
+                Err(next) => last = next,
+            }
+        }
+        Block::free(block);
+    }
 }
 
 impl<T> Drop for Queue<T> {
     fn drop(&mut self) {
         unsafe {
-            let mut cur = *self.tail.get();
-            while !cur.is_null() {
-                let next = (*cur).next.load(Ordering::Relaxed);
-                drop(Box::from_raw(cur));
-                cur = next;
+            let tail = *self.tail.get_mut();
+            let mut head = *self.head.get();
+            let mut block = *self.head_block.get();
+
+            // No push can be in progress, so every slot up to the tail holds
+            // a message.
+            while head != tail {
+                let offset = head % LAP;
+                ptr::drop_in_place((*(*block).slots[offset].msg.get()).as_mut_ptr());
+                if offset + 1 == BLOCK_CAP {
+                    let next = *(*block).next.get_mut();
+                    Block::free(block);
+                    block = next;
+                    head = head.wrapping_add(2);
+                } else {
+                    head = head.wrapping_add(1);
+                }
+            }
+
+            // Free the last block along with any linked in for reuse.
+            while !block.is_null() {
+                let next = *(*block).next.get_mut();
+                Block::free(block);
+                block = next;
             }
         }
     }
 }
+
+impl<T> fmt::Debug for Queue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Queue")
+            .field("tail", &self.tail)
+            .finish()
+    }
+}
@@ -0,0 +1,131 @@
+use futures_core::stream::Stream;
+use futures_core::task::{Context, Poll};
+use std::fmt;
+use std::pin::Pin;
+
+use super::Receiver;
+
+/// A set of keyed [`Receiver`]s which can be received from as one stream.
+///
+/// The set yields each message along with the key of the receiver it came
+/// from. Receivers are polled in turn, starting after the one which yielded
+/// the previous message, so a busy receiver cannot starve the others.
+/// Receivers are removed from the set once they end, and the stream ends
+/// when the set is empty.
+pub struct ReceiverSet<K, T> {
+    receivers: Vec<(K, Receiver<T>)>,
+    // Index of the receiver to poll first.
+    next: usize,
+}
+
+impl<K, T> Unpin for ReceiverSet<K, T> {}
+
+impl<K, T> ReceiverSet<K, T> {
+    /// Creates an empty set.
+    pub fn new() -> ReceiverSet<K, T> {
+        ReceiverSet { receivers: Vec::new(), next: 0 }
+    }
+
+    /// Returns the number of receivers in the set.
+    pub fn len(&self) -> usize {
+        self.receivers.len()
+    }
+
+    /// Returns `true` if the set contains no receivers.
+    pub fn is_empty(&self) -> bool {
+        self.receivers.is_empty()
+    }
+
+    /// Returns an iterator over the keys of the receivers in the set.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.receivers.iter().map(|(key, _)| key)
+    }
+
+    fn remove_at(&mut self, index: usize) -> (K, Receiver<T>) {
+        if index < self.next {
+            self.next -= 1;
+        }
+        self.receivers.remove(index)
+    }
+}
+
+impl<K: PartialEq, T> ReceiverSet<K, T> {
+    /// Adds a receiver to the set under `key`, returning the receiver which
+    /// was previously in the set under that key.
+    pub fn insert(&mut self, key: K, receiver: Receiver<T>) -> Option<Receiver<T>> {
+        match self.receivers.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, old)) => Some(std::mem::replace(old, receiver)),
+            None => {
+                self.receivers.push((key, receiver));
+                None
+            }
+        }
+    }
+
+    /// Removes the receiver under `key` from the set.
+    pub fn remove(&mut self, key: &K) -> Option<Receiver<T>> {
+        let index = self.receivers.iter().position(|(k, _)| k == key)?;
+        Some(self.remove_at(index).1)
+    }
+
+    /// Returns `true` if the set contains a receiver under `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.receivers.iter().any(|(k, _)| k == key)
+    }
+
+    /// Returns a mutable reference to the receiver under `key`.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut Receiver<T>> {
+        self.receivers.iter_mut().find(|(k, _)| k == key).map(|(_, rx)| rx)
+    }
+}
+
+impl<K, T> Default for ReceiverSet<K, T> {
+    fn default() -> ReceiverSet<K, T> {
+        ReceiverSet::new()
+    }
+}
+
+impl<K: Clone, T> Stream for ReceiverSet<K, T> {
+    type Item = (K, T);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<(K, T)>> {
+        let this = &mut *self;
+        let mut polled = 0;
+        while polled < this.receivers.len() {
+            if this.next >= this.receivers.len() {
+                this.next = 0;
+            }
+            let index = this.next;
+            let (key, rx) = &mut this.receivers[index];
+            match Pin::new(rx).poll_next(cx) {
+                Poll::Ready(Some(msg)) => {
+                    let key = key.clone();
+                    this.next = index + 1;
+                    return Poll::Ready(Some((key, msg)));
+                }
+                Poll::Ready(None) => {
+                    // The following receiver moves into this index.
+                    this.remove_at(index);
+                    this.next = index;
+                }
+                Poll::Pending => {
+                    this.next = index + 1;
+                    polled += 1;
+                }
+            }
+        }
+        if this.receivers.is_empty() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<K: fmt::Debug, T> fmt::Debug for ReceiverSet<K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReceiverSet")
+            .field("keys", &self.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,254 @@
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::{SendError, SendErrorKind, TryRecvError, TrySendError};
+
+/// The transmission end of a ring buffer mpsc channel.
+///
+/// This value is created by the [`ring_channel`](ring_channel) function.
+pub struct RingSender<T> {
+    shared: Option<Arc<Shared<T>>>,
+}
+
+/// The receiving end of a ring buffer mpsc channel.
+///
+/// This value is created by the [`ring_channel`](ring_channel) function.
+pub struct RingReceiver<T> {
+    shared: Arc<Shared<T>>,
+    terminated: bool,
+}
+
+// The channel never projects Pin to the inner T
+impl<T> Unpin for RingSender<T> {}
+impl<T> Unpin for RingReceiver<T> {}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    capacity: usize,
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    // Total number of messages evicted to make room for newer ones.
+    dropped: u64,
+    is_open: bool,
+    num_senders: usize,
+    recv_task: Option<Waker>,
+}
+
+/// Creates a bounded mpsc channel which drops the oldest message when full.
+///
+/// Sending never waits: if `capacity` messages are queued, the oldest one
+/// is evicted to make room for the new one. This suits telemetry or UI
+/// updates, where stale data is better lost than slowing down the sender.
+/// The number of evicted messages is available from
+/// [`RingReceiver::dropped_count`](RingReceiver::dropped_count).
+///
+/// # Panics
+///
+/// This function panics if `capacity` is zero.
+pub fn ring_channel<T>(capacity: usize) -> (RingSender<T>, RingReceiver<T>) {
+    assert!(capacity > 0, "ring channel capacity must be non-zero");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::with_capacity(capacity),
+            dropped: 0,
+            is_open: true,
+            num_senders: 1,
+            recv_task: None,
+        }),
+        capacity,
+    });
+    let rx = RingReceiver { shared: shared.clone(), terminated: false };
+    (RingSender { shared: Some(shared) }, rx)
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+}
+
+fn disconnected<T>(msg: T) -> TrySendError<T> {
+    TrySendError {
+        err: SendError { kind: SendErrorKind::Disconnected },
+        val: msg,
+    }
+}
+
+impl<T> RingSender<T> {
+    /// Sends a message on the channel without waiting.
+    ///
+    /// If the channel is full, the oldest queued message is evicted and
+    /// returned. An error containing the message is returned if the receiver
+    /// has been dropped or closed.
+    pub fn send(&self, msg: T) -> Result<Option<T>, TrySendError<T>> {
+        let shared = match &self.shared {
+            Some(shared) => shared,
+            None => return Err(disconnected(msg)),
+        };
+        let mut state = shared.lock();
+        if !state.is_open {
+            return Err(disconnected(msg));
+        }
+        let evicted = if state.queue.len() == shared.capacity {
+            state.dropped += 1;
+            state.queue.pop_front()
+        } else {
+            None
+        };
+        state.queue.push_back(msg);
+        if let Some(task) = state.recv_task.take() {
+            task.wake();
+        }
+        Ok(evicted)
+    }
+
+    /// Returns the total number of messages evicted from the channel so far.
+    pub fn dropped_count(&self) -> u64 {
+        self.shared.as_ref().map(|shared| shared.lock().dropped).unwrap_or(0)
+    }
+
+    /// Returns whether this channel is closed without needing a context.
+    pub fn is_closed(&self) -> bool {
+        self.shared.as_ref().map(|shared| !shared.lock().is_open).unwrap_or(true)
+    }
+
+    /// Disconnects this sender from the channel, closing it if there are no
+    /// more senders left.
+    pub fn disconnect(&mut self) {
+        if let Some(shared) = self.shared.take() {
+            let mut state = shared.lock();
+            state.num_senders -= 1;
+            if state.num_senders == 0 {
+                if let Some(task) = state.recv_task.take() {
+                    task.wake();
+                }
+            }
+        }
+    }
+
+    /// Returns whether the senders send to the same receiver.
+    pub fn same_receiver(&self, other: &Self) -> bool {
+        match (&self.shared, &other.shared) {
+            (Some(shared), Some(other)) => Arc::ptr_eq(shared, other),
+            _ => false,
+        }
+    }
+}
+
+impl<T> Clone for RingSender<T> {
+    fn clone(&self) -> RingSender<T> {
+        if let Some(shared) = &self.shared {
+            shared.lock().num_senders += 1;
+        }
+        RingSender { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for RingSender<T> {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+impl<T> RingReceiver<T> {
+    /// Closes the receiving half of a channel, without dropping it.
+    ///
+    /// This prevents any further messages from being sent on the channel while
+    /// still enabling the receiver to drain messages that are buffered.
+    pub fn close(&mut self) {
+        self.shared.lock().is_open = false;
+    }
+
+    /// Tries to receive the next message without notifying a context if
+    /// empty.
+    ///
+    /// Returns `Ok(None)` once all senders have been dropped and all messages
+    /// have been received.
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        match self.next_message(None) {
+            Poll::Ready(msg) => Ok(msg),
+            Poll::Pending => Err(TryRecvError { _inner: () }),
+        }
+    }
+
+    /// Returns the total number of messages evicted from the channel so far.
+    ///
+    /// Comparing this with a previous value tells how many messages were
+    /// lost in between.
+    pub fn dropped_count(&self) -> u64 {
+        self.shared.lock().dropped
+    }
+
+    /// Returns the number of messages queued in the channel.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// Returns `true` if no messages are queued in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn next_message(&mut self, cx: Option<&mut Context<'_>>) -> Poll<Option<T>> {
+        let mut state = self.shared.lock();
+        if let Some(msg) = state.queue.pop_front() {
+            return Poll::Ready(Some(msg));
+        }
+        if state.num_senders == 0 {
+            return Poll::Ready(None);
+        }
+        if let Some(cx) = cx {
+            state.recv_task = Some(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Stream for RingReceiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+        let msg = self.next_message(Some(cx));
+        if let Poll::Ready(None) = msg {
+            self.terminated = true;
+        }
+        msg
+    }
+}
+
+impl<T> FusedStream for RingReceiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<T> Drop for RingReceiver<T> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl<T> fmt::Debug for RingSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RingSender")
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+impl<T> fmt::Debug for RingReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RingReceiver")
+            .field("terminated", &self.terminated)
+            .finish()
+    }
+}
@@ -1,6 +1,10 @@
-use super::{SendError, Sender, TrySendError, UnboundedSender};
+use super::{
+    CoalescingSender, PrioritySender, RingSender, SendError, SendErrorKind, Sender, TrySendError,
+    UnboundedSender, WeightedSender,
+};
 use futures_core::task::{Context, Poll};
 use futures_sink::Sink;
+use std::hash::Hash;
 use std::pin::Pin;
 
 impl<T> Sink<T> for Sender<T> {
@@ -105,3 +109,146 @@ impl<T> Sink<T> for &UnboundedSender<T> {
         Poll::Ready(Ok(()))
     }
 }
+
+impl<T: Ord> Sink<T> for PrioritySender<T> {
+    type Error = SendError;
+
+    fn poll_ready(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        (*self).poll_ready(cx)
+    }
+
+    fn start_send(
+        mut self: Pin<&mut Self>,
+        msg: T,
+    ) -> Result<(), Self::Error> {
+        (*self).start_send(msg)
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.disconnect();
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> Sink<T> for RingSender<T> {
+    type Error = SendError;
+
+    fn poll_ready(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        if self.is_closed() {
+            Poll::Ready(Err(SendError { kind: SendErrorKind::Disconnected }))
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn start_send(
+        self: Pin<&mut Self>,
+        msg: T,
+    ) -> Result<(), Self::Error> {
+        self.send(msg)
+            .map(drop)
+            .map_err(TrySendError::into_send_error)
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.disconnect();
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> Sink<T> for WeightedSender<T> {
+    type Error = SendError;
+
+    fn poll_ready(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        (*self).poll_ready(cx)
+    }
+
+    fn start_send(
+        mut self: Pin<&mut Self>,
+        msg: T,
+    ) -> Result<(), Self::Error> {
+        (*self).start_send(msg)
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.disconnect();
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<K: Hash + Eq + Clone, T> Sink<(K, T)> for CoalescingSender<K, T> {
+    type Error = SendError;
+
+    fn poll_ready(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        if self.is_closed() {
+            Poll::Ready(Err(SendError { kind: SendErrorKind::Disconnected }))
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn start_send(
+        self: Pin<&mut Self>,
+        (key, msg): (K, T),
+    ) -> Result<(), Self::Error> {
+        self.send(key, msg)
+            .map_err(TrySendError::into_send_error)
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.disconnect();
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,341 @@
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::{SendError, SendErrorKind, TryRecvError, TrySendError};
+
+/// The transmission end of a weighted mpsc channel.
+///
+/// This value is created by the [`weighted_channel`](weighted_channel)
+/// function.
+pub struct WeightedSender<T> {
+    shared: Option<Arc<Shared<T>>>,
+    id: u64,
+}
+
+/// The receiving end of a weighted mpsc channel.
+///
+/// This value is created by the [`weighted_channel`](weighted_channel)
+/// function.
+pub struct WeightedReceiver<T> {
+    shared: Arc<Shared<T>>,
+    terminated: bool,
+}
+
+// The channel never projects Pin to the inner T
+impl<T> Unpin for WeightedSender<T> {}
+impl<T> Unpin for WeightedReceiver<T> {}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    capacity: usize,
+    weigh: Box<dyn Fn(&T) -> usize + Send + Sync>,
+}
+
+struct State<T> {
+    // Messages along with their weight, so it is only computed once.
+    queue: VecDeque<(T, usize)>,
+    // Sum of the weights of all queued messages.
+    weight: usize,
+    is_open: bool,
+    num_senders: usize,
+    next_id: u64,
+    recv_task: Option<Waker>,
+    send_tasks: HashMap<u64, Waker>,
+}
+
+/// Creates a bounded mpsc channel whose capacity is measured by the weight
+/// of the queued messages rather than their number.
+///
+/// The weight of each message is computed by `weigh`, for example its size
+/// in bytes. Messages are accepted as long as the total weight of the queued
+/// messages stays within `capacity`, which bounds the memory held by the
+/// channel even if message sizes vary widely.
+///
+/// A message heavier than `capacity` is still accepted once the channel is
+/// empty, so it cannot block the channel forever.
+///
+/// # Panics
+///
+/// This function panics if `capacity` is zero.
+pub fn weighted_channel<T, F>(capacity: usize, weigh: F) -> (WeightedSender<T>, WeightedReceiver<T>)
+where
+    F: Fn(&T) -> usize + Send + Sync + 'static,
+{
+    assert!(capacity > 0, "weighted channel capacity must be non-zero");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            weight: 0,
+            is_open: true,
+            num_senders: 1,
+            next_id: 1,
+            recv_task: None,
+            send_tasks: HashMap::new(),
+        }),
+        capacity,
+        weigh: Box::new(weigh),
+    });
+    let rx = WeightedReceiver { shared: shared.clone(), terminated: false };
+    (WeightedSender { shared: Some(shared), id: 0 }, rx)
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+
+    fn push(&self, state: &mut State<T>, msg: T, weight: usize) {
+        state.queue.push_back((msg, weight));
+        state.weight += weight;
+        if let Some(task) = state.recv_task.take() {
+            task.wake();
+        }
+    }
+}
+
+impl<T> State<T> {
+    fn wake_senders(&mut self) {
+        for (_, task) in self.send_tasks.drain() {
+            task.wake();
+        }
+    }
+}
+
+fn disconnected() -> SendError {
+    SendError { kind: SendErrorKind::Disconnected }
+}
+
+impl<T> WeightedSender<T> {
+    /// Attempts to send a message on this `WeightedSender`, returning the
+    /// message if there was an error.
+    ///
+    /// The message is rejected as full if it would take the total weight of
+    /// the channel above its capacity, unless the channel is empty.
+    pub fn try_send(&mut self, msg: T) -> Result<(), TrySendError<T>> {
+        let shared = match &self.shared {
+            Some(shared) => shared,
+            None => return Err(TrySendError { err: disconnected(), val: msg }),
+        };
+        let weight = (shared.weigh)(&msg);
+        let mut state = shared.lock();
+        if !state.is_open {
+            return Err(TrySendError { err: disconnected(), val: msg });
+        }
+        if state.weight != 0 && state.weight + weight > shared.capacity {
+            return Err(TrySendError {
+                err: SendError { kind: SendErrorKind::Full },
+                val: msg,
+            });
+        }
+        shared.push(&mut state, msg, weight);
+        Ok(())
+    }
+
+    /// Send a message on the channel.
+    ///
+    /// This function should only be called after
+    /// [`poll_ready`](WeightedSender::poll_ready) has reported that the
+    /// channel is ready to receive a message. As the weight of the message is
+    /// not known in advance, it is accepted even if it takes the total weight
+    /// of the channel above its capacity.
+    pub fn start_send(&mut self, msg: T) -> Result<(), SendError> {
+        let shared = self.shared.as_ref().ok_or_else(disconnected)?;
+        let weight = (shared.weigh)(&msg);
+        let mut state = shared.lock();
+        if !state.is_open {
+            return Err(disconnected());
+        }
+        shared.push(&mut state, msg, weight);
+        Ok(())
+    }
+
+    /// Polls the channel to determine if there is capacity to send a message.
+    ///
+    /// This returns `Poll::Ready(Ok(()))` while the total weight of the queued
+    /// messages is below the capacity of the channel, so the next message may
+    /// overshoot the capacity by its own weight.
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SendError>> {
+        let shared = self.shared.as_ref().ok_or_else(disconnected)?;
+        let mut state = shared.lock();
+        if !state.is_open {
+            return Poll::Ready(Err(disconnected()));
+        }
+        if state.weight < shared.capacity {
+            state.send_tasks.remove(&self.id);
+            Poll::Ready(Ok(()))
+        } else {
+            state.send_tasks.insert(self.id, cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    /// Returns whether this channel is closed without needing a context.
+    pub fn is_closed(&self) -> bool {
+        self.shared.as_ref().map(|shared| !shared.lock().is_open).unwrap_or(true)
+    }
+
+    /// Returns the total weight of the messages queued in the channel.
+    pub fn weight(&self) -> usize {
+        self.shared.as_ref().map(|shared| shared.lock().weight).unwrap_or(0)
+    }
+
+    /// Returns the total weight of messages the channel can hold.
+    pub fn capacity(&self) -> usize {
+        self.shared.as_ref().map(|shared| shared.capacity).unwrap_or(0)
+    }
+
+    /// Disconnects this sender from the channel, closing it if there are no
+    /// more senders left.
+    pub fn disconnect(&mut self) {
+        if let Some(shared) = self.shared.take() {
+            let mut state = shared.lock();
+            state.send_tasks.remove(&self.id);
+            state.num_senders -= 1;
+            if state.num_senders == 0 {
+                if let Some(task) = state.recv_task.take() {
+                    task.wake();
+                }
+            }
+        }
+    }
+
+    /// Returns whether the senders send to the same receiver.
+    pub fn same_receiver(&self, other: &Self) -> bool {
+        match (&self.shared, &other.shared) {
+            (Some(shared), Some(other)) => Arc::ptr_eq(shared, other),
+            _ => false,
+        }
+    }
+}
+
+impl<T> Clone for WeightedSender<T> {
+    fn clone(&self) -> WeightedSender<T> {
+        match &self.shared {
+            Some(shared) => {
+                let mut state = shared.lock();
+                state.num_senders += 1;
+                let id = state.next_id;
+                state.next_id += 1;
+                WeightedSender { shared: Some(shared.clone()), id }
+            }
+            None => WeightedSender { shared: None, id: 0 },
+        }
+    }
+}
+
+impl<T> Drop for WeightedSender<T> {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+impl<T> WeightedReceiver<T> {
+    /// Closes the receiving half of a channel, without dropping it.
+    ///
+    /// This prevents any further messages from being sent on the channel while
+    /// still enabling the receiver to drain messages that are buffered.
+    pub fn close(&mut self) {
+        let mut state = self.shared.lock();
+        state.is_open = false;
+        state.wake_senders();
+    }
+
+    /// Tries to receive the next message without notifying a context if
+    /// empty.
+    ///
+    /// Returns `Ok(None)` once all senders have been dropped and all messages
+    /// have been received.
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        match self.next_message(None) {
+            Poll::Ready(msg) => Ok(msg),
+            Poll::Pending => Err(TryRecvError { _inner: () }),
+        }
+    }
+
+    /// Returns the number of messages queued in the channel.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// Returns `true` if no messages are queued in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total weight of the messages queued in the channel.
+    pub fn weight(&self) -> usize {
+        self.shared.lock().weight
+    }
+
+    /// Returns the total weight of messages the channel can hold.
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+
+    fn next_message(&mut self, cx: Option<&mut Context<'_>>) -> Poll<Option<T>> {
+        let mut state = self.shared.lock();
+        if let Some((msg, weight)) = state.queue.pop_front() {
+            state.weight -= weight;
+            if state.weight < self.shared.capacity {
+                state.wake_senders();
+            }
+            return Poll::Ready(Some(msg));
+        }
+        if state.num_senders == 0 {
+            return Poll::Ready(None);
+        }
+        if let Some(cx) = cx {
+            state.recv_task = Some(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Stream for WeightedReceiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+        let msg = self.next_message(Some(cx));
+        if let Poll::Ready(None) = msg {
+            self.terminated = true;
+        }
+        msg
+    }
+}
+
+impl<T> FusedStream for WeightedReceiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<T> Drop for WeightedReceiver<T> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl<T> fmt::Debug for WeightedSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeightedSender")
+            .field("weight", &self.weight())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<T> fmt::Debug for WeightedReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeightedReceiver")
+            .field("weight", &self.weight())
+            .field("terminated", &self.terminated)
+            .finish()
+    }
+}
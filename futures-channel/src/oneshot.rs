@@ -1,6 +1,7 @@
 //! A channel for sending a single message between asynchronous tasks.
 
-use alloc::sync::Arc;
+use alloc::sync::{Arc, Weak};
+use alloc::vec::Vec;
 use core::fmt;
 use core::pin::Pin;
 use core::sync::atomic::AtomicBool;
@@ -66,6 +67,10 @@ struct Inner<T> {
     /// Like `rx_task` above, except for the task blocked in
     /// `Sender::poll_cancel`. Additionally, `Lock` cannot be `UnsafeCell`.
     tx_task: Lock<Option<Waker>>,
+
+    /// The pool this oneshot is returned to once both halves are dropped, if
+    /// it was created by a `Pool`.
+    pool: Option<Weak<PoolInner<T>>>,
 }
 
 /// Creates a new one-shot channel for sending values across asynchronous tasks.
@@ -100,7 +105,11 @@ struct Inner<T> {
 /// # futures::executor::block_on(t.join().unwrap());
 /// ```
 pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
-    let inner = Arc::new(Inner::new());
+    let inner = Arc::new(Inner::new(None));
+    channel_from(inner)
+}
+
+fn channel_from<T>(inner: Arc<Inner<T>>) -> (Sender<T>, Receiver<T>) {
     let receiver = Receiver {
         inner: inner.clone(),
     };
@@ -111,12 +120,30 @@ pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
 }
 
 impl<T> Inner<T> {
-    fn new() -> Inner<T> {
+    fn new(pool: Option<Weak<PoolInner<T>>>) -> Inner<T> {
         Inner {
             complete: AtomicBool::new(false),
             data: Lock::new(None),
             rx_task: Lock::new(None),
             tx_task: Lock::new(None),
+            pool,
+        }
+    }
+
+    /// Returns a oneshot which both halves are done with to its pool, if it
+    /// has one. Called by both halves when they are dropped, and the last one
+    /// to go finds it unshared.
+    fn recycle(this: &mut Arc<Inner<T>>) {
+        let pool = match this.pool.as_ref().and_then(Weak::upgrade) {
+            Some(pool) => pool,
+            None => return,
+        };
+        if let Some(inner) = Arc::get_mut(this) {
+            *inner.complete.get_mut() = false;
+            inner.data = Lock::new(None);
+            inner.rx_task = Lock::new(None);
+            inner.tx_task = Lock::new(None);
+            pool.put(this.clone());
         }
     }
 
@@ -367,11 +394,50 @@ impl<T> Sender<T> {
     pub fn is_canceled(&self) -> bool {
         self.inner.is_canceled()
     }
+
+    /// Tests to see whether this `Sender`'s corresponding `Receiver` is
+    /// still alive.
+    ///
+    /// This is the inverse of [`is_canceled`](Sender::is_canceled), and
+    /// likewise only reports the current state, which may be subject to
+    /// concurrent modification.
+    pub fn is_connected(&self) -> bool {
+        !self.inner.is_canceled()
+    }
+
+    /// Creates a future that resolves once this `Sender`'s corresponding
+    /// [`Receiver`](Receiver) has been dropped or closed.
+    ///
+    /// This allows abandoning expensive work for a result as soon as nobody
+    /// is interested in it any more, for example by racing the work against
+    /// this future.
+    pub fn cancellation(&mut self) -> Cancellation<'_, T> {
+        Cancellation { inner: self }
+    }
+}
+
+/// A future that resolves when the receiving end of a channel has hung up.
+///
+/// This is an `.await`-friendly interface around
+/// [`poll_cancel`](Sender::poll_cancel).
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct Cancellation<'a, T> {
+    inner: &'a mut Sender<T>,
+}
+
+impl<T> Future for Cancellation<'_, T> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.inner.poll_cancel(cx)
+    }
 }
 
 impl<T> Drop for Sender<T> {
     fn drop(&mut self) {
-        self.inner.drop_tx()
+        self.inner.drop_tx();
+        Inner::recycle(&mut self.inner);
     }
 }
 
@@ -427,6 +493,105 @@ impl<T> Future for Receiver<T> {
 
 impl<T> Drop for Receiver<T> {
     fn drop(&mut self) {
-        self.inner.drop_rx()
+        self.inner.drop_rx();
+        Inner::recycle(&mut self.inner);
+    }
+}
+
+/// A pool of oneshot channels which are reused once both of their halves
+/// have been dropped.
+///
+/// Every call to [`channel`] allocates the state shared by the two halves.
+/// Channels created by a pool instead return that state to the pool when
+/// they are done with, so workloads which create a lot of short-lived
+/// channels, such as request/response protocols, mostly avoid the allocator.
+///
+/// The pool keeps at most a given number of idle channels. Cloning a pool
+/// returns a handle to the same pool.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::oneshot;
+///
+/// let pool = oneshot::Pool::new(16);
+///
+/// let (tx, rx) = pool.channel();
+/// tx.send(1).unwrap();
+/// assert_eq!(futures::executor::block_on(rx), Ok(1));
+/// assert_eq!(pool.idle_count(), 1);
+///
+/// // Reuses the channel from above.
+/// let (_tx, _rx) = pool.channel();
+/// assert_eq!(pool.idle_count(), 0);
+/// ```
+pub struct Pool<T> {
+    inner: Arc<PoolInner<T>>,
+}
+
+struct PoolInner<T> {
+    idle: Lock<Vec<Arc<Inner<T>>>>,
+    max_idle: usize,
+}
+
+impl<T> Pool<T> {
+    /// Creates a pool which keeps up to `max_idle` channels for reuse.
+    pub fn new(max_idle: usize) -> Pool<T> {
+        Pool {
+            inner: Arc::new(PoolInner {
+                idle: Lock::new(Vec::new()),
+                max_idle,
+            }),
+        }
+    }
+
+    /// Creates a new oneshot channel, reusing an idle one if possible.
+    ///
+    /// See [`channel`].
+    pub fn channel(&self) -> (Sender<T>, Receiver<T>) {
+        let idle = self.inner.idle.try_lock().and_then(|mut idle| idle.pop());
+        let inner = idle.unwrap_or_else(|| {
+            Arc::new(Inner::new(Some(Arc::downgrade(&self.inner))))
+        });
+        channel_from(inner)
+    }
+
+    /// Returns the number of idle channels in the pool.
+    pub fn idle_count(&self) -> usize {
+        self.inner.idle.try_lock().map(|idle| idle.len()).unwrap_or(0)
+    }
+}
+
+impl<T> PoolInner<T> {
+    fn put(&self, inner: Arc<Inner<T>>) {
+        // The pool is only a cache, so give up on it instead of waiting.
+        if let Some(mut idle) = self.idle.try_lock() {
+            if idle.len() < self.max_idle {
+                idle.push(inner);
+            }
+        }
+    }
+}
+
+impl<T> Clone for Pool<T> {
+    fn clone(&self) -> Pool<T> {
+        Pool { inner: self.inner.clone() }
+    }
+}
+
+impl<T> fmt::Debug for Pool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field("idle", &self.idle_count())
+            .field("max_idle", &self.inner.max_idle)
+            .finish()
+    }
+}
+
+impl<T> fmt::Debug for PoolInner<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoolInner")
+            .field("max_idle", &self.max_idle)
+            .finish()
     }
 }
@@ -0,0 +1,367 @@
+//! A single-producer, single-consumer queue for sending values across
+//! asynchronous tasks.
+//!
+//! This channel works like a bounded [`mpsc`](crate::mpsc) channel, but as
+//! the [`Sender`] cannot be cloned, it gets away with a lock-free ring buffer
+//! of fixed capacity and does not need to queue parked senders. Each side
+//! only touches the waker of the other side when that side is actually
+//! waiting, so sending and receiving on a busy channel costs a few atomic
+//! operations.
+//!
+//! The error types are shared with the [`mpsc`](crate::mpsc) module.
+
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+use futures_core::task::__internal::AtomicWaker;
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize};
+use std::sync::atomic::Ordering::SeqCst;
+
+pub use crate::mpsc::{SendError, TryRecvError, TrySendError};
+use crate::mpsc::SendErrorKind;
+
+/// The transmission end of a spsc channel.
+///
+/// This value is created by the [`channel`](channel) function.
+pub struct Sender<T> {
+    inner: Arc<Inner<T>>,
+}
+
+/// The receiving end of a spsc channel.
+///
+/// This value is created by the [`channel`](channel) function.
+pub struct Receiver<T> {
+    inner: Arc<Inner<T>>,
+    terminated: bool,
+}
+
+// The channel never projects Pin to the inner T
+impl<T> Unpin for Sender<T> {}
+impl<T> Unpin for Receiver<T> {}
+
+struct Inner<T> {
+    buffer: Box<[UnsafeCell<MaybeUninit<T>>]>,
+
+    // Position of the next message to receive, only written by the receiver.
+    head: AtomicUsize,
+
+    // Position of the next message to send, only written by the sender.
+    tail: AtomicUsize,
+
+    // Set when the respective half is dropped or the receiver is closed.
+    tx_closed: AtomicBool,
+    rx_closed: AtomicBool,
+
+    // Set by each half right before it returns `Poll::Pending`, so the other
+    // half only has to wake it if it is actually waiting.
+    tx_parked: AtomicBool,
+    rx_parked: AtomicBool,
+    tx_task: AtomicWaker,
+    rx_task: AtomicWaker,
+}
+
+// The buffer slots are only accessed by the half which currently owns them,
+// as determined by `head` and `tail`.
+unsafe impl<T: Send> Send for Inner<T> {}
+unsafe impl<T: Send> Sync for Inner<T> {}
+
+/// Creates a bounded spsc channel for communicating between asynchronous
+/// tasks.
+///
+/// Up to `buffer` messages can be queued before the sender has to wait for
+/// the receiver.
+///
+/// # Panics
+///
+/// This function panics if `buffer` is zero.
+pub fn channel<T>(buffer: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(buffer > 0, "spsc channel buffer must be non-zero");
+    let inner = Arc::new(Inner {
+        buffer: (0..buffer).map(|_| UnsafeCell::new(MaybeUninit::uninit())).collect(),
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        tx_closed: AtomicBool::new(false),
+        rx_closed: AtomicBool::new(false),
+        tx_parked: AtomicBool::new(false),
+        rx_parked: AtomicBool::new(false),
+        tx_task: AtomicWaker::new(),
+        rx_task: AtomicWaker::new(),
+    });
+    let rx = Receiver { inner: inner.clone(), terminated: false };
+    (Sender { inner }, rx)
+}
+
+impl<T> Inner<T> {
+    fn slot(&self, pos: usize) -> *mut MaybeUninit<T> {
+        self.buffer[pos % self.buffer.len()].get()
+    }
+
+    fn is_full(&self) -> bool {
+        let tail = self.tail.load(SeqCst);
+        tail.wrapping_sub(self.head.load(SeqCst)) == self.buffer.len()
+    }
+
+    fn wake_tx(&self) {
+        if self.tx_parked.load(SeqCst) && self.tx_parked.swap(false, SeqCst) {
+            self.tx_task.wake();
+        }
+    }
+
+    fn wake_rx(&self) {
+        if self.rx_parked.load(SeqCst) && self.rx_parked.swap(false, SeqCst) {
+            self.rx_task.wake();
+        }
+    }
+}
+
+impl<T> Drop for Inner<T> {
+    fn drop(&mut self) {
+        let tail = *self.tail.get_mut();
+        let mut head = *self.head.get_mut();
+        while head != tail {
+            unsafe { std::ptr::drop_in_place((*self.slot(head)).as_mut_ptr()) };
+            head = head.wrapping_add(1);
+        }
+    }
+}
+
+impl<T> Sender<T> {
+    /// Attempts to send a message on this `Sender`, returning the message
+    /// if there was an error.
+    pub fn try_send(&mut self, msg: T) -> Result<(), TrySendError<T>> {
+        let inner = &*self.inner;
+        if inner.rx_closed.load(SeqCst) {
+            return Err(TrySendError {
+                err: SendError { kind: SendErrorKind::Disconnected },
+                val: msg,
+            });
+        }
+        if inner.is_full() {
+            return Err(TrySendError {
+                err: SendError { kind: SendErrorKind::Full },
+                val: msg,
+            });
+        }
+
+        let tail = inner.tail.load(SeqCst);
+        unsafe { (*inner.slot(tail)).as_mut_ptr().write(msg) };
+        inner.tail.store(tail.wrapping_add(1), SeqCst);
+        inner.wake_rx();
+        Ok(())
+    }
+
+    /// Send a message on the channel.
+    ///
+    /// This function should only be called after
+    /// [`poll_ready`](Sender::poll_ready) has reported that the channel is
+    /// ready to receive a message.
+    pub fn start_send(&mut self, msg: T) -> Result<(), SendError> {
+        self.try_send(msg).map_err(|e| e.err)
+    }
+
+    /// Polls the channel to determine if there is capacity to send a message.
+    ///
+    /// As there is only one sender, the capacity stays available until the
+    /// next message is sent.
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SendError>> {
+        let inner = &*self.inner;
+        if inner.rx_closed.load(SeqCst) {
+            return Poll::Ready(Err(SendError { kind: SendErrorKind::Disconnected }));
+        }
+        if !inner.is_full() {
+            return Poll::Ready(Ok(()));
+        }
+
+        inner.tx_task.register(cx.waker());
+        inner.tx_parked.store(true, SeqCst);
+        // Check again, the receiver may have made room before it could see
+        // that this sender is parked.
+        if inner.rx_closed.load(SeqCst) {
+            Poll::Ready(Err(SendError { kind: SendErrorKind::Disconnected }))
+        } else if inner.is_full() {
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    /// Returns whether this channel is closed without needing a context.
+    pub fn is_closed(&self) -> bool {
+        self.inner.rx_closed.load(SeqCst)
+    }
+
+    /// Returns the number of messages queued in the channel.
+    pub fn len(&self) -> usize {
+        // Load `head` first, it never overtakes `tail`.
+        let head = self.inner.head.load(SeqCst);
+        self.inner.tail.load(SeqCst).wrapping_sub(head)
+    }
+
+    /// Returns `true` if no messages are queued in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of messages the channel can hold.
+    pub fn capacity(&self) -> usize {
+        self.inner.buffer.len()
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.inner.tx_closed.store(true, SeqCst);
+        self.inner.rx_parked.store(false, SeqCst);
+        self.inner.rx_task.wake();
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Closes the receiving half of a channel, without dropping it.
+    ///
+    /// This prevents any further messages from being sent on the channel while
+    /// still enabling the receiver to drain messages that are buffered.
+    pub fn close(&mut self) {
+        self.inner.rx_closed.store(true, SeqCst);
+        self.inner.tx_parked.store(false, SeqCst);
+        self.inner.tx_task.wake();
+    }
+
+    /// Tries to receive the next message without notifying a context if
+    /// empty.
+    ///
+    /// Returns `Ok(None)` once the sender has been dropped and all messages
+    /// have been received.
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        match self.next_message() {
+            Poll::Ready(msg) => Ok(msg),
+            Poll::Pending => Err(TryRecvError { _inner: () }),
+        }
+    }
+
+    /// Returns the number of messages queued in the channel.
+    pub fn len(&self) -> usize {
+        // Load `head` first, it never overtakes `tail`.
+        let head = self.inner.head.load(SeqCst);
+        self.inner.tail.load(SeqCst).wrapping_sub(head)
+    }
+
+    /// Returns `true` if no messages are queued in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn next_message(&mut self) -> Poll<Option<T>> {
+        let inner = &*self.inner;
+        let head = inner.head.load(SeqCst);
+        if head == inner.tail.load(SeqCst) {
+            if !inner.tx_closed.load(SeqCst) {
+                return Poll::Pending;
+            }
+            // The sender publishes its last message before closing, so
+            // check the queue once more after seeing it closed.
+            if head == inner.tail.load(SeqCst) {
+                return Poll::Ready(None);
+            }
+        }
+
+        let msg = unsafe { (*inner.slot(head)).as_ptr().read() };
+        inner.head.store(head.wrapping_add(1), SeqCst);
+        inner.wake_tx();
+        Poll::Ready(Some(msg))
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+        let msg = match self.next_message() {
+            Poll::Ready(msg) => msg,
+            Poll::Pending => {
+                self.inner.rx_task.register(cx.waker());
+                self.inner.rx_parked.store(true, SeqCst);
+                // Check again, the sender may have sent a message before it
+                // could see that this receiver is parked.
+                match self.next_message() {
+                    Poll::Ready(msg) => msg,
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+        };
+        if msg.is_none() {
+            self.terminated = true;
+        }
+        Poll::Ready(msg)
+    }
+}
+
+impl<T> FusedStream for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+#[cfg(feature = "sink")]
+impl<T> futures_sink::Sink<T> for Sender<T> {
+    type Error = SendError;
+
+    fn poll_ready(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        (*self).poll_ready(cx)
+    }
+
+    fn start_send(
+        mut self: Pin<&mut Self>,
+        msg: T,
+    ) -> Result<(), Self::Error> {
+        (*self).start_send(msg)
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("len", &self.len())
+            .field("terminated", &self.terminated)
+            .finish()
+    }
+}
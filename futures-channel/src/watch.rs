@@ -0,0 +1,360 @@
+//! A single-producer, multi-consumer channel which only keeps the latest
+//! value.
+//!
+//! The channel always holds a value, which receivers can look at with
+//! [`Receiver::borrow`] at any time. Every value sent replaces the previous
+//! one and increments the version of the channel, and receivers can wait for
+//! a value newer than the last one they saw with [`Receiver::changed`].
+//! Intermediate values may be skipped if a receiver is slower than the
+//! sender, which makes this channel a good fit for propagating configuration
+//! or state where only the most recent value matters.
+//!
+//! Receivers can also be used as a [`Stream`](futures_core::stream::Stream)
+//! of the values they observe. The stream ends once the [`Sender`] has been
+//! dropped.
+
+use futures_core::future::Future;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+
+/// The transmission end of a watch channel.
+///
+/// This value is created by the [`channel`](channel) function.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The receiving end of a watch channel.
+///
+/// This value is created by the [`channel`](channel) function or the
+/// [`Sender::subscribe`](Sender::subscribe) method.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    // Version of the last value this receiver has seen.
+    version: u64,
+    id: u64,
+    terminated: bool,
+}
+
+// The channel never projects Pin to the inner T
+impl<T> Unpin for Sender<T> {}
+impl<T> Unpin for Receiver<T> {}
+
+/// A reference to the current value of a watch channel.
+///
+/// The sender cannot update the value while this reference is held, so it
+/// should be dropped quickly.
+pub struct Ref<'a, T> {
+    guard: RwLockReadGuard<'a, Value<T>>,
+}
+
+/// The error returned by [`Sender::send`](Sender::send) when there are no
+/// receivers left, containing the value that could not be sent.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+/// The error returned by [`Receiver::changed`](Receiver::changed) when the
+/// sender has been dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError(());
+
+struct Shared<T> {
+    value: RwLock<Value<T>>,
+    state: Mutex<State>,
+}
+
+struct Value<T> {
+    value: T,
+    version: u64,
+}
+
+struct State {
+    closed: bool,
+    num_receivers: usize,
+    next_id: u64,
+    wakers: HashMap<u64, Waker>,
+}
+
+/// Creates a watch channel holding `initial`.
+///
+/// Receivers consider the initial value as seen, so
+/// [`changed`](Receiver::changed) only resolves once a new value is sent.
+pub fn channel<T>(initial: T) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        value: RwLock::new(Value { value: initial, version: 0 }),
+        state: Mutex::new(State {
+            closed: false,
+            num_receivers: 1,
+            next_id: 1,
+            wakers: HashMap::new(),
+        }),
+    });
+    let rx = Receiver {
+        shared: shared.clone(),
+        version: 0,
+        id: 0,
+        terminated: false,
+    };
+    (Sender { shared }, rx)
+}
+
+impl<T> Shared<T> {
+    fn version(&self) -> u64 {
+        self.value.read().unwrap().version
+    }
+
+    fn new_receiver(this: &Arc<Self>, version: u64) -> Receiver<T> {
+        let mut state = this.state.lock().unwrap();
+        state.num_receivers += 1;
+        let id = state.next_id;
+        state.next_id += 1;
+        Receiver { shared: this.clone(), version, id, terminated: false }
+    }
+
+    fn wake_all(&self) {
+        let wakers: Vec<_> = self.state.lock().unwrap().wakers.drain().map(|(_, w)| w).collect();
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Sender<T> {
+    /// Replaces the value of the channel and notifies all receivers.
+    ///
+    /// An error containing the value is returned if there are no receivers.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        if self.receiver_count() == 0 {
+            return Err(SendError(value));
+        }
+        self.send_replace(value);
+        Ok(())
+    }
+
+    /// Replaces the value of the channel and notifies all receivers, even if
+    /// there are none, returning the previous value.
+    pub fn send_replace(&self, value: T) -> T {
+        let old = {
+            let mut current = self.shared.value.write().unwrap();
+            current.version += 1;
+            std::mem::replace(&mut current.value, value)
+        };
+        self.shared.wake_all();
+        old
+    }
+
+    /// Returns a reference to the current value.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref { guard: self.shared.value.read().unwrap() }
+    }
+
+    /// Returns the version of the current value, which is incremented by
+    /// every value sent.
+    pub fn version(&self) -> u64 {
+        self.shared.version()
+    }
+
+    /// Creates a new receiver which considers the current value as seen.
+    pub fn subscribe(&self) -> Receiver<T> {
+        Shared::new_receiver(&self.shared, self.shared.version())
+    }
+
+    /// Returns the number of receivers of this channel.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.state.lock().unwrap().num_receivers
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.shared.state.lock().unwrap().closed = true;
+        self.shared.wake_all();
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Returns a reference to the current value.
+    ///
+    /// This does not mark the value as seen, see
+    /// [`borrow_and_update`](Receiver::borrow_and_update).
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref { guard: self.shared.value.read().unwrap() }
+    }
+
+    /// Returns a reference to the current value and marks it as seen.
+    pub fn borrow_and_update(&mut self) -> Ref<'_, T> {
+        let guard = self.shared.value.read().unwrap();
+        self.version = guard.version;
+        Ref { guard }
+    }
+
+    /// Returns the version of the last value this receiver has seen.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Returns `true` if a value newer than the last one seen by this
+    /// receiver is available.
+    pub fn has_changed(&self) -> bool {
+        self.shared.version() != self.version
+    }
+
+    /// Polls for a value newer than the last one seen by this receiver, and
+    /// marks it as seen.
+    ///
+    /// Returns `Poll::Ready(Err(RecvError))` if there is no newer value and
+    /// the sender has been dropped.
+    pub fn poll_changed(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), RecvError>> {
+        if let Some(result) = self.check_changed() {
+            return Poll::Ready(result);
+        }
+        self.shared.state.lock().unwrap().wakers.insert(self.id, cx.waker().clone());
+        // Check again in case a value was sent before the waker was
+        // registered.
+        match self.check_changed() {
+            Some(result) => Poll::Ready(result),
+            None => Poll::Pending,
+        }
+    }
+
+    /// Waits for a value newer than the last one seen by this receiver, and
+    /// marks it as seen.
+    ///
+    /// The returned future resolves to an error if there is no newer value
+    /// and the sender has been dropped.
+    pub fn changed(&mut self) -> Changed<'_, T> {
+        Changed { receiver: self }
+    }
+
+    fn check_changed(&mut self) -> Option<Result<(), RecvError>> {
+        let version = self.shared.version();
+        if version != self.version {
+            self.version = version;
+            Some(Ok(()))
+        } else if self.shared.state.lock().unwrap().closed {
+            Some(Err(RecvError(())))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    /// Creates a new receiver which has seen the same values as this one.
+    fn clone(&self) -> Receiver<T> {
+        let mut rx = Shared::new_receiver(&self.shared, self.version);
+        rx.terminated = self.terminated;
+        rx
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.num_receivers -= 1;
+        state.wakers.remove(&self.id);
+    }
+}
+
+impl<T: Clone> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+        match futures_core::ready!(self.poll_changed(cx)) {
+            Ok(()) => Poll::Ready(Some(self.borrow_and_update().clone())),
+            Err(_) => {
+                self.terminated = true;
+                Poll::Ready(None)
+            }
+        }
+    }
+}
+
+impl<T: Clone> FusedStream for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+/// Future for the [`changed`](Receiver::changed) method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Changed<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<T> Future for Changed<'_, T> {
+    type Output = Result<(), RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.receiver.poll_changed(cx)
+    }
+}
+
+impl<T> Deref for Ref<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard.value
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender")
+            .field("value", &*self.borrow())
+            .finish()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Changed<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Changed")
+            .field("receiver", &self.receiver)
+            .finish()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Ref<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SendError").finish()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "send failed because there are no receivers")
+    }
+}
+
+impl<T> Error for SendError<T> {}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "receive failed because the sender was dropped")
+    }
+}
+
+impl Error for RecvError {}
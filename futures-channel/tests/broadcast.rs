@@ -0,0 +1,106 @@
+use futures::channel::broadcast::{self, Lagged, TryRecvError};
+use futures::executor::{block_on, block_on_stream};
+use futures::stream::StreamExt;
+use futures::task::{Context, Poll};
+use futures_test::task::{new_count_waker, noop_context};
+use std::thread;
+
+#[test]
+fn every_receiver_sees_every_message() {
+    let (tx, rx1) = broadcast::channel::<i32>(4);
+    let rx2 = tx.subscribe();
+    assert_eq!(tx.receiver_count(), 2);
+
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    drop(tx);
+
+    assert_eq!(block_on(rx1.collect::<Vec<_>>()), vec![Ok(1), Ok(2)]);
+    assert_eq!(block_on(rx2.collect::<Vec<_>>()), vec![Ok(1), Ok(2)]);
+}
+
+#[test]
+fn subscribe_starts_at_next_message() {
+    let (tx, mut rx1) = broadcast::channel::<i32>(4);
+    tx.send(1).unwrap();
+    let mut rx2 = tx.subscribe();
+    tx.send(2).unwrap();
+
+    assert_eq!(rx1.try_recv(), Ok(1));
+    assert_eq!(rx1.try_recv(), Ok(2));
+    assert_eq!(rx2.try_recv(), Ok(2));
+    assert_eq!(rx2.try_recv(), Err(TryRecvError::Empty));
+
+    let mut rx3 = rx1.clone();
+    tx.send(3).unwrap();
+    assert_eq!(rx1.len(), 1);
+    assert_eq!(rx3.try_recv(), Ok(3));
+    drop(tx);
+    assert_eq!(rx3.try_recv(), Err(TryRecvError::Closed));
+    assert_eq!(rx1.try_recv(), Ok(3));
+    assert_eq!(rx1.try_recv(), Err(TryRecvError::Closed));
+}
+
+#[test]
+fn lagged_receiver() {
+    let (tx, rx) = broadcast::channel::<i32>(2);
+    for i in 0..5 {
+        tx.send(i).unwrap();
+    }
+    drop(tx);
+
+    let items: Vec<_> = block_on_stream(rx).collect();
+    assert_eq!(items, vec![Err(Lagged(3)), Ok(3), Ok(4)]);
+}
+
+#[test]
+fn send_without_receivers() {
+    let (tx, rx) = broadcast::channel::<i32>(2);
+    drop(rx);
+    assert_eq!(tx.send(1).unwrap_err().0, 1);
+
+    let mut rx = tx.subscribe();
+    tx.send(2).unwrap();
+    assert_eq!(rx.try_recv(), Ok(2));
+}
+
+#[test]
+fn wakes_receivers() {
+    let (tx, mut rx1) = broadcast::channel::<i32>(2);
+    let mut rx2 = rx1.clone();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(rx1.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(rx2.poll_next_unpin(&mut cx), Poll::Pending);
+    tx.send(1).unwrap();
+    assert_eq!(count, 2);
+
+    assert_eq!(rx1.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(Ok(1))));
+    assert_eq!(rx1.poll_next_unpin(&mut cx), Poll::Pending);
+    drop(tx);
+    assert_eq!(count, 3);
+    assert_eq!(rx1.poll_next_unpin(&mut cx), Poll::Ready(None));
+    assert_eq!(rx2.poll_next_unpin(&mut cx), Poll::Ready(Some(Ok(1))));
+}
+
+#[test]
+fn across_threads() {
+    let (tx, rx) = broadcast::channel::<usize>(1000);
+    let receivers: Vec<_> = (0..4)
+        .map(|_| {
+            let rx = rx.clone();
+            thread::spawn(move || block_on(rx.map(Result::unwrap).collect::<Vec<_>>()))
+        })
+        .collect();
+    drop(rx);
+
+    for i in 0..1000 {
+        tx.send(i).unwrap();
+    }
+    drop(tx);
+
+    for handle in receivers {
+        assert_eq!(handle.join().unwrap(), (0..1000).collect::<Vec<_>>());
+    }
+}
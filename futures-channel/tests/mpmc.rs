@@ -0,0 +1,104 @@
+use futures::channel::mpmc;
+use futures::executor::block_on;
+use futures::sink::SinkExt;
+use futures::stream::StreamExt;
+use futures::task::{Context, Poll};
+use futures_test::task::{new_count_waker, noop_context};
+use std::thread;
+
+#[test]
+fn each_message_delivered_once() {
+    let (mut tx, mut rx1) = mpmc::channel::<i32>(4);
+    let mut rx2 = rx1.clone();
+    assert_eq!(rx1.receiver_count(), 2);
+
+    for i in 0..4 {
+        tx.try_send(i).unwrap();
+    }
+    assert!(tx.try_send(4).unwrap_err().is_full());
+
+    assert_eq!(rx1.try_next().unwrap(), Some(0));
+    assert_eq!(rx2.try_next().unwrap(), Some(1));
+    assert_eq!(rx2.try_next().unwrap(), Some(2));
+    assert_eq!(rx1.try_next().unwrap(), Some(3));
+    assert!(rx1.try_next().is_err());
+    drop(tx);
+    assert_eq!(rx1.try_next().unwrap(), None);
+    assert_eq!(rx2.try_next().unwrap(), None);
+}
+
+#[test]
+fn wakes_one_receiver_per_message() {
+    let (mut tx, mut rx1) = mpmc::channel::<i32>(4);
+    let mut rx2 = rx1.clone();
+    let (waker1, count1) = new_count_waker();
+    let (waker2, count2) = new_count_waker();
+
+    assert_eq!(rx1.poll_next_unpin(&mut Context::from_waker(&waker1)), Poll::Pending);
+    assert_eq!(rx2.poll_next_unpin(&mut Context::from_waker(&waker2)), Poll::Pending);
+    tx.try_send(1).unwrap();
+    assert_eq!(count1.get() + count2.get(), 1);
+    tx.try_send(2).unwrap();
+    assert_eq!(count1.get() + count2.get(), 2);
+
+    drop(tx);
+    let mut cx = noop_context();
+    assert_eq!(rx1.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(rx2.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(rx1.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn dropped_receiver_hands_over_wakeup() {
+    let (mut tx, mut rx1) = mpmc::channel::<i32>(4);
+    let mut rx2 = rx1.clone();
+    let (waker1, count1) = new_count_waker();
+    let (waker2, count2) = new_count_waker();
+
+    assert_eq!(rx1.poll_next_unpin(&mut Context::from_waker(&waker1)), Poll::Pending);
+    assert_eq!(rx2.poll_next_unpin(&mut Context::from_waker(&waker2)), Poll::Pending);
+    tx.try_send(1).unwrap();
+    if count1.get() == 1 {
+        drop(rx1);
+        assert_eq!(count2, 1);
+    } else {
+        drop(rx2);
+        assert_eq!(count1, 1);
+    }
+}
+
+#[test]
+fn disconnect() {
+    let (mut tx, rx1) = mpmc::channel::<i32>(1);
+    let tx2 = tx.clone();
+    assert!(tx.same_channel(&tx2));
+    let rx2 = rx1.clone();
+    drop(rx1);
+    assert!(!tx.is_closed());
+    drop(rx2);
+    assert!(tx.is_closed());
+    assert!(tx.try_send(1).unwrap_err().is_disconnected());
+}
+
+#[test]
+fn work_distribution() {
+    let (mut tx, rx) = mpmc::channel::<u32>(8);
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let rx = rx.clone();
+            thread::spawn(move || block_on(rx.collect::<Vec<_>>()))
+        })
+        .collect();
+    drop(rx);
+
+    block_on(async {
+        for i in 0..1000 {
+            tx.send(i).await.unwrap();
+        }
+    });
+    drop(tx);
+
+    let mut all: Vec<_> = workers.into_iter().flat_map(|w| w.join().unwrap()).collect();
+    all.sort();
+    assert_eq!(all, (0..1000).collect::<Vec<_>>());
+}
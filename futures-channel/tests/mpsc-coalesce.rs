@@ -0,0 +1,73 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::sink::SinkExt;
+use futures::stream::StreamExt;
+use futures::task::{Context, Poll};
+use futures_test::task::new_count_waker;
+
+#[test]
+fn replaces_queued_message() {
+    let (tx, rx) = mpsc::coalescing_channel::<&str, i32>();
+    tx.send("a", 1).unwrap();
+    tx.send("b", 1).unwrap();
+    tx.send("a", 2).unwrap();
+    tx.send("c", 1).unwrap();
+    tx.send("b", 2).unwrap();
+    assert_eq!(tx.coalesced_count(), 2);
+    assert_eq!(rx.len(), 3);
+    drop(tx);
+
+    let msgs: Vec<_> = block_on(rx.collect());
+    assert_eq!(msgs, vec![("a", 2), ("b", 2), ("c", 1)]);
+}
+
+#[test]
+fn merges_queued_message() {
+    let (tx, mut rx) = mpsc::coalescing_channel_with(|old: &mut Vec<i32>, new| old.extend(new));
+    tx.send(1, vec![1]).unwrap();
+    tx.send(1, vec![2, 3]).unwrap();
+    assert_eq!(rx.try_next().unwrap(), Some((1, vec![1, 2, 3])));
+
+    // Once received, a key starts over.
+    tx.send(1, vec![4]).unwrap();
+    assert_eq!(rx.try_next().unwrap(), Some((1, vec![4])));
+    assert!(rx.try_next().is_err());
+}
+
+#[test]
+fn wakes_receiver_for_new_keys() {
+    let (tx, mut rx) = mpsc::coalescing_channel::<u8, u8>();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Pending);
+    tx.send(1, 1).unwrap();
+    assert_eq!(count, 1);
+    tx.send(1, 2).unwrap();
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(Some((1, 2))));
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Pending);
+    drop(tx);
+    assert_eq!(count, 2);
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn closed_receiver() {
+    let (tx, mut rx) = mpsc::coalescing_channel::<u8, u8>();
+    tx.send(1, 1).unwrap();
+    rx.close();
+    assert!(tx.is_closed());
+    assert_eq!(tx.send(2, 2).unwrap_err().into_inner(), (2, 2));
+    assert_eq!(rx.try_next().unwrap(), Some((1, 1)));
+}
+
+#[test]
+fn sink() {
+    let (mut tx, rx) = mpsc::coalescing_channel::<u8, u8>();
+    block_on(async {
+        SinkExt::send(&mut tx, (1, 1)).await.unwrap();
+        SinkExt::send(&mut tx, (1, 2)).await.unwrap();
+        tx.close().await.unwrap();
+    });
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), vec![(1, 2)]);
+}
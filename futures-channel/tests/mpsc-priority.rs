@@ -0,0 +1,110 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::sink::SinkExt;
+use futures::stream::StreamExt;
+use futures::task::{Context, Poll};
+use futures_test::task::{new_count_waker, noop_context};
+use std::cmp::Ordering;
+use std::thread;
+
+#[test]
+fn greatest_first() {
+    let (mut tx, rx) = mpsc::priority_channel::<(u8, &str)>(8);
+    tx.try_send((1, "bulk a")).unwrap();
+    tx.try_send((9, "control")).unwrap();
+    tx.try_send((1, "bulk b")).unwrap();
+    tx.try_send((5, "normal")).unwrap();
+    drop(tx);
+
+    let msgs: Vec<_> = block_on(rx.collect());
+    assert_eq!(msgs, vec![(9, "control"), (5, "normal"), (1, "bulk b"), (1, "bulk a")]);
+}
+
+#[test]
+fn equal_messages_in_send_order() {
+    // Orders by priority only, ignoring the payload.
+    #[derive(Debug)]
+    struct Msg(u8, u32);
+
+    impl PartialEq for Msg {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+    impl Eq for Msg {}
+    impl PartialOrd for Msg {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Msg {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let (mut tx, mut rx) = mpsc::priority_channel::<Msg>(8);
+    for i in 0..6 {
+        tx.try_send(Msg((i % 2) as u8, i)).unwrap();
+    }
+    let order: Vec<_> = (0..6).map(|_| rx.try_next().unwrap().unwrap().1).collect();
+    assert_eq!(order, [1, 3, 5, 0, 2, 4]);
+}
+
+#[test]
+fn backpressure() {
+    let (mut tx, mut rx) = mpsc::priority_channel::<i32>(1);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+    tx.start_send(1).unwrap();
+    assert!(tx.try_send(2).unwrap_err().is_full());
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Pending);
+
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(count, 1);
+    assert!(rx.try_next().is_err());
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+
+    // The slot reserved by `poll_ready` is not available to other senders.
+    let mut tx2 = tx.clone();
+    assert!(tx2.try_send(3).unwrap_err().is_full());
+    tx.try_send(2).unwrap();
+    assert_eq!(rx.try_next().unwrap(), Some(2));
+}
+
+#[test]
+fn disconnect() {
+    let (mut tx, mut rx) = mpsc::priority_channel::<i32>(2);
+    let mut tx2 = tx.clone();
+    assert!(tx.same_receiver(&tx2));
+    tx.try_send(1).unwrap();
+    drop(tx);
+    assert_eq!(rx.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(1)));
+    assert_eq!(rx.poll_next_unpin(&mut noop_context()), Poll::Pending);
+
+    rx.close();
+    assert!(tx2.is_closed());
+    assert!(tx2.try_send(2).unwrap_err().is_disconnected());
+    drop(tx2);
+    assert_eq!(rx.try_next().unwrap(), None);
+}
+
+#[test]
+fn sink_across_threads() {
+    let (mut tx, rx) = mpsc::priority_channel::<u32>(4);
+    let handle = thread::spawn(move || {
+        block_on(async {
+            for i in 0..100 {
+                tx.send(i).await.unwrap();
+            }
+        })
+    });
+
+    let mut msgs: Vec<_> = block_on(rx.collect());
+    handle.join().unwrap();
+    assert_eq!(msgs.len(), 100);
+    msgs.sort();
+    assert_eq!(msgs, (0..100).collect::<Vec<_>>());
+}
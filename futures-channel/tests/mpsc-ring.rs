@@ -0,0 +1,61 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::sink::SinkExt;
+use futures::stream::{self, StreamExt};
+use futures::task::{Context, Poll};
+use futures_test::task::new_count_waker;
+
+#[test]
+fn evicts_oldest() {
+    let (tx, rx) = mpsc::ring_channel::<i32>(3);
+    for i in 0..3 {
+        assert_eq!(tx.send(i).unwrap(), None);
+    }
+    assert_eq!(tx.send(3).unwrap(), Some(0));
+    assert_eq!(tx.send(4).unwrap(), Some(1));
+    assert_eq!(tx.dropped_count(), 2);
+    assert_eq!(rx.dropped_count(), 2);
+    assert_eq!(rx.len(), 3);
+    drop(tx);
+
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), vec![2, 3, 4]);
+}
+
+#[test]
+fn wakes_receiver() {
+    let (tx, mut rx) = mpsc::ring_channel::<i32>(1);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Pending);
+    tx.send(1).unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Pending);
+    drop(tx);
+    assert_eq!(count, 2);
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn closed_receiver() {
+    let (tx, mut rx) = mpsc::ring_channel::<i32>(2);
+    let tx2 = tx.clone();
+    assert!(tx.same_receiver(&tx2));
+    tx.send(1).unwrap();
+    rx.close();
+    assert!(tx2.is_closed());
+    assert!(tx2.send(2).unwrap_err().is_disconnected());
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert!(rx.try_next().is_err());
+    drop((tx, tx2));
+    assert_eq!(rx.try_next().unwrap(), None);
+}
+
+#[test]
+fn sink_never_waits() {
+    let (mut tx, rx) = mpsc::ring_channel::<i32>(2);
+    block_on(tx.send_all(&mut stream::iter(0..10))).unwrap();
+    drop(tx);
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), vec![8, 9]);
+}
@@ -0,0 +1,88 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::sink::SinkExt;
+use futures::stream::StreamExt;
+use futures::task::{Context, Poll};
+use futures_test::task::{new_count_waker, noop_context};
+use std::thread;
+
+#[test]
+fn bounded_by_weight() {
+    let (mut tx, mut rx) = mpsc::weighted_channel(10, |msg: &Vec<u8>| msg.len());
+    tx.try_send(vec![0; 4]).unwrap();
+    tx.try_send(vec![0; 6]).unwrap();
+    assert_eq!(tx.weight(), 10);
+
+    let err = tx.try_send(vec![0; 1]).unwrap_err();
+    assert!(err.is_full());
+
+    assert_eq!(rx.try_next().unwrap().unwrap().len(), 4);
+    assert_eq!(rx.weight(), 6);
+    tx.try_send(vec![0; 3]).unwrap();
+    assert!(tx.try_send(vec![0; 2]).unwrap_err().is_full());
+    assert_eq!(rx.len(), 2);
+}
+
+#[test]
+fn oversized_message_accepted_when_empty() {
+    let (mut tx, mut rx) = mpsc::weighted_channel(10, |msg: &Vec<u8>| msg.len());
+    tx.try_send(vec![0; 100]).unwrap();
+    assert!(tx.try_send(vec![0; 1]).unwrap_err().is_full());
+    assert_eq!(rx.try_next().unwrap().unwrap().len(), 100);
+    assert_eq!(rx.weight(), 0);
+    tx.try_send(vec![0; 1]).unwrap();
+}
+
+#[test]
+fn poll_ready_waits_for_weight() {
+    let (mut tx, mut rx) = mpsc::weighted_channel(10, |msg: &u32| *msg as usize);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+    // `start_send` may overshoot the capacity.
+    tx.start_send(12).unwrap();
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Pending);
+
+    assert_eq!(rx.try_next().unwrap(), Some(12));
+    assert_eq!(count, 1);
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn close_rejects_sends() {
+    let (mut tx, mut rx) = mpsc::weighted_channel(10, |_: &u32| 1);
+    tx.try_send(1).unwrap();
+    rx.close();
+    assert!(tx.is_closed());
+    assert!(tx.try_send(2).unwrap_err().is_disconnected());
+    assert!(tx.poll_ready(&mut noop_context()).is_ready());
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    drop(tx);
+    assert_eq!(rx.try_next().unwrap(), None);
+}
+
+#[test]
+fn send_across_threads() {
+    let (tx, rx) = mpsc::weighted_channel(16, |msg: &String| msg.len());
+
+    let threads: Vec<_> = (0..4)
+        .map(|i| {
+            let mut tx = tx.clone();
+            thread::spawn(move || {
+                block_on(async {
+                    for j in 0..50 {
+                        tx.send(format!("{}:{}", i, j)).await.unwrap();
+                    }
+                })
+            })
+        })
+        .collect();
+    drop(tx);
+
+    let msgs: Vec<_> = block_on(rx.collect());
+    assert_eq!(msgs.len(), 200);
+    for t in threads {
+        t.join().unwrap();
+    }
+}
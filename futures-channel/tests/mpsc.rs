@@ -229,6 +229,49 @@ fn stress_shared_unbounded() {
     t.join().ok().unwrap();
 }
 
+#[test]
+fn stress_unbounded_keeps_order_per_sender() {
+    const AMT: usize = 10000;
+    const NTHREADS: usize = 8;
+    let (tx, rx) = mpsc::unbounded::<(usize, usize)>();
+
+    let threads: Vec<_> = (0..NTHREADS).map(|id| {
+        let tx = tx.clone();
+        thread::spawn(move|| {
+            for i in 0..AMT {
+                tx.unbounded_send((id, i)).unwrap();
+            }
+        })
+    }).collect();
+    drop(tx);
+
+    let mut next = vec![0; NTHREADS];
+    for (id, i) in block_on_stream(rx) {
+        assert_eq!(next[id], i);
+        next[id] += 1;
+    }
+    assert_eq!(next, vec![AMT; NTHREADS]);
+    for t in threads {
+        t.join().unwrap();
+    }
+}
+
+#[test]
+fn unreceived_messages_dropped() {
+    let msg = Arc::new(());
+    let (tx, mut rx) = mpsc::unbounded();
+    for _ in 0..100 {
+        tx.unbounded_send(msg.clone()).unwrap();
+    }
+    for _ in 0..40 {
+        block_on(rx.next()).unwrap();
+    }
+    assert_eq!(Arc::strong_count(&msg), 61);
+    drop(rx);
+    assert_eq!(Arc::strong_count(&msg), 1);
+    drop(tx);
+}
+
 #[test]
 fn stress_shared_bounded_hard() {
     const AMT: u32 = 10000;
@@ -571,3 +614,379 @@ fn send_backpressure_multi_senders() {
     let item = block_on(rx.next()).unwrap();
     assert_eq!(item, 2);
 }
+
+#[test]
+fn recv_many() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(8);
+    for i in 0..5 {
+        tx.try_send(i).unwrap();
+    }
+
+    let mut buf = Vec::new();
+    assert_eq!(block_on(rx.recv_many(&mut buf, 3)), 3);
+    assert_eq!(buf, [0, 1, 2]);
+    assert_eq!(block_on(rx.recv_many(&mut buf, 10)), 2);
+    assert_eq!(buf, [0, 1, 2, 3, 4]);
+    assert_eq!(block_on(rx.recv_many(&mut buf, 0)), 0);
+
+    drop(tx);
+    assert_eq!(block_on(rx.recv_many(&mut buf, 10)), 0);
+    assert_eq!(block_on(rx.recv_many(&mut buf, 10)), 0);
+    assert_eq!(buf.len(), 5);
+}
+
+#[test]
+fn recv_many_waits_for_messages() {
+    let (tx, mut rx) = mpsc::unbounded::<i32>();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut buf = Vec::new();
+
+    assert_eq!(rx.poll_recv_many(&mut cx, &mut buf, 4), Poll::Pending);
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(count, 1);
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(rx.poll_recv_many(&mut cx, &mut buf, 4), Poll::Ready(2));
+    assert_eq!(buf, [1, 2]);
+
+    assert_eq!(rx.poll_recv_many(&mut cx, &mut buf, 4), Poll::Pending);
+    drop(tx);
+    assert_eq!(rx.poll_recv_many(&mut cx, &mut buf, 4), Poll::Ready(0));
+}
+
+#[test]
+fn recv_many_unparks_senders() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+    let mut tx2 = tx.clone();
+    tx.try_send(1).unwrap();
+    tx2.try_send(2).unwrap();
+    assert!(tx.try_send(3).unwrap_err().is_full());
+
+    let mut buf = Vec::new();
+    assert_eq!(block_on(rx.recv_many(&mut buf, 10)), 2);
+    assert_eq!(buf, [1, 2]);
+    tx.try_send(3).unwrap();
+    tx2.try_send(4).unwrap();
+}
+
+#[test]
+fn reserve_send() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+
+    let permit = block_on(tx.reserve()).unwrap();
+    permit.send(1);
+    assert!(tx.try_reserve().unwrap_err().is_full());
+
+    let mut reserve = tx.reserve();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    assert!(reserve.poll_unpin(&mut cx).is_pending());
+
+    assert_eq!(block_on(rx.next()), Some(1));
+    assert_eq!(count, 1);
+    match reserve.poll_unpin(&mut cx) {
+        Poll::Ready(Ok(permit)) => permit.send(2),
+        _ => panic!("expected a permit"),
+    }
+    assert_eq!(block_on(rx.next()), Some(2));
+}
+
+#[test]
+fn reserve_unused_permit() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+
+    {
+        let _permit = tx.try_reserve().unwrap();
+    }
+    tx.try_reserve().unwrap().send(1);
+    assert_eq!(block_on(rx.next()), Some(1));
+}
+
+#[test]
+fn reserve_disconnected() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(1);
+
+    let permit = tx.try_reserve().unwrap();
+    rx.close();
+    permit.send(1);
+    assert_eq!(block_on(rx.next()), None);
+
+    assert!(tx.try_reserve().unwrap_err().is_disconnected());
+    assert!(block_on(tx.reserve()).unwrap_err().is_disconnected());
+}
+
+#[test]
+fn len_and_capacity() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(2);
+    let mut tx2 = tx.clone();
+    assert_eq!(tx.capacity(), 2);
+    assert_eq!(rx.capacity(), 2);
+    assert!(tx.is_empty());
+    assert!(!rx.is_full());
+
+    tx.try_send(1).unwrap();
+    tx2.try_send(2).unwrap();
+    assert_eq!(tx.len(), 2);
+    assert_eq!(rx.len(), 2);
+    assert!(tx.is_full());
+    assert!(rx.is_full());
+
+    // Each sender still has its guaranteed slot.
+    tx.try_send(3).unwrap();
+    assert_eq!(rx.len(), 3);
+    assert!(tx.try_send(4).unwrap_err().is_full());
+
+    assert_eq!(block_on(rx.next()), Some(1));
+    assert_eq!(rx.len(), 2);
+    assert_eq!(block_on(rx.next()), Some(2));
+    assert!(!rx.is_full());
+
+    tx.disconnect();
+    assert_eq!(tx.len(), 0);
+    assert_eq!(tx.capacity(), 0);
+    drop(tx2);
+    assert_eq!(block_on(rx.next()), Some(3));
+    assert_eq!(block_on(rx.next()), None);
+    assert_eq!(rx.len(), 0);
+}
+
+#[test]
+fn weak_sender() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(1);
+    let weak = tx.downgrade();
+
+    let mut tx2 = weak.upgrade().unwrap();
+    assert!(tx2.same_receiver(&tx));
+    tx2.try_send(1).unwrap();
+    drop(tx2);
+    tx.try_send(2).unwrap();
+    drop(tx);
+
+    // The weak sender does not keep the channel open.
+    assert_eq!(block_on_stream(&mut rx).collect::<Vec<_>>(), vec![1, 2]);
+    assert!(weak.upgrade().is_none());
+    assert!(weak.clone().upgrade().is_none());
+}
+
+#[test]
+fn weak_unbounded_sender() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let weak = tx.downgrade();
+    weak.upgrade().unwrap().unbounded_send(1).unwrap();
+    drop(tx);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), vec![1]);
+
+    let mut tx = mpsc::unbounded::<i32>().0;
+    tx.disconnect();
+    assert!(tx.downgrade().upgrade().is_none());
+}
+
+#[test]
+fn drain_after_close() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(4);
+    for i in 0..3 {
+        tx.try_send(i).unwrap();
+    }
+    rx.close();
+    assert!(tx.try_send(3).unwrap_err().is_disconnected());
+
+    assert_eq!(rx.drain().collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(rx.drain().next(), None);
+    assert_eq!(block_on(rx.next()), None);
+    assert_eq!(rx.drain().next(), None);
+}
+
+#[test]
+fn drain_does_not_wait() {
+    let (tx, mut rx) = mpsc::unbounded::<i32>();
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(rx.drain().collect::<Vec<_>>(), vec![1, 2]);
+
+    // The channel is still open, so more messages can arrive later.
+    tx.unbounded_send(3).unwrap();
+    assert_eq!(rx.drain().collect::<Vec<_>>(), vec![3]);
+    drop(tx);
+    assert_eq!(block_on(rx.next()), None);
+}
+
+#[test]
+fn peek() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(4);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(rx.poll_peek(&mut cx), Poll::Pending);
+    tx.try_send(1).unwrap();
+    assert_eq!(count, 1);
+    tx.try_send(2).unwrap();
+    assert_eq!(rx.poll_peek(&mut cx), Poll::Ready(Some(&1)));
+    assert_eq!(block_on(rx.peek()), Some(&1));
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(block_on(rx.peek()), Some(&2));
+
+    drop(tx);
+    assert_eq!(block_on(rx.peek()), Some(&2));
+    assert_eq!(block_on(rx.next()), Some(2));
+    assert_eq!(block_on(rx.peek()), None);
+    assert_eq!(block_on(rx.next()), None);
+    assert_eq!(block_on(rx.peek()), None);
+}
+
+#[test]
+fn peek_unbounded_across_threads() {
+    let (tx, mut rx) = mpsc::unbounded::<i32>();
+    let t = thread::spawn(move || {
+        for i in 0..100 {
+            tx.unbounded_send(i).unwrap();
+        }
+    });
+
+    for i in 0..100 {
+        assert_eq!(block_on(rx.peek()), Some(&i));
+        assert_eq!(block_on(rx.next()), Some(i));
+    }
+    t.join().unwrap();
+    assert_eq!(block_on(rx.peek()), None);
+}
+
+#[test]
+fn blocking_send_recv() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(1);
+
+    let t = thread::spawn(move || {
+        for i in 0..20 {
+            tx.blocking_send(i).unwrap();
+        }
+    });
+    for i in 0..20 {
+        assert_eq!(rx.blocking_recv(), Some(i));
+    }
+    t.join().unwrap();
+    assert_eq!(rx.blocking_recv(), None);
+}
+
+#[test]
+fn blocking_send_disconnected() {
+    let (mut tx, rx) = mpsc::channel::<i32>(0);
+    tx.blocking_send(1).unwrap();
+
+    let t = thread::spawn(move || tx.blocking_send(2));
+    drop(rx);
+    assert!(t.join().unwrap().unwrap_err().is_disconnected());
+}
+
+#[test]
+fn blocking_recv_unbounded() {
+    let (tx, mut rx) = mpsc::unbounded::<i32>();
+    let t = thread::spawn(move || {
+        for i in 0..20 {
+            tx.unbounded_send(i).unwrap();
+        }
+    });
+    for i in 0..20 {
+        assert_eq!(rx.blocking_recv(), Some(i));
+    }
+    t.join().unwrap();
+    assert_eq!(rx.blocking_recv(), None);
+}
+
+#[test]
+fn take_rejected() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(4);
+    tx.keep_rejected();
+    for i in 0..4 {
+        tx.try_send(i).unwrap();
+    }
+    assert_eq!(rx.try_next().unwrap(), Some(0));
+    drop(rx);
+
+    let tx2 = tx.clone();
+    assert_eq!(tx2.take_rejected(), vec![1, 2, 3]);
+    assert_eq!(tx.take_rejected(), Vec::<i32>::new());
+    assert_eq!(tx.try_send(4).unwrap_err().into_inner(), 4);
+}
+
+#[test]
+fn rejected_dropped_by_default() {
+    let msg = Arc::new(());
+    let (tx, rx) = mpsc::unbounded();
+    tx.unbounded_send(msg.clone()).unwrap();
+    drop(rx);
+    assert_eq!(Arc::strong_count(&msg), 1);
+    assert!(tx.take_rejected().is_empty());
+}
+
+#[test]
+fn take_rejected_unbounded_after_close() {
+    let (tx, mut rx) = mpsc::unbounded::<i32>();
+    tx.keep_rejected();
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    rx.close();
+    assert!(tx.take_rejected().is_empty());
+    drop(rx);
+    assert_eq!(tx.take_rejected(), vec![1, 2]);
+}
+
+#[test]
+fn receiver_set_is_fair() {
+    let mut set = mpsc::ReceiverSet::new();
+    let (mut tx_a, rx_a) = mpsc::channel::<i32>(8);
+    let (mut tx_b, rx_b) = mpsc::channel::<i32>(8);
+    assert!(set.insert("a", rx_a).is_none());
+    assert!(set.insert("b", rx_b).is_none());
+
+    for i in 0..4 {
+        tx_a.try_send(i).unwrap();
+    }
+    tx_b.try_send(10).unwrap();
+    tx_b.try_send(11).unwrap();
+
+    let mut cx = noop_context();
+    let mut msgs = Vec::new();
+    while let Poll::Ready(Some(msg)) = set.poll_next_unpin(&mut cx) {
+        msgs.push(msg);
+    }
+    assert_eq!(msgs, vec![("a", 0), ("b", 10), ("a", 1), ("b", 11), ("a", 2), ("a", 3)]);
+}
+
+#[test]
+fn receiver_set_removes_ended_receivers() {
+    let mut set = mpsc::ReceiverSet::new();
+    let (tx_a, rx_a) = mpsc::channel::<i32>(8);
+    let (mut tx_b, rx_b) = mpsc::channel::<i32>(8);
+    set.insert(1, rx_a);
+    set.insert(2, rx_b);
+
+    drop(tx_a);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(set.keys().collect::<Vec<_>>(), vec![&2]);
+
+    tx_b.try_send(1).unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Ready(Some((2, 1))));
+
+    assert!(set.remove(&2).is_some());
+    assert!(set.is_empty());
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn receiver_set_replaces_key() {
+    let mut set = mpsc::ReceiverSet::new();
+    let (_tx_a, rx_a) = mpsc::channel::<i32>(8);
+    let (mut tx_b, rx_b) = mpsc::channel::<i32>(8);
+    set.insert("key", rx_a);
+    let mut old = set.insert("key", rx_b).unwrap();
+    assert_eq!(set.len(), 1);
+    assert!(set.contains_key(&"key"));
+    assert!(old.try_next().is_err());
+
+    tx_b.try_send(5).unwrap();
+    drop(tx_b);
+    assert_eq!(block_on(set.collect::<Vec<_>>()), vec![("key", 5)]);
+}
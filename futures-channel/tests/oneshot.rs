@@ -263,3 +263,89 @@ fn cancel_sends() {
 //         },
 //     }
 // }
+
+#[test]
+fn cancellation() {
+    let (mut tx, rx) = oneshot::channel::<u32>();
+    assert!(tx.is_connected());
+
+    let t = thread::spawn(move || {
+        block_on(tx.cancellation());
+        assert!(!tx.is_connected());
+    });
+    drop(rx);
+    t.join().unwrap();
+}
+
+#[test]
+fn cancellation_after_close() {
+    let (mut tx, mut rx) = oneshot::channel::<u32>();
+    assert!(tx.cancellation().now_or_never().is_none());
+    rx.close();
+    assert!(!tx.is_connected());
+    assert_eq!(tx.cancellation().now_or_never(), Some(()));
+}
+
+#[test]
+fn pool_reuses_channels() {
+    let pool = oneshot::Pool::<u32>::new(2);
+    let (tx, rx) = pool.channel();
+    tx.send(1).unwrap();
+    assert_eq!(block_on(rx), Ok(1));
+    assert_eq!(pool.idle_count(), 1);
+
+    // A reused channel starts out fresh.
+    let (tx, mut rx) = pool.channel();
+    assert_eq!(pool.idle_count(), 0);
+    assert!(!tx.is_canceled());
+    assert_eq!(rx.try_recv(), Ok(None));
+    drop(tx);
+    assert_eq!(rx.try_recv(), Err(oneshot::Canceled));
+    drop(rx);
+
+    let channels: Vec<_> = (0..4).map(|_| pool.channel()).collect();
+    drop(channels);
+    assert_eq!(pool.idle_count(), 2);
+}
+
+#[test]
+fn pool_drops_unreceived_value() {
+    let value = std::sync::Arc::new(());
+    let pool = oneshot::Pool::new(2);
+    let (tx, rx) = pool.channel();
+    tx.send(value.clone()).unwrap();
+    drop(rx);
+    assert_eq!(std::sync::Arc::strong_count(&value), 1);
+    assert_eq!(pool.idle_count(), 1);
+}
+
+#[test]
+fn pool_outlived_by_channels() {
+    let pool = oneshot::Pool::<u32>::new(2);
+    let (tx, rx) = pool.channel();
+    drop(pool);
+    tx.send(1).unwrap();
+    assert_eq!(block_on(rx), Ok(1));
+}
+
+#[test]
+fn pool_across_threads() {
+    let pool = oneshot::Pool::new(8);
+    let t = {
+        let pool = pool.clone();
+        let (tx, rx) = mpsc::channel::<Sender<u32>>();
+        let t = thread::spawn(move || {
+            for tx in rx {
+                tx.send(7).unwrap();
+            }
+        });
+        for _ in 0..1000 {
+            let (otx, orx) = pool.channel();
+            tx.send(otx).unwrap();
+            assert_eq!(block_on(orx), Ok(7));
+        }
+        t
+    };
+    t.join().unwrap();
+    assert!(pool.idle_count() >= 1);
+}
@@ -0,0 +1,117 @@
+use futures::channel::spsc;
+use futures::executor::block_on;
+use futures::sink::SinkExt;
+use futures::stream::StreamExt;
+use futures::task::{Context, Poll};
+use futures_test::task::{new_count_waker, noop_context};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+#[test]
+fn send_recv() {
+    let (mut tx, mut rx) = spsc::channel::<i32>(2);
+    assert_eq!(tx.capacity(), 2);
+    tx.try_send(1).unwrap();
+    tx.try_send(2).unwrap();
+    assert!(tx.try_send(3).unwrap_err().is_full());
+    assert_eq!(rx.len(), 2);
+
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    tx.try_send(3).unwrap();
+    drop(tx);
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), vec![2, 3]);
+}
+
+#[test]
+fn backpressure_wakes_sender() {
+    let (mut tx, mut rx) = spsc::channel::<i32>(1);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+    tx.start_send(1).unwrap();
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Pending);
+    assert_eq!(count, 0);
+
+    assert_eq!(rx.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(1)));
+    assert_eq!(count, 1);
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn receiver_wakes_on_send_and_drop() {
+    let (mut tx, mut rx) = spsc::channel::<i32>(1);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Pending);
+    tx.try_send(1).unwrap();
+    assert_eq!(count, 1);
+    // The receiver is no longer waiting, so it isn't woken again.
+    drop(tx);
+    assert_eq!(count, 1);
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(None));
+
+    let (tx, mut rx) = spsc::channel::<i32>(1);
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Pending);
+    drop(tx);
+    assert_eq!(count, 2);
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn close_receiver() {
+    let (mut tx, mut rx) = spsc::channel::<i32>(1);
+    tx.try_send(1).unwrap();
+    let (waker, count) = new_count_waker();
+    assert_eq!(tx.poll_ready(&mut Context::from_waker(&waker)), Poll::Pending);
+
+    rx.close();
+    assert_eq!(count, 1);
+    assert!(tx.is_closed());
+    assert!(tx.try_send(2).unwrap_err().is_disconnected());
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert!(rx.try_next().is_err());
+    drop(tx);
+    assert_eq!(rx.try_next().unwrap(), None);
+}
+
+#[test]
+fn drops_queued_messages() {
+    struct Counted(Arc<AtomicUsize>);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let drops = Arc::new(AtomicUsize::new(0));
+    let (mut tx, rx) = spsc::channel(4);
+    for _ in 0..3 {
+        tx.try_send(Counted(drops.clone())).unwrap();
+    }
+    drop(rx);
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    drop(tx);
+    assert_eq!(drops.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn across_threads() {
+    const N: usize = 10_000;
+    let (mut tx, rx) = spsc::channel::<usize>(16);
+    let handle = thread::spawn(move || {
+        block_on(async {
+            for i in 0..N {
+                tx.send(i).await.unwrap();
+            }
+        })
+    });
+
+    let received: Vec<_> = block_on(rx.collect());
+    handle.join().unwrap();
+    assert_eq!(received, (0..N).collect::<Vec<_>>());
+}
@@ -0,0 +1,81 @@
+use futures::channel::watch;
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures::stream::StreamExt;
+use futures::task::{Context, Poll};
+use futures_test::task::new_count_waker;
+use std::thread;
+
+#[test]
+fn borrow_and_changed() {
+    let (tx, mut rx) = watch::channel("initial");
+    assert_eq!(*rx.borrow(), "initial");
+    assert!(!rx.has_changed());
+
+    tx.send("first").unwrap();
+    tx.send("second").unwrap();
+    assert_eq!(tx.version(), 2);
+    assert!(rx.has_changed());
+    assert_eq!(*rx.borrow(), "second");
+
+    block_on(rx.changed()).unwrap();
+    assert_eq!(rx.version(), 2);
+    assert!(!rx.has_changed());
+    assert!(rx.changed().now_or_never().is_none());
+
+    drop(tx);
+    assert!(block_on(rx.changed()).is_err());
+    assert_eq!(*rx.borrow(), "second");
+}
+
+#[test]
+fn changed_wakes_receivers() {
+    let (tx, mut rx1) = watch::channel(0);
+    let mut rx2 = tx.subscribe();
+    assert_eq!(tx.receiver_count(), 2);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(rx1.poll_changed(&mut cx), Poll::Pending);
+    assert_eq!(rx2.poll_changed(&mut cx), Poll::Pending);
+    tx.send(1).unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(rx1.poll_changed(&mut cx), Poll::Ready(Ok(())));
+    assert_eq!(*rx2.borrow_and_update(), 1);
+    assert_eq!(rx2.poll_changed(&mut cx), Poll::Pending);
+}
+
+#[test]
+fn send_without_receivers() {
+    let (tx, rx) = watch::channel(0);
+    drop(rx);
+    assert_eq!(tx.send(1).unwrap_err().0, 1);
+    assert_eq!(tx.send_replace(2), 0);
+    assert_eq!(*tx.borrow(), 2);
+}
+
+#[test]
+fn clone_keeps_version() {
+    let (tx, mut rx1) = watch::channel(0);
+    tx.send(1).unwrap();
+    let rx2 = rx1.clone();
+    assert!(rx2.has_changed());
+    block_on(rx1.changed()).unwrap();
+    assert!(!rx1.has_changed());
+    assert!(rx2.has_changed());
+}
+
+#[test]
+fn stream_of_values() {
+    let (tx, rx) = watch::channel(0);
+    let handle = thread::spawn(move || block_on(rx.collect::<Vec<_>>()));
+    for i in 1..=100 {
+        tx.send(i).unwrap();
+    }
+    drop(tx);
+
+    // Intermediate values may be skipped, but the last one is always seen.
+    let values = handle.join().unwrap();
+    assert_eq!(values.last(), Some(&100));
+    assert!(values.windows(2).all(|w| w[0] < w[1]));
+}
@@ -1,4 +1,5 @@
 use core::{
+    any::TypeId,
     mem,
     fmt,
     future::Future,
@@ -16,6 +17,10 @@ use core::{
 pub struct LocalFutureObj<'a, T> {
     future: *mut (dyn Future<Output = T> + 'static),
     drop_fn: unsafe fn(*mut (dyn Future<Output = T> + 'static)),
+    // The type of the future, if it is known to be a `Pin<Box<_>>`.
+    type_id: Option<TypeId>,
+    #[cfg(feature = "alloc")]
+    config: Option<alloc::boxed::Box<crate::task::SpawnConfig>>,
     _marker: PhantomData<&'a ()>,
 }
 
@@ -43,10 +48,29 @@ impl<'a, T> LocalFutureObj<'a, T> {
         LocalFutureObj {
             future: unsafe { remove_future_lifetime(f.into_raw()) },
             drop_fn: unsafe { remove_drop_lifetime(F::drop) },
+            type_id: None,
+            #[cfg(feature = "alloc")]
+            config: None,
             _marker: PhantomData,
         }
     }
 
+    /// Returns the [`TypeId`] of the future, if this object was created with
+    /// [`from_boxed`](LocalFutureObj::from_boxed).
+    #[inline]
+    pub fn type_id(&self) -> Option<TypeId> {
+        self.type_id
+    }
+
+    /// Returns `true` if the future is of type `F`.
+    ///
+    /// This is only ever the case for objects created with
+    /// [`from_boxed`](LocalFutureObj::from_boxed).
+    #[inline]
+    pub fn is<F: 'static>(&self) -> bool {
+        self.type_id == Some(TypeId::of::<F>())
+    }
+
     /// Converts the `LocalFutureObj` into a `FutureObj`
     /// To make this operation safe one has to ensure that the `UnsafeFutureObj`
     /// instance from which this `LocalFutureObj` was created actually
@@ -111,6 +135,22 @@ impl<'a, T> FutureObj<'a, T> {
     pub fn new<F: UnsafeFutureObj<'a, T> + Send>(f: F) -> FutureObj<'a, T> {
         FutureObj(LocalFutureObj::new(f))
     }
+
+    /// Returns the [`TypeId`] of the future, if this object was created with
+    /// [`from_boxed`](FutureObj::from_boxed).
+    #[inline]
+    pub fn type_id(&self) -> Option<TypeId> {
+        self.0.type_id()
+    }
+
+    /// Returns `true` if the future is of type `F`.
+    ///
+    /// This is only ever the case for objects created with
+    /// [`from_boxed`](FutureObj::from_boxed).
+    #[inline]
+    pub fn is<F: 'static>(&self) -> bool {
+        self.0.is::<F>()
+    }
 }
 
 impl<T> fmt::Debug for FutureObj<'_, T> {
@@ -219,6 +259,7 @@ unsafe impl<'a, T> UnsafeFutureObj<'a, T> for Pin<&'a mut (dyn Future<Output = T
 #[cfg(feature = "alloc")]
 mod if_alloc {
     use super::*;
+    use crate::task::SpawnConfig;
     use alloc::boxed::Box;
 
     unsafe impl<'a, T, F> UnsafeFutureObj<'a, T> for Box<F>
@@ -292,6 +333,128 @@ mod if_alloc {
         }
     }
 
+    impl<'a, T> LocalFutureObj<'a, T> {
+        /// Attaches the name and metadata of the task to this object.
+        #[inline]
+        pub fn with_config(mut self, config: SpawnConfig) -> LocalFutureObj<'a, T> {
+            self.config = Some(Box::new(config));
+            self
+        }
+
+        /// Returns the config attached with
+        /// [`with_config`](LocalFutureObj::with_config), if any.
+        #[inline]
+        pub fn config(&self) -> Option<&SpawnConfig> {
+            self.config.as_ref().map(|config| &**config)
+        }
+
+        /// Removes and returns the config attached with
+        /// [`with_config`](LocalFutureObj::with_config), if any.
+        #[inline]
+        pub fn take_config(&mut self) -> Option<SpawnConfig> {
+            self.config.take().map(|config| *config)
+        }
+    }
+
+    impl<T> FutureObj<'_, T> {
+        /// Attaches the name and metadata of the task to this object.
+        ///
+        /// ```
+        /// use futures::future::FutureObj;
+        /// use futures::task::SpawnConfig;
+        ///
+        /// let obj = FutureObj::new(Box::new(async {}))
+        ///     .with_config(SpawnConfig::new().with_name("worker"));
+        /// assert_eq!(obj.config().and_then(SpawnConfig::name), Some("worker"));
+        /// ```
+        #[inline]
+        pub fn with_config(self, config: SpawnConfig) -> Self {
+            FutureObj(self.0.with_config(config))
+        }
+
+        /// Returns the config attached with
+        /// [`with_config`](FutureObj::with_config), if any.
+        #[inline]
+        pub fn config(&self) -> Option<&SpawnConfig> {
+            self.0.config()
+        }
+
+        /// Removes and returns the config attached with
+        /// [`with_config`](FutureObj::with_config), if any.
+        #[inline]
+        pub fn take_config(&mut self) -> Option<SpawnConfig> {
+            self.0.take_config()
+        }
+    }
+
+    impl<'a, T: 'a> LocalFutureObj<'a, T> {
+        /// Creates a `LocalFutureObj` from a boxed future, which can be
+        /// recovered with [`downcast`](LocalFutureObj::downcast).
+        ///
+        /// Objects created in other ways, such as by converting a box with
+        /// `into`, do not record the type of their future.
+        pub fn from_boxed<F>(future: Pin<Box<F>>) -> LocalFutureObj<'a, T>
+        where
+            F: Future<Output = T> + 'static,
+        {
+            let mut obj = LocalFutureObj::new(future);
+            obj.type_id = Some(TypeId::of::<F>());
+            obj
+        }
+
+        /// Returns the boxed future if it is of type `F`, or `self` back if
+        /// it is not.
+        ///
+        /// This only succeeds for objects created with
+        /// [`from_boxed`](LocalFutureObj::from_boxed).
+        pub fn downcast<F>(self) -> Result<Pin<Box<F>>, LocalFutureObj<'a, T>>
+        where
+            F: Future<Output = T> + 'static,
+        {
+            if !self.is::<F>() {
+                return Err(self);
+            }
+            let this = mem::ManuallyDrop::new(self);
+            // Safety: `from_boxed` created the object from a `Pin<Box<F>>`,
+            // whose ownership is passed on instead of being dropped.
+            unsafe { Ok(Pin::new_unchecked(Box::from_raw(this.future as *mut F))) }
+        }
+    }
+
+    impl<'a, T: 'a> FutureObj<'a, T> {
+        /// Creates a `FutureObj` from a boxed future, which can be recovered
+        /// with [`downcast`](FutureObj::downcast).
+        ///
+        /// Objects created in other ways, such as by converting a box with
+        /// `into`, do not record the type of their future.
+        ///
+        /// ```
+        /// use futures::future::{self, FutureObj, Ready};
+        ///
+        /// let obj = FutureObj::from_boxed(Box::pin(future::ready(1)));
+        /// assert!(obj.is::<Ready<i32>>());
+        /// let fut = obj.downcast::<Ready<i32>>().unwrap();
+        /// ```
+        pub fn from_boxed<F>(future: Pin<Box<F>>) -> FutureObj<'a, T>
+        where
+            F: Future<Output = T> + Send + 'static,
+        {
+            FutureObj(LocalFutureObj::from_boxed(future))
+        }
+
+        /// Returns the boxed future if it is of type `F`, or `self` back if
+        /// it is not.
+        ///
+        /// This only succeeds for objects created with
+        /// [`from_boxed`](FutureObj::from_boxed).
+        pub fn downcast<F>(self) -> Result<Pin<Box<F>>, FutureObj<'a, T>>
+        where
+            F: Future<Output = T> + Send + 'static,
+        {
+            self.0.downcast().map_err(FutureObj)
+        }
+    }
+
     impl<'a, F: Future<Output = ()> + Send + 'a> From<Box<F>> for FutureObj<'a, ()> {
         fn from(boxed: Box<F>) -> Self {
             FutureObj::new(boxed)
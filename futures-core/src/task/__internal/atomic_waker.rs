@@ -2,6 +2,8 @@ use core::fmt;
 use core::cell::UnsafeCell;
 use core::sync::atomic::AtomicUsize;
 use core::sync::atomic::Ordering::{Acquire, Release, AcqRel};
+#[cfg(feature = "atomic-waker-stats")]
+use core::sync::atomic::Ordering::Relaxed;
 use crate::task::Waker;
 
 /// A synchronization primitive for task wakeup.
@@ -27,6 +29,52 @@ use crate::task::Waker;
 pub struct AtomicWaker {
     state: AtomicUsize,
     waker: UnsafeCell<Option<Waker>>,
+    #[cfg(feature = "atomic-waker-stats")]
+    stats: Stats,
+}
+
+#[cfg(feature = "atomic-waker-stats")]
+#[derive(Default)]
+struct Stats {
+    registrations: AtomicUsize,
+    wakes: AtomicUsize,
+    empty_wakes: AtomicUsize,
+}
+
+/// Counters of the calls made on an [`AtomicWaker`], returned by
+/// [`AtomicWaker::stats`].
+///
+/// This is only available with the `atomic-waker-stats` feature, and is
+/// meant for diagnosing lost wakeups: a wake which finds no waker, such as
+/// because it raced with another wake or happened before the task
+/// registered, is counted as an empty wake.
+#[cfg(feature = "atomic-waker-stats")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AtomicWakerStats {
+    registrations: usize,
+    wakes: usize,
+    empty_wakes: usize,
+}
+
+#[cfg(feature = "atomic-waker-stats")]
+impl AtomicWakerStats {
+    /// Returns the number of calls to [`register`](AtomicWaker::register).
+    pub fn registrations(&self) -> usize {
+        self.registrations
+    }
+
+    /// Returns the number of calls to [`wake`](AtomicWaker::wake) and
+    /// [`wake_by_ref`](AtomicWaker::wake_by_ref) which woke a waker.
+    pub fn wakes(&self) -> usize {
+        self.wakes
+    }
+
+    /// Returns the number of calls to [`wake`](AtomicWaker::wake) and
+    /// [`wake_by_ref`](AtomicWaker::wake_by_ref) which found no waker to
+    /// wake.
+    pub fn empty_wakes(&self) -> usize {
+        self.empty_wakes
+    }
 }
 
 // `AtomicWaker` is a multi-consumer, single-producer transfer cell. The cell
@@ -143,6 +191,8 @@ impl AtomicWaker {
         AtomicWaker {
             state: AtomicUsize::new(WAITING),
             waker: UnsafeCell::new(None),
+            #[cfg(feature = "atomic-waker-stats")]
+            stats: Stats::default(),
         }
     }
 
@@ -197,6 +247,9 @@ impl AtomicWaker {
     /// }
     /// ```
     pub fn register(&self, waker: &Waker) {
+        #[cfg(feature = "atomic-waker-stats")]
+        self.stats.registrations.fetch_add(1, Relaxed);
+
         match self.state.compare_and_swap(WAITING, REGISTERING, Acquire) {
             WAITING => {
                 unsafe {
@@ -259,13 +312,67 @@ impl AtomicWaker {
 
     /// Calls `wake` on the last `Waker` passed to `register`.
     ///
-    /// If `register` has not been called yet, then this does nothing.
+    /// The waker is taken out of the `AtomicWaker`, so it is not woken again
+    /// until it is registered again. If `register` has not been called yet,
+    /// then this does nothing.
     pub fn wake(&self) {
-        if let Some(waker) = self.take() {
+        let waker = self.take();
+        self.count_wake(waker.is_some());
+        if let Some(waker) = waker {
             waker.wake();
         }
     }
 
+    /// Calls `wake_by_ref` on the last `Waker` passed to `register`, leaving
+    /// it registered.
+    ///
+    /// Unlike with [`wake`](AtomicWaker::wake), later calls to `wake` and
+    /// `wake_by_ref` wake the same task again without it registering again,
+    /// which suits events that the task may miss without harm, such as
+    /// readiness notifications. If `register` has not been called yet, then
+    /// this does nothing.
+    pub fn wake_by_ref(&self) {
+        let waker = match self.state.fetch_or(WAKING, AcqRel) {
+            WAITING => {
+                // The waking lock has been acquired. The waker is cloned so
+                // that it is woken outside of the lock, in case waking it
+                // registers a new one.
+                let waker = unsafe { (*self.waker.get()).clone() };
+                self.state.fetch_and(!WAKING, Release);
+                waker
+            }
+            // As in `take`, a concurrent call handles the wakeup.
+            _ => None,
+        };
+        self.count_wake(waker.is_some());
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Returns the number of registrations and wakes so far.
+    #[cfg(feature = "atomic-waker-stats")]
+    pub fn stats(&self) -> AtomicWakerStats {
+        AtomicWakerStats {
+            registrations: self.stats.registrations.load(Relaxed),
+            wakes: self.stats.wakes.load(Relaxed),
+            empty_wakes: self.stats.empty_wakes.load(Relaxed),
+        }
+    }
+
+    #[cfg(feature = "atomic-waker-stats")]
+    fn count_wake(&self, woke: bool) {
+        if woke {
+            self.stats.wakes.fetch_add(1, Relaxed);
+        } else {
+            self.stats.empty_wakes.fetch_add(1, Relaxed);
+        }
+    }
+
+    #[cfg(not(feature = "atomic-waker-stats"))]
+    #[inline]
+    fn count_wake(&self, _woke: bool) {}
+
     /// Returns the last `Waker` passed to `register`, so that the user can wake it.
     ///
     ///
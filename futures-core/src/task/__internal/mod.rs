@@ -8,3 +8,9 @@ mod atomic_waker;
     cfg(all(target_has_atomic = "cas", target_has_atomic = "ptr"))
 )]
 pub use self::atomic_waker::AtomicWaker;
+#[cfg_attr(
+    feature = "cfg-target-has-atomic",
+    cfg(all(target_has_atomic = "cas", target_has_atomic = "ptr"))
+)]
+#[cfg(feature = "atomic-waker-stats")]
+pub use self::atomic_waker::AtomicWakerStats;
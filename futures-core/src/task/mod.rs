@@ -6,6 +6,11 @@ mod poll;
 mod spawn;
 pub use self::spawn::{Spawn, LocalSpawn, SpawnError};
 
+#[cfg(feature = "alloc")]
+mod spawn_config;
+#[cfg(feature = "alloc")]
+pub use self::spawn_config::SpawnConfig;
+
 #[doc(hidden)]
 pub mod __internal;
 
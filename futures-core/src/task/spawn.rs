@@ -54,36 +54,143 @@ pub trait LocalSpawn {
 }
 
 /// An error that occurred during spawning.
+///
+/// Errors for which [`is_retryable`](SpawnError::is_retryable) returns
+/// `true` are temporary, so spawning again later may succeed.
 pub struct SpawnError {
-    _hidden: (),
+    kind: Kind,
+}
+
+enum Kind {
+    Shutdown,
+    QueueFull,
+    ResourceExhausted,
+    #[cfg(feature = "std")]
+    Custom(Box<dyn std::error::Error + Send + Sync>),
 }
 
 impl fmt::Debug for SpawnError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("SpawnError")
-            .field(&"shutdown")
-            .finish()
+        let mut f = f.debug_tuple("SpawnError");
+        match &self.kind {
+            Kind::Shutdown => f.field(&"shutdown"),
+            Kind::QueueFull => f.field(&"queue full"),
+            Kind::ResourceExhausted => f.field(&"resource exhausted"),
+            #[cfg(feature = "std")]
+            Kind::Custom(err) => f.field(err),
+        };
+        f.finish()
     }
 }
 
 impl fmt::Display for SpawnError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Executor is shutdown")
+        match &self.kind {
+            Kind::Shutdown => write!(f, "Executor is shutdown"),
+            Kind::QueueFull => write!(f, "Executor queue is full"),
+            Kind::ResourceExhausted => write!(f, "Executor ran out of resources"),
+            #[cfg(feature = "std")]
+            Kind::Custom(err) => err.fmt(f),
+        }
     }
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for SpawnError {}
+impl std::error::Error for SpawnError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            Kind::Custom(err) => Some(&**err),
+            _ => None,
+        }
+    }
+}
 
 impl SpawnError {
     /// Spawning failed because the executor has been shut down.
     pub fn shutdown() -> Self {
-        Self { _hidden: () }
+        Self { kind: Kind::Shutdown }
+    }
+
+    /// Spawning failed because the executor has too many tasks queued
+    /// already. Spawning again later may succeed.
+    pub fn queue_full() -> Self {
+        Self { kind: Kind::QueueFull }
+    }
+
+    /// Spawning failed because the executor ran out of a resource it needs
+    /// to run the task, such as memory or threads. Spawning again later may
+    /// succeed.
+    pub fn resource_exhausted() -> Self {
+        Self { kind: Kind::ResourceExhausted }
+    }
+
+    /// Spawning failed with an error specific to the executor.
+    ///
+    /// The error is returned by
+    /// [`Error::source`](std::error::Error::source), and is not considered
+    /// [retryable](SpawnError::is_retryable).
+    #[cfg(feature = "std")]
+    pub fn custom<E>(err: E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        Self { kind: Kind::Custom(err.into()) }
     }
 
     /// Check whether spawning failed to the executor being shut down.
     pub fn is_shutdown(&self) -> bool {
-        true
+        match self.kind {
+            Kind::Shutdown => true,
+            _ => false,
+        }
+    }
+
+    /// Check whether spawning failed due to the queue of the executor being
+    /// full.
+    pub fn is_queue_full(&self) -> bool {
+        match self.kind {
+            Kind::QueueFull => true,
+            _ => false,
+        }
+    }
+
+    /// Check whether spawning failed due to the executor running out of
+    /// resources.
+    pub fn is_resource_exhausted(&self) -> bool {
+        match self.kind {
+            Kind::ResourceExhausted => true,
+            _ => false,
+        }
+    }
+
+    /// Check whether spawning failed with an error specific to the executor.
+    pub fn is_custom(&self) -> bool {
+        #[cfg(feature = "std")]
+        {
+            match self.kind {
+                Kind::Custom(_) => true,
+                _ => false,
+            }
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            false
+        }
+    }
+
+    /// Check whether spawning failed for a temporary reason, such that
+    /// spawning again later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        self.is_queue_full() || self.is_resource_exhausted()
+    }
+
+    /// Returns the error passed to [`custom`](SpawnError::custom), if any.
+    #[cfg(feature = "std")]
+    pub fn get_ref(&self) -> Option<&(dyn std::error::Error + Send + Sync + 'static)> {
+        match &self.kind {
+            Kind::Custom(err) => Some(&**err),
+            _ => None,
+        }
     }
 }
 
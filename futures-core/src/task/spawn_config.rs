@@ -0,0 +1,59 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::any::Any;
+use core::fmt;
+
+/// The name and metadata of a task, which are passed to the executor along
+/// with its future.
+///
+/// The config is attached to a [`FutureObj`](crate::future::FutureObj) with
+/// [`with_config`](crate::future::FutureObj::with_config), which is what
+/// `SpawnExt::spawn_with_config` does, and executors can read it back with
+/// [`config`](crate::future::FutureObj::config), such as to identify tasks
+/// by name in diagnostics. Executors are free to ignore it.
+#[derive(Default)]
+pub struct SpawnConfig {
+    name: Option<String>,
+    metadata: Option<Box<dyn Any + Send + Sync>>,
+}
+
+impl SpawnConfig {
+    /// Creates a config with neither a name nor metadata.
+    pub fn new() -> SpawnConfig {
+        SpawnConfig::default()
+    }
+
+    /// Sets the name of the task.
+    pub fn with_name(mut self, name: impl Into<String>) -> SpawnConfig {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the metadata of the task, which can be any value.
+    pub fn with_metadata<T>(mut self, metadata: T) -> SpawnConfig
+    where
+        T: Any + Send + Sync,
+    {
+        self.metadata = Some(Box::new(metadata));
+        self
+    }
+
+    /// Returns the name of the task, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(String::as_str)
+    }
+
+    /// Returns the metadata of the task, if it has metadata of type `T`.
+    pub fn metadata<T: Any>(&self) -> Option<&T> {
+        self.metadata.as_ref().and_then(|metadata| metadata.downcast_ref())
+    }
+}
+
+impl fmt::Debug for SpawnConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpawnConfig")
+            .field("name", &self.name)
+            .field("metadata", &self.metadata.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,158 @@
+#![feature(test)]
+
+extern crate test;
+use crate::test::Bencher;
+
+use futures::channel::{mpsc, oneshot};
+use futures::executor::{block_on, ThreadPool};
+use futures::future;
+use futures::sink::SinkExt;
+use futures::stream::StreamExt;
+use futures::task::{Context, Poll};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const NUM_TASKS: usize = 1_000;
+const NUM_PAIRS: usize = 16;
+const NUM_PINGS: usize = 100;
+
+/// Completes `tx` once `count` reaches zero.
+fn countdown(count: &AtomicUsize, tx: &std::sync::Mutex<Option<oneshot::Sender<()>>>) {
+    if count.fetch_sub(1, Ordering::SeqCst) == 1 {
+        tx.lock().unwrap().take().unwrap().send(()).unwrap();
+    }
+}
+
+fn spawn_leaves(pool: &ThreadPool, num: usize) -> oneshot::Receiver<()> {
+    let (tx, rx) = oneshot::channel();
+    let count = Arc::new(AtomicUsize::new(num));
+    let tx = Arc::new(std::sync::Mutex::new(Some(tx)));
+    for _ in 0..num {
+        let count = count.clone();
+        let tx = tx.clone();
+        pool.spawn_ok(async move { countdown(&count, &tx) });
+    }
+    rx
+}
+
+/// Tasks spawned from outside the pool.
+#[bench]
+fn spawn_many_remote(b: &mut Bencher) {
+    let pool = ThreadPool::new().unwrap();
+    b.iter(|| {
+        block_on(spawn_leaves(&pool, NUM_TASKS)).unwrap();
+    });
+}
+
+/// Tasks spawned from tasks running on the pool.
+#[bench]
+fn spawn_many_local(b: &mut Bencher) {
+    let pool = ThreadPool::new().unwrap();
+    b.iter(|| {
+        let (tx, rx) = oneshot::channel();
+        let inner = pool.clone();
+        pool.spawn_ok(async move {
+            let _ = tx.send(spawn_leaves(&inner, NUM_TASKS));
+        });
+        block_on(async { rx.await.unwrap().await.unwrap() });
+    });
+}
+
+/// Tasks which wake themselves repeatedly.
+#[bench]
+fn yield_many(b: &mut Bencher) {
+    struct Yield {
+        rem: usize,
+    }
+
+    impl future::Future for Yield {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.rem == 0 {
+                Poll::Ready(())
+            } else {
+                self.rem -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    let pool = ThreadPool::new().unwrap();
+    b.iter(|| {
+        let (tx, rx) = oneshot::channel();
+        let count = Arc::new(AtomicUsize::new(NUM_PAIRS));
+        let tx = Arc::new(std::sync::Mutex::new(Some(tx)));
+        for _ in 0..NUM_PAIRS {
+            let count = count.clone();
+            let tx = tx.clone();
+            pool.spawn_ok(async move {
+                Yield { rem: NUM_PINGS }.await;
+                countdown(&count, &tx);
+            });
+        }
+        block_on(rx).unwrap();
+    });
+}
+
+/// Pairs of tasks passing a message back and forth.
+#[bench]
+fn ping_pong(b: &mut Bencher) {
+    let pool = ThreadPool::new().unwrap();
+    b.iter(|| {
+        let (done_tx, done_rx) = oneshot::channel();
+        let count = Arc::new(AtomicUsize::new(NUM_PAIRS));
+        let done_tx = Arc::new(std::sync::Mutex::new(Some(done_tx)));
+        for _ in 0..NUM_PAIRS {
+            let (mut ping_tx, mut ping_rx) = mpsc::channel::<usize>(1);
+            let (mut pong_tx, mut pong_rx) = mpsc::channel::<usize>(1);
+            pool.spawn_ok(async move {
+                while let Some(n) = ping_rx.next().await {
+                    pong_tx.send(n).await.unwrap();
+                }
+            });
+            let count = count.clone();
+            let done_tx = done_tx.clone();
+            pool.spawn_ok(async move {
+                for n in 0..NUM_PINGS {
+                    ping_tx.send(n).await.unwrap();
+                    assert_eq!(pong_rx.next().await, Some(n));
+                }
+                countdown(&count, &done_tx);
+            });
+        }
+        block_on(done_rx).unwrap();
+    });
+}
+
+/// Tasks spawned as trait objects through `Spawn`, which are boxed by the
+/// caller.
+#[bench]
+fn spawn_many_obj(b: &mut Bencher) {
+    use futures::task::SpawnExt;
+
+    let mut pool = ThreadPool::new().unwrap();
+    b.iter(|| {
+        let (tx, rx) = oneshot::channel();
+        let count = Arc::new(AtomicUsize::new(NUM_TASKS));
+        let tx = Arc::new(std::sync::Mutex::new(Some(tx)));
+        for _ in 0..NUM_TASKS {
+            let count = count.clone();
+            let tx = tx.clone();
+            SpawnExt::spawn(&mut pool, async move { countdown(&count, &tx) }).unwrap();
+        }
+        block_on(rx).unwrap();
+    });
+}
+
+/// Tasks spawned with a `JoinHandle`.
+#[bench]
+fn spawn_many_joinable(b: &mut Bencher) {
+    let pool = ThreadPool::new().unwrap();
+    b.iter(|| {
+        let handles = (0..NUM_TASKS).map(|i| pool.spawn_with_join_handle(async move { i })).collect::<Vec<_>>();
+        block_on(future::join_all(handles));
+    });
+}
@@ -0,0 +1,168 @@
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::VecDeque;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A pool of threads for running blocking closures, which grows as closures
+/// are submitted and shrinks again as its threads go idle.
+pub(crate) struct BlockingPool {
+    state: Mutex<BlockingState>,
+    cv: Condvar,
+    max_threads: usize,
+    keep_alive: Duration,
+    name_prefix: Option<String>,
+    stack_size: usize,
+}
+
+struct BlockingState {
+    queue: VecDeque<Job>,
+    // Number of threads alive, and how many of them are waiting for a job.
+    threads: usize,
+    idle: usize,
+    // Used to number the threads.
+    spawned: usize,
+}
+
+impl BlockingPool {
+    pub(crate) fn new(
+        max_threads: usize,
+        keep_alive: Duration,
+        name_prefix: Option<String>,
+        stack_size: usize,
+    ) -> BlockingPool {
+        BlockingPool {
+            state: Mutex::new(BlockingState {
+                queue: VecDeque::new(),
+                threads: 0,
+                idle: 0,
+                spawned: 0,
+            }),
+            cv: Condvar::new(),
+            max_threads,
+            keep_alive,
+            name_prefix,
+            stack_size,
+        }
+    }
+
+    /// Runs `f` on a thread of the pool, returning a future for its result.
+    pub(crate) fn spawn<F, T>(this: &Arc<Self>, f: F) -> SpawnBlocking<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let slot = Arc::new(Mutex::new(Slot { result: None, waker: None }));
+        let tx = slot.clone();
+        BlockingPool::submit(this, Box::new(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(f));
+            let mut slot = tx.lock().unwrap();
+            slot.result = Some(result);
+            if let Some(waker) = slot.waker.take() {
+                waker.wake();
+            }
+        }));
+        SpawnBlocking { slot }
+    }
+
+    fn submit(this: &Arc<Self>, job: Job) {
+        let mut state = this.state.lock().unwrap();
+        state.queue.push_back(job);
+        if state.idle >= state.queue.len() {
+            this.cv.notify_one();
+            return;
+        }
+        if state.threads >= this.max_threads {
+            // One of the busy threads picks the job up once it is done.
+            return;
+        }
+
+        state.threads += 1;
+        state.spawned += 1;
+        let mut builder = thread::Builder::new();
+        if let Some(ref name_prefix) = this.name_prefix {
+            builder = builder.name(format!("{}blocking-{}", name_prefix, state.spawned));
+        }
+        if this.stack_size > 0 {
+            builder = builder.stack_size(this.stack_size);
+        }
+        let pool = this.clone();
+        if let Err(e) = builder.spawn(move || pool.run()) {
+            state.threads -= 1;
+            if state.threads == 0 {
+                panic!("failed to spawn a blocking thread: {}", e);
+            }
+        }
+    }
+
+    fn run(&self) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(job) = state.queue.pop_front() {
+                drop(state);
+                job();
+                state = self.state.lock().unwrap();
+                continue;
+            }
+
+            state.idle += 1;
+            let (guard, timeout) = self.cv.wait_timeout(state, self.keep_alive).unwrap();
+            state = guard;
+            state.idle -= 1;
+            if timeout.timed_out() && state.queue.is_empty() {
+                state.threads -= 1;
+                return;
+            }
+        }
+    }
+}
+
+struct Slot<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+/// Future for the [`spawn_blocking`](crate::ThreadPool::spawn_blocking)
+/// method.
+///
+/// Dropping this future does not cancel the closure, which still runs to
+/// completion. If the closure panics, the panic is resumed when this future
+/// is polled.
+pub struct SpawnBlocking<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> Unpin for SpawnBlocking<T> {}
+
+impl<T> Future for SpawnBlocking<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut slot = self.slot.lock().unwrap();
+        match slot.result.take() {
+            Some(Ok(t)) => Poll::Ready(t),
+            Some(Err(payload)) => {
+                drop(slot);
+                panic::resume_unwind(payload)
+            }
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for SpawnBlocking<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpawnBlocking")
+            .field("done", &self.slot.lock().unwrap().result.is_some())
+            .finish()
+    }
+}
@@ -33,7 +33,11 @@ pub struct EnterError {
 /// ```
 ///
 /// Doing so ensures that executors aren't
-/// accidentally invoked in a nested fashion.
+/// accidentally invoked in a nested fashion. While the thread is marked,
+/// [`block_on`](crate::block_on) and the run methods of
+/// [`LocalPool`](crate::LocalPool) panic, and
+/// [`try_block_on`](crate::try_block_on) returns an error, so executors of
+/// other crates which call this are protected from them as well.
 ///
 /// # Error
 ///
@@ -51,6 +55,39 @@ pub fn enter() -> Result<Enter, EnterError> {
     })
 }
 
+/// Returns `true` if the current thread is within the dynamic extent of an
+/// executor, as marked by [`enter`](enter()).
+///
+/// Every executor of this crate marks the threads running its tasks, and
+/// executors of other crates can do the same by calling `enter`. Library code
+/// which blocks the current thread can use this to fail early instead of
+/// deadlocking the executor it is called from:
+///
+/// ```
+/// use futures::executor::{block_on, is_entered};
+///
+/// fn blocking_call() {
+///     assert!(!is_entered(), "`blocking_call` must not be called from within an executor");
+///     // ...
+/// }
+///
+/// blocking_call();
+/// block_on(async {
+///     assert!(is_entered());
+/// });
+/// ```
+pub fn is_entered() -> bool {
+    ENTERED.with(|c| c.get())
+}
+
+impl fmt::Display for EnterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an execution scope has already been entered")
+    }
+}
+
+impl std::error::Error for EnterError {}
+
 impl fmt::Debug for Enter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Enter").finish()
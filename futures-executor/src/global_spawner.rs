@@ -0,0 +1,112 @@
+use futures_core::future::FutureObj;
+use futures_core::task::{Spawn, SpawnError};
+use std::error::Error;
+use std::fmt;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::Mutex;
+
+type Global = Mutex<Option<Box<dyn Spawn + Send>>>;
+
+fn global() -> &'static Global {
+    static GLOBAL: AtomicPtr<Global> = AtomicPtr::new(ptr::null_mut());
+
+    let mut global = GLOBAL.load(Ordering::Acquire);
+    if global.is_null() {
+        let new = Box::into_raw(Box::new(Mutex::new(None)));
+        global = match GLOBAL.compare_exchange(ptr::null_mut(), new, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => new,
+            Err(existing) => {
+                drop(unsafe { Box::from_raw(new) });
+                existing
+            }
+        };
+    }
+    // Safety: the slot is never freed once it has been published.
+    unsafe { &*global }
+}
+
+/// Sets the spawner which [`global_spawner`](global_spawner) spawns onto.
+///
+/// This is meant to be called once by an application, early on, so that
+/// libraries which just need to spawn tasks somewhere can do so without
+/// being handed a spawner. Returns an error if a global spawner has been set
+/// already, in which case it is left in place.
+///
+/// ```
+/// use futures::executor::{global_spawner, set_global_spawner, ThreadPool};
+/// use futures::task::SpawnExt;
+///
+/// set_global_spawner(ThreadPool::new().unwrap()).unwrap();
+///
+/// // Later, in a library:
+/// global_spawner().spawn(async { /* ... */ }).unwrap();
+/// ```
+pub fn set_global_spawner<S>(spawner: S) -> Result<(), SetGlobalSpawnerError>
+where
+    S: Spawn + Send + 'static,
+{
+    let mut global = global().lock().unwrap();
+    if global.is_some() {
+        return Err(SetGlobalSpawnerError { _a: () });
+    }
+    *global = Some(Box::new(spawner));
+    Ok(())
+}
+
+/// Returns a handle to the spawner set with
+/// [`set_global_spawner`](set_global_spawner).
+///
+/// The handle can be obtained before the global spawner is set. Spawning
+/// through it fails with a [shutdown error](SpawnError::shutdown) until then.
+pub fn global_spawner() -> GlobalSpawner {
+    GlobalSpawner { _a: () }
+}
+
+/// A handle to the global spawner, which implements
+/// [`Spawn`](futures_core::task::Spawn).
+///
+/// This value is created by the [`global_spawner`](global_spawner) function.
+#[derive(Clone, Copy)]
+pub struct GlobalSpawner {
+    _a: (),
+}
+
+impl Spawn for GlobalSpawner {
+    fn spawn_obj(&mut self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        match global().lock().unwrap().as_mut() {
+            Some(spawner) => spawner.spawn_obj(future),
+            None => Err(SpawnError::shutdown()),
+        }
+    }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        match global().lock().unwrap().as_ref() {
+            Some(spawner) => spawner.status(),
+            None => Err(SpawnError::shutdown()),
+        }
+    }
+}
+
+impl fmt::Debug for GlobalSpawner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalSpawner")
+            .field("set", &global().lock().unwrap().is_some())
+            .finish()
+    }
+}
+
+/// The error returned by [`set_global_spawner`](set_global_spawner) if a
+/// global spawner has been set already.
+#[derive(Debug)]
+pub struct SetGlobalSpawnerError {
+    _a: (),
+}
+
+impl fmt::Display for SetGlobalSpawnerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the global spawner has already been set")
+    }
+}
+
+impl Error for SetGlobalSpawnerError {}
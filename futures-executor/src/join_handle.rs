@@ -0,0 +1,252 @@
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll, Waker};
+use futures_util::task::AtomicWaker;
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A handle to a task spawned with
+/// [`ThreadPool::spawn_with_join_handle`](crate::ThreadPool::spawn_with_join_handle).
+///
+/// Awaiting the handle resolves to the output of the task, or to a
+/// [`JoinError`] if the task was aborted or panicked. Dropping the handle
+/// detaches the task, which keeps running.
+pub struct JoinHandle<T> {
+    state: Arc<JoinState<T>>,
+}
+
+/// The error returned by a [`JoinHandle`] when its task did not complete.
+pub struct JoinError {
+    repr: Repr,
+}
+
+enum Repr {
+    Cancelled,
+    Panic(Box<dyn Any + Send>),
+}
+
+struct JoinState<T> {
+    output: Mutex<Output<T>>,
+    aborted: AtomicBool,
+    finished: AtomicBool,
+    // The waker of the task, to wake it when it is aborted.
+    task: AtomicWaker,
+}
+
+struct Output<T> {
+    result: Option<Result<T, JoinError>>,
+    waker: Option<Waker>,
+}
+
+/// Wraps the future of a task, storing its output in the `JoinHandle`.
+pub(crate) struct Joinable<F: Future> {
+    future: F,
+    state: Arc<JoinState<F::Output>>,
+}
+
+pub(crate) fn joinable<F: Future>(future: F) -> (Joinable<F>, JoinHandle<F::Output>) {
+    let state = Arc::new(JoinState::new());
+    (Joinable { future, state: state.clone() }, JoinHandle { state })
+}
+
+/// The output slot of a task whose future is created later, on the thread
+/// which runs it.
+pub(crate) struct JoinSlot<T> {
+    state: Option<Arc<JoinState<T>>>,
+}
+
+pub(crate) fn join_slot<T>() -> (JoinSlot<T>, JoinHandle<T>) {
+    let state = Arc::new(JoinState::new());
+    (JoinSlot { state: Some(state.clone()) }, JoinHandle { state })
+}
+
+impl<T> JoinSlot<T> {
+    /// Creates the future of the task with `f`, reporting a panic of `f`
+    /// through the handle.
+    pub(crate) fn wrap<F, Fut>(mut self, f: F) -> Option<Joinable<Fut>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let state = self.state.take().unwrap();
+        match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(future) => Some(Joinable { future, state }),
+            Err(payload) => {
+                state.complete(Err(JoinError { repr: Repr::Panic(payload) }));
+                None
+            }
+        }
+    }
+}
+
+// A task which is never created, such as because its thread exits first,
+// still has to resolve its handle.
+impl<T> Drop for JoinSlot<T> {
+    fn drop(&mut self) {
+        if let Some(state) = self.state.take() {
+            state.complete(Err(JoinError { repr: Repr::Cancelled }));
+        }
+    }
+}
+
+impl<T> JoinState<T> {
+    fn new() -> JoinState<T> {
+        JoinState {
+            output: Mutex::new(Output { result: None, waker: None }),
+            aborted: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+            task: AtomicWaker::new(),
+        }
+    }
+
+    fn complete(&self, result: Result<T, JoinError>) {
+        let mut output = self.output.lock().unwrap();
+        output.result = Some(result);
+        self.finished.store(true, Ordering::SeqCst);
+        if let Some(waker) = output.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<F: Future> Future for Joinable<F> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // Safety: `future` is never moved out of `self`.
+        let this = unsafe { self.get_unchecked_mut() };
+        // Register before checking for an abort, so that an abort racing
+        // with this poll either is seen here or wakes the task again.
+        this.state.task.register(cx.waker());
+        if this.state.aborted.load(Ordering::SeqCst) {
+            this.state.complete(Err(JoinError { repr: Repr::Cancelled }));
+            return Poll::Ready(());
+        }
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        match panic::catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(output)) => {
+                this.state.complete(Ok(output));
+                Poll::Ready(())
+            }
+            Err(payload) => {
+                this.state.complete(Err(JoinError { repr: Repr::Panic(payload) }));
+                Poll::Ready(())
+            }
+        }
+    }
+}
+
+// A task dropped without completing, such as by a load-shedding callback,
+// still has to resolve its handle.
+impl<F: Future> Drop for Joinable<F> {
+    fn drop(&mut self) {
+        if !self.state.finished.load(Ordering::SeqCst) {
+            self.state.complete(Err(JoinError { repr: Repr::Cancelled }));
+        }
+    }
+}
+
+impl<T> JoinHandle<T> {
+    /// Returns a handle which resolves to a cancellation error right away,
+    /// for a task which could not be spawned.
+    pub(crate) fn cancelled() -> JoinHandle<T> {
+        let state = JoinState::new();
+        state.complete(Err(JoinError { repr: Repr::Cancelled }));
+        JoinHandle { state: Arc::new(state) }
+    }
+
+    /// Aborts the task.
+    ///
+    /// The future of the task is dropped the next time the task would have
+    /// been polled, and the handle resolves to a cancellation error. This has
+    /// no effect if the task has already finished.
+    pub fn abort(&self) {
+        self.state.aborted.store(true, Ordering::SeqCst);
+        self.state.task.wake();
+    }
+
+    /// Returns `true` if the task has finished, whether it completed, was
+    /// aborted or panicked.
+    pub fn is_finished(&self) -> bool {
+        self.state.finished.load(Ordering::SeqCst)
+    }
+}
+
+impl<T> Unpin for JoinHandle<T> {}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut output = self.state.output.lock().unwrap();
+        match output.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                output.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for JoinHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinHandle")
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+impl JoinError {
+    /// Returns `true` if the task was aborted, was dropped without being run
+    /// to completion, or could not be spawned because the pool was shut down
+    /// or its queue was full.
+    pub fn is_cancelled(&self) -> bool {
+        match self.repr {
+            Repr::Cancelled => true,
+            Repr::Panic(_) => false,
+        }
+    }
+
+    /// Returns `true` if the task panicked.
+    pub fn is_panic(&self) -> bool {
+        !self.is_cancelled()
+    }
+
+    /// Returns the payload of the panic of the task, or `self` back if it
+    /// was cancelled.
+    ///
+    /// The payload can be passed on to
+    /// [`std::panic::resume_unwind`](std::panic::resume_unwind).
+    pub fn try_into_panic(self) -> Result<Box<dyn Any + Send>, JoinError> {
+        match self.repr {
+            Repr::Panic(payload) => Ok(payload),
+            Repr::Cancelled => Err(self),
+        }
+    }
+}
+
+impl fmt::Debug for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.repr {
+            Repr::Cancelled => f.debug_tuple("JoinError::Cancelled").finish(),
+            Repr::Panic(_) => f.debug_tuple("JoinError::Panic").finish(),
+        }
+    }
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.repr {
+            Repr::Cancelled => write!(f, "task was cancelled"),
+            Repr::Panic(_) => write!(f, "task panicked"),
+        }
+    }
+}
+
+impl Error for JoinError {}
@@ -17,16 +17,54 @@
 #[cfg(feature = "std")]
 mod local_pool;
 #[cfg(feature = "std")]
-pub use crate::local_pool::{block_on, block_on_stream, BlockingStream, LocalPool, LocalSpawner};
+pub use crate::local_pool::{block_on, block_on_stream, block_on_timeout, try_block_on, BlockingStream, LocalPool, LocalPoolMetrics, LocalSpawner, RemoteSpawner, TickStats};
+
+#[cfg(feature = "std")]
+mod panic_policy;
+#[cfg(feature = "std")]
+pub use crate::panic_policy::PanicPolicy;
+
+#[cfg(feature = "std")]
+mod queue_full_policy;
+#[cfg(feature = "std")]
+pub use crate::queue_full_policy::QueueFullPolicy;
+
+#[cfg(feature = "std")]
+mod join_handle;
+#[cfg(feature = "std")]
+pub use crate::join_handle::{JoinError, JoinHandle};
+
+#[cfg(feature = "std")]
+mod blocking;
+#[cfg(feature = "std")]
+pub use crate::blocking::SpawnBlocking;
+
+#[cfg(feature = "std")]
+mod task_hooks;
+#[cfg(feature = "std")]
+pub use crate::task_hooks::{TaskHooks, TaskInfo};
+
+#[cfg(feature = "std")]
+mod local_worker;
+
+#[cfg(feature = "std")]
+mod thread_config;
+#[cfg(feature = "std")]
+pub use crate::thread_config::ThreadPriority;
 
 #[cfg(feature = "std")]
 mod unpark_mutex;
 #[cfg(feature = "std")]
 mod thread_pool;
 #[cfg(feature = "std")]
-pub use crate::thread_pool::{ThreadPool, ThreadPoolBuilder};
+pub use crate::thread_pool::{Priority, Shutdown, ThreadPool, ThreadPoolBuilder, ThreadPoolMetrics};
+
+#[cfg(feature = "std")]
+mod global_spawner;
+#[cfg(feature = "std")]
+pub use crate::global_spawner::{global_spawner, set_global_spawner, GlobalSpawner, SetGlobalSpawnerError};
 
 #[cfg(feature = "std")]
 mod enter;
 #[cfg(feature = "std")]
-pub use crate::enter::{enter, Enter, EnterError};
+pub use crate::enter::{enter, is_entered, Enter, EnterError};
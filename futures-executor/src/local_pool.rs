@@ -1,16 +1,23 @@
-use crate::enter;
+use crate::enter::{enter, Enter, EnterError};
+use crate::panic_policy::PanicPolicy;
+use crate::task_hooks::{TaskHooks, TaskInfo};
+use futures_channel::mpsc;
 use futures_core::future::{Future, FutureObj, LocalFutureObj};
 use futures_core::stream::{Stream};
-use futures_core::task::{Context, Poll, Spawn, LocalSpawn, SpawnError};
-use futures_util::task::{waker_ref, ArcWake};
+use futures_core::task::{Context, Poll, Spawn, LocalSpawn, SpawnConfig, SpawnError, Waker};
+use futures_util::task::{budget, waker_ref, ArcWake};
 use futures_util::stream::FuturesUnordered;
 use futures_util::stream::StreamExt;
 use futures_util::pin_mut;
-use std::cell::{RefCell};
+use std::cell::{Cell, RefCell};
+use std::mem;
 use std::ops::{Deref, DerefMut};
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
 use std::rc::{Rc, Weak};
 use std::sync::Arc;
 use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
 
 /// A single-threaded task pool for polling futures to completion.
 ///
@@ -25,8 +32,11 @@ use std::thread::{self, Thread};
 /// futures, via [`spawn_local_obj`](futures_core::task::LocalSpawn::spawn_local_obj).
 #[derive(Debug)]
 pub struct LocalPool {
-    pool: FuturesUnordered<LocalFutureObj<'static, ()>>,
+    pool: FuturesUnordered<LocalTask>,
     incoming: Rc<Incoming>,
+    remote: mpsc::UnboundedReceiver<FutureObj<'static, ()>>,
+    remote_tx: mpsc::UnboundedSender<FutureObj<'static, ()>>,
+    shared: Rc<LocalShared>,
 }
 
 /// A handle to a [`LocalPool`](LocalPool) that implements
@@ -36,7 +46,217 @@ pub struct LocalSpawner {
     incoming: Weak<Incoming>,
 }
 
-type Incoming = RefCell<Vec<LocalFutureObj<'static, ()>>>;
+/// A handle to a [`LocalPool`](LocalPool) that implements
+/// [`Spawn`](futures_core::task::Spawn) and can be sent to other threads.
+///
+/// This value is created by the [`remote_spawner`](LocalPool::remote_spawner)
+/// method. The spawned futures are sent to the pool, which starts running
+/// them on its own thread the next time it is run. Spawning fails once the
+/// pool has been dropped.
+#[derive(Clone, Debug)]
+pub struct RemoteSpawner {
+    tx: mpsc::UnboundedSender<FutureObj<'static, ()>>,
+}
+
+/// Metrics of a [`LocalPool`](LocalPool).
+///
+/// This value is created by the [`metrics`](LocalPool::metrics) method.
+#[derive(Clone, Debug)]
+pub struct LocalPoolMetrics {
+    incoming: Weak<Incoming>,
+    shared: Rc<LocalShared>,
+}
+
+/// Statistics about one tick of a [`LocalPool`](LocalPool).
+///
+/// This value is returned by the [`tick`](LocalPool::tick) method.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TickStats {
+    polled: usize,
+    woken: usize,
+    spawned: usize,
+    completed: usize,
+}
+
+impl TickStats {
+    /// Returns the number of times tasks were polled.
+    pub fn polled(&self) -> usize {
+        self.polled
+    }
+
+    /// Returns the number of polls of tasks which had been polled before, and
+    /// have been woken up since.
+    pub fn woken(&self) -> usize {
+        self.woken
+    }
+
+    /// Returns the number of newly spawned tasks which the pool picked up.
+    pub fn spawned(&self) -> usize {
+        self.spawned
+    }
+
+    /// Returns the number of tasks which completed, including ones which
+    /// panicked and were dropped according to the
+    /// [panic policy](LocalPool::set_panic_policy).
+    pub fn completed(&self) -> usize {
+        self.completed
+    }
+}
+
+// The newly spawned futures, along with their names.
+type Incoming = RefCell<Vec<(LocalFutureObj<'static, ()>, Option<String>)>>;
+
+// State shared by the pool with its tasks and metrics.
+#[derive(Debug)]
+struct LocalShared {
+    // Number of tasks in the pool, not counting incoming ones.
+    pooled: Cell<usize>,
+    polls: Cell<u64>,
+    busy: Cell<Duration>,
+    panic_policy: RefCell<PanicPolicy>,
+    task_hooks: RefCell<TaskHooks>,
+    task_ids: Cell<u64>,
+    poll_budget: Cell<usize>,
+    max_task_polls: Cell<usize>,
+    coop_budget: Cell<Option<usize>>,
+    // The current tick, and how many polls it has left.
+    tick: Cell<u64>,
+    budget: Cell<usize>,
+    // Wakers of the tasks which were ready but ran out of budget, to wake
+    // once the tick is over.
+    deferred: RefCell<Vec<Waker>>,
+    stats: Cell<TickStats>,
+}
+
+impl LocalShared {
+    fn count(&self, f: impl FnOnce(&mut TickStats)) {
+        let mut stats = self.stats.get();
+        f(&mut stats);
+        self.stats.set(stats);
+    }
+}
+
+impl Default for LocalShared {
+    fn default() -> LocalShared {
+        LocalShared {
+            pooled: Cell::new(0),
+            polls: Cell::new(0),
+            busy: Cell::new(Duration::from_secs(0)),
+            panic_policy: RefCell::new(PanicPolicy::default()),
+            task_hooks: RefCell::new(TaskHooks::default()),
+            task_ids: Cell::new(0),
+            poll_budget: Cell::new(usize::max_value()),
+            max_task_polls: Cell::new(usize::max_value()),
+            coop_budget: Cell::new(None),
+            tick: Cell::new(0),
+            budget: Cell::new(usize::max_value()),
+            deferred: RefCell::new(Vec::new()),
+            stats: Cell::new(TickStats::default()),
+        }
+    }
+}
+
+/// A task in the pool, which counts its polls.
+#[derive(Debug)]
+struct LocalTask {
+    future: LocalFutureObj<'static, ()>,
+    shared: Rc<LocalShared>,
+    id: u64,
+    name: Option<String>,
+    polled: bool,
+    // The last tick the task was polled in, and how often.
+    tick: u64,
+    tick_polls: usize,
+}
+
+impl LocalTask {
+    fn new(
+        future: LocalFutureObj<'static, ()>,
+        name: Option<String>,
+        shared: Rc<LocalShared>,
+    ) -> LocalTask {
+        // A name passed to the spawner takes precedence over the config.
+        let name = name.or_else(|| future.config().and_then(SpawnConfig::name).map(String::from));
+        let id = shared.task_ids.get();
+        shared.task_ids.set(id + 1);
+        let tick = shared.tick.get();
+        let task = LocalTask { future, shared, id, name, polled: false, tick, tick_polls: 0 };
+        task.shared.count(|stats| stats.spawned += 1);
+        task.shared.task_hooks.borrow().spawned(&task.info());
+        task
+    }
+
+    fn info(&self) -> TaskInfo<'_> {
+        TaskInfo::new(self.id, self.name.as_ref().map(String::as_str))
+    }
+}
+
+impl Future for LocalTask {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+        let shared = &this.shared;
+        if this.tick != shared.tick.get() {
+            this.tick = shared.tick.get();
+            this.tick_polls = 0;
+        }
+        if shared.budget.get() == 0 || this.tick_polls >= shared.max_task_polls.get() {
+            // Not waking the task right away keeps the pool from polling it
+            // again in this tick.
+            shared.deferred.borrow_mut().push(cx.waker().clone());
+            return Poll::Pending;
+        }
+        shared.budget.set(shared.budget.get() - 1);
+        this.tick_polls += 1;
+
+        this.shared.polls.set(this.shared.polls.get() + 1);
+        let first = !mem::replace(&mut this.polled, true);
+        this.shared.count(|stats| {
+            stats.polled += 1;
+            if !first {
+                stats.woken += 1;
+            }
+        });
+        let info = TaskInfo::new(this.id, this.name.as_ref().map(String::as_str));
+        let propagate = match *this.shared.panic_policy.borrow() {
+            PanicPolicy::Propagate => true,
+            _ => false,
+        };
+        let coop_budget = this.shared.coop_budget.get();
+        let future = &mut this.future;
+        let mut poll = move || match coop_budget {
+            Some(n) => budget::with_budget(n, || Pin::new(&mut *future).poll(cx)),
+            None => Pin::new(&mut *future).poll(cx),
+        };
+        let mut panicked = None;
+        let res = this.shared.task_hooks.borrow().poll(&info, first, || {
+            if propagate {
+                return poll();
+            }
+            match panic::catch_unwind(AssertUnwindSafe(poll)) {
+                Ok(poll) => poll,
+                Err(payload) => {
+                    panicked = Some(payload);
+                    Poll::Pending
+                }
+            }
+        });
+        if let Some(payload) = panicked {
+            let policy = this.shared.panic_policy.borrow().clone();
+            if let PanicPolicy::CatchAndLog(f) = policy {
+                f(payload);
+            }
+            // The task is dropped by the pool like a completed one.
+            this.shared.count(|stats| stats.completed += 1);
+            return Poll::Ready(());
+        }
+        if res.is_ready() {
+            this.shared.count(|stats| stats.completed += 1);
+        }
+        res
+    }
+}
 
 pub(crate) struct ThreadNotify {
     thread: Thread
@@ -54,13 +274,27 @@ impl ArcWake for ThreadNotify {
     }
 }
 
+// Marks the thread as running an executor, panicking if it is running one
+// already.
+fn enter_executor() -> Enter {
+    match enter() {
+        Ok(enter) => enter,
+        Err(_) => panic!(
+            "cannot block on a future or run a `LocalPool` from within an \
+             executor, as the executor could not run its other tasks in the \
+             meantime, which may deadlock; spawn the future or `.await` it \
+             instead"
+        ),
+    }
+}
+
 // Set up and run a basic single-threaded spawner loop, invoking `f` on each
 // turn.
-fn run_executor<T, F: FnMut(&mut Context<'_>) -> Poll<T>>(mut f: F) -> T {
-    let _enter = enter()
-        .expect("cannot execute `LocalPool` executor from within \
-                 another executor");
+fn run_executor<T, F: FnMut(&mut Context<'_>) -> Poll<T>>(f: F) -> T {
+    run_entered(enter_executor(), f)
+}
 
+fn run_entered<T, F: FnMut(&mut Context<'_>) -> Poll<T>>(_enter: Enter, mut f: F) -> T {
     CURRENT_THREAD_NOTIFY.with(|thread_notify| {
         let waker = waker_ref(thread_notify);
         let mut cx = Context::from_waker(&waker);
@@ -73,10 +307,31 @@ fn run_executor<T, F: FnMut(&mut Context<'_>) -> Poll<T>>(mut f: F) -> T {
     })
 }
 
+// Like `run_executor`, but gives up once `deadline` has passed.
+fn run_executor_until<T, F: FnMut(&mut Context<'_>) -> Poll<T>>(
+    deadline: Instant,
+    mut f: F,
+) -> Option<T> {
+    let _enter = enter_executor();
+
+    CURRENT_THREAD_NOTIFY.with(|thread_notify| {
+        let waker = waker_ref(thread_notify);
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(t) = f(&mut cx) {
+                return Some(t);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            thread::park_timeout(deadline - now);
+        }
+    })
+}
+
 fn poll_executor<T, F: FnMut(&mut Context<'_>) -> T>(mut f: F) -> T {
-    let _enter = enter()
-        .expect("cannot execute `LocalPool` executor from within \
-                 another executor");
+    let _enter = enter_executor();
 
     CURRENT_THREAD_NOTIFY.with(|thread_notify| {
         let waker = waker_ref(thread_notify);
@@ -88,9 +343,13 @@ fn poll_executor<T, F: FnMut(&mut Context<'_>) -> T>(mut f: F) -> T {
 impl LocalPool {
     /// Create a new, empty pool of tasks.
     pub fn new() -> LocalPool {
+        let (remote_tx, remote) = mpsc::unbounded();
         LocalPool {
             pool: FuturesUnordered::new(),
             incoming: Default::default(),
+            remote,
+            remote_tx,
+            shared: Default::default(),
         }
     }
 
@@ -101,6 +360,115 @@ impl LocalPool {
         }
     }
 
+    /// Get a clonable handle to the pool as a [`Spawn`] which can be sent to
+    /// other threads.
+    ///
+    /// A pool blocked in [`run`](LocalPool::run) or
+    /// [`run_until`](LocalPool::run_until) is woken up to run the futures
+    /// spawned through the handle.
+    ///
+    /// ```
+    /// use futures::channel::oneshot;
+    /// use futures::executor::LocalPool;
+    /// use futures::task::SpawnExt;
+    /// use std::thread;
+    ///
+    /// let mut pool = LocalPool::new();
+    /// let mut spawner = pool.remote_spawner();
+    /// let (tx, rx) = oneshot::channel();
+    ///
+    /// thread::spawn(move || {
+    ///     spawner.spawn(async move { tx.send(1).unwrap() }).unwrap();
+    /// });
+    ///
+    /// assert_eq!(pool.run_until(rx), Ok(1));
+    /// ```
+    pub fn remote_spawner(&self) -> RemoteSpawner {
+        RemoteSpawner {
+            tx: self.remote_tx.clone(),
+        }
+    }
+
+    /// Sets what the pool does when a task panics.
+    ///
+    /// By default, the panic propagates out of the method running the pool.
+    /// See [`PanicPolicy`](PanicPolicy) for the alternatives.
+    pub fn set_panic_policy(&mut self, policy: PanicPolicy) {
+        *self.shared.panic_policy.borrow_mut() = policy;
+    }
+
+    /// Sets how many times tasks may be polled in one tick of the pool.
+    ///
+    /// A tick is one call to [`run_until_stalled`](LocalPool::run_until_stalled)
+    /// or [`try_run_one`](LocalPool::try_run_one), or one turn of
+    /// [`run`](LocalPool::run) and [`run_until`](LocalPool::run_until), which
+    /// poll their future in between turns. Once the budget is used up, the
+    /// tick ends even if tasks are still ready to run; they run in the next
+    /// tick. By default, there is no limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `budget` is zero.
+    pub fn set_poll_budget(&mut self, budget: usize) {
+        assert!(budget > 0, "poll budget must be greater than zero");
+        self.shared.poll_budget.set(budget);
+    }
+
+    /// Sets how many times a single task may be polled in one tick of the
+    /// pool.
+    ///
+    /// Ready tasks are polled in turn, so this keeps a task which keeps
+    /// waking itself from hogging a tick: once it has been polled `max`
+    /// times, it waits for the next tick while the other tasks run. This
+    /// also lets [`run_until_stalled`](LocalPool::run_until_stalled) return
+    /// for such a task. By default, there is no limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    pub fn set_max_polls_per_task(&mut self, max: usize) {
+        assert!(max > 0, "max polls per task must be greater than zero");
+        self.shared.max_task_polls.set(max);
+    }
+
+    /// Sets the [cooperative budget](futures_util::task::budget) of each
+    /// poll of a task.
+    ///
+    /// Futures which spend the budget with
+    /// [`budget::consume`](futures_util::task::budget::consume) yield once
+    /// they have done so `budget` times in one poll. By default, there is no
+    /// budget.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `budget` is zero.
+    pub fn set_coop_budget(&mut self, budget: usize) {
+        assert!(budget > 0, "coop budget must be greater than zero");
+        self.shared.coop_budget.set(Some(budget));
+    }
+
+    /// Sets the hooks called as the pool runs each of its tasks.
+    ///
+    /// The spawn hook is called once the pool picks up a newly spawned task,
+    /// the next time it is run. Tasks spawned with
+    /// [`LocalSpawner::spawn_local_named`](LocalSpawner::spawn_local_named)
+    /// are passed to the hooks along with their name. By default, there are
+    /// no hooks.
+    pub fn set_task_hooks(&mut self, hooks: TaskHooks) {
+        *self.shared.task_hooks.borrow_mut() = hooks;
+    }
+
+    /// Returns a handle to the metrics of this pool.
+    ///
+    /// The handle reads the counters of the pool live. As the pool runs on a
+    /// single thread, the busy time is the time spent polling its tasks.
+    pub fn metrics(&self) -> LocalPoolMetrics {
+        LocalPoolMetrics {
+            incoming: Rc::downgrade(&self.incoming),
+            shared: self.shared.clone(),
+        }
+    }
+
     /// Run all tasks in the pool to completion.
     ///
     /// The given spawner, `spawn`, is used as the default spawner for any
@@ -121,7 +489,7 @@ impl LocalPool {
     /// The function will block the calling thread until *all* tasks in the pool
     /// are complete, including any spawned while running existing tasks.
     pub fn run(&mut self) {
-        run_executor(|cx| self.poll_pool(cx))
+        run_executor(|cx| self.with_tick(|pool| pool.poll_pool(cx)))
     }
 
     /// Runs all the tasks in the pool until the given future completes.
@@ -159,7 +527,7 @@ impl LocalPool {
                 }
             }
 
-            let _ = self.poll_pool(cx);
+            let _ = self.with_tick(|pool| pool.poll_pool(cx));
             Poll::Pending
         })
     }
@@ -194,7 +562,7 @@ impl LocalPool {
     /// Though only one task will be completed, progress may be made on multiple tasks.
     pub fn try_run_one(&mut self) -> bool {
         poll_executor(|ctx| {
-            let ret = self.poll_pool_once(ctx);
+            let ret = self.with_tick(|pool| pool.poll_pool_once(ctx));
 
             // return if we really have executed a future
             match ret {
@@ -229,20 +597,121 @@ impl LocalPool {
     /// remaining incomplete tasks in the pool can continue with further use of one
     /// of the pool's run or poll methods. While the function is running, all tasks
     /// in the pool will try to make progress.
+    ///
+    /// If a [poll budget](LocalPool::set_poll_budget) or a
+    /// [maximum of polls per task](LocalPool::set_max_polls_per_task) is set,
+    /// this also returns once those are used up.
     pub fn run_until_stalled(&mut self) {
-        poll_executor(|ctx| {
-            loop {
-                let result = self.poll_pool_once(ctx);
+        self.tick();
+    }
 
-                // if there are no more ready futures exit
-                match result {
-                    Poll::Pending | Poll::Ready(None) => return,
-                    _ => continue
+    /// Performs one tick of the pool, returning statistics about it.
+    ///
+    /// This runs the tasks in the pool like
+    /// [`run_until_stalled`](LocalPool::run_until_stalled), so it does not
+    /// block the calling thread, and lets the pool be driven once per frame
+    /// of a game or GUI loop. The amount of work done per tick can be limited
+    /// with [`set_poll_budget`](LocalPool::set_poll_budget).
+    ///
+    /// ```
+    /// use futures::executor::LocalPool;
+    /// use futures::task::LocalSpawnExt;
+    /// use futures::future::{ready, pending};
+    ///
+    /// let mut pool = LocalPool::new();
+    /// let mut spawner = pool.spawner();
+    ///
+    /// spawner.spawn_local(ready(())).unwrap();
+    /// spawner.spawn_local(pending()).unwrap();
+    ///
+    /// let stats = pool.tick();
+    /// assert_eq!(stats.spawned(), 2);
+    /// assert_eq!(stats.polled(), 2);
+    /// assert_eq!(stats.completed(), 1);
+    /// ```
+    pub fn tick(&mut self) -> TickStats {
+        poll_executor(|ctx| self.with_tick(|pool| {
+            pool.run_tick(ctx);
+            pool.shared.stats.get()
+        }))
+    }
+
+    /// Runs the tasks in the pool until they have been polled `n_polls` times
+    /// in total, or until no more progress can be made, returning the number
+    /// of polls made.
+    ///
+    /// This does not block the calling thread, which lets the pool be driven
+    /// in slices from another event loop.
+    ///
+    /// ```
+    /// use futures::executor::LocalPool;
+    /// use futures::task::LocalSpawnExt;
+    /// use futures::future::{ready, pending};
+    ///
+    /// let mut pool = LocalPool::new();
+    /// let mut spawner = pool.spawner();
+    ///
+    /// spawner.spawn_local(ready(())).unwrap();
+    /// spawner.spawn_local(ready(())).unwrap();
+    /// spawner.spawn_local(pending()).unwrap();
+    ///
+    /// assert_eq!(pool.run_for(2), 2);
+    /// assert_eq!(pool.run_for(2), 1);
+    /// assert_eq!(pool.run_for(2), 0);
+    /// ```
+    pub fn run_for(&mut self, n_polls: usize) -> usize {
+        poll_executor(|ctx| {
+            let mut polled = 0;
+            while polled < n_polls {
+                let budget = self.shared.poll_budget.get().min(n_polls - polled);
+                let (used, more) = self.with_tick_budget(budget, |pool| {
+                    pool.run_tick(ctx);
+                    let used = budget - pool.shared.budget.get();
+                    let more = pool.shared.budget.get() == 0
+                        || !pool.shared.deferred.borrow().is_empty();
+                    (used, more)
+                });
+                polled += used;
+                if used == 0 || !more {
+                    break;
                 }
             }
+            polled
         })
     }
 
+    // Runs `f` as one tick of the pool, with the configured poll budget.
+    fn with_tick<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        let budget = self.shared.poll_budget.get();
+        self.with_tick_budget(budget, f)
+    }
+
+    fn with_tick_budget<T>(&mut self, budget: usize, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.shared.tick.set(self.shared.tick.get() + 1);
+        self.shared.budget.set(budget);
+        self.shared.stats.set(TickStats::default());
+        let ret = f(self);
+        // Requeue the tasks which ran out of budget for the next tick.
+        let deferred = mem::replace(&mut *self.shared.deferred.borrow_mut(), Default::default());
+        for waker in deferred {
+            waker.wake();
+        }
+        ret
+    }
+
+    // Polls tasks until none is ready in this tick.
+    fn run_tick(&mut self, cx: &mut Context<'_>) {
+        loop {
+            let result = self.poll_pool_once(cx);
+
+            // if there are no more ready futures exit
+            match result {
+                Poll::Pending | Poll::Ready(None) => return,
+                _ => continue
+            }
+        }
+    }
+
     // Make maximal progress on the entire pool of spawned task, returning `Ready`
     // if the pool is empty and `Pending` if no further progress can be made.
     fn poll_pool(&mut self, cx: &mut Context<'_>) -> Poll<()> {
@@ -269,13 +738,47 @@ impl LocalPool {
         // empty the incoming queue of newly-spawned tasks
         {
             let mut incoming = self.incoming.borrow_mut();
-            for task in incoming.drain(..) {
-                self.pool.push(task)
+            for (future, name) in incoming.drain(..) {
+                self.pool.push(LocalTask::new(future, name, self.shared.clone()))
             }
         }
 
+        // and the futures sent by remote spawners, registering to be woken
+        // up for further ones
+        while let Poll::Ready(Some(future)) = self.remote.poll_next_unpin(cx) {
+            self.pool.push(LocalTask::new(future.into(), None, self.shared.clone()))
+        }
+
         // try to execute the next ready future
-        self.pool.poll_next_unpin(cx)
+        let start = Instant::now();
+        let ret = self.pool.poll_next_unpin(cx);
+        self.shared.busy.set(self.shared.busy.get() + start.elapsed());
+        self.shared.pooled.set(self.pool.len());
+        ret
+    }
+}
+
+impl LocalPoolMetrics {
+    /// Returns the number of tasks which have been spawned onto the pool but
+    /// have not been polled yet.
+    pub fn queue_depth(&self) -> usize {
+        self.incoming.upgrade().map(|incoming| incoming.borrow().len()).unwrap_or(0)
+    }
+
+    /// Returns the number of tasks which have been spawned and have not
+    /// completed yet.
+    pub fn alive_tasks(&self) -> usize {
+        self.shared.pooled.get() + self.queue_depth()
+    }
+
+    /// Returns the total number of times tasks have been polled.
+    pub fn total_polls(&self) -> u64 {
+        self.shared.polls.get()
+    }
+
+    /// Returns the total time spent polling tasks.
+    pub fn busy_time(&self) -> Duration {
+        self.shared.busy.get()
     }
 }
 
@@ -291,11 +794,63 @@ impl Default for LocalPool {
 ///
 /// Use a [`LocalPool`](LocalPool) if you need finer-grained control over
 /// spawned tasks.
+///
+/// # Panics
+///
+/// Panics if the current thread is already running an executor, such as when
+/// called from within a task or another call to `block_on`. Use
+/// [`try_block_on`](try_block_on) to get an error instead.
 pub fn block_on<F: Future>(f: F) -> F::Output {
     pin_mut!(f);
     run_executor(|cx| f.as_mut().poll(cx))
 }
 
+/// Run a future to completion on the current thread, or return an error if
+/// the thread is already running an executor.
+///
+/// [`block_on`](block_on) panics in that case instead, since blocking a
+/// thread from within an executor keeps the executor from running its other
+/// tasks, such as the ones the future is waiting for.
+///
+/// ```
+/// use futures::executor::{block_on, try_block_on};
+///
+/// assert_eq!(try_block_on(async { 1 }).unwrap(), 1);
+///
+/// block_on(async {
+///     assert!(try_block_on(async { 1 }).is_err());
+/// });
+/// ```
+pub fn try_block_on<F: Future>(f: F) -> Result<F::Output, EnterError> {
+    let enter = enter()?;
+    pin_mut!(f);
+    Ok(run_entered(enter, |cx| f.as_mut().poll(cx)))
+}
+
+/// Run a future to completion on the current thread, blocking for at most
+/// `dur`.
+///
+/// Returns `None` if the future did not complete in time, in which case it
+/// is dropped. The future is always polled at least once, even if `dur` is
+/// zero.
+///
+/// ```
+/// use futures::executor::block_on_timeout;
+/// use futures::future;
+/// use std::time::Duration;
+///
+/// assert_eq!(block_on_timeout(async { 1 }, Duration::from_secs(1)), Some(1));
+/// assert_eq!(block_on_timeout(future::pending::<()>(), Duration::from_millis(10)), None);
+/// ```
+pub fn block_on_timeout<F: Future>(f: F, dur: Duration) -> Option<F::Output> {
+    pin_mut!(f);
+    match Instant::now().checked_add(dur) {
+        Some(deadline) => run_executor_until(deadline, |cx| f.as_mut().poll(cx)),
+        // A deadline that far out is never reached.
+        None => Some(run_executor(|cx| f.as_mut().poll(cx))),
+    }
+}
+
 /// Turn a stream into a blocking iterator.
 ///
 /// When `next` is called on the resulting `BlockingStream`, the caller
@@ -326,6 +881,30 @@ impl<S: Stream + Unpin> BlockingStream<S> {
     pub fn into_inner(self) -> S {
         self.stream
     }
+
+    /// Blocks for at most `dur` waiting for the next element of the stream.
+    ///
+    /// Returns `None` if no element became available in time, and
+    /// `Some(None)` if the stream has ended. The stream is left untouched by
+    /// a timeout, so it can be waited on again.
+    ///
+    /// ```
+    /// use futures::channel::mpsc;
+    /// use futures::executor::block_on_stream;
+    /// use std::time::Duration;
+    ///
+    /// let (tx, rx) = mpsc::unbounded();
+    /// let mut iter = block_on_stream(rx);
+    /// assert_eq!(iter.next_timeout(Duration::from_millis(10)), None);
+    ///
+    /// tx.unbounded_send(1).unwrap();
+    /// drop(tx);
+    /// assert_eq!(iter.next_timeout(Duration::from_millis(10)), Some(Some(1)));
+    /// assert_eq!(iter.next_timeout(Duration::from_millis(10)), Some(None));
+    /// ```
+    pub fn next_timeout(&mut self, dur: Duration) -> Option<Option<S::Item>> {
+        block_on_timeout(self.stream.next(), dur)
+    }
 }
 
 impl<S: Stream + Unpin> Iterator for BlockingStream<S> {
@@ -335,13 +914,35 @@ impl<S: Stream + Unpin> Iterator for BlockingStream<S> {
     }
 }
 
+impl LocalSpawner {
+    /// Spawns a task polling the given future, which is passed to the
+    /// [task hooks](LocalPool::set_task_hooks) of the pool under the given
+    /// name.
+    pub fn spawn_local_named<Fut>(
+        &self,
+        name: impl Into<String>,
+        future: Fut,
+    ) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = ()> + 'static,
+    {
+        if let Some(incoming) = self.incoming.upgrade() {
+            let future = LocalFutureObj::new(Box::new(future));
+            incoming.borrow_mut().push((future, Some(name.into())));
+            Ok(())
+        } else {
+            Err(SpawnError::shutdown())
+        }
+    }
+}
+
 impl Spawn for LocalSpawner {
     fn spawn_obj(
         &mut self,
         future: FutureObj<'static, ()>,
     ) -> Result<(), SpawnError> {
         if let Some(incoming) = self.incoming.upgrade() {
-            incoming.borrow_mut().push(future.into());
+            incoming.borrow_mut().push((future.into(), None));
             Ok(())
         } else {
             Err(SpawnError::shutdown())
@@ -357,13 +958,30 @@ impl Spawn for LocalSpawner {
     }
 }
 
+impl Spawn for RemoteSpawner {
+    fn spawn_obj(
+        &mut self,
+        future: FutureObj<'static, ()>,
+    ) -> Result<(), SpawnError> {
+        self.tx.unbounded_send(future).map_err(|_| SpawnError::shutdown())
+    }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        if self.tx.is_closed() {
+            Err(SpawnError::shutdown())
+        } else {
+            Ok(())
+        }
+    }
+}
+
 impl LocalSpawn for LocalSpawner {
     fn spawn_local_obj(
         &mut self,
         future: LocalFutureObj<'static, ()>,
     ) -> Result<(), SpawnError> {
         if let Some(incoming) = self.incoming.upgrade() {
-            incoming.borrow_mut().push(future);
+            incoming.borrow_mut().push((future, None));
             Ok(())
         } else {
             Err(SpawnError::shutdown())
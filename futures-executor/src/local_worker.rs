@@ -0,0 +1,103 @@
+use crate::local_pool::{LocalPool, LocalSpawner};
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_util::task::AtomicWaker;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+type Job = Box<dyn FnOnce(&mut LocalSpawner) + Send>;
+
+/// A thread running a [`LocalPool`](LocalPool), onto which other threads
+/// submit closures creating `!Send` tasks.
+pub(crate) struct LocalWorker {
+    jobs: Mutex<Jobs>,
+    waker: AtomicWaker,
+}
+
+struct Jobs {
+    queue: VecDeque<Job>,
+    closed: bool,
+}
+
+impl LocalWorker {
+    /// Starts the thread, which calls `on_exit` once it has been closed and
+    /// has dropped its tasks.
+    pub(crate) fn start<E>(
+        builder: thread::Builder,
+        on_exit: E,
+    ) -> io::Result<(Arc<LocalWorker>, thread::JoinHandle<()>)>
+    where
+        E: FnOnce() + Send + 'static,
+    {
+        let worker = Arc::new(LocalWorker {
+            jobs: Mutex::new(Jobs { queue: VecDeque::new(), closed: false }),
+            waker: AtomicWaker::new(),
+        });
+        let intake_worker = worker.clone();
+        let thread = builder.spawn(move || {
+            let mut pool = LocalPool::new();
+            let spawner = pool.spawner();
+            pool.run_until(Intake { worker: intake_worker, spawner });
+            drop(pool);
+            on_exit();
+        })?;
+        Ok((worker, thread))
+    }
+
+    /// Submits a job to the thread, returning `false` if it has been closed.
+    pub(crate) fn submit(&self, job: Job) -> bool {
+        let mut jobs = self.jobs.lock().unwrap();
+        if jobs.closed {
+            return false;
+        }
+        jobs.queue.push_back(job);
+        drop(jobs);
+        self.waker.wake();
+        true
+    }
+
+    /// Makes the thread exit, dropping the tasks which have not completed.
+    pub(crate) fn close(&self) {
+        self.jobs.lock().unwrap().closed = true;
+        self.waker.wake();
+    }
+}
+
+impl fmt::Debug for LocalWorker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalWorker")
+            .field("closed", &self.jobs.lock().unwrap().closed)
+            .finish()
+    }
+}
+
+/// The future run by the thread, which spawns the submitted jobs until the
+/// thread is closed.
+struct Intake {
+    worker: Arc<LocalWorker>,
+    spawner: LocalSpawner,
+}
+
+impl Future for Intake {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.worker.waker.register(cx.waker());
+        loop {
+            let mut jobs = self.worker.jobs.lock().unwrap();
+            if jobs.closed {
+                return Poll::Ready(());
+            }
+            let job = match jobs.queue.pop_front() {
+                Some(job) => job,
+                None => return Poll::Pending,
+            };
+            drop(jobs);
+            job(&mut self.spawner);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+/// What an executor does when a task it polls panics.
+///
+/// This is set with
+/// [`ThreadPoolBuilder::panic_policy`](crate::ThreadPoolBuilder::panic_policy)
+/// and [`LocalPool::set_panic_policy`](crate::LocalPool::set_panic_policy).
+///
+/// Tasks spawned with a handle, such as with
+/// [`spawn_with_handle`](futures_util::task::SpawnExt::spawn_with_handle),
+/// catch their own panics and report them through the handle, so this only
+/// concerns tasks which nobody waits on.
+#[derive(Clone)]
+pub enum PanicPolicy {
+    /// Let the panic unwind out of the executor.
+    ///
+    /// This takes down the worker thread of a `ThreadPool`, whose tasks are
+    /// picked up by the remaining workers, and makes the method running a
+    /// `LocalPool` panic. This is the default.
+    Propagate,
+    /// Drop the task and pass the panic payload to the callback, then carry
+    /// on running the other tasks.
+    CatchAndLog(Arc<dyn Fn(Box<dyn Any + Send>) + Send + Sync>),
+    /// Drop the task and replace the worker thread which polled it with a new
+    /// one, which runs the
+    /// [`after_start`](crate::ThreadPoolBuilder::after_start) hook again.
+    ///
+    /// A `LocalPool` has no worker thread to replace, so it just drops the
+    /// task and carries on.
+    RestartWorker,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for PanicPolicy {
+    fn default() -> PanicPolicy {
+        PanicPolicy::Propagate
+    }
+}
+
+impl PanicPolicy {
+    /// Creates a [`CatchAndLog`](PanicPolicy::CatchAndLog) policy calling `f`.
+    pub fn catch_and_log<F>(f: F) -> PanicPolicy
+    where
+        F: Fn(Box<dyn Any + Send>) + Send + Sync + 'static,
+    {
+        PanicPolicy::CatchAndLog(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for PanicPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PanicPolicy::Propagate => f.write_str("Propagate"),
+            PanicPolicy::CatchAndLog(_) => f.debug_tuple("CatchAndLog").field(&"...").finish(),
+            PanicPolicy::RestartWorker => f.write_str("RestartWorker"),
+        }
+    }
+}
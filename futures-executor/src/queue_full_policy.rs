@@ -0,0 +1,56 @@
+use futures_core::future::FutureObj;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// What a [`ThreadPool`](crate::ThreadPool) does with a new task while it has
+/// [`max_queued_tasks`](crate::ThreadPoolBuilder::max_queued_tasks) tasks
+/// waiting to be run already.
+///
+/// This is set with
+/// [`ThreadPoolBuilder::queue_full_policy`](crate::ThreadPoolBuilder::queue_full_policy).
+#[derive(Clone)]
+pub enum QueueFullPolicy {
+    /// Fail to spawn the task with a
+    /// [`SpawnError`](futures_core::task::SpawnError) for which
+    /// [`is_queue_full`](futures_core::task::SpawnError::is_queue_full)
+    /// returns `true`. This is the default.
+    Reject,
+    /// Block the spawning thread until the queue has room for the task, or
+    /// reject the task like [`Reject`](QueueFullPolicy::Reject) once the
+    /// timeout has passed.
+    ///
+    /// Tasks spawned from the worker threads of the pool are rejected right
+    /// away, since blocking a worker keeps it from draining the queue.
+    Block(Duration),
+    /// Pass the task to the callback instead of queueing it, and report it as
+    /// spawned. The callback can drop the task or run it elsewhere.
+    Shed(Arc<dyn Fn(FutureObj<'static, ()>) + Send + Sync>),
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for QueueFullPolicy {
+    fn default() -> QueueFullPolicy {
+        QueueFullPolicy::Reject
+    }
+}
+
+impl QueueFullPolicy {
+    /// Creates a [`Shed`](QueueFullPolicy::Shed) policy calling `f`.
+    pub fn shed<F>(f: F) -> QueueFullPolicy
+    where
+        F: Fn(FutureObj<'static, ()>) + Send + Sync + 'static,
+    {
+        QueueFullPolicy::Shed(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for QueueFullPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueueFullPolicy::Reject => f.write_str("Reject"),
+            QueueFullPolicy::Block(timeout) => f.debug_tuple("Block").field(timeout).finish(),
+            QueueFullPolicy::Shed(_) => f.debug_tuple("Shed").field(&"...").finish(),
+        }
+    }
+}
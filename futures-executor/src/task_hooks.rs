@@ -0,0 +1,151 @@
+use futures_core::task::Poll;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+type TaskHook = Arc<dyn Fn(&TaskInfo<'_>) + Send + Sync>;
+type PollHook = Arc<dyn Fn(&TaskInfo<'_>, Duration) + Send + Sync>;
+
+/// Callbacks which an executor invokes as it runs each of its tasks.
+///
+/// This is set with
+/// [`ThreadPoolBuilder::task_hooks`](crate::ThreadPoolBuilder::task_hooks)
+/// and [`LocalPool::set_task_hooks`](crate::LocalPool::set_task_hooks), and
+/// is meant for building tracing and diagnostics tools on top of the
+/// executors. The hooks are called on the thread running the task, in
+/// between its polls, so they should return quickly.
+///
+/// ```
+/// use futures::executor::{block_on, TaskHooks, ThreadPool};
+///
+/// let hooks = TaskHooks::new()
+///     .on_poll(|task, duration| {
+///         println!("task {} polled for {:?}", task.name().unwrap_or("?"), duration);
+///     });
+/// let pool = ThreadPool::builder().task_hooks(hooks).create().unwrap();
+///
+/// block_on(pool.spawn_named("answer", async { 42 })).unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct TaskHooks {
+    on_spawn: Option<TaskHook>,
+    on_first_poll: Option<TaskHook>,
+    on_poll: Option<PollHook>,
+    on_complete: Option<TaskHook>,
+}
+
+/// The task passed to the callbacks of [`TaskHooks`](TaskHooks).
+#[derive(Clone, Copy, Debug)]
+pub struct TaskInfo<'a> {
+    id: u64,
+    name: Option<&'a str>,
+}
+
+impl TaskHooks {
+    /// Creates a set of hooks which do nothing.
+    pub fn new() -> TaskHooks {
+        TaskHooks::default()
+    }
+
+    /// Calls `f` when a task is spawned, before it is first polled.
+    pub fn on_spawn<F>(mut self, f: F) -> TaskHooks
+    where
+        F: Fn(&TaskInfo<'_>) + Send + Sync + 'static,
+    {
+        self.on_spawn = Some(Arc::new(f));
+        self
+    }
+
+    /// Calls `f` right before a task is polled for the first time.
+    pub fn on_first_poll<F>(mut self, f: F) -> TaskHooks
+    where
+        F: Fn(&TaskInfo<'_>) + Send + Sync + 'static,
+    {
+        self.on_first_poll = Some(Arc::new(f));
+        self
+    }
+
+    /// Calls `f` after each poll of a task, with the time the poll took.
+    pub fn on_poll<F>(mut self, f: F) -> TaskHooks
+    where
+        F: Fn(&TaskInfo<'_>, Duration) + Send + Sync + 'static,
+    {
+        self.on_poll = Some(Arc::new(f));
+        self
+    }
+
+    /// Calls `f` once a task has run to completion.
+    ///
+    /// This is not called for tasks which panic, or which are dropped before
+    /// completing.
+    pub fn on_complete<F>(mut self, f: F) -> TaskHooks
+    where
+        F: Fn(&TaskInfo<'_>) + Send + Sync + 'static,
+    {
+        self.on_complete = Some(Arc::new(f));
+        self
+    }
+
+    pub(crate) fn spawned(&self, task: &TaskInfo<'_>) {
+        if let Some(f) = &self.on_spawn {
+            f(task);
+        }
+    }
+
+    /// Polls a task with `poll`, calling the hooks around it.
+    pub(crate) fn poll<T>(
+        &self,
+        task: &TaskInfo<'_>,
+        first: bool,
+        poll: impl FnOnce() -> Poll<T>,
+    ) -> Poll<T> {
+        if first {
+            if let Some(f) = &self.on_first_poll {
+                f(task);
+            }
+        }
+        let res = match &self.on_poll {
+            Some(f) => {
+                let start = Instant::now();
+                let res = poll();
+                f(task, start.elapsed());
+                res
+            }
+            None => poll(),
+        };
+        if res.is_ready() {
+            if let Some(f) = &self.on_complete {
+                f(task);
+            }
+        }
+        res
+    }
+}
+
+impl fmt::Debug for TaskHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskHooks")
+            .field("on_spawn", &self.on_spawn.is_some())
+            .field("on_first_poll", &self.on_first_poll.is_some())
+            .field("on_poll", &self.on_poll.is_some())
+            .field("on_complete", &self.on_complete.is_some())
+            .finish()
+    }
+}
+
+impl<'a> TaskInfo<'a> {
+    pub(crate) fn new(id: u64, name: Option<&'a str>) -> TaskInfo<'a> {
+        TaskInfo { id, name }
+    }
+
+    /// Returns the identifier of the task, which is unique among the tasks
+    /// of its executor.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the name the task was spawned with, if any.
+    pub fn name(&self) -> Option<&'a str> {
+        self.name
+    }
+}
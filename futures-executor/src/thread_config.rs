@@ -0,0 +1,110 @@
+use std::io;
+
+/// The scheduling priority of the worker threads of a
+/// [`ThreadPool`](crate::ThreadPool).
+///
+/// This is set with
+/// [`ThreadPoolBuilder::thread_priority`](crate::ThreadPoolBuilder::thread_priority).
+/// On Linux, the priorities map to nice values from 19 for `Lowest` to -20
+/// for `Highest`, where raising the priority above `Normal` usually requires
+/// privileges. On other platforms, the priority is left alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ThreadPriority {
+    /// Only run when nothing else wants the CPU.
+    Lowest,
+    /// Below the priority of other threads.
+    Low,
+    /// The priority threads get by default.
+    Normal,
+    /// Above the priority of other threads.
+    High,
+    /// Ahead of nearly everything else.
+    Highest,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for ThreadPriority {
+    fn default() -> ThreadPriority {
+        ThreadPriority::Normal
+    }
+}
+
+/// The configuration applied by each worker thread to itself when it starts.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ThreadConfig {
+    pub(crate) cores: Vec<usize>,
+    pub(crate) priority: ThreadPriority,
+}
+
+impl ThreadConfig {
+    /// Configures the current thread as the worker `idx`.
+    ///
+    /// This is best effort: the worker runs regardless of whether the
+    /// platform allows it to be configured.
+    pub(crate) fn apply(&self, idx: usize) {
+        if !self.cores.is_empty() {
+            let _ = pin_to_core(self.cores[idx % self.cores.len()]);
+        }
+        if self.priority != ThreadPriority::Normal {
+            let _ = set_priority(self.priority);
+        }
+    }
+}
+
+/// Returns whether a thread can be pinned to `core` on this platform.
+#[cfg(target_os = "linux")]
+pub(crate) fn is_valid_core(core: usize) -> bool {
+    core < libc::CPU_SETSIZE as usize
+}
+
+#[cfg(target_os = "linux")]
+fn pin_to_core(core: usize) -> io::Result<()> {
+    if !is_valid_core(core) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "core index out of range"));
+    }
+    // Safety: the set is a plain bitmask which `core` is within, and the
+    // calls only touch the current thread.
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn set_priority(priority: ThreadPriority) -> io::Result<()> {
+    let nice = match priority {
+        ThreadPriority::Lowest => 19,
+        ThreadPriority::Low => 10,
+        ThreadPriority::Normal => 0,
+        ThreadPriority::High => -10,
+        ThreadPriority::Highest => -20,
+    };
+    // Safety: on Linux, the nice value of a thread id only affects that
+    // thread.
+    unsafe {
+        let tid = libc::syscall(libc::SYS_gettid) as libc::id_t;
+        if libc::setpriority(libc::PRIO_PROCESS as _, tid, nice) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn is_valid_core(_core: usize) -> bool {
+    true
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_to_core(_core: usize) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Other, "core affinity is not supported"))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_priority(_priority: ThreadPriority) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Other, "thread priorities are not supported"))
+}
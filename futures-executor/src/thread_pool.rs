@@ -1,21 +1,41 @@
+use crate::blocking::{BlockingPool, SpawnBlocking};
 use crate::enter;
+use crate::join_handle::{join_slot, joinable, JoinHandle, Joinable};
+use crate::local_worker::LocalWorker;
+use crate::panic_policy::PanicPolicy;
+use crate::queue_full_policy::QueueFullPolicy;
+use crate::task_hooks::{TaskHooks, TaskInfo};
+use crate::thread_config::{is_valid_core, ThreadConfig, ThreadPriority};
 use crate::unpark_mutex::UnparkMutex;
-use futures_core::future::{Future, FutureObj};
-use futures_core::task::{Context, Poll, Spawn, SpawnError};
-use futures_util::future::FutureExt;
-use futures_util::task::{ArcWake, waker_ref};
+use crossbeam_deque::{Injector, Steal, Stealer, Worker};
+use futures_core::future::{Future, FutureObj, LocalFutureObj};
+use futures_core::task::{Context, LocalSpawn, Poll, Spawn, SpawnConfig, SpawnError, Waker};
+use futures_util::task::{budget, ArcWake, waker_ref};
+use std::cell::{RefCell, UnsafeCell};
+use std::cmp;
 use std::io;
-use std::sync::{Arc, Mutex};
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::mpsc;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::atomic::{fence, AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::thread;
+use std::time::{Duration, Instant};
 use std::fmt;
 
 /// A general-purpose thread pool for scheduling tasks that poll futures to
 /// completion.
 ///
-/// The thread pool multiplexes any number of tasks onto a fixed number of
-/// worker threads.
+/// The thread pool multiplexes any number of tasks onto a number of worker
+/// threads, which is fixed unless the pool is created with
+/// [`min_threads`](ThreadPoolBuilder::min_threads) lower than
+/// [`max_threads`](ThreadPoolBuilder::max_threads).
+///
+/// Each worker thread has a queue of its own, which receives the tasks
+/// spawned by the tasks it runs, and idle workers steal tasks from the queues
+/// of busy ones. A task woken by a task running on a worker is run next by
+/// that worker, so tasks passing messages to each other keep running on the
+/// same thread while the message is hot in its cache.
 ///
 /// This type is a clonable handle to the threadpool itself.
 /// Cloning it will only create a new reference, not a new threadpool.
@@ -26,20 +46,104 @@ pub struct ThreadPool {
 /// Thread pool configuration object.
 pub struct ThreadPoolBuilder {
     pool_size: usize,
+    min_threads: Option<usize>,
+    max_threads: Option<usize>,
+    idle_timeout: Duration,
     stack_size: usize,
     name_prefix: Option<String>,
+    thread_config: ThreadConfig,
     after_start: Option<Arc<dyn Fn(usize) + Send + Sync>>,
     before_stop: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    max_blocking_threads: usize,
+    blocking_keep_alive: Duration,
+    panic_policy: PanicPolicy,
+    max_queued_tasks: Option<usize>,
+    queue_full_policy: QueueFullPolicy,
+    task_hooks: TaskHooks,
+    coop_budget: Option<usize>,
 }
 
 trait AssertSendSync: Send + Sync {}
 impl AssertSendSync for ThreadPool {}
 
 struct PoolState {
-    tx: Mutex<mpsc::Sender<Message>>,
-    rx: Mutex<mpsc::Receiver<Message>>,
+    // Tasks spawned or woken from outside the worker threads.
+    injector: Injector<Task>,
+    // Tasks of high and low priority, which never go through the queues of
+    // the workers.
+    high: Injector<Task>,
+    low: Injector<Task>,
+    // Handles to steal from the queue of each worker.
+    stealers: Vec<Stealer<Task>>,
+    // Number of tasks pushed onto the injectors.
+    injected: AtomicU64,
+    stats: Vec<WorkerStats>,
+    // Number of workers waiting on `sleep_cv` for a task.
+    sleepers: AtomicUsize,
+    // Number of sleepers which have been notified and have not woken up yet.
+    sleep: Mutex<usize>,
+    sleep_cv: Condvar,
+    // Limit on `queued`, the number of spawned tasks which have not been run
+    // yet, if any.
+    max_queued: Option<usize>,
+    queued: AtomicUsize,
+    queue_full_policy: QueueFullPolicy,
+    // Number of threads waiting on `queue_cv` for room in the queue.
+    queue_waiters: AtomicUsize,
+    queue: Mutex<()>,
+    queue_cv: Condvar,
+    blocking: Arc<BlockingPool>,
+    // The thread running tasks which are not `Send`, once there has been one.
+    local_worker: Mutex<Option<Arc<LocalWorker>>>,
+    panic_policy: PanicPolicy,
+    task_hooks: TaskHooks,
+    coop_budget: Option<usize>,
+    // Used to number the tasks.
+    task_ids: AtomicU64,
+    // Configuration for starting replacement workers.
+    name_prefix: Option<String>,
+    stack_size: usize,
+    thread_config: ThreadConfig,
     cnt: AtomicUsize,
+    // The maximum number of workers, which is the number of slots in
+    // `stealers` and `stats`.
     size: usize,
+    // The number of workers kept running while idle.
+    min_workers: usize,
+    idle_timeout: Duration,
+    // Number of workers running, and the slots of the others along with
+    // their queues. `live` is only changed while holding `free`.
+    live: AtomicUsize,
+    free: Mutex<Vec<(usize, Worker<Task>)>>,
+    // The hooks for workers started under load, if there can be any.
+    grow_hooks: Hooks,
+    // Number of spawned tasks which have not completed yet.
+    active: AtomicUsize,
+    // Set once a shutdown has been requested, after which spawning fails.
+    closed: AtomicBool,
+    // Set once the workers have been told to stop.
+    closing: AtomicBool,
+    shutdown: Mutex<ShutdownState>,
+    shutdown_cv: Condvar,
+}
+
+// Counters for a single worker, each only updated by the worker itself.
+#[derive(Default)]
+struct WorkerStats {
+    // Tasks scheduled onto this worker by the tasks it runs.
+    scheduled: AtomicU64,
+    // Tasks taken from any queue to be run.
+    taken: AtomicU64,
+    polls: AtomicU64,
+    steals: AtomicU64,
+    busy_nanos: AtomicU64,
+}
+
+struct ShutdownState {
+    // Number of worker threads which have not exited yet.
+    running: usize,
+    workers: Vec<thread::JoinHandle<()>>,
+    wakers: Vec<Waker>,
 }
 
 impl fmt::Debug for ThreadPool {
@@ -54,14 +158,73 @@ impl fmt::Debug for ThreadPoolBuilder {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ThreadPoolBuilder")
             .field("pool_size", &self.pool_size)
+            .field("min_threads", &self.min_threads)
+            .field("max_threads", &self.max_threads)
             .field("name_prefix", &self.name_prefix)
             .finish()
     }
 }
 
-enum Message {
-    Run(Task),
-    Close,
+// How many times in a row a worker runs the task in its LIFO slot before
+// giving the tasks in its queue a turn, so a pair of tasks waking each other
+// cannot starve them.
+const MAX_LIFO_POLLS: usize = 3;
+
+// How often a worker checks the injector before its own queue, so tasks from
+// outside the pool are not starved by tasks spawning more tasks.
+const INJECTOR_INTERVAL: usize = 61;
+
+// How many high priority tasks a worker runs in a row before giving the
+// other tasks a turn.
+const MAX_HIGH_POLLS: usize = 7;
+
+// How often a worker checks for low priority tasks before any others, so they
+// are not starved by a steady stream of higher priority tasks.
+const LOW_INTERVAL: usize = 31;
+
+// How many times an idle worker yields its thread looking for tasks before
+// going to sleep.
+const MAX_IDLE_YIELDS: usize = 3;
+
+/// The state of a worker thread, which tasks running on it schedule onto.
+struct Local {
+    // The pool the worker belongs to, only used for comparison.
+    pool: *const PoolState,
+    idx: usize,
+    queue: Worker<Task>,
+    // The task most recently woken by a task running on this worker.
+    lifo: Option<Task>,
+    lifo_polls: usize,
+    high_polls: usize,
+    ticks: usize,
+    low_ticks: usize,
+}
+
+/// The priority of a task spawned with
+/// [`ThreadPool::spawn_with_priority`](ThreadPool::spawn_with_priority).
+///
+/// Workers run tasks of a higher priority first, but still give tasks of a
+/// lower priority a turn every so often, so they make progress even while
+/// the pool is flooded with higher priority tasks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// For batch work which should not hold up other tasks.
+    Low,
+    /// The priority of tasks spawned by any other means.
+    Normal,
+    /// For latency-sensitive work.
+    High,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for Priority {
+    fn default() -> Priority {
+        Priority::Normal
+    }
+}
+
+thread_local! {
+    static LOCAL: RefCell<Option<Local>> = RefCell::new(None);
 }
 
 impl ThreadPool {
@@ -99,17 +262,47 @@ impl ThreadPool {
     /// Spawns a future that will be run to completion.
     ///
     /// > **Note**: This method is similar to `Spawn::spawn_obj`, except that
-    /// >           it is guaranteed to always succeed.
+    /// >           it is guaranteed to always succeed. If the pool has been
+    /// >           shut down or its queue is full, the future is dropped
+    /// >           without being run.
     pub fn spawn_obj_ok(&self, future: FutureObj<'static, ()>) {
-        let task = Task {
-            future,
-            wake_handle: Arc::new(WakeHandle {
-                exec: self.clone(),
-                mutex: UnparkMutex::new(),
-            }),
+        let name = config_name(&future);
+        let _ = self.try_spawn(future, Priority::Normal, name);
+    }
+
+    fn try_spawn<Fut>(
+        &self,
+        future: Fut,
+        priority: Priority,
+        name: Option<String>,
+    ) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        if !self.state.start_task() {
+            return Err(SpawnError::shutdown());
+        }
+        if !self.state.reserve_queue_slot() {
+            self.state.complete_task();
+            return match &self.state.queue_full_policy {
+                QueueFullPolicy::Shed(f) => {
+                    f(FutureObj::new(Box::new(future)));
+                    Ok(())
+                }
+                _ => Err(SpawnError::queue_full()),
+            };
+        }
+        let cell = Arc::new(TaskCell {
+            mutex: UnparkMutex::new(),
+            slot: UnsafeCell::new(Slot { future: Some(future), polled: false }),
             exec: self.clone(),
-        };
-        self.state.send(Message::Run(task));
+            priority,
+            id: self.state.task_ids.fetch_add(1, Ordering::Relaxed),
+            name,
+        });
+        self.state.task_hooks.spawned(&cell.info());
+        PoolState::schedule(&self.state, Task(cell), false);
+        Ok(())
     }
 
     /// Spawns a task that polls the given future with output `()` to
@@ -130,7 +323,207 @@ impl ThreadPool {
     where
         Fut: Future<Output = ()> + Send + 'static,
     {
-        self.spawn_obj_ok(FutureObj::new(Box::new(future)))
+        let _ = self.try_spawn(future, Priority::Normal, None);
+    }
+
+    /// Spawns a task that polls the given future to completion, returning a
+    /// [`JoinHandle`](JoinHandle) for its output.
+    ///
+    /// The handle can abort the task, and reports a panic of the task as an
+    /// error instead of taking down a worker thread. If the pool has been
+    /// shut down, the handle resolves to a cancellation error.
+    ///
+    /// ```
+    /// use futures::executor::{block_on, ThreadPool};
+    ///
+    /// let pool = ThreadPool::new().unwrap();
+    ///
+    /// let handle = pool.spawn_with_join_handle(async { 1 + 2 });
+    /// assert_eq!(block_on(handle).unwrap(), 3);
+    /// ```
+    pub fn spawn_with_join_handle<Fut>(&self, future: Fut) -> JoinHandle<Fut::Output>
+    where
+        Fut: Future + Send + 'static,
+        Fut::Output: Send + 'static,
+    {
+        self.spawn_with_priority(future, Priority::Normal)
+    }
+
+    /// Spawns a task like
+    /// [`spawn_with_join_handle`](ThreadPool::spawn_with_join_handle), which
+    /// is passed to the [task hooks](ThreadPoolBuilder::task_hooks) of the
+    /// pool under the given name.
+    pub fn spawn_named<Fut>(&self, name: impl Into<String>, future: Fut) -> JoinHandle<Fut::Output>
+    where
+        Fut: Future + Send + 'static,
+        Fut::Output: Send + 'static,
+    {
+        self.spawn_inner(future, Priority::Normal, Some(name.into()))
+    }
+
+    /// Spawns a task like
+    /// [`spawn_with_join_handle`](ThreadPool::spawn_with_join_handle), which
+    /// is run at the given priority.
+    ///
+    /// The priority applies to every poll of the task, including after it is
+    /// woken.
+    ///
+    /// ```
+    /// use futures::executor::{block_on, Priority, ThreadPool};
+    ///
+    /// let pool = ThreadPool::new().unwrap();
+    ///
+    /// let report = pool.spawn_with_priority(async { /* ... */ }, Priority::Low);
+    /// let request = pool.spawn_with_priority(async { /* ... */ }, Priority::High);
+    /// block_on(request).unwrap();
+    /// block_on(report).unwrap();
+    /// ```
+    pub fn spawn_with_priority<Fut>(&self, future: Fut, priority: Priority) -> JoinHandle<Fut::Output>
+    where
+        Fut: Future + Send + 'static,
+        Fut::Output: Send + 'static,
+    {
+        self.spawn_inner(future, priority, None)
+    }
+
+    fn spawn_inner<Fut>(&self, future: Fut, priority: Priority, name: Option<String>) -> JoinHandle<Fut::Output>
+    where
+        Fut: Future + Send + 'static,
+        Fut::Output: Send + 'static,
+    {
+        let (future, handle) = joinable(future);
+        match self.try_spawn(future, priority, name) {
+            Ok(()) => handle,
+            Err(_) => JoinHandle::cancelled(),
+        }
+    }
+
+    /// Runs a blocking closure on a separate pool of threads, returning a
+    /// future which resolves to its result.
+    ///
+    /// This keeps blocking calls and long computations from holding up the
+    /// worker threads, which would keep other tasks from making progress.
+    /// The blocking threads are started as needed, up to
+    /// [`max_blocking_threads`](ThreadPoolBuilder::max_blocking_threads), and
+    /// exit after being idle for
+    /// [`blocking_keep_alive`](ThreadPoolBuilder::blocking_keep_alive).
+    ///
+    /// ```
+    /// use futures::executor::{block_on, ThreadPool};
+    ///
+    /// let pool = ThreadPool::new().unwrap();
+    ///
+    /// let contents = pool.spawn_blocking(|| std::fs::read_to_string("Cargo.toml"));
+    /// assert!(block_on(contents).is_ok());
+    /// ```
+    pub fn spawn_blocking<F, T>(&self, f: F) -> SpawnBlocking<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        BlockingPool::spawn(&self.state.blocking, f)
+    }
+
+    /// Spawns a task polling a future which is not `Send` to completion, on
+    /// a thread dedicated to such tasks, returning a
+    /// [`JoinHandle`](JoinHandle) for its output.
+    ///
+    /// The future is created by calling `f` on the dedicated thread, which
+    /// runs every such task of the pool on a [`LocalPool`](crate::LocalPool).
+    /// The thread is started along with the first of these tasks, and exits
+    /// along with the worker threads. The handle behaves like the one
+    /// returned by
+    /// [`spawn_with_join_handle`](ThreadPool::spawn_with_join_handle), and
+    /// also reports a panic of `f` as an error.
+    ///
+    /// ```
+    /// use futures::executor::{block_on, ThreadPool};
+    /// use std::rc::Rc;
+    ///
+    /// let pool = ThreadPool::new().unwrap();
+    ///
+    /// let handle = pool.spawn_local_on_dedicated(|| {
+    ///     let shared = Rc::new(1);
+    ///     async move { *shared + 2 }
+    /// });
+    /// assert_eq!(block_on(handle).unwrap(), 3);
+    /// ```
+    pub fn spawn_local_on_dedicated<F, Fut>(&self, f: F) -> JoinHandle<Fut::Output>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future + 'static,
+        Fut::Output: Send + 'static,
+    {
+        if !self.state.start_task() {
+            return JoinHandle::cancelled();
+        }
+        let guard = TaskGuard(self.state.clone());
+        let worker = match PoolState::local_worker(&self.state) {
+            Ok(worker) => worker,
+            Err(_) => return JoinHandle::cancelled(),
+        };
+        let (slot, handle) = join_slot();
+        // If the job or the task is dropped without running, the slot and
+        // the guard still settle the handle and the count of tasks.
+        worker.submit(Box::new(move |spawner| {
+            if let Some(future) = slot.wrap(f) {
+                let task = LocalTask { future, _guard: guard };
+                let _ = spawner.spawn_local_obj(LocalFutureObj::new(Box::new(task)));
+            }
+        }));
+        handle
+    }
+
+    /// Returns a handle to the metrics of this thread pool.
+    ///
+    /// The handle reads the counters of the pool live, and does not keep the
+    /// pool running.
+    pub fn metrics(&self) -> ThreadPoolMetrics {
+        ThreadPoolMetrics { state: self.state.clone() }
+    }
+
+    /// Shuts the thread pool down, returning a future which completes once
+    /// every task has run to completion and the worker threads have exited.
+    ///
+    /// New tasks are rejected as soon as this is called, by all handles to
+    /// the pool, while tasks which have already been spawned keep running.
+    /// The future should be awaited from outside the pool: a task running on
+    /// the pool which awaits it is itself in flight, so it never completes.
+    ///
+    /// ```
+    /// use futures::executor::{block_on, ThreadPool};
+    ///
+    /// let pool = ThreadPool::new().unwrap();
+    /// pool.spawn_ok(async { /* ... */ });
+    ///
+    /// block_on(pool.shutdown());
+    /// ```
+    pub fn shutdown(&self) -> Shutdown {
+        self.state.begin_shutdown();
+        Shutdown { state: self.state.clone() }
+    }
+
+    /// Shuts the thread pool down like [`shutdown`](ThreadPool::shutdown),
+    /// blocking the current thread for at most `timeout` until it completes.
+    ///
+    /// Returns `true` if every task completed and the worker threads exited
+    /// in time. Otherwise the pool keeps rejecting new tasks, and the worker
+    /// threads exit in the background once the remaining tasks complete.
+    pub fn shutdown_timeout(&self, timeout: Duration) -> bool {
+        self.state.begin_shutdown();
+        let deadline = Instant::now() + timeout;
+        let mut shutdown = self.state.shutdown.lock().unwrap();
+        while shutdown.running > 0 || !self.state.closing.load(Ordering::SeqCst) {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            shutdown = self.state.shutdown_cv.wait_timeout(shutdown, deadline - now).unwrap().0;
+        }
+        let workers = shutdown.workers.drain(..).collect::<Vec<_>>();
+        drop(shutdown);
+        join_workers(workers);
+        true
     }
 }
 
@@ -139,8 +532,12 @@ impl Spawn for ThreadPool {
         &mut self,
         future: FutureObj<'static, ()>,
     ) -> Result<(), SpawnError> {
-        self.spawn_obj_ok(future);
-        Ok(())
+        let name = config_name(&future);
+        self.try_spawn(future, Priority::Normal, name)
+    }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        self.state.status()
     }
 }
 
@@ -149,34 +546,675 @@ impl Spawn for &ThreadPool {
         &mut self,
         future: FutureObj<'static, ()>,
     ) -> Result<(), SpawnError> {
-        self.spawn_obj_ok(future);
-        Ok(())
+        let name = config_name(&future);
+        self.try_spawn(future, Priority::Normal, name)
+    }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        self.state.status()
     }
 }
 
 impl PoolState {
-    fn send(&self, msg: Message) {
-        self.tx.lock().unwrap().send(msg).unwrap();
+    /// Schedules a task to be run, onto the current worker if it belongs to
+    /// this pool.
+    fn schedule(this: &Arc<Self>, task: Task, woken: bool) {
+        let injector = match task.0.priority() {
+            Priority::High => &this.high,
+            Priority::Low => &this.low,
+            Priority::Normal => return PoolState::schedule_normal(this, task, woken),
+        };
+        this.injected.fetch_add(1, Ordering::Relaxed);
+        injector.push(task);
+        PoolState::notify_one(this);
     }
 
-    fn work(&self,
-            idx: usize,
-            after_start: Option<Arc<dyn Fn(usize) + Send + Sync>>,
-            before_stop: Option<Arc<dyn Fn(usize) + Send + Sync>>) {
-        let _scope = enter().unwrap();
-        if let Some(after_start) = after_start {
-            after_start(idx);
+    fn schedule_normal(this: &Arc<Self>, task: Task, woken: bool) {
+        let me = &**this as *const PoolState;
+        let mut task = Some(task);
+        let mut queued = true;
+        let _ = LOCAL.try_with(|local| {
+            if let Ok(mut local) = local.try_borrow_mut() {
+                match &mut *local {
+                    Some(local) if local.pool == me => {
+                        let task = task.take().unwrap();
+                        this.stats[local.idx].scheduled.fetch_add(1, Ordering::Relaxed);
+                        if woken {
+                            match local.lifo.replace(task) {
+                                Some(prev) => local.queue.push(prev),
+                                None => queued = false,
+                            }
+                        } else {
+                            local.queue.push(task);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        });
+        if let Some(task) = task {
+            this.injected.fetch_add(1, Ordering::Relaxed);
+            this.injector.push(task);
+        }
+        // A task in the LIFO slot cannot be stolen, so there is no point in
+        // waking another worker for it.
+        if queued {
+            PoolState::notify_one(this);
+        }
+    }
+
+    fn notify_one(this: &Arc<Self>) {
+        // Pairs with the fence in `sleep`: either the sleeper sees the new
+        // task, or we see the sleeper.
+        fence(Ordering::SeqCst);
+        if this.sleepers.load(Ordering::SeqCst) > 0 {
+            let mut notified = this.sleep.lock().unwrap();
+            // The sleepers may have been notified for other tasks already, or
+            // have retired since, in which case they have not seen this one.
+            if this.sleepers.load(Ordering::SeqCst) > *notified {
+                *notified += 1;
+                this.sleep_cv.notify_one();
+                return;
+            }
+        }
+        PoolState::grow(this);
+    }
+
+    /// Starts another worker if the pool is below its maximum size.
+    fn grow(this: &Arc<Self>) {
+        if this.live.load(Ordering::SeqCst) >= this.size || this.closing.load(Ordering::SeqCst) {
+            return;
+        }
+        let mut free = this.free.lock().unwrap();
+        let (idx, queue) = match free.pop() {
+            Some(slot) => slot,
+            None => return,
+        };
+        this.live.fetch_add(1, Ordering::SeqCst);
+        // A failure leaves the slot unused, and the existing workers pick up
+        // the task.
+        if PoolState::spawn_worker(this, idx, queue, this.grow_hooks.clone()).is_err() {
+            this.live.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Gives up the slot of the worker, returning `false` if the pool would
+    /// drop below its minimum size.
+    fn retire(&self, local: &mut Local) -> bool {
+        let mut free = self.free.lock().unwrap();
+        if self.live.load(Ordering::SeqCst) <= self.min_workers {
+            return false;
+        }
+        self.live.fetch_sub(1, Ordering::SeqCst);
+        // The queue is empty, as the worker has run out of tasks.
+        free.push((local.idx, mem::replace(&mut local.queue, Worker::new_fifo())));
+        true
+    }
+
+    /// Finds the next task for the worker `idx` to run.
+    fn find_task(&self, idx: usize, local: &mut Local) -> Option<Task> {
+        local.low_ticks += 1;
+        if local.low_ticks == LOW_INTERVAL {
+            local.low_ticks = 0;
+            if let Some(task) = steal(|| self.low.steal()) {
+                return Some(task);
+            }
+        }
+        if local.high_polls < MAX_HIGH_POLLS {
+            if let Some(task) = steal(|| self.high.steal()) {
+                local.high_polls += 1;
+                return Some(task);
+            }
+        }
+        local.high_polls = 0;
+        self.find_normal_task(idx, local)
+            .or_else(|| steal(|| self.high.steal()))
+            .or_else(|| steal(|| self.low.steal()))
+    }
+
+    fn find_normal_task(&self, idx: usize, local: &mut Local) -> Option<Task> {
+        local.ticks += 1;
+        if local.ticks == INJECTOR_INTERVAL {
+            local.ticks = 0;
+            if let Some(task) = steal(|| self.injector.steal_batch_and_pop(&local.queue)) {
+                return Some(task);
+            }
+        }
+        if let Some(task) = local.lifo.take() {
+            if local.lifo_polls < MAX_LIFO_POLLS {
+                local.lifo_polls += 1;
+                return Some(task);
+            }
+            local.queue.push(task);
+        }
+        local.lifo_polls = 0;
+        if let Some(task) = local.queue.pop() {
+            return Some(task);
+        }
+        if let Some(task) = steal(|| self.injector.steal_batch_and_pop(&local.queue)) {
+            return Some(task);
+        }
+        // Start with the next worker so they are not all robbing the first.
+        let size = self.stealers.len();
+        let task = (1..size).find_map(|i| {
+            let stealer = &self.stealers[(idx + i) % size];
+            steal(|| stealer.steal_batch_and_pop(&local.queue))
+        });
+        if task.is_some() {
+            self.stats[idx].steals.fetch_add(1, Ordering::Relaxed);
+        }
+        task
+    }
+
+    fn has_tasks(&self) -> bool {
+        !self.injector.is_empty()
+            || !self.high.is_empty()
+            || !self.low.is_empty()
+            || self.stealers.iter().any(|s| !s.is_empty())
+    }
+
+    /// Blocks the current worker until a task may be available or the pool
+    /// is closing, returning `true` if the worker has been idle for long
+    /// enough to exit instead.
+    fn sleep(&self, local: &mut Local) -> bool {
+        let mut notified = self.sleep.lock().unwrap();
+        self.sleepers.fetch_add(1, Ordering::SeqCst);
+        fence(Ordering::SeqCst);
+        let mut retired = false;
+        if !self.has_tasks() && !self.closing.load(Ordering::SeqCst) {
+            let timed_out = if self.min_workers == self.size {
+                notified = self.sleep_cv.wait(notified).unwrap();
+                false
+            } else {
+                let (guard, timeout) = self.sleep_cv.wait_timeout(notified, self.idle_timeout).unwrap();
+                notified = guard;
+                timeout.timed_out()
+            };
+            // Whichever sleeper wakes up takes the notification, which has
+            // the same effect.
+            let was_notified = *notified > 0;
+            if was_notified {
+                *notified -= 1;
+            }
+            // Still holding the lock, so `notify_one` either sees this
+            // worker waiting or sees it gone.
+            retired = timed_out && !was_notified && !self.has_tasks() && self.retire(local);
         }
+        self.sleepers.fetch_sub(1, Ordering::SeqCst);
+        drop(notified);
+        retired
+    }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        if self.closed.load(Ordering::SeqCst) {
+            Err(SpawnError::shutdown())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Counts a new task as in flight, returning `false` if the pool has been
+    /// shut down.
+    fn start_task(&self) -> bool {
+        // The count is raised before checking `closed`, and `begin_shutdown`
+        // does the opposite, so one of them always sees the other.
+        self.active.fetch_add(1, Ordering::SeqCst);
+        if self.closed.load(Ordering::SeqCst) {
+            self.complete_task();
+            return false;
+        }
+        true
+    }
+
+    /// Takes a place in the queue for a new task, returning `false` if the
+    /// queue is full and stays full for as long as the policy allows.
+    fn reserve_queue_slot(&self) -> bool {
+        let max = match self.max_queued {
+            Some(max) => max,
+            None => return true,
+        };
+        if self.try_reserve_queue_slot(max) {
+            return true;
+        }
+        let timeout = match self.queue_full_policy {
+            QueueFullPolicy::Block(timeout) if !self.on_worker() => timeout,
+            _ => return false,
+        };
+        let deadline = Instant::now() + timeout;
+        let mut queue = self.queue.lock().unwrap();
+        self.queue_waiters.fetch_add(1, Ordering::SeqCst);
+        let reserved = loop {
+            // Pairs with the fence in `release_queue_slot`.
+            fence(Ordering::SeqCst);
+            if self.try_reserve_queue_slot(max) {
+                break true;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                break false;
+            }
+            queue = self.queue_cv.wait_timeout(queue, deadline - now).unwrap().0;
+        };
+        self.queue_waiters.fetch_sub(1, Ordering::SeqCst);
+        reserved
+    }
+
+    fn try_reserve_queue_slot(&self, max: usize) -> bool {
+        let mut n = self.queued.load(Ordering::SeqCst);
         loop {
-            let msg = self.rx.lock().unwrap().recv().unwrap();
-            match msg {
-                Message::Run(task) => task.run(),
-                Message::Close => break,
+            if n >= max {
+                return false;
+            }
+            match self.queued.compare_exchange(n, n + 1, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => return true,
+                Err(actual) => n = actual,
+            }
+        }
+    }
+
+    fn release_queue_slot(&self) {
+        self.queued.fetch_sub(1, Ordering::SeqCst);
+        fence(Ordering::SeqCst);
+        if self.queue_waiters.load(Ordering::SeqCst) > 0 {
+            let _queue = self.queue.lock().unwrap();
+            self.queue_cv.notify_one();
+        }
+    }
+
+    /// Returns `true` if the current thread is a worker of this pool.
+    fn on_worker(&self) -> bool {
+        let me = self as *const PoolState;
+        LOCAL.try_with(|local| match &*local.borrow() {
+            Some(local) => local.pool == me,
+            None => false,
+        }).unwrap_or(false)
+    }
+
+    /// Returns the thread running tasks which are not `Send`, starting it if
+    /// there is none yet.
+    fn local_worker(this: &Arc<Self>) -> io::Result<Arc<LocalWorker>> {
+        let mut local_worker = this.local_worker.lock().unwrap();
+        if let Some(worker) = &*local_worker {
+            return Ok(worker.clone());
+        }
+        let mut thread_builder = thread::Builder::new();
+        if let Some(ref name_prefix) = this.name_prefix {
+            thread_builder = thread_builder.name(format!("{}local", name_prefix));
+        }
+        if this.stack_size > 0 {
+            thread_builder = thread_builder.stack_size(this.stack_size);
+        }
+        this.shutdown.lock().unwrap().running += 1;
+        let state = this.clone();
+        match LocalWorker::start(thread_builder, move || state.worker_exited()) {
+            Ok((worker, thread)) => {
+                this.shutdown.lock().unwrap().workers.push(thread);
+                *local_worker = Some(worker.clone());
+                Ok(worker)
+            }
+            Err(e) => {
+                this.worker_exited();
+                Err(e)
+            }
+        }
+    }
+
+    fn complete_task(&self) {
+        if self.active.fetch_sub(1, Ordering::SeqCst) == 1 && self.closed.load(Ordering::SeqCst) {
+            self.close_workers();
+        }
+    }
+
+    fn begin_shutdown(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        if self.active.load(Ordering::SeqCst) == 0 {
+            self.close_workers();
+        }
+    }
+
+    fn close_workers(&self) {
+        if !self.closing.swap(true, Ordering::SeqCst) {
+            let sleep = self.sleep.lock().unwrap();
+            self.sleep_cv.notify_all();
+            drop(sleep);
+            if let Some(worker) = &*self.local_worker.lock().unwrap() {
+                worker.close();
+            }
+            // Every worker may have exited for being idle already.
+            let mut shutdown = self.shutdown.lock().unwrap();
+            if shutdown.running == 0 {
+                self.shutdown_complete(&mut shutdown);
+            }
+        }
+    }
+
+    fn shutdown_complete(&self, shutdown: &mut ShutdownState) {
+        for waker in shutdown.wakers.drain(..) {
+            waker.wake();
+        }
+        self.shutdown_cv.notify_all();
+    }
+
+    fn spawn_worker(this: &Arc<Self>, idx: usize, queue: Worker<Task>, hooks: Hooks) -> io::Result<()> {
+        let mut thread_builder = thread::Builder::new();
+        if let Some(ref name_prefix) = this.name_prefix {
+            thread_builder = thread_builder.name(format!("{}{}", name_prefix, idx));
+        }
+        if this.stack_size > 0 {
+            thread_builder = thread_builder.stack_size(this.stack_size);
+        }
+        // Count the worker before it starts so it cannot exit first.
+        this.shutdown.lock().unwrap().running += 1;
+        let state = this.clone();
+        match thread_builder.spawn(move || state.work(idx, queue, hooks)) {
+            Ok(worker) => {
+                this.shutdown.lock().unwrap().workers.push(worker);
+                Ok(())
+            }
+            Err(e) => {
+                this.worker_exited();
+                Err(e)
             }
         }
-        if let Some(before_stop) = before_stop {
+    }
+
+    fn work(self: Arc<Self>, idx: usize, queue: Worker<Task>, mut hooks: Hooks) {
+        let scope = enter().unwrap();
+        LOCAL.with(|local| {
+            *local.borrow_mut() = Some(Local {
+                pool: &*self,
+                idx,
+                queue,
+                lifo: None,
+                lifo_polls: 0,
+                high_polls: 0,
+                ticks: 0,
+                low_ticks: 0,
+            });
+        });
+        self.thread_config.apply(idx);
+        if let Some(after_start) = &hooks.after_start {
+            after_start(idx);
+        }
+        // The hook is only needed again to start a replacement worker.
+        match self.panic_policy {
+            PanicPolicy::RestartWorker => {}
+            _ => hooks.after_start = None,
+        }
+        let stats = &self.stats[idx];
+        let mut idle = 0;
+        // When the worker last started running tasks after being idle.
+        let mut busy_since: Option<Instant> = None;
+        let mut retired = false;
+        let panicked = loop {
+            let task = LOCAL.with(|local| {
+                self.find_task(idx, local.borrow_mut().as_mut().unwrap())
+            });
+            if task.is_none() {
+                if let Some(since) = busy_since.take() {
+                    let busy = since.elapsed();
+                    stats.busy_nanos.fetch_add(busy.as_nanos() as u64, Ordering::Relaxed);
+                }
+            }
+            match task {
+                Some(task) => {
+                    idle = 0;
+                    busy_since.get_or_insert_with(Instant::now);
+                    stats.taken.fetch_add(1, Ordering::Relaxed);
+                    match panic::catch_unwind(AssertUnwindSafe(move || task.run())) {
+                        Ok(polls) => {
+                            stats.polls.fetch_add(polls, Ordering::Relaxed);
+                        }
+                        Err(payload) => {
+                            // The task has been dropped while unwinding.
+                            self.complete_task();
+                            match &self.panic_policy {
+                                PanicPolicy::CatchAndLog(f) => f(payload),
+                                _ => break Some(payload),
+                            }
+                        }
+                    }
+                }
+                // The pool only closes once no more tasks can be scheduled.
+                None if self.closing.load(Ordering::SeqCst) => break None,
+                // Going to sleep is expensive for both the worker and whoever
+                // wakes it, so give other threads a chance to produce tasks
+                // first.
+                None if idle < MAX_IDLE_YIELDS => {
+                    idle += 1;
+                    thread::yield_now();
+                }
+                None => {
+                    idle = 0;
+                    retired = LOCAL.with(|local| self.sleep(local.borrow_mut().as_mut().unwrap()));
+                    if retired {
+                        break None;
+                    }
+                }
+            }
+        };
+
+        let mut local = LOCAL.with(|local| local.borrow_mut().take()).unwrap();
+        // The queue of the worker can still be stolen from, but the task in
+        // its LIFO slot has to be moved.
+        if let Some(task) = local.lifo.take() {
+            local.queue.push(task);
+            PoolState::notify_one(&self);
+        }
+        if let Some(before_stop) = &hooks.before_stop {
             before_stop(idx);
         }
+        drop(scope);
+
+        match panicked {
+            Some(payload) => {
+                // Start the replacement before exiting so the pool never
+                // looks like it has shut down.
+                let restarted = match self.panic_policy {
+                    PanicPolicy::RestartWorker => {
+                        PoolState::spawn_worker(&self, idx, local.queue, hooks).is_ok()
+                    }
+                    _ => false,
+                };
+                self.worker_exited();
+                if !restarted {
+                    panic::resume_unwind(payload);
+                }
+            }
+            None => {
+                if retired {
+                    self.detach_worker();
+                }
+                self.worker_exited();
+            }
+        }
+    }
+
+    fn worker_exited(&self) {
+        let mut shutdown = self.shutdown.lock().unwrap();
+        shutdown.running -= 1;
+        if shutdown.running == 0 && self.closing.load(Ordering::SeqCst) {
+            self.shutdown_complete(&mut shutdown);
+        }
+    }
+
+    /// Forgets the handle of the current worker thread, which exits while the
+    /// pool keeps running.
+    fn detach_worker(&self) {
+        let id = thread::current().id();
+        self.shutdown.lock().unwrap().workers.retain(|w| w.thread().id() != id);
+    }
+}
+
+/// Counts a task of the dedicated thread as completed once dropped.
+struct TaskGuard(Arc<PoolState>);
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.0.complete_task();
+    }
+}
+
+/// A task run on the thread for tasks which are not `Send`, which is
+/// counted as in flight until it is dropped.
+struct LocalTask<Fut: Future> {
+    future: Joinable<Fut>,
+    _guard: TaskGuard,
+}
+
+impl<Fut: Future> Future for LocalTask<Fut> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // Safety: `future` is never moved out of `self`.
+        unsafe { self.map_unchecked_mut(|this| &mut this.future) }.poll(cx)
+    }
+}
+
+/// The hooks run by each worker thread.
+#[derive(Clone)]
+struct Hooks {
+    after_start: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    before_stop: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+}
+
+/// Metrics of a [`ThreadPool`](ThreadPool).
+///
+/// This value is created by the [`metrics`](ThreadPool::metrics) method.
+#[derive(Clone)]
+pub struct ThreadPoolMetrics {
+    state: Arc<PoolState>,
+}
+
+impl ThreadPoolMetrics {
+    /// Returns the maximum number of worker threads of the pool, which the
+    /// indices of the workers range up to.
+    pub fn num_workers(&self) -> usize {
+        self.state.size
+    }
+
+    /// Returns the number of worker threads currently running, which is
+    /// lower than [`num_workers`](ThreadPoolMetrics::num_workers) while a
+    /// pool with [`min_threads`](ThreadPoolBuilder::min_threads) is not
+    /// fully loaded.
+    pub fn running_workers(&self) -> usize {
+        self.state.live.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of tasks which are scheduled to run but have not
+    /// been picked up by a worker yet.
+    pub fn queue_depth(&self) -> usize {
+        let stats = &self.state.stats;
+        let scheduled = stats.iter().map(|s| s.scheduled.load(Ordering::Relaxed))
+            .fold(self.state.injected.load(Ordering::Relaxed), u64::wrapping_add);
+        let taken = stats.iter().map(|s| s.taken.load(Ordering::Relaxed))
+            .fold(0, u64::wrapping_add);
+        // The counters are read one by one, so they may be slightly out of
+        // step with each other.
+        scheduled.saturating_sub(taken) as usize
+    }
+
+    /// Returns the number of tasks which have been spawned and have not
+    /// completed yet.
+    pub fn alive_tasks(&self) -> usize {
+        self.state.active.load(Ordering::SeqCst)
+    }
+
+    /// Returns the total number of times tasks have been polled.
+    pub fn total_polls(&self) -> u64 {
+        self.state.stats.iter().map(|s| s.polls.load(Ordering::Relaxed)).sum()
+    }
+
+    /// Returns the total time the worker `worker` has spent running tasks.
+    ///
+    /// This is updated whenever the worker runs out of tasks, so it does not
+    /// include a stretch of work which is still going on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `worker` is not less than [`num_workers`](ThreadPoolMetrics::num_workers).
+    pub fn worker_busy_time(&self, worker: usize) -> Duration {
+        Duration::from_nanos(self.state.stats[worker].busy_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Returns the number of times the worker `worker` has stolen tasks from
+    /// another worker.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `worker` is not less than [`num_workers`](ThreadPoolMetrics::num_workers).
+    pub fn worker_steal_count(&self, worker: usize) -> u64 {
+        self.state.stats[worker].steals.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of times any worker has stolen tasks from another
+    /// worker.
+    pub fn steal_count(&self) -> u64 {
+        self.state.stats.iter().map(|s| s.steals.load(Ordering::Relaxed)).sum()
+    }
+}
+
+impl fmt::Debug for ThreadPoolMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThreadPoolMetrics")
+            .field("queue_depth", &self.queue_depth())
+            .field("alive_tasks", &self.alive_tasks())
+            .field("total_polls", &self.total_polls())
+            .field("steal_count", &self.steal_count())
+            .finish()
+    }
+}
+
+// The name of a task from the config attached to its future, if any.
+fn config_name(future: &FutureObj<'static, ()>) -> Option<String> {
+    future.config().and_then(SpawnConfig::name).map(String::from)
+}
+
+fn steal(mut f: impl FnMut() -> Steal<Task>) -> Option<Task> {
+    loop {
+        match f() {
+            Steal::Success(task) => return Some(task),
+            Steal::Empty => return None,
+            Steal::Retry => {}
+        }
+    }
+}
+
+fn join_workers(workers: Vec<thread::JoinHandle<()>>) {
+    for worker in workers {
+        // Tasks are not run under `catch_unwind`, so a worker may have
+        // panicked, which has been reported already.
+        let _ = worker.join();
+    }
+}
+
+/// Future for the [`shutdown`](ThreadPool::shutdown) method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Shutdown {
+    state: Arc<PoolState>,
+}
+
+impl Future for Shutdown {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut shutdown = self.state.shutdown.lock().unwrap();
+        if shutdown.running > 0 || !self.state.closing.load(Ordering::SeqCst) {
+            shutdown.wakers.push(cx.waker().clone());
+            return Poll::Pending;
+        }
+        let workers = shutdown.workers.drain(..).collect::<Vec<_>>();
+        drop(shutdown);
+        join_workers(workers);
+        Poll::Ready(())
+    }
+}
+
+impl fmt::Debug for Shutdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shutdown")
+            .field("size", &self.state.size)
+            .finish()
     }
 }
 
@@ -190,9 +1228,7 @@ impl Clone for ThreadPool {
 impl Drop for ThreadPool {
     fn drop(&mut self) {
         if self.state.cnt.fetch_sub(1, Ordering::Relaxed) == 1 {
-            for _ in 0..self.state.size {
-                self.state.send(Message::Close);
-            }
+            self.state.close_workers();
         }
     }
 }
@@ -204,10 +1240,21 @@ impl ThreadPoolBuilder {
     pub fn new() -> ThreadPoolBuilder {
         ThreadPoolBuilder {
             pool_size: num_cpus::get(),
+            min_threads: None,
+            max_threads: None,
+            idle_timeout: Duration::from_secs(10),
             stack_size: 0,
             name_prefix: None,
+            thread_config: ThreadConfig::default(),
             after_start: None,
             before_stop: None,
+            max_blocking_threads: 512,
+            blocking_keep_alive: Duration::from_secs(10),
+            panic_policy: PanicPolicy::Propagate,
+            max_queued_tasks: None,
+            queue_full_policy: QueueFullPolicy::Reject,
+            task_hooks: TaskHooks::default(),
+            coop_budget: None,
         }
     }
 
@@ -220,6 +1267,41 @@ impl ThreadPoolBuilder {
         self
     }
 
+    /// Set the minimum number of worker threads, which keep running while
+    /// idle.
+    ///
+    /// If this is lower than [`max_threads`](ThreadPoolBuilder::max_threads),
+    /// only this many workers are started with the pool. More are started
+    /// as tasks are scheduled while every worker is busy, and they exit again
+    /// after being idle for [`idle_timeout`](ThreadPoolBuilder::idle_timeout).
+    ///
+    /// By default, this is the [`pool_size`](ThreadPoolBuilder::pool_size),
+    /// or `max_threads` if that is set lower.
+    pub fn min_threads(&mut self, min: usize) -> &mut Self {
+        self.min_threads = Some(min);
+        self
+    }
+
+    /// Set the maximum number of worker threads.
+    ///
+    /// By default, this is the [`pool_size`](ThreadPoolBuilder::pool_size),
+    /// or [`min_threads`](ThreadPoolBuilder::min_threads) if that is set
+    /// higher.
+    pub fn max_threads(&mut self, max: usize) -> &mut Self {
+        self.max_threads = Some(max);
+        self
+    }
+
+    /// Set how long a worker thread beyond
+    /// [`min_threads`](ThreadPoolBuilder::min_threads) waits for a task
+    /// before exiting.
+    ///
+    /// By default, this is 10 seconds.
+    pub fn idle_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
     /// Set stack size of threads in the pool.
     ///
     /// By default, worker threads use Rust's standard stack size.
@@ -239,12 +1321,49 @@ impl ThreadPoolBuilder {
         self
     }
 
+    /// Pin each worker thread to one of the given CPU cores.
+    ///
+    /// The worker with index `i` is pinned to the core at `cores[i %
+    /// cores.len()]`, so a pool with as many workers as cores gets one worker
+    /// per core. Pinning is only supported on Linux, and is best effort: a
+    /// worker which cannot be pinned runs unpinned. For other placements, the
+    /// threads can be configured in [`after_start`](ThreadPoolBuilder::after_start),
+    /// which receives the index of the worker.
+    ///
+    /// By default, the workers are not pinned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the cores is beyond what the platform can pin a
+    /// thread to, which on Linux is core 1023.
+    pub fn core_affinity<I>(&mut self, cores: I) -> &mut Self
+        where I: IntoIterator<Item = usize>
+    {
+        let cores: Vec<usize> = cores.into_iter().collect();
+        assert!(cores.iter().all(|&core| is_valid_core(core)), "core index out of range");
+        self.thread_config.cores = cores;
+        self
+    }
+
+    /// Set the scheduling priority of the worker threads.
+    ///
+    /// This is best effort: a worker whose priority cannot be changed, such
+    /// as for lack of privileges, runs at its normal priority. See
+    /// [`ThreadPriority`](ThreadPriority) for what the priorities map to.
+    ///
+    /// By default, the workers run at normal priority.
+    pub fn thread_priority(&mut self, priority: ThreadPriority) -> &mut Self {
+        self.thread_config.priority = priority;
+        self
+    }
+
     /// Execute the closure `f` immediately after each worker thread is started,
     /// but before running any tasks on it.
     ///
     /// This hook is intended for bookkeeping and monitoring.
     /// The closure `f` will be dropped after the `builder` is dropped
-    /// and all worker threads in the pool have executed it.
+    /// and all worker threads in the pool have executed it, unless the
+    /// pool restarts workers under [`PanicPolicy::RestartWorker`].
     ///
     /// The closure provided will receive an index corresponding to the worker
     /// thread it's running on.
@@ -270,35 +1389,177 @@ impl ThreadPoolBuilder {
         self
     }
 
+    /// Set what the pool does when a task panics.
+    ///
+    /// By default, the panic takes down the worker thread which polled the
+    /// task. See [`PanicPolicy`](PanicPolicy) for the alternatives.
+    pub fn panic_policy(&mut self, policy: PanicPolicy) -> &mut Self {
+        self.panic_policy = policy;
+        self
+    }
+
+    /// Set the maximum number of spawned tasks waiting to be run for the
+    /// first time.
+    ///
+    /// Tasks spawned while this many are waiting are handled according to
+    /// the [`queue_full_policy`](ThreadPoolBuilder::queue_full_policy). Tasks
+    /// which have been run already are not counted, and are always scheduled
+    /// when they are woken. By default, the number of waiting tasks is
+    /// unbounded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    pub fn max_queued_tasks(&mut self, max: usize) -> &mut Self {
+        assert!(max > 0, "max_queued_tasks must be non-zero");
+        self.max_queued_tasks = Some(max);
+        self
+    }
+
+    /// Set what the pool does with a new task while
+    /// [`max_queued_tasks`](ThreadPoolBuilder::max_queued_tasks) tasks are
+    /// waiting to be run.
+    ///
+    /// By default, the task fails to spawn. See
+    /// [`QueueFullPolicy`](QueueFullPolicy) for the alternatives.
+    pub fn queue_full_policy(&mut self, policy: QueueFullPolicy) -> &mut Self {
+        self.queue_full_policy = policy;
+        self
+    }
+
+    /// Set the hooks called as the pool runs each of its tasks.
+    ///
+    /// Tasks spawned with [`spawn_named`](ThreadPool::spawn_named) are passed
+    /// to the hooks along with their name. Tasks spawned with
+    /// [`spawn_local_on_dedicated`](ThreadPool::spawn_local_on_dedicated) are
+    /// not passed to the hooks. By default, there are no hooks.
+    pub fn task_hooks(&mut self, hooks: TaskHooks) -> &mut Self {
+        self.task_hooks = hooks;
+        self
+    }
+
+    /// Set the [cooperative budget](futures_util::task::budget) of each poll
+    /// of a task.
+    ///
+    /// Futures which spend the budget with
+    /// [`budget::consume`](futures_util::task::budget::consume) yield once
+    /// they have done so `budget` times in one poll, and their task is put
+    /// at the back of the queue. By default, there is no budget.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `budget` is zero.
+    pub fn coop_budget(&mut self, budget: usize) -> &mut Self {
+        assert!(budget > 0, "coop_budget must be non-zero");
+        self.coop_budget = Some(budget);
+        self
+    }
+
+    /// Set the maximum number of threads running closures passed to
+    /// [`spawn_blocking`](ThreadPool::spawn_blocking).
+    ///
+    /// Closures submitted while this many are running wait for one of them
+    /// to finish. By default, this is 512.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is zero.
+    pub fn max_blocking_threads(&mut self, max: usize) -> &mut Self {
+        assert!(max > 0, "max_blocking_threads must be non-zero");
+        self.max_blocking_threads = max;
+        self
+    }
+
+    /// Set how long a thread running closures passed to
+    /// [`spawn_blocking`](ThreadPool::spawn_blocking) waits for another one
+    /// before exiting.
+    ///
+    /// By default, this is 10 seconds.
+    pub fn blocking_keep_alive(&mut self, keep_alive: Duration) -> &mut Self {
+        self.blocking_keep_alive = keep_alive;
+        self
+    }
+
     /// Create a [`ThreadPool`](ThreadPool) with the given configuration.
     ///
     /// # Panics
     ///
-    /// Panics if `pool_size == 0`.
+    /// Panics if `pool_size == 0`, if `max_threads == 0`, or if
+    /// `min_threads > max_threads`.
     pub fn create(&mut self) -> Result<ThreadPool, io::Error> {
-        let (tx, rx) = mpsc::channel();
+        assert!(self.pool_size > 0);
+        let (min, max) = match (self.min_threads, self.max_threads) {
+            (None, None) => (self.pool_size, self.pool_size),
+            (Some(min), None) => (min, cmp::max(min, self.pool_size)),
+            (None, Some(max)) => (cmp::min(max, self.pool_size), max),
+            (Some(min), Some(max)) => (min, max),
+        };
+        assert!(max > 0, "max_threads must be non-zero");
+        assert!(min <= max, "min_threads must not be greater than max_threads");
+        let mut queues = (0..max).map(|_| Worker::new_fifo()).collect::<Vec<_>>();
+        let hooks = Hooks {
+            after_start: self.after_start.clone(),
+            before_stop: self.before_stop.clone(),
+        };
         let pool = ThreadPool {
             state: Arc::new(PoolState {
-                tx: Mutex::new(tx),
-                rx: Mutex::new(rx),
+                injector: Injector::new(),
+                high: Injector::new(),
+                low: Injector::new(),
+                stealers: queues.iter().map(Worker::stealer).collect(),
+                injected: AtomicU64::new(0),
+                stats: (0..max).map(|_| WorkerStats::default()).collect(),
+                sleepers: AtomicUsize::new(0),
+                sleep: Mutex::new(0),
+                sleep_cv: Condvar::new(),
+                max_queued: self.max_queued_tasks,
+                queued: AtomicUsize::new(0),
+                queue_full_policy: self.queue_full_policy.clone(),
+                queue_waiters: AtomicUsize::new(0),
+                queue: Mutex::new(()),
+                queue_cv: Condvar::new(),
+                local_worker: Mutex::new(None),
+                blocking: Arc::new(BlockingPool::new(
+                    self.max_blocking_threads,
+                    self.blocking_keep_alive,
+                    self.name_prefix.clone(),
+                    self.stack_size,
+                )),
+                panic_policy: self.panic_policy.clone(),
+                task_hooks: self.task_hooks.clone(),
+                coop_budget: self.coop_budget,
+                task_ids: AtomicU64::new(0),
+                name_prefix: self.name_prefix.clone(),
+                stack_size: self.stack_size,
+                thread_config: self.thread_config.clone(),
                 cnt: AtomicUsize::new(1),
-                size: self.pool_size,
+                size: max,
+                min_workers: min,
+                idle_timeout: self.idle_timeout,
+                live: AtomicUsize::new(min),
+                free: Mutex::new(Vec::new()),
+                grow_hooks: if min < max {
+                    hooks.clone()
+                } else {
+                    Hooks { after_start: None, before_stop: None }
+                },
+                active: AtomicUsize::new(0),
+                closed: AtomicBool::new(false),
+                closing: AtomicBool::new(false),
+                shutdown: Mutex::new(ShutdownState {
+                    running: 0,
+                    workers: Vec::new(),
+                    wakers: Vec::new(),
+                }),
+                shutdown_cv: Condvar::new(),
             }),
         };
-        assert!(self.pool_size > 0);
 
-        for counter in 0..self.pool_size {
-            let state = pool.state.clone();
-            let after_start = self.after_start.clone();
-            let before_stop = self.before_stop.clone();
-            let mut thread_builder = thread::Builder::new();
-            if let Some(ref name_prefix) = self.name_prefix {
-                thread_builder = thread_builder.name(format!("{}{}", name_prefix, counter));
-            }
-            if self.stack_size > 0 {
-                thread_builder = thread_builder.stack_size(self.stack_size);
-            }
-            thread_builder.spawn(move || state.work(counter, after_start, before_stop))?;
+        // The slots beyond the minimum are taken from the end.
+        let free = queues.split_off(min).into_iter().enumerate().rev();
+        pool.state.free.lock().unwrap().extend(free.map(|(i, queue)| (min + i, queue)));
+        for (counter, queue) in queues.into_iter().enumerate() {
+            PoolState::spawn_worker(&pool.state, counter, queue, hooks.clone())?;
         }
         Ok(pool)
     }
@@ -311,51 +1572,131 @@ impl Default for ThreadPoolBuilder {
 }
 
 /// A task responsible for polling a future to completion.
-struct Task {
-    future: FutureObj<'static, ()>,
-    exec: ThreadPool,
-    wake_handle: Arc<WakeHandle>,
+///
+/// This is a handle to a single allocation, which holds the future of the
+/// task along with everything needed to schedule it, and also serves as the
+/// waker of the task.
+struct Task(Arc<dyn RunTask>);
+
+trait RunTask: Send + Sync {
+    /// Actually run the task (invoking `poll` on the future) on the current
+    /// thread, returning how many times it was polled.
+    fn run(self: Arc<Self>) -> u64;
+
+    fn priority(&self) -> Priority;
 }
 
-struct WakeHandle {
-    mutex: UnparkMutex<Task>,
+struct TaskCell<F> {
+    // Guards `slot`, which only the thread running the task accesses.
+    mutex: UnparkMutex<()>,
+    slot: UnsafeCell<Slot<F>>,
     exec: ThreadPool,
+    priority: Priority,
+    id: u64,
+    name: Option<String>,
+}
+
+struct Slot<F> {
+    // Dropped as soon as the task completes, even if it is still referenced
+    // by wakers.
+    future: Option<F>,
+    polled: bool,
 }
 
+// Safety: `slot` is only accessed through the mutex, which hands it to a
+// single thread at a time.
+unsafe impl<F: Send> Sync for TaskCell<F> {}
+
 impl Task {
-    /// Actually run the task (invoking `poll` on the future) on the current
-    /// thread.
-    fn run(self) {
-        let Task { mut future, wake_handle, mut exec } = self;
-        let waker = waker_ref(&wake_handle);
+    fn run(self) -> u64 {
+        self.0.run()
+    }
+}
+
+impl<F> TaskCell<F> {
+    fn info(&self) -> TaskInfo<'_> {
+        TaskInfo::new(self.id, self.name.as_ref().map(String::as_str))
+    }
+}
+
+impl<F> RunTask for TaskCell<F>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    fn run(self: Arc<Self>) -> u64 {
+        let state = &self.exec.state;
+        let info = self.info();
+        let waker = waker_ref(&self);
         let mut cx = Context::from_waker(&waker);
+        let guard = PanicGuard(&*self);
 
         // Safety: The ownership of this `Task` object is evidence that
-        // we are in the `POLLING`/`REPOLL` state for the mutex.
+        // we are in the `POLLING`/`REPOLL` state for the mutex, or that the
+        // task has just been spawned, which gives us exclusive access to the
+        // slot.
         unsafe {
-            wake_handle.mutex.start_poll();
+            self.mutex.start_poll();
+            let slot = &mut *self.slot.get();
+            // The task took up a place in the queue until now.
+            if !slot.polled && state.max_queued.is_some() {
+                state.release_queue_slot();
+            }
+            let mut polls = 0;
 
             loop {
-                let res = future.poll_unpin(&mut cx);
-                match res {
-                    Poll::Pending => {}
-                    Poll::Ready(()) => return wake_handle.mutex.complete(),
+                let first = !mem::replace(&mut slot.polled, true);
+                // The future is never moved out of the allocation.
+                let future = Pin::new_unchecked(slot.future.as_mut().unwrap());
+                let mut exhausted = false;
+                let res = state.task_hooks.poll(&info, first, || match state.coop_budget {
+                    Some(n) => budget::with_budget(n, || {
+                        let res = future.poll(&mut cx);
+                        exhausted = budget::remaining() == Some(0);
+                        res
+                    }),
+                    None => future.poll(&mut cx),
+                });
+                polls += 1;
+                if res.is_ready() {
+                    mem::forget(guard);
+                    self.mutex.complete();
+                    slot.future = None;
+                    state.complete_task();
+                    return polls;
                 }
-                let task = Task {
-                    future,
-                    wake_handle: wake_handle.clone(),
-                    exec,
-                };
-                match wake_handle.mutex.wait(task) {
-                    Ok(()) => return, // we've waited
-                    Err(task) => { // someone's notified us
-                        future = task.future;
-                        exec = task.exec;
-                    }
+                // Unless someone's notified us, we've waited.
+                if self.mutex.wait(()).is_ok() {
+                    mem::forget(guard);
+                    return polls;
+                }
+                // A task which used up its budget and woke itself goes to
+                // the back of the queue rather than being polled again.
+                if exhausted {
+                    mem::forget(guard);
+                    PoolState::schedule(state, Task(self.clone()), true);
+                    return polls;
                 }
             }
         }
     }
+
+    fn priority(&self) -> Priority {
+        self.priority
+    }
+}
+
+/// Drops the future of a task whose poll panicked, like the future of a
+/// completed task.
+struct PanicGuard<'a, F>(&'a TaskCell<F>);
+
+impl<F> Drop for PanicGuard<'_, F> {
+    fn drop(&mut self) {
+        // Safety: The guard is only dropped by the thread polling the task.
+        unsafe {
+            self.0.mutex.complete();
+            (*self.0.slot.get()).future = None;
+        }
+    }
 }
 
 impl fmt::Debug for Task {
@@ -366,11 +1707,13 @@ impl fmt::Debug for Task {
     }
 }
 
-impl ArcWake for WakeHandle {
+impl<F> ArcWake for TaskCell<F>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
     fn wake_by_ref(arc_self: &Arc<Self>) {
-        match arc_self.mutex.notify() {
-            Ok(task) => arc_self.exec.state.send(Message::Run(task)),
-            Err(()) => {}
+        if arc_self.mutex.notify().is_ok() {
+            PoolState::schedule(&arc_self.exec.state, Task(arc_self.clone()), true);
         }
     }
 }
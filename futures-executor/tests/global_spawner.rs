@@ -0,0 +1,27 @@
+use futures::channel::oneshot;
+use futures::executor::{block_on, global_spawner, set_global_spawner, ThreadPool};
+use futures::task::{Spawn, SpawnExt};
+
+// The global spawner can only be set once per process, so this is the only
+// test in this file.
+#[test]
+fn global_spawner_is_set_once() {
+    let mut spawner = global_spawner();
+    assert!(spawner.status().is_err());
+    assert!(spawner.spawn(async {}).is_err());
+
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    set_global_spawner(pool.clone()).unwrap();
+    assert!(set_global_spawner(pool.clone()).is_err());
+
+    // Handles obtained before the spawner was set use it as well.
+    let (tx, rx) = oneshot::channel();
+    spawner.spawn(async move { tx.send(1).unwrap() }).unwrap();
+    assert_eq!(block_on(rx), Ok(1));
+
+    let handle = global_spawner().spawn_with_handle(async { 2 }).unwrap();
+    assert_eq!(block_on(handle), 2);
+
+    block_on(pool.shutdown());
+    assert!(global_spawner().status().is_err());
+}
@@ -1,10 +1,12 @@
 use futures::channel::oneshot;
-use futures::executor::LocalPool;
+use futures::executor::{block_on, block_on_timeout, try_block_on, LocalPool};
 use futures::future::{Future, lazy, poll_fn};
 use futures::task::{Context, Poll, Spawn, LocalSpawn, Waker};
 use std::cell::{Cell, RefCell};
 use std::pin::Pin;
 use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 struct Pending(Rc<()>);
 
@@ -311,3 +313,315 @@ fn tasks_are_scheduled_fairly() {
 
     pool.run();
 }
+
+#[test]
+fn metrics() {
+    let mut pool = LocalPool::new();
+    let mut spawn = pool.spawner();
+    let metrics = pool.metrics();
+
+    let (tx, rx) = oneshot::channel::<()>();
+    spawn.spawn_local_obj(Box::pin(async { let _ = rx.await; }).into()).unwrap();
+    spawn.spawn_local_obj(Box::pin(async {}).into()).unwrap();
+    assert_eq!(metrics.queue_depth(), 2);
+    assert_eq!(metrics.alive_tasks(), 2);
+
+    pool.run_until_stalled();
+    assert_eq!(metrics.queue_depth(), 0);
+    assert_eq!(metrics.alive_tasks(), 1);
+    assert_eq!(metrics.total_polls(), 2);
+
+    drop(tx);
+    pool.run();
+    assert_eq!(metrics.alive_tasks(), 0);
+    assert_eq!(metrics.total_polls(), 3);
+}
+
+#[test]
+fn panic_policy_catch_and_log() {
+    use futures::executor::PanicPolicy;
+    use std::sync::{Arc, Mutex};
+
+    let caught = Arc::new(Mutex::new(Vec::new()));
+    let caught2 = caught.clone();
+    let mut pool = LocalPool::new();
+    pool.set_panic_policy(PanicPolicy::catch_and_log(move |payload| {
+        caught2.lock().unwrap().push(*payload.downcast::<&str>().unwrap());
+    }));
+    let mut spawn = pool.spawner();
+    let ran = Rc::new(Cell::new(false));
+    let ran2 = ran.clone();
+    spawn.spawn_local_obj(Box::pin(async { panic!("boom") }).into()).unwrap();
+    spawn.spawn_local_obj(Box::pin(async move { ran2.set(true) }).into()).unwrap();
+
+    pool.run();
+    assert!(ran.get());
+    assert_eq!(*caught.lock().unwrap(), vec!["boom"]);
+}
+
+#[test]
+#[should_panic(expected = "boom")]
+fn panic_policy_propagate() {
+    let mut pool = LocalPool::new();
+    let mut spawn = pool.spawner();
+    spawn.spawn_local_obj(Box::pin(async { panic!("boom") }).into()).unwrap();
+    pool.run();
+}
+
+#[test]
+fn block_on_timeout_completes_when_woken() {
+    let (tx, rx) = oneshot::channel();
+    let sender = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        tx.send(1).unwrap();
+    });
+    assert_eq!(block_on_timeout(rx, Duration::from_secs(10)), Some(Ok(1)));
+    sender.join().unwrap();
+}
+
+#[test]
+fn block_on_timeout_gives_up() {
+    let (_tx, rx) = oneshot::channel::<()>();
+    let start = Instant::now();
+    assert_eq!(block_on_timeout(rx, Duration::from_millis(20)), None);
+    assert!(start.elapsed() >= Duration::from_millis(20));
+}
+
+#[test]
+#[should_panic(expected = "cannot block on a future or run a `LocalPool` from within an executor")]
+fn nested_block_on_panics() {
+    block_on(async { block_on(async {}) });
+}
+
+#[test]
+fn try_block_on_detects_reentrancy() {
+    let mut pool = LocalPool::new();
+    let mut spawn = pool.spawner();
+    let nested = Rc::new(Cell::new(None));
+    let nested2 = nested.clone();
+    spawn.spawn_local_obj(Box::pin(async move {
+        nested2.set(Some(try_block_on(async { 1 }).is_err()));
+    }).into()).unwrap();
+    pool.run();
+    assert_eq!(nested.get(), Some(true));
+
+    // The thread is usable again once the pool is done.
+    assert_eq!(try_block_on(async { 1 }).unwrap(), 1);
+}
+
+// A task which wakes itself on every poll and never completes.
+fn spinner(polls: Rc<Cell<usize>>) -> impl Future<Output = ()> {
+    poll_fn(move |cx| {
+        polls.set(polls.get() + 1);
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+}
+
+#[test]
+fn max_polls_per_task_lets_run_until_stalled_return() {
+    let mut pool = LocalPool::new();
+    pool.set_max_polls_per_task(4);
+    let mut spawn = pool.spawner();
+    let spins = Rc::new(Cell::new(0));
+    let ran = Rc::new(Cell::new(false));
+    let ran2 = ran.clone();
+    spawn.spawn_local_obj(Box::pin(spinner(spins.clone())).into()).unwrap();
+    spawn.spawn_local_obj(Box::pin(async move { ran2.set(true) }).into()).unwrap();
+
+    pool.run_until_stalled();
+    assert_eq!(spins.get(), 4);
+    assert!(ran.get());
+
+    pool.run_until_stalled();
+    assert_eq!(spins.get(), 8);
+}
+
+#[test]
+fn poll_budget_limits_each_tick() {
+    let mut pool = LocalPool::new();
+    pool.set_poll_budget(5);
+    let mut spawn = pool.spawner();
+    let a = Rc::new(Cell::new(0));
+    let b = Rc::new(Cell::new(0));
+    spawn.spawn_local_obj(Box::pin(spinner(a.clone())).into()).unwrap();
+    spawn.spawn_local_obj(Box::pin(spinner(b.clone())).into()).unwrap();
+
+    pool.run_until_stalled();
+    assert_eq!(a.get() + b.get(), 5);
+    // Ready tasks take turns.
+    assert!((a.get() as isize - b.get() as isize).abs() <= 1);
+
+    // The main future of `run_until` is polled in between ticks.
+    let ticks = Rc::new(Cell::new(0));
+    let ticks2 = ticks.clone();
+    pool.run_until(poll_fn(move |cx| {
+        ticks2.set(ticks2.get() + 1);
+        if ticks2.get() == 3 {
+            return Poll::Ready(());
+        }
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }));
+    assert_eq!(a.get() + b.get(), 15);
+}
+
+#[test]
+fn run_for() {
+    let mut pool = LocalPool::new();
+    pool.set_poll_budget(3);
+    pool.set_max_polls_per_task(2);
+    let mut spawn = pool.spawner();
+    let spins = Rc::new(Cell::new(0));
+    spawn.spawn_local_obj(Box::pin(spinner(spins.clone())).into()).unwrap();
+
+    // The budgets of single ticks do not cap `run_for`.
+    assert_eq!(pool.run_for(10), 10);
+    assert_eq!(spins.get(), 10);
+
+    let mut pool = LocalPool::new();
+    let mut spawn = pool.spawner();
+    let (tx, rx) = oneshot::channel::<()>();
+    spawn.spawn_local_obj(Box::pin(async { let _ = rx.await; }).into()).unwrap();
+    assert_eq!(pool.run_for(10), 1);
+    assert_eq!(pool.run_for(10), 0);
+    drop(tx);
+    assert_eq!(pool.run_for(10), 1);
+    assert_eq!(pool.metrics().alive_tasks(), 0);
+}
+
+#[test]
+fn remote_spawner() {
+    use futures::executor::RemoteSpawner;
+    use futures::task::SpawnExt;
+    use std::sync::mpsc;
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    let mut pool = LocalPool::new();
+    let mut spawner = pool.remote_spawner();
+    assert_send(&spawner);
+    let (tx, rx) = mpsc::channel();
+    let (done_tx, done_rx) = oneshot::channel();
+
+    let sender = thread::spawn(move || {
+        for i in 0..10 {
+            let tx = tx.clone();
+            spawner.spawn(async move { tx.send(i).unwrap() }).unwrap();
+        }
+        spawner.spawn(async move { done_tx.send(()).unwrap() }).unwrap();
+        spawner
+    });
+    pool.run_until(done_rx).unwrap();
+    let mut spawner: RemoteSpawner = sender.join().unwrap();
+
+    let mut got = rx.try_iter().collect::<Vec<_>>();
+    got.sort();
+    assert_eq!(got, (0..10).collect::<Vec<_>>());
+
+    assert!(spawner.status().is_ok());
+    drop(pool);
+    assert!(spawner.status().is_err());
+    assert!(spawner.spawn(async {}).is_err());
+}
+
+#[test]
+fn task_hooks() {
+    use futures::executor::TaskHooks;
+    use std::sync::{Arc, Mutex};
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let (e1, e2, e3, e4) = (events.clone(), events.clone(), events.clone(), events.clone());
+    let mut pool = LocalPool::new();
+    pool.set_task_hooks(TaskHooks::new()
+        .on_spawn(move |task| e1.lock().unwrap().push(("spawn", task.id(), task.name().map(String::from))))
+        .on_first_poll(move |task| e2.lock().unwrap().push(("first", task.id(), None)))
+        .on_poll(move |task, _| e3.lock().unwrap().push(("poll", task.id(), None)))
+        .on_complete(move |task| e4.lock().unwrap().push(("done", task.id(), None))));
+    let spawn = pool.spawner();
+
+    let (tx, rx) = oneshot::channel::<()>();
+    spawn.spawn_local_named("waiter", async { rx.await.unwrap() }).unwrap();
+    pool.run_until_stalled();
+    spawn.spawn_local_named("other", async {}).unwrap();
+    tx.send(()).unwrap();
+    pool.run();
+
+    let events = events.lock().unwrap();
+    let of_task = |id| events.iter().filter(|e| e.1 == id).cloned().collect::<Vec<_>>();
+    assert_eq!(of_task(0), vec![
+        ("spawn", 0, Some("waiter".to_string())),
+        ("first", 0, None),
+        ("poll", 0, None),
+        ("poll", 0, None),
+        ("done", 0, None),
+    ]);
+    assert_eq!(of_task(1), vec![
+        ("spawn", 1, Some("other".to_string())),
+        ("first", 1, None),
+        ("poll", 1, None),
+        ("done", 1, None),
+    ]);
+}
+
+#[test]
+fn blocking_stream_next_timeout() {
+    use futures::channel::mpsc;
+    use futures::executor::block_on_stream;
+    use futures::stream::FusedStream;
+
+    let (tx, rx) = mpsc::unbounded();
+    let mut iter = block_on_stream(rx);
+    let start = Instant::now();
+    assert_eq!(iter.next_timeout(Duration::from_millis(20)), None);
+    assert!(start.elapsed() >= Duration::from_millis(20));
+
+    let sender = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        tx.unbounded_send(1).unwrap();
+    });
+    assert_eq!(iter.next_timeout(Duration::from_secs(10)), Some(Some(1)));
+    sender.join().unwrap();
+    assert_eq!(iter.next_timeout(Duration::from_secs(10)), Some(None));
+    assert!(iter.into_inner().is_terminated());
+}
+
+#[test]
+fn tick_stats() {
+    let mut pool = LocalPool::new();
+    let mut spawn = pool.spawner();
+    let (tx, rx) = oneshot::channel::<()>();
+    spawn.spawn_local_obj(Box::pin(async { let _ = rx.await; }).into()).unwrap();
+    spawn.spawn_local_obj(Box::pin(async {}).into()).unwrap();
+
+    let stats = pool.tick();
+    assert_eq!((stats.spawned(), stats.polled(), stats.woken(), stats.completed()), (2, 2, 0, 1));
+
+    let stats = pool.tick();
+    assert_eq!((stats.spawned(), stats.polled(), stats.woken(), stats.completed()), (0, 0, 0, 0));
+
+    drop(tx);
+    let stats = pool.tick();
+    assert_eq!((stats.spawned(), stats.polled(), stats.woken(), stats.completed()), (0, 1, 1, 1));
+}
+
+#[test]
+fn coop_budget_yields_to_other_tasks() {
+    use futures::task::budget;
+
+    let mut pool = LocalPool::new();
+    pool.set_coop_budget(16);
+    let mut spawn = pool.spawner();
+    let done = Rc::new(Cell::new(false));
+    let done2 = done.clone();
+    spawn.spawn_local_obj(Box::pin(poll_fn(move |cx| loop {
+        if done2.get() {
+            return Poll::Ready(());
+        }
+        futures::ready!(budget::consume(cx));
+    })).into()).unwrap();
+    let done2 = done.clone();
+    spawn.spawn_local_obj(Box::pin(async move { done2.set(true) }).into()).unwrap();
+    pool.run();
+    assert!(done.get());
+}
@@ -0,0 +1,670 @@
+use futures::channel::oneshot;
+use futures::executor::{block_on, PanicPolicy, Priority, QueueFullPolicy, TaskHooks, ThreadPool, ThreadPriority};
+use futures::task::{Spawn, SpawnExt};
+use std::rc::Rc;
+use std::sync::{mpsc, Arc, Barrier, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn shutdown_waits_for_tasks() {
+    let pool = ThreadPool::builder().pool_size(2).create().unwrap();
+    let done = Arc::new(AtomicUsize::new(0));
+    for _ in 0..10 {
+        let done = done.clone();
+        pool.spawn_ok(async move {
+            thread::sleep(Duration::from_millis(10));
+            done.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+
+    block_on(pool.shutdown());
+    assert_eq!(done.load(Ordering::SeqCst), 10);
+}
+
+#[test]
+fn shutdown_waits_for_pending_tasks() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let (tx, rx) = oneshot::channel::<()>();
+    let (done_tx, done_rx) = oneshot::channel();
+    pool.spawn_ok(async move {
+        rx.await.unwrap();
+        done_tx.send(()).unwrap();
+    });
+
+    let shutdown = pool.shutdown();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        tx.send(()).unwrap();
+    });
+    block_on(shutdown);
+    assert_eq!(block_on(done_rx), Ok(()));
+}
+
+#[test]
+fn spawn_after_shutdown_fails() {
+    let mut pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let mut handle = pool.clone();
+    block_on(pool.shutdown());
+
+    assert!(pool.status().unwrap_err().is_shutdown());
+    assert!(handle.spawn(async {}).unwrap_err().is_shutdown());
+    assert!(block_on(pool.spawn_with_join_handle(async {})).unwrap_err().is_cancelled());
+}
+
+#[test]
+fn shutdown_timeout() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let (tx, rx) = oneshot::channel::<()>();
+    pool.spawn_ok(async move {
+        let _ = rx.await;
+    });
+
+    assert!(!pool.shutdown_timeout(Duration::from_millis(20)));
+    drop(tx);
+    assert!(pool.shutdown_timeout(Duration::from_secs(10)));
+}
+
+#[test]
+fn shutdown_runs_before_stop() {
+    let stopped = Arc::new(AtomicUsize::new(0));
+    let stopped2 = stopped.clone();
+    let pool = ThreadPool::builder()
+        .pool_size(3)
+        .before_stop(move |_| { stopped2.fetch_add(1, Ordering::SeqCst); })
+        .create()
+        .unwrap();
+
+    block_on(pool.shutdown());
+    assert_eq!(stopped.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn idle_worker_steals_local_tasks() {
+    let pool = ThreadPool::builder().pool_size(2).create().unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    let inner = pool.clone();
+    pool.spawn_ok(async move {
+        // The task lands in the queue of this worker, which then blocks, so
+        // it only runs if the other worker steals it.
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        inner.spawn_ok(async move { done_tx.send(()).unwrap() });
+        done_rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        tx.send(()).unwrap();
+    });
+    rx.recv_timeout(Duration::from_secs(10)).unwrap();
+}
+
+#[test]
+fn ping_pong_between_tasks() {
+    use futures::channel::mpsc;
+    use futures::sink::SinkExt;
+    use futures::stream::StreamExt;
+
+    let pool = ThreadPool::builder().pool_size(2).create().unwrap();
+    let (mut ping_tx, mut ping_rx) = mpsc::channel::<usize>(0);
+    let (mut pong_tx, mut pong_rx) = mpsc::channel::<usize>(0);
+    pool.spawn_ok(async move {
+        while let Some(n) = ping_rx.next().await {
+            pong_tx.send(n + 1).await.unwrap();
+        }
+    });
+    let (tx, rx) = oneshot::channel();
+    pool.spawn_ok(async move {
+        let mut n = 0;
+        for _ in 0..1000 {
+            ping_tx.send(n).await.unwrap();
+            n = pong_rx.next().await.unwrap();
+        }
+        tx.send(n).unwrap();
+    });
+    assert_eq!(block_on(rx), Ok(1000));
+}
+
+#[test]
+fn metrics() {
+    let pool = ThreadPool::builder().pool_size(2).create().unwrap();
+    let metrics = pool.metrics();
+    assert_eq!(metrics.num_workers(), 2);
+
+    let (tx, rx) = oneshot::channel::<()>();
+    pool.spawn_ok(async move {
+        let _ = rx.await;
+    });
+    for _ in 0..10 {
+        pool.spawn_ok(async {});
+    }
+    while metrics.alive_tasks() > 1 {
+        thread::yield_now();
+    }
+
+    drop(tx);
+    block_on(pool.shutdown());
+    assert_eq!(metrics.alive_tasks(), 0);
+    assert_eq!(metrics.queue_depth(), 0);
+    assert_eq!(metrics.total_polls(), 12);
+    let busy = (0..2).map(|i| metrics.worker_busy_time(i)).sum::<Duration>();
+    assert!(busy > Duration::from_secs(0));
+}
+
+#[test]
+fn spawn_blocking() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let inner = pool.clone();
+    let (tx, rx) = oneshot::channel();
+    pool.spawn_ok(async move {
+        let n = inner.spawn_blocking(|| {
+            thread::sleep(Duration::from_millis(10));
+            1
+        }).await;
+        tx.send(n).unwrap();
+    });
+    assert_eq!(block_on(rx), Ok(1));
+}
+
+#[test]
+fn spawn_blocking_does_not_block_workers() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let (block_tx, block_rx) = std::sync::mpsc::channel::<()>();
+    let blocked = pool.spawn_blocking(move || block_rx.recv().unwrap());
+
+    // The only worker is still free to run tasks.
+    let (tx, rx) = oneshot::channel();
+    pool.spawn_ok(async move { tx.send(()).unwrap() });
+    assert_eq!(block_on(rx), Ok(()));
+
+    block_tx.send(()).unwrap();
+    block_on(blocked);
+}
+
+#[test]
+fn spawn_blocking_limits_threads() {
+    let pool = ThreadPool::builder()
+        .pool_size(1)
+        .max_blocking_threads(2)
+        .create()
+        .unwrap();
+    let running = Arc::new(AtomicUsize::new(0));
+    let max = Arc::new(AtomicUsize::new(0));
+    let handles = (0..8).map(|_| {
+        let running = running.clone();
+        let max = max.clone();
+        pool.spawn_blocking(move || {
+            let n = running.fetch_add(1, Ordering::SeqCst) + 1;
+            max.fetch_max(n, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(5));
+            running.fetch_sub(1, Ordering::SeqCst);
+        })
+    }).collect::<Vec<_>>();
+    block_on(futures::future::join_all(handles));
+    assert!(max.load(Ordering::SeqCst) <= 2);
+}
+
+#[test]
+#[should_panic(expected = "boom")]
+fn spawn_blocking_resumes_panic() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    block_on(pool.spawn_blocking(|| panic!("boom")));
+}
+
+#[test]
+fn panic_policy_catch_and_log() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let tx = std::sync::Mutex::new(tx);
+    let pool = ThreadPool::builder()
+        .pool_size(1)
+        .panic_policy(PanicPolicy::catch_and_log(move |payload| {
+            let msg = *payload.downcast::<&str>().unwrap();
+            tx.lock().unwrap().send(msg).unwrap();
+        }))
+        .create()
+        .unwrap();
+
+    pool.spawn_ok(async { panic!("boom") });
+    assert_eq!(rx.recv_timeout(Duration::from_secs(10)), Ok("boom"));
+
+    // The worker is still running.
+    let (done_tx, done_rx) = oneshot::channel();
+    pool.spawn_ok(async move { done_tx.send(()).unwrap() });
+    assert_eq!(block_on(done_rx), Ok(()));
+    assert!(pool.shutdown_timeout(Duration::from_secs(10)));
+}
+
+#[test]
+fn panic_policy_restart_worker() {
+    let started = Arc::new(AtomicUsize::new(0));
+    let started2 = started.clone();
+    let pool = ThreadPool::builder()
+        .pool_size(1)
+        .panic_policy(PanicPolicy::RestartWorker)
+        .after_start(move |_| { started2.fetch_add(1, Ordering::SeqCst); })
+        .create()
+        .unwrap();
+
+    pool.spawn_ok(async { panic!("boom") });
+    let (done_tx, done_rx) = oneshot::channel();
+    pool.spawn_ok(async move { done_tx.send(()).unwrap() });
+    assert_eq!(block_on(done_rx), Ok(()));
+    assert_eq!(started.load(Ordering::SeqCst), 2);
+    assert!(pool.shutdown_timeout(Duration::from_secs(10)));
+}
+
+#[test]
+fn panic_policy_propagate_keeps_shutdown_working() {
+    let pool = ThreadPool::builder().pool_size(2).create().unwrap();
+    pool.spawn_ok(async { panic!("boom") });
+    let (done_tx, done_rx) = oneshot::channel();
+    pool.spawn_ok(async move { done_tx.send(()).unwrap() });
+    assert_eq!(block_on(done_rx), Ok(()));
+    assert!(pool.shutdown_timeout(Duration::from_secs(10)));
+}
+
+#[test]
+fn join_handle_output() {
+    let pool = ThreadPool::builder().pool_size(2).create().unwrap();
+    let handle = pool.spawn_with_join_handle(async { 1 + 2 });
+    assert_eq!(block_on(handle).unwrap(), 3);
+}
+
+#[test]
+fn join_handle_abort() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let (tx, rx) = oneshot::channel::<()>();
+    let (dropped_tx, dropped_rx) = oneshot::channel::<()>();
+    let handle = pool.spawn_with_join_handle(async move {
+        let _dropped_tx = dropped_tx;
+        rx.await.unwrap();
+    });
+    assert!(!handle.is_finished());
+
+    handle.abort();
+    // The future is dropped along with the sender inside it.
+    assert!(block_on(dropped_rx).is_err());
+    assert!(handle.is_finished());
+    assert!(block_on(handle).unwrap_err().is_cancelled());
+    drop(tx);
+}
+
+#[test]
+fn join_handle_panic() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let handle = pool.spawn_with_join_handle(async { panic!("boom") });
+    let err = block_on(handle).unwrap_err();
+    assert!(err.is_panic());
+    assert_eq!(*err.try_into_panic().unwrap().downcast::<&str>().unwrap(), "boom");
+
+    // The worker survived the panic.
+    assert_eq!(block_on(pool.spawn_with_join_handle(async { 1 })).unwrap(), 1);
+}
+
+#[test]
+fn join_handle_detach() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let (tx, rx) = oneshot::channel();
+    drop(pool.spawn_with_join_handle(async move { tx.send(1).unwrap() }));
+    assert_eq!(block_on(rx), Ok(1));
+}
+
+#[test]
+fn high_priority_runs_first() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let (started_tx, started_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel::<()>();
+    pool.spawn_ok(async move {
+        started_tx.send(()).unwrap();
+        release_rx.recv().unwrap();
+    });
+    started_rx.recv().unwrap();
+
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let mut handles = Vec::new();
+    for &priority in &[Priority::Low, Priority::High] {
+        for _ in 0..10 {
+            let order = order.clone();
+            handles.push(pool.spawn_with_priority(async move {
+                order.lock().unwrap().push(priority);
+            }, priority));
+        }
+    }
+    release_tx.send(()).unwrap();
+    for handle in handles {
+        block_on(handle).unwrap();
+    }
+
+    // A low priority task may get its turn among the high priority ones.
+    let order = order.lock().unwrap();
+    let last_high = order.iter().rposition(|&p| p == Priority::High).unwrap();
+    assert!(order[..last_high].iter().filter(|&&p| p == Priority::Low).count() <= 1);
+}
+
+#[test]
+fn low_priority_is_not_starved() {
+    fn spawn_busy(pool: ThreadPool, stop: Arc<AtomicBool>) {
+        let pool2 = pool.clone();
+        drop(pool.spawn_with_priority(async move {
+            if !stop.load(Ordering::SeqCst) {
+                spawn_busy(pool2, stop);
+            }
+        }, Priority::High));
+    }
+
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let stop = Arc::new(AtomicBool::new(false));
+    spawn_busy(pool.clone(), stop.clone());
+
+    block_on(pool.spawn_with_priority(async move {
+        stop.store(true, Ordering::SeqCst);
+    }, Priority::Low)).unwrap();
+}
+
+// Spawns a task which keeps the only worker of `pool` busy until the
+// returned sender is used or dropped.
+fn block_worker(pool: &ThreadPool) -> mpsc::Sender<()> {
+    let (started_tx, started_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel::<()>();
+    pool.spawn_ok(async move {
+        started_tx.send(()).unwrap();
+        let _ = release_rx.recv();
+    });
+    started_rx.recv().unwrap();
+    release_tx
+}
+
+#[test]
+fn queue_full_rejects() {
+    let pool = ThreadPool::builder().pool_size(1).max_queued_tasks(2).create().unwrap();
+    let release = block_worker(&pool);
+
+    let (tx, rx) = mpsc::channel();
+    for i in 0..2 {
+        let tx = tx.clone();
+        pool.spawn_obj_ok(Box::new(async move { tx.send(i).unwrap() }).into());
+    }
+    let err = (&pool).spawn(async {}).unwrap_err();
+    assert!(err.is_queue_full());
+    assert!(!err.is_shutdown());
+
+    drop(release);
+    assert_eq!(rx.recv().unwrap(), 0);
+    assert_eq!(rx.recv().unwrap(), 1);
+    // Room has been made in the queue.
+    assert_eq!(block_on(pool.spawn_with_join_handle(async { 2 })).unwrap(), 2);
+}
+
+#[test]
+fn queue_full_blocks() {
+    let pool = ThreadPool::builder()
+        .pool_size(1)
+        .max_queued_tasks(1)
+        .queue_full_policy(QueueFullPolicy::Block(Duration::from_secs(10)))
+        .create()
+        .unwrap();
+    let release = block_worker(&pool);
+    pool.spawn_ok(async {});
+
+    let releaser = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        drop(release);
+    });
+    assert_eq!(block_on(pool.spawn_with_join_handle(async { 1 })).unwrap(), 1);
+    releaser.join().unwrap();
+}
+
+#[test]
+fn queue_full_block_times_out() {
+    let pool = ThreadPool::builder()
+        .pool_size(1)
+        .max_queued_tasks(1)
+        .queue_full_policy(QueueFullPolicy::Block(Duration::from_millis(10)))
+        .create()
+        .unwrap();
+    let _release = block_worker(&pool);
+    pool.spawn_ok(async {});
+    assert!((&pool).spawn(async {}).unwrap_err().is_queue_full());
+}
+
+#[test]
+fn queue_full_sheds() {
+    let shed = Arc::new(AtomicUsize::new(0));
+    let shed2 = shed.clone();
+    let pool = ThreadPool::builder()
+        .pool_size(1)
+        .max_queued_tasks(1)
+        .queue_full_policy(QueueFullPolicy::shed(move |_| {
+            shed2.fetch_add(1, Ordering::SeqCst);
+        }))
+        .create()
+        .unwrap();
+    let release = block_worker(&pool);
+    let queued = pool.spawn_with_join_handle(async { 1 });
+    let dropped = pool.spawn_with_join_handle(async { 2 });
+    assert_eq!(shed.load(Ordering::SeqCst), 1);
+    assert!(block_on(dropped).unwrap_err().is_cancelled());
+
+    drop(release);
+    assert_eq!(block_on(queued).unwrap(), 1);
+}
+
+// Waits for `f` to return `true`, failing the test after a while.
+fn wait_until(f: impl Fn() -> bool) {
+    for _ in 0..500 {
+        if f() {
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn dynamic_pool_grows_and_shrinks() {
+    let pool = ThreadPool::builder()
+        .min_threads(1)
+        .max_threads(4)
+        .idle_timeout(Duration::from_millis(20))
+        .create()
+        .unwrap();
+    let metrics = pool.metrics();
+    assert_eq!(metrics.num_workers(), 4);
+    assert_eq!(metrics.running_workers(), 1);
+
+    // The tasks only complete once they all run at the same time.
+    let barrier = Arc::new(Barrier::new(4));
+    let handles = (0..4).map(|_| {
+        let barrier = barrier.clone();
+        pool.spawn_with_join_handle(async move { barrier.wait(); })
+    }).collect::<Vec<_>>();
+    for handle in handles {
+        block_on(handle).unwrap();
+    }
+    assert_eq!(metrics.running_workers(), 4);
+
+    wait_until(|| metrics.running_workers() == 1);
+    assert_eq!(block_on(pool.spawn_with_join_handle(async { 1 })).unwrap(), 1);
+}
+
+#[test]
+fn dynamic_pool_without_min_threads() {
+    let pool = ThreadPool::builder()
+        .min_threads(0)
+        .max_threads(2)
+        .idle_timeout(Duration::from_millis(10))
+        .create()
+        .unwrap();
+    assert_eq!(pool.metrics().running_workers(), 0);
+    assert_eq!(block_on(pool.spawn_with_join_handle(async { 1 })).unwrap(), 1);
+
+    let metrics = pool.metrics();
+    wait_until(|| metrics.running_workers() == 0);
+    assert_eq!(block_on(pool.spawn_with_join_handle(async { 2 })).unwrap(), 2);
+    block_on(pool.shutdown());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn thread_configuration() {
+    let pool = ThreadPool::builder()
+        .pool_size(1)
+        .core_affinity(vec![0])
+        .thread_priority(ThreadPriority::Lowest)
+        .create()
+        .unwrap();
+    let stat = block_on(pool.spawn_with_join_handle(async {
+        std::fs::read_to_string("/proc/thread-self/stat").unwrap()
+    })).unwrap();
+    let status = block_on(pool.spawn_with_join_handle(async {
+        std::fs::read_to_string("/proc/thread-self/status").unwrap()
+    })).unwrap();
+
+    // The nice value is the 19th field, counting from the pid.
+    let fields = stat.rsplit(')').next().unwrap().split_whitespace().collect::<Vec<_>>();
+    assert_eq!(fields[16], "19");
+    assert!(status.lines().any(|l| l.starts_with("Cpus_allowed_list:") && l.ends_with("\t0")));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+#[should_panic(expected = "core index out of range")]
+fn core_affinity_out_of_range() {
+    ThreadPool::builder().core_affinity(vec![0, 4096]);
+}
+
+#[test]
+fn spawn_local_on_dedicated() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let first = pool.spawn_local_on_dedicated(|| {
+        let id = Rc::new(thread::current().id());
+        async move { *id }
+    });
+    let second = pool.spawn_local_on_dedicated(|| async { thread::current().id() });
+    let first = block_on(first).unwrap();
+    assert_eq!(first, block_on(second).unwrap());
+    assert_ne!(first, thread::current().id());
+
+    let err = block_on(pool.spawn_local_on_dedicated(|| -> futures::future::Ready<()> {
+        panic!("boom")
+    })).unwrap_err();
+    assert!(err.is_panic());
+}
+
+#[test]
+fn shutdown_waits_for_local_tasks() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let (tx, rx) = oneshot::channel::<()>();
+    let handle = pool.spawn_local_on_dedicated(move || async move {
+        rx.await.unwrap();
+        1
+    });
+
+    assert!(!pool.shutdown_timeout(Duration::from_millis(10)));
+    tx.send(()).unwrap();
+    block_on(pool.shutdown());
+    assert_eq!(block_on(handle).unwrap(), 1);
+    assert!(block_on(pool.spawn_local_on_dedicated(|| async {})).unwrap_err().is_cancelled());
+}
+
+#[test]
+fn task_hooks() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let (e1, e2, e3, e4) = (events.clone(), events.clone(), events.clone(), events.clone());
+    let hooks = TaskHooks::new()
+        .on_spawn(move |task| e1.lock().unwrap().push(format!("spawn {:?}", task.name())))
+        .on_first_poll(move |task| e2.lock().unwrap().push(format!("first {:?}", task.name())))
+        .on_poll(move |task, _| e3.lock().unwrap().push(format!("poll {:?}", task.name())))
+        .on_complete(move |task| e4.lock().unwrap().push(format!("done {:?}", task.name())));
+    let pool = ThreadPool::builder().pool_size(1).task_hooks(hooks).create().unwrap();
+
+    let (tx, rx) = oneshot::channel::<()>();
+    let handle = pool.spawn_named("worker", async move { rx.await.unwrap() });
+    thread::sleep(Duration::from_millis(20));
+    tx.send(()).unwrap();
+    block_on(handle).unwrap();
+    // The handle resolves right before the hooks of the last poll run.
+    wait_until(|| events.lock().unwrap().len() == 5);
+    assert_eq!(*events.lock().unwrap(), vec![
+        "spawn Some(\"worker\")",
+        "first Some(\"worker\")",
+        "poll Some(\"worker\")",
+        "poll Some(\"worker\")",
+        "done Some(\"worker\")",
+    ]);
+
+    events.lock().unwrap().clear();
+    block_on(pool.spawn_with_join_handle(async {})).unwrap();
+    wait_until(|| events.lock().unwrap().len() == 4);
+    assert_eq!(*events.lock().unwrap(), vec!["spawn None", "first None", "poll None", "done None"]);
+}
+
+#[test]
+fn workers_are_entered() {
+    use futures::executor::is_entered;
+
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    assert!(!is_entered());
+    assert!(block_on(pool.spawn_with_join_handle(async { is_entered() })).unwrap());
+    assert!(!block_on(pool.spawn_blocking(is_entered)));
+}
+
+#[test]
+fn panicked_task_is_dropped_while_waker_is_alive() {
+    struct SetOnDrop(Arc<AtomicBool>);
+
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let pool = ThreadPool::builder()
+        .pool_size(1)
+        .panic_policy(PanicPolicy::catch_and_log(move |_| tx.send(()).unwrap()))
+        .create()
+        .unwrap();
+    let dropped = Arc::new(AtomicBool::new(false));
+    let waker = Arc::new(Mutex::new(None));
+    let (guard, waker2) = (SetOnDrop(dropped.clone()), waker.clone());
+    pool.spawn_ok(futures::future::poll_fn(move |cx| {
+        let _guard = &guard;
+        *waker2.lock().unwrap() = Some(cx.waker().clone());
+        panic!("boom")
+    }));
+    rx.recv().unwrap();
+    assert!(dropped.load(Ordering::SeqCst));
+    assert!(waker.lock().unwrap().is_some());
+}
+
+#[test]
+fn coop_budget_yields_to_other_tasks() {
+    use futures::task::{budget, Poll};
+
+    let pool = ThreadPool::builder().pool_size(1).coop_budget(16).create().unwrap();
+    let done = Arc::new(AtomicBool::new(false));
+    let done2 = done.clone();
+    // Without a budget, this would keep the only worker busy forever.
+    let spinner = pool.spawn_with_join_handle(futures::future::poll_fn(move |cx| loop {
+        if done2.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+        futures::ready!(budget::consume(cx));
+    }));
+    let done2 = done.clone();
+    pool.spawn_ok(async move { done2.store(true, Ordering::SeqCst) });
+    block_on(spinner).unwrap();
+}
+
+#[test]
+fn spawn_with_config_names_task() {
+    use futures::task::SpawnConfig;
+
+    let (tx, rx) = mpsc::channel();
+    let hooks = TaskHooks::new()
+        .on_complete(move |task| tx.send(task.name().map(String::from)).unwrap());
+    let mut pool = ThreadPool::builder().pool_size(1).task_hooks(hooks).create().unwrap();
+
+    pool.spawn_with_config(async {}, SpawnConfig::new().with_name("worker")).unwrap();
+    assert_eq!(rx.recv().unwrap().as_deref(), Some("worker"));
+    pool.spawn_ok(async {});
+    assert_eq!(rx.recv().unwrap(), None);
+}
@@ -22,6 +22,7 @@
 #[cfg(feature = "std")]
 mod if_std {
     use std::cmp;
+    use std::collections::VecDeque;
     use std::io;
     use std::ops::DerefMut;
     use std::pin::Pin;
@@ -216,6 +217,22 @@ mod if_std {
             }
         }
 
+        /// Returns whether this writer has an efficient
+        /// [`poll_write_vectored`](AsyncWrite::poll_write_vectored)
+        /// implementation.
+        ///
+        /// If this returns `false`, `poll_write_vectored` only writes the
+        /// first buffer, so writing many small buffers costs one
+        /// operation each. Callers can use this hint to coalesce buffers
+        /// before writing them.
+        ///
+        /// The default implementation returns `false`. Objects which override
+        /// `poll_write_vectored` to write all of the buffers should override
+        /// this method as well.
+        fn is_write_vectored(&self) -> bool {
+            false
+        }
+
         /// Attempt to flush the object, ensuring that any buffered data reach
         /// their destination.
         ///
@@ -340,6 +357,63 @@ mod if_std {
         fn consume(self: Pin<&mut Self>, amt: usize);
     }
 
+    /// Read bytes asynchronously from a given offset.
+    ///
+    /// This trait is analogous to the `read_at` methods of the platform
+    /// specific `FileExt` traits of the standard library, but integrates with
+    /// the asynchronous task system. Reading at an offset does not use or
+    /// change any cursor position the object may have, which makes it
+    /// suitable for random-access data such as files.
+    pub trait AsyncReadAt {
+        /// Attempt to read from the object at `offset` into `buf`.
+        ///
+        /// On success, returns `Poll::Ready(Ok(num_bytes_read))`. A return
+        /// value of `0` means that `offset` is at or past the end of the data,
+        /// or that `buf` is empty.
+        ///
+        /// If no data is available for reading, the method returns
+        /// `Poll::Pending` and arranges for the current task (via
+        /// `cx.waker().wake_by_ref()`) to receive a notification when the object becomes
+        /// readable or is closed.
+        ///
+        /// # Implementation
+        ///
+        /// This function may not return errors of kind `WouldBlock` or
+        /// `Interrupted`.  Implementations must convert `WouldBlock` into
+        /// `Poll::Pending` and either internally retry or convert
+        /// `Interrupted` into another error kind.
+        fn poll_read_at(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8], offset: u64)
+            -> Poll<Result<usize>>;
+    }
+
+    /// Write bytes asynchronously at a given offset.
+    ///
+    /// This trait is analogous to the `write_at` methods of the platform
+    /// specific `FileExt` traits of the standard library, but integrates with
+    /// the asynchronous task system. Writing at an offset does not use or
+    /// change any cursor position the object may have.
+    pub trait AsyncWriteAt {
+        /// Attempt to write bytes from `buf` into the object at `offset`.
+        ///
+        /// On success, returns `Poll::Ready(Ok(num_bytes_written))`. Writing
+        /// past the end of the data extends it; what the gap is filled with,
+        /// if any, is up to the implementation.
+        ///
+        /// If the object is not ready for writing, the method returns
+        /// `Poll::Pending` and arranges for the current task (via
+        /// `cx.waker().wake_by_ref()`) to receive a notification when the object becomes
+        /// writable or is closed.
+        ///
+        /// # Implementation
+        ///
+        /// This function may not return errors of kind `WouldBlock` or
+        /// `Interrupted`.  Implementations must convert `WouldBlock` into
+        /// `Poll::Pending` and either internally retry or convert
+        /// `Interrupted` into another error kind.
+        fn poll_write_at(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8], offset: u64)
+            -> Poll<Result<usize>>;
+    }
+
     macro_rules! deref_async_read {
         () => {
             unsafe fn initializer(&self) -> Initializer {
@@ -428,6 +502,26 @@ mod if_std {
         unsafe_delegate_async_read_to_stdio!();
     }
 
+    /// Reads from the front of the queue, removing the bytes that were read.
+    impl AsyncRead for VecDeque<u8> {
+        unsafe fn initializer(&self) -> Initializer {
+            Initializer::nop()
+        }
+
+        fn poll_read(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &mut [u8])
+            -> Poll<Result<usize>>
+        {
+            let this = &mut *self;
+            let (front, back) = this.as_slices();
+            let n1 = cmp::min(front.len(), buf.len());
+            buf[..n1].copy_from_slice(&front[..n1]);
+            let n2 = cmp::min(back.len(), buf.len() - n1);
+            buf[n1..n1 + n2].copy_from_slice(&back[..n2]);
+            this.drain(..n1 + n2);
+            Poll::Ready(Ok(n1 + n2))
+        }
+    }
+
     macro_rules! deref_async_write {
         () => {
             fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
@@ -442,6 +536,10 @@ mod if_std {
                 Pin::new(&mut **self).poll_write_vectored(cx, bufs)
             }
 
+            fn is_write_vectored(&self) -> bool {
+                (**self).is_write_vectored()
+            }
+
             fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
                 Pin::new(&mut **self).poll_flush(cx)
             }
@@ -477,6 +575,10 @@ mod if_std {
             self.get_mut().as_mut().poll_write_vectored(cx, bufs)
         }
 
+        fn is_write_vectored(&self) -> bool {
+            (**self).is_write_vectored()
+        }
+
         fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
             self.get_mut().as_mut().poll_flush(cx)
         }
@@ -500,6 +602,10 @@ mod if_std {
                 Poll::Ready(io::Write::write_vectored(&mut *self, bufs))
             }
 
+            fn is_write_vectored(&self) -> bool {
+                true
+            }
+
             fn poll_flush(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<()>> {
                 Poll::Ready(io::Write::flush(&mut *self))
             }
@@ -547,6 +653,39 @@ mod if_std {
         delegate_async_write_to_stdio!();
     }
 
+    /// Writes to the back of the queue.
+    impl AsyncWrite for VecDeque<u8> {
+        fn poll_write(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8])
+            -> Poll<Result<usize>>
+        {
+            self.extend(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_write_vectored(mut self: Pin<&mut Self>, _: &mut Context<'_>, bufs: &[IoSlice<'_>])
+            -> Poll<Result<usize>>
+        {
+            let len = bufs.iter().map(|b| b.len()).sum();
+            self.reserve(len);
+            for buf in bufs {
+                self.extend(&**buf);
+            }
+            Poll::Ready(Ok(len))
+        }
+
+        fn is_write_vectored(&self) -> bool {
+            true
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+            self.poll_flush(cx)
+        }
+    }
+
     impl AsyncWrite for io::Sink {
         delegate_async_write_to_stdio!();
     }
@@ -658,6 +797,125 @@ mod if_std {
     impl<T: AsRef<[u8]> + Unpin> AsyncBufRead for io::Cursor<T> {
         delegate_async_buf_read_to_stdio!();
     }
+
+    /// Returns the front contiguous part of the queue, which may be shorter
+    /// than the queue if it wraps around its ring buffer.
+    impl AsyncBufRead for VecDeque<u8> {
+        fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<&[u8]>> {
+            Poll::Ready(Ok(self.get_mut().as_slices().0))
+        }
+
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            self.get_mut().drain(..amt);
+        }
+    }
+
+    macro_rules! deref_async_read_at {
+        () => {
+            fn poll_read_at(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8], offset: u64)
+                -> Poll<Result<usize>>
+            {
+                Pin::new(&mut **self).poll_read_at(cx, buf, offset)
+            }
+        }
+    }
+
+    impl<T: ?Sized + AsyncReadAt + Unpin> AsyncReadAt for Box<T> {
+        deref_async_read_at!();
+    }
+
+    impl<T: ?Sized + AsyncReadAt + Unpin> AsyncReadAt for &mut T {
+        deref_async_read_at!();
+    }
+
+    impl<P> AsyncReadAt for Pin<P>
+    where
+        P: DerefMut + Unpin,
+        P::Target: AsyncReadAt,
+    {
+        fn poll_read_at(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8], offset: u64)
+            -> Poll<Result<usize>>
+        {
+            self.get_mut().as_mut().poll_read_at(cx, buf, offset)
+        }
+    }
+
+    fn read_at_slice(data: &[u8], buf: &mut [u8], offset: u64) -> usize {
+        let start = cmp::min(offset, data.len() as u64) as usize;
+        let len = cmp::min(buf.len(), data.len() - start);
+        buf[..len].copy_from_slice(&data[start..start + len]);
+        len
+    }
+
+    impl AsyncReadAt for &[u8] {
+        fn poll_read_at(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &mut [u8], offset: u64)
+            -> Poll<Result<usize>>
+        {
+            Poll::Ready(Ok(read_at_slice(*self, buf, offset)))
+        }
+    }
+
+    impl AsyncReadAt for Vec<u8> {
+        fn poll_read_at(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &mut [u8], offset: u64)
+            -> Poll<Result<usize>>
+        {
+            Poll::Ready(Ok(read_at_slice(&self, buf, offset)))
+        }
+    }
+
+    macro_rules! deref_async_write_at {
+        () => {
+            fn poll_write_at(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8], offset: u64)
+                -> Poll<Result<usize>>
+            {
+                Pin::new(&mut **self).poll_write_at(cx, buf, offset)
+            }
+        }
+    }
+
+    impl<T: ?Sized + AsyncWriteAt + Unpin> AsyncWriteAt for Box<T> {
+        deref_async_write_at!();
+    }
+
+    impl<T: ?Sized + AsyncWriteAt + Unpin> AsyncWriteAt for &mut T {
+        deref_async_write_at!();
+    }
+
+    impl<P> AsyncWriteAt for Pin<P>
+    where
+        P: DerefMut + Unpin,
+        P::Target: AsyncWriteAt,
+    {
+        fn poll_write_at(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8], offset: u64)
+            -> Poll<Result<usize>>
+        {
+            self.get_mut().as_mut().poll_write_at(cx, buf, offset)
+        }
+    }
+
+    /// Writing past the end of the vector fills the gap with zeros.
+    impl AsyncWriteAt for Vec<u8> {
+        fn poll_write_at(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8], offset: u64)
+            -> Poll<Result<usize>>
+        {
+            if offset > usize::max_value() as u64 {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "offset does not fit in the address space",
+                )));
+            }
+            let start = offset as usize;
+            let end = start.checked_add(buf.len()).ok_or_else(|| io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "write would overflow the address space",
+            ))?;
+            if self.len() < end {
+                self.resize(end, 0);
+            }
+            self[start..end].copy_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -3,6 +3,9 @@ use futures_core::stream::Stream;
 #[doc(hidden)]
 pub fn assert_is_unpin_stream<S: Stream + Unpin>(_: &mut S) {}
 
+#[doc(hidden)]
+pub fn assert_is_unpin_sink<S: Unpin>(_: &mut S) {}
+
 /// Assert that the next poll to the provided stream will return
 /// [`Poll::Pending`](futures_core::task::Poll::Pending).
 ///
@@ -119,3 +122,170 @@ macro_rules! assert_stream_done {
         }
     }}
 }
+
+/// Assert that the next call to `poll_ready` on the provided sink will return
+/// [`Poll::Pending`](futures_core::task::Poll::Pending).
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::mpsc;
+/// use futures::sink::Sink;
+/// use futures_test::{
+///     assert_sink_pending, assert_sink_ready, assert_sink_flushed,
+///     assert_sink_closed,
+/// };
+///
+/// let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+///
+/// assert_sink_ready!(tx);
+/// std::pin::Pin::new(&mut tx).start_send(1).unwrap();
+/// assert_sink_pending!(tx);
+/// assert_eq!(rx.try_next().unwrap(), Some(1));
+/// assert_sink_flushed!(tx);
+/// assert_sink_closed!(tx);
+/// ```
+#[macro_export]
+macro_rules! assert_sink_pending {
+    ($sink:expr) => {{
+        let mut sink = &mut $sink;
+        $crate::assert::assert_is_unpin_sink(sink);
+        let sink = $crate::std_reexport::pin::Pin::new(sink);
+        let mut cx = $crate::task::noop_context();
+        match $crate::futures_sink_reexport::Sink::poll_ready(sink, &mut cx) {
+            $crate::futures_core_reexport::task::Poll::Ready(Ok(())) => {
+                panic!("assertion failed: expected sink to be pending but it was ready");
+            }
+            $crate::futures_core_reexport::task::Poll::Ready(Err(_)) => {
+                panic!("assertion failed: sink returned an error");
+            }
+            $crate::futures_core_reexport::task::Poll::Pending => {}
+        }
+    }};
+}
+
+/// Assert that the next call to `poll_ready` on the provided sink will return
+/// [`Poll::Ready(Ok(()))`](futures_core::task::Poll::Ready), meaning it can
+/// accept an item.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::mpsc;
+/// use futures::sink::Sink;
+/// use futures_test::{
+///     assert_sink_pending, assert_sink_ready, assert_sink_flushed,
+///     assert_sink_closed,
+/// };
+///
+/// let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+///
+/// assert_sink_ready!(tx);
+/// std::pin::Pin::new(&mut tx).start_send(1).unwrap();
+/// assert_sink_pending!(tx);
+/// assert_eq!(rx.try_next().unwrap(), Some(1));
+/// assert_sink_flushed!(tx);
+/// assert_sink_closed!(tx);
+/// ```
+#[macro_export]
+macro_rules! assert_sink_ready {
+    ($sink:expr) => {{
+        let mut sink = &mut $sink;
+        $crate::assert::assert_is_unpin_sink(sink);
+        let sink = $crate::std_reexport::pin::Pin::new(sink);
+        let mut cx = $crate::task::noop_context();
+        match $crate::futures_sink_reexport::Sink::poll_ready(sink, &mut cx) {
+            $crate::futures_core_reexport::task::Poll::Ready(Ok(())) => {}
+            $crate::futures_core_reexport::task::Poll::Ready(Err(_)) => {
+                panic!("assertion failed: sink returned an error");
+            }
+            $crate::futures_core_reexport::task::Poll::Pending => {
+                panic!("assertion failed: expected sink to be ready but it was pending");
+            }
+        }
+    }};
+}
+
+/// Assert that the next call to `poll_flush` on the provided sink will return
+/// [`Poll::Ready(Ok(()))`](futures_core::task::Poll::Ready), meaning all of
+/// its items have been flushed.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::mpsc;
+/// use futures::sink::Sink;
+/// use futures_test::{
+///     assert_sink_pending, assert_sink_ready, assert_sink_flushed,
+///     assert_sink_closed,
+/// };
+///
+/// let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+///
+/// assert_sink_ready!(tx);
+/// std::pin::Pin::new(&mut tx).start_send(1).unwrap();
+/// assert_sink_pending!(tx);
+/// assert_eq!(rx.try_next().unwrap(), Some(1));
+/// assert_sink_flushed!(tx);
+/// assert_sink_closed!(tx);
+/// ```
+#[macro_export]
+macro_rules! assert_sink_flushed {
+    ($sink:expr) => {{
+        let mut sink = &mut $sink;
+        $crate::assert::assert_is_unpin_sink(sink);
+        let sink = $crate::std_reexport::pin::Pin::new(sink);
+        let mut cx = $crate::task::noop_context();
+        match $crate::futures_sink_reexport::Sink::poll_flush(sink, &mut cx) {
+            $crate::futures_core_reexport::task::Poll::Ready(Ok(())) => {}
+            $crate::futures_core_reexport::task::Poll::Ready(Err(_)) => {
+                panic!("assertion failed: sink returned an error");
+            }
+            $crate::futures_core_reexport::task::Poll::Pending => {
+                panic!("assertion failed: expected sink to be flushed but flushing was pending");
+            }
+        }
+    }};
+}
+
+/// Assert that the next call to `poll_close` on the provided sink will return
+/// [`Poll::Ready(Ok(()))`](futures_core::task::Poll::Ready), meaning it has
+/// been closed.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::mpsc;
+/// use futures::sink::Sink;
+/// use futures_test::{
+///     assert_sink_pending, assert_sink_ready, assert_sink_flushed,
+///     assert_sink_closed,
+/// };
+///
+/// let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+///
+/// assert_sink_ready!(tx);
+/// std::pin::Pin::new(&mut tx).start_send(1).unwrap();
+/// assert_sink_pending!(tx);
+/// assert_eq!(rx.try_next().unwrap(), Some(1));
+/// assert_sink_flushed!(tx);
+/// assert_sink_closed!(tx);
+/// ```
+#[macro_export]
+macro_rules! assert_sink_closed {
+    ($sink:expr) => {{
+        let mut sink = &mut $sink;
+        $crate::assert::assert_is_unpin_sink(sink);
+        let sink = $crate::std_reexport::pin::Pin::new(sink);
+        let mut cx = $crate::task::noop_context();
+        match $crate::futures_sink_reexport::Sink::poll_close(sink, &mut cx) {
+            $crate::futures_core_reexport::task::Poll::Ready(Ok(())) => {}
+            $crate::futures_core_reexport::task::Poll::Ready(Err(_)) => {
+                panic!("assertion failed: sink returned an error");
+            }
+            $crate::futures_core_reexport::task::Poll::Pending => {
+                panic!("assertion failed: expected sink to be closed but closing was pending");
+            }
+        }
+    }};
+}
@@ -0,0 +1,185 @@
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_io::{self as io, AsyncWrite};
+use futures_sink::Sink;
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::ptr;
+use std::thread::panicking;
+
+/// Wrapper that asserts it is not moved after being polled.
+///
+/// See the `assert_unmoved` methods on:
+/// * [`FutureTestExt`](crate::future::FutureTestExt::assert_unmoved)
+/// * [`SinkTestExt`](crate::sink::SinkTestExt::assert_unmoved_sink)
+/// * [`AsyncWriteTestExt`](crate::io::AsyncWriteTestExt::assert_unmoved_write)
+#[derive(Debug, Clone)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct AssertUnmoved<T> {
+    inner: T,
+    this_ptr: *const AssertUnmoved<T>,
+    _pinned: PhantomPinned,
+}
+
+impl<T> AssertUnmoved<T> {
+    unsafe_pinned!(inner: T);
+    unsafe_unpinned!(this_ptr: *const Self);
+
+    pub(crate) fn new(inner: T) -> Self {
+        Self {
+            inner,
+            this_ptr: ptr::null(),
+            _pinned: PhantomPinned,
+        }
+    }
+
+    /// Acquires a reference to the underlying value that this adaptor is
+    /// wrapping.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    // Records the location of the wrapper on the first poll, and asserts
+    // that it is unchanged on the others. `kind` names the polled trait in
+    // the panic message.
+    fn check(mut self: Pin<&mut Self>, kind: &str) -> Pin<&mut T> {
+        let cur_this = &*self as *const Self;
+        if self.this_ptr.is_null() {
+            // First time being polled
+            *self.as_mut().this_ptr() = cur_this;
+        } else {
+            assert_eq!(self.this_ptr, cur_this, "{} moved between poll calls", kind);
+        }
+        self.inner()
+    }
+}
+
+impl<Fut: Future> Future for AssertUnmoved<Fut> {
+    type Output = Fut::Output;
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Self::Output> {
+        self.check("Future").poll(cx)
+    }
+}
+
+impl<Si: Sink<Item>, Item> Sink<Item> for AssertUnmoved<Si> {
+    type Error = Si::Error;
+
+    fn poll_ready(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.check("Sink").poll_ready(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        self.check("Sink").start_send(item)
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.check("Sink").poll_flush(cx)
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.check("Sink").poll_close(cx)
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for AssertUnmoved<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.check("AsyncWrite").poll_write(cx, buf)
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.check("AsyncWrite").poll_flush(cx)
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.check("AsyncWrite").poll_close(cx)
+    }
+}
+
+impl<T> Drop for AssertUnmoved<T> {
+    fn drop(&mut self) {
+        // If the thread is panicking then we can't panic again as that will
+        // cause the process to be aborted.
+        if !panicking() && !self.this_ptr.is_null() {
+            let cur_this = &*self as *const Self;
+            assert_eq!(self.this_ptr, cur_this, "Value moved before drop");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_core::future::Future;
+    use futures_core::task::{Context, Poll};
+    use futures_sink::Sink;
+    use futures_util::future::pending;
+    use futures_util::task::noop_waker;
+    use std::pin::Pin;
+
+    use super::AssertUnmoved;
+
+    #[test]
+    fn dont_panic_when_not_polled() {
+        // This shouldn't panic.
+        let future = AssertUnmoved::new(pending::<()>());
+        drop(future);
+    }
+
+    #[test]
+    #[should_panic(expected = "Future moved between poll calls")]
+    fn dont_double_panic() {
+        // This test should only panic, not abort the process.
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        // First we allocate the future on the stack and poll it.
+        let mut future = AssertUnmoved::new(pending::<()>());
+        let pinned_future = unsafe { Pin::new_unchecked(&mut future) };
+        assert_eq!(pinned_future.poll(&mut cx), Poll::Pending);
+
+        // Next we move it back to the heap and poll it again. This second call
+        // should panic (as the future is moved), but we shouldn't panic again
+        // whilst dropping `AssertUnmoved`.
+        let mut future = Box::new(future);
+        let pinned_boxed_future = unsafe { Pin::new_unchecked(&mut *future) };
+        assert_eq!(pinned_boxed_future.poll(&mut cx), Poll::Pending);
+    }
+
+    #[test]
+    #[should_panic(expected = "Sink moved between poll calls")]
+    fn sink_moved() {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut sink = AssertUnmoved::new(Vec::<i32>::new());
+        let pinned_sink = unsafe { Pin::new_unchecked(&mut sink) };
+        assert_eq!(pinned_sink.poll_ready(&mut cx), Poll::Ready(Ok(())));
+
+        let mut sink = Box::new(sink);
+        let pinned_boxed_sink = unsafe { Pin::new_unchecked(&mut *sink) };
+        let _ = pinned_boxed_sink.poll_ready(&mut cx);
+    }
+}
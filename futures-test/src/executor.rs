@@ -0,0 +1,273 @@
+//! A deterministic executor for tests.
+
+use futures_core::future::{Future, FutureObj, LocalFutureObj};
+use futures_core::task::{Context, LocalSpawn, Poll, Spawn, SpawnError, Waker};
+use futures_util::task::{waker, ArcWake};
+use futures_util::timer::VirtualClock;
+use pin_utils::unsafe_pinned;
+use std::cell::RefCell;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::sync::{Arc, Mutex};
+
+/// A single-threaded executor which runs tasks in a reproducible order, and
+/// drives the [timers](futures_util::timer) of its tasks with a
+/// [`VirtualClock`](futures_util::timer::VirtualClock).
+///
+/// Whenever several tasks are ready to be polled, the executor picks one with
+/// a random number generator seeded on creation, so a test which runs with
+/// the same seed sees the same interleaving every time, and a test which runs
+/// with many seeds covers many interleavings. Once every task is waiting,
+/// the executor advances its clock to the next deadline of a timer, so code
+/// with long timeouts is tested without waiting for them.
+///
+/// # Examples
+///
+/// ```
+/// use futures::timer::Delay;
+/// use futures_test::executor::DeterministicExecutor;
+/// use std::time::Duration;
+///
+/// let mut executor = DeterministicExecutor::new(42);
+/// let clock = executor.clock().clone();
+/// let start = clock.now();
+///
+/// executor.run_until(async {
+///     Delay::new(Duration::from_secs(3600)).await;
+/// });
+/// assert_eq!(clock.now() - start, Duration::from_secs(3600));
+/// ```
+#[derive(Debug)]
+pub struct DeterministicExecutor {
+    tasks: Vec<Option<LocalFutureObj<'static, ()>>>,
+    wakers: Vec<Waker>,
+    incoming: Rc<RefCell<Vec<LocalFutureObj<'static, ()>>>>,
+    ready: Arc<Mutex<ReadyQueue>>,
+    alive: usize,
+    rng: u64,
+    clock: VirtualClock,
+}
+
+#[derive(Debug, Default)]
+struct ReadyQueue {
+    ids: Vec<usize>,
+    queued: Vec<bool>,
+}
+
+#[derive(Debug)]
+struct TaskWaker {
+    id: usize,
+    ready: Arc<Mutex<ReadyQueue>>,
+}
+
+impl ArcWake for TaskWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.ready.lock().unwrap().push(arc_self.id);
+    }
+}
+
+impl ReadyQueue {
+    fn push(&mut self, id: usize) {
+        if !self.queued[id] {
+            self.queued[id] = true;
+            self.ids.push(id);
+        }
+    }
+}
+
+impl DeterministicExecutor {
+    /// Creates an executor whose choices are determined by `seed`.
+    pub fn new(seed: u64) -> DeterministicExecutor {
+        DeterministicExecutor {
+            tasks: Vec::new(),
+            wakers: Vec::new(),
+            incoming: Default::default(),
+            ready: Default::default(),
+            alive: 0,
+            // The generator gets stuck at zero.
+            rng: seed ^ 0x9e37_79b9_7f4a_7c15,
+            clock: VirtualClock::new(),
+        }
+    }
+
+    /// Returns the clock driving the timers of the tasks.
+    ///
+    /// The clock only moves when the executor runs out of tasks to poll, or
+    /// when it is advanced by hand.
+    pub fn clock(&self) -> &VirtualClock {
+        &self.clock
+    }
+
+    /// Returns a handle for spawning tasks onto the executor.
+    pub fn spawner(&self) -> DeterministicSpawner {
+        DeterministicSpawner { incoming: Rc::downgrade(&self.incoming) }
+    }
+
+    /// Returns the number of tasks which have not completed yet.
+    pub fn alive_tasks(&self) -> usize {
+        self.alive + self.incoming.borrow().len()
+    }
+
+    /// Polls the tasks which are ready until none are, without advancing the
+    /// clock.
+    pub fn run_until_stalled(&mut self) {
+        let _guard = self.clock.enter();
+        loop {
+            self.add_incoming();
+            let id = match self.pick() {
+                Some(id) => id,
+                None => return,
+            };
+            let mut cx = Context::from_waker(&self.wakers[id]);
+            let done = match &mut self.tasks[id] {
+                Some(task) => Pin::new(task).poll(&mut cx).is_ready(),
+                None => false,
+            };
+            if done {
+                self.tasks[id] = None;
+                self.alive -= 1;
+            }
+        }
+    }
+
+    /// Runs the tasks, advancing the clock whenever they are all waiting,
+    /// until they have completed.
+    ///
+    /// This returns early if the remaining tasks are waiting for something
+    /// other than a timer, since they cannot make progress anymore.
+    pub fn run(&mut self) {
+        loop {
+            self.run_until_stalled();
+            if self.alive_tasks() == 0 || !self.advance() {
+                return;
+            }
+        }
+    }
+
+    /// Runs the tasks along with `future`, advancing the clock whenever they
+    /// are all waiting, until `future` completes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `future` cannot complete because every task is waiting for
+    /// something other than a timer.
+    pub fn run_until<F: Future + 'static>(&mut self, future: F) -> F::Output {
+        let output = Rc::new(RefCell::new(None));
+        let task = RunUntil { future, output: output.clone() };
+        self.incoming.borrow_mut().push(LocalFutureObj::new(Box::new(task)));
+        loop {
+            self.run_until_stalled();
+            if let Some(output) = output.borrow_mut().take() {
+                return output;
+            }
+            if !self.advance() {
+                panic!("the future can never complete: every task is waiting, with no timer left to fire");
+            }
+        }
+    }
+
+    // Advances the clock to the next timer, returning `false` if there is
+    // none.
+    fn advance(&mut self) -> bool {
+        match self.clock.next_deadline() {
+            Some(deadline) => {
+                self.clock.advance_to(deadline);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn add_incoming(&mut self) {
+        let incoming = self.incoming.replace(Vec::new());
+        for task in incoming {
+            let id = self.tasks.len();
+            self.tasks.push(Some(task));
+            self.wakers.push(waker(Arc::new(TaskWaker { id, ready: self.ready.clone() })));
+            self.alive += 1;
+            let mut ready = self.ready.lock().unwrap();
+            ready.queued.push(false);
+            ready.push(id);
+        }
+    }
+
+    // Takes a random task off the ready queue.
+    fn pick(&mut self) -> Option<usize> {
+        let mut ready = self.ready.lock().unwrap();
+        if ready.ids.is_empty() {
+            return None;
+        }
+        // xorshift64*
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        let n = self.rng.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        let index = (n % ready.ids.len() as u64) as usize;
+        let id = ready.ids.swap_remove(index);
+        ready.queued[id] = false;
+        Some(id)
+    }
+}
+
+/// A handle for spawning tasks onto a
+/// [`DeterministicExecutor`](DeterministicExecutor).
+///
+/// Spawning fails once the executor has been dropped.
+#[derive(Clone, Debug)]
+pub struct DeterministicSpawner {
+    incoming: Weak<RefCell<Vec<LocalFutureObj<'static, ()>>>>,
+}
+
+// The task of `run_until`, which stores the output of its future.
+struct RunUntil<F: Future> {
+    future: F,
+    output: Rc<RefCell<Option<F::Output>>>,
+}
+
+impl<F: Future> RunUntil<F> {
+    unsafe_pinned!(future: F);
+}
+
+impl<F: Future> Future for RunUntil<F> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        match self.as_mut().future().poll(cx) {
+            Poll::Ready(output) => {
+                *self.output.borrow_mut() = Some(output);
+                Poll::Ready(())
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Spawn for DeterministicSpawner {
+    fn spawn_obj(&mut self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.spawn_local_obj(future.into())
+    }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        self.status_local()
+    }
+}
+
+impl LocalSpawn for DeterministicSpawner {
+    fn spawn_local_obj(&mut self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
+        match self.incoming.upgrade() {
+            Some(incoming) => {
+                incoming.borrow_mut().push(future);
+                Ok(())
+            }
+            None => Err(SpawnError::shutdown()),
+        }
+    }
+
+    fn status_local(&self) -> Result<(), SpawnError> {
+        if self.incoming.upgrade().is_some() {
+            Ok(())
+        } else {
+            Err(SpawnError::shutdown())
+        }
+    }
+}
@@ -1,8 +1,5 @@
 //! Additional combinators for testing futures.
 
-mod assert_unmoved;
-pub use self::assert_unmoved::AssertUnmoved;
-
 mod pending_once;
 pub use self::pending_once::PendingOnce;
 
@@ -10,6 +7,7 @@ use futures_core::future::Future;
 use futures_executor;
 use std::thread;
 
+pub use crate::assert_unmoved::AssertUnmoved;
 pub use crate::interleave_pending::InterleavePending;
 
 /// Additional combinators for testing futures.
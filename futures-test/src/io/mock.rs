@@ -0,0 +1,229 @@
+use futures_io::{self as io, AsyncRead, AsyncWrite};
+use std::collections::VecDeque;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+/// Builds a [`Mock`] I/O object, which plays back a script of reads, writes,
+/// pending gaps and errors.
+///
+/// Each call to a method of the builder appends an action to the script.
+/// The mock performs the actions in order:
+///
+/// * [`read`](Builder::read) makes reads return the given bytes, possibly
+///   split over several calls to `poll_read`.
+/// * [`write`](Builder::write) expects the given bytes to be written,
+///   possibly split over several calls to `poll_write`, and panics if other
+///   bytes are written instead.
+/// * [`pending`](Builder::pending) makes the next read or write return
+///   [`Poll::Pending`](std::task::Poll::Pending) once, waking the task
+///   immediately.
+/// * [`read_error`](Builder::read_error) and
+///   [`write_error`](Builder::write_error) make the next read or write fail.
+///
+/// A read which is polled while the mock expects a write returns
+/// `Poll::Pending` until the write has happened, while a write which is
+/// polled while the mock expects a read panics. Once the script is done,
+/// reads return end of file and writes panic. Dropping the mock before the
+/// script is done panics as well, so that a test notices operations which
+/// never happened.
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::io::{AsyncReadExt, AsyncWriteExt};
+/// use futures_test::io::Builder;
+///
+/// let mut mock = Builder::new()
+///     .write(b"ping")
+///     .pending()
+///     .read(b"pong")
+///     .build();
+///
+/// block_on(async {
+///     mock.write_all(b"ping").await?;
+///     let mut buf = [0; 4];
+///     mock.read_exact(&mut buf).await?;
+///     assert_eq!(&buf, b"pong");
+///     Ok::<(), std::io::Error>(())
+/// })?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct Builder {
+    actions: VecDeque<Action>,
+}
+
+#[derive(Debug)]
+enum Action {
+    Read(Vec<u8>),
+    Write(Vec<u8>),
+    Pending,
+    ReadError(io::Error),
+    WriteError(io::Error),
+}
+
+impl Builder {
+    /// Creates a builder with an empty script.
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Appends a read of `buf` to the script.
+    pub fn read(&mut self, buf: &[u8]) -> &mut Builder {
+        if !buf.is_empty() {
+            self.actions.push_back(Action::Read(buf.to_vec()));
+        }
+        self
+    }
+
+    /// Appends an expected write of `buf` to the script.
+    pub fn write(&mut self, buf: &[u8]) -> &mut Builder {
+        if !buf.is_empty() {
+            self.actions.push_back(Action::Write(buf.to_vec()));
+        }
+        self
+    }
+
+    /// Appends a single [`Poll::Pending`](std::task::Poll::Pending) to the
+    /// script, which is returned by the next read or write.
+    pub fn pending(&mut self) -> &mut Builder {
+        self.actions.push_back(Action::Pending);
+        self
+    }
+
+    /// Appends a read which fails with `error` to the script.
+    pub fn read_error(&mut self, error: io::Error) -> &mut Builder {
+        self.actions.push_back(Action::ReadError(error));
+        self
+    }
+
+    /// Appends a write which fails with `error` to the script.
+    pub fn write_error(&mut self, error: io::Error) -> &mut Builder {
+        self.actions.push_back(Action::WriteError(error));
+        self
+    }
+
+    /// Builds a [`Mock`] which plays back the script, leaving the builder
+    /// with an empty script.
+    pub fn build(&mut self) -> Mock {
+        Mock { actions: mem::replace(&mut self.actions, Default::default()), read_waker: None }
+    }
+}
+
+/// A mock I/O object, created by a [`Builder`].
+#[derive(Debug)]
+pub struct Mock {
+    actions: VecDeque<Action>,
+    // The task of a read which is waiting for a write to happen.
+    read_waker: Option<Waker>,
+}
+
+impl Mock {
+    // Called once an action has been performed, as the task of a waiting
+    // read may be able to make progress now.
+    fn advance(&mut self) {
+        if let Some(waker) = self.read_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl AsyncRead for Mock {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+        match this.actions.front_mut() {
+            None => Poll::Ready(Ok(0)),
+            Some(Action::Read(data)) => {
+                let n = buf.len().min(data.len());
+                buf[..n].copy_from_slice(&data[..n]);
+                data.drain(..n);
+                if data.is_empty() {
+                    this.actions.pop_front();
+                    this.advance();
+                }
+                Poll::Ready(Ok(n))
+            }
+            Some(Action::Pending) => {
+                this.actions.pop_front();
+                this.advance();
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            Some(Action::ReadError(_)) => match this.actions.pop_front() {
+                Some(Action::ReadError(error)) => {
+                    this.advance();
+                    Poll::Ready(Err(error))
+                }
+                _ => unreachable!(),
+            },
+            Some(Action::Write(_)) | Some(Action::WriteError(_)) => {
+                this.read_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl AsyncWrite for Mock {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+        match this.actions.front_mut() {
+            Some(Action::Write(expected)) => {
+                let n = buf.len().min(expected.len());
+                assert_eq!(
+                    &buf[..n],
+                    &expected[..n],
+                    "mock I/O object was written other bytes than expected",
+                );
+                expected.drain(..n);
+                if expected.is_empty() {
+                    this.actions.pop_front();
+                    this.advance();
+                }
+                Poll::Ready(Ok(n))
+            }
+            Some(Action::Pending) => {
+                this.actions.pop_front();
+                this.advance();
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            Some(Action::WriteError(_)) => match this.actions.pop_front() {
+                Some(Action::WriteError(error)) => {
+                    this.advance();
+                    Poll::Ready(Err(error))
+                }
+                _ => unreachable!(),
+            },
+            Some(action) => panic!("mock I/O object was written to while expecting {:?}", action),
+            None => panic!("mock I/O object was written to after the end of its script"),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Drop for Mock {
+    fn drop(&mut self) {
+        if !thread::panicking() && !self.actions.is_empty() {
+            panic!("mock I/O object was dropped before performing {:?}", self.actions);
+        }
+    }
+}
@@ -2,6 +2,9 @@
 
 mod limited;
 
+mod mock;
+pub use mock::{Builder, Mock};
+
 pub mod read;
 pub use read::AsyncReadTestExt;
 
@@ -3,6 +3,7 @@
 use futures_io::AsyncWrite;
 
 pub use super::limited::Limited;
+pub use crate::assert_unmoved::AssertUnmoved;
 pub use crate::interleave_pending::InterleavePending;
 
 /// Additional combinators for testing async writers.
@@ -48,6 +49,38 @@ pub trait AsyncWriteTestExt: AsyncWrite {
         InterleavePending::new(self)
     }
 
+    /// Asserts that the given is not moved after being polled.
+    ///
+    /// A check for movement is performed each time the writer is polled
+    /// and when `Drop` is called.
+    ///
+    /// Aside from keeping track of the location at which the writer was first
+    /// polled and providing assertions, this writer adds no runtime behavior
+    /// and simply delegates to the child writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::io::AsyncWriteExt;
+    /// use futures_test::io::AsyncWriteTestExt;
+    /// use futures::pin_mut;
+    ///
+    /// let writer = Vec::new().assert_unmoved_write();
+    /// pin_mut!(writer);
+    ///
+    /// block_on(writer.write_all(b"hello"))?;
+    /// assert_eq!(writer.get_ref(), b"hello");
+    ///
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn assert_unmoved_write(self) -> AssertUnmoved<Self>
+    where
+        Self: Sized,
+    {
+        AssertUnmoved::new(self)
+    }
+
     /// Limit the number of bytes allowed to be written on each call to `poll_write`.
     ///
     /// # Examples
@@ -20,6 +20,10 @@ pub use std as std_reexport;
 #[cfg(feature = "std")]
 pub extern crate futures_core as futures_core_reexport;
 
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub extern crate futures_sink as futures_sink_reexport;
+
 #[macro_use]
 #[doc(hidden)]
 #[cfg(feature = "std")]
@@ -34,7 +38,15 @@ pub mod future;
 #[cfg(feature = "std")]
 pub mod stream;
 
+#[cfg(feature = "std")]
+pub mod sink;
+
 #[cfg(feature = "std")]
 pub mod io;
 
+#[cfg(feature = "std")]
+pub mod executor;
+
+mod assert_unmoved;
+
 mod interleave_pending;
@@ -0,0 +1,76 @@
+//! Additional combinators for testing sinks.
+
+use futures_sink::Sink;
+
+pub use crate::assert_unmoved::AssertUnmoved;
+pub use crate::interleave_pending::InterleavePending;
+
+/// Additional combinators for testing sinks.
+pub trait SinkTestExt<Item>: Sink<Item> {
+    /// Asserts that the given is not moved after being polled.
+    ///
+    /// A check for movement is performed each time the sink is polled
+    /// and when `Drop` is called.
+    ///
+    /// Aside from keeping track of the location at which the sink was first
+    /// polled and providing assertions, this sink adds no runtime behavior
+    /// and simply delegates to the child sink.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::sink::SinkExt;
+    /// use futures_test::sink::SinkTestExt;
+    /// use futures::pin_mut;
+    ///
+    /// let sink = Vec::new().assert_unmoved_sink();
+    /// pin_mut!(sink);
+    ///
+    /// block_on(sink.send(1)).unwrap();
+    /// assert_eq!(sink.get_ref(), &[1]);
+    /// ```
+    fn assert_unmoved_sink(self) -> AssertUnmoved<Self>
+    where
+        Self: Sized,
+    {
+        AssertUnmoved::new(self)
+    }
+
+    /// Introduces an extra [`Poll::Pending`](futures_core::task::Poll::Pending)
+    /// in between each operation on the sink.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::task::Poll;
+    /// use futures::sink::Sink;
+    /// use futures_test::task::noop_context;
+    /// use futures_test::sink::SinkTestExt;
+    /// use futures::pin_mut;
+    ///
+    /// let sink = Vec::new().interleave_pending_sink();
+    /// pin_mut!(sink);
+    ///
+    /// let mut cx = noop_context();
+    ///
+    /// assert_eq!(sink.as_mut().poll_ready(&mut cx), Poll::Pending);
+    /// assert_eq!(sink.as_mut().poll_ready(&mut cx), Poll::Ready(Ok(())));
+    /// sink.as_mut().start_send(1).unwrap();
+    /// assert_eq!(sink.get_ref(), &[1]);
+    ///
+    /// assert_eq!(sink.as_mut().poll_flush(&mut cx), Poll::Pending);
+    /// assert_eq!(sink.as_mut().poll_flush(&mut cx), Poll::Ready(Ok(())));
+    ///
+    /// assert_eq!(sink.as_mut().poll_close(&mut cx), Poll::Pending);
+    /// assert_eq!(sink.as_mut().poll_close(&mut cx), Poll::Ready(Ok(())));
+    /// ```
+    fn interleave_pending_sink(self) -> InterleavePending<Self>
+    where
+        Self: Sized,
+    {
+        InterleavePending::new(self)
+    }
+}
+
+impl<Si, Item> SinkTestExt<Item> for Si where Si: Sink<Item> {}
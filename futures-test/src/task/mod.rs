@@ -40,7 +40,9 @@
 //! [`panic_waker_ref`](crate::task::panic_waker_ref), [`noop_spawner_mut`](crate::task::noop_spawner_mut) and [`panic_spawner_mut`](crate::task::panic_spawner_mut).
 
 mod context;
-pub use self::context::{noop_context, panic_context};
+pub use self::context::panic_context;
+
+pub use futures_util::task::noop_context;
 
 mod noop_spawner;
 pub use self::noop_spawner::{noop_spawner_mut, NoopSpawner};
@@ -56,5 +58,4 @@ pub use self::panic_waker::{panic_waker, panic_waker_ref};
 mod record_spawner;
 pub use self::record_spawner::RecordSpawner;
 
-mod wake_counter;
-pub use self::wake_counter::{AwokenCount, new_count_waker};
+pub use futures_util::task::{AwokenCount, new_count_waker};
@@ -369,9 +369,11 @@ unsafe impl UnsafeNotify01 for NotifyWaker {
 mod io {
     use super::*;
     use futures_io::{
-        AsyncRead as AsyncRead03, AsyncWrite as AsyncWrite03, Initializer,
+        AsyncBufRead as AsyncBufRead03, AsyncRead as AsyncRead03,
+        AsyncSeek as AsyncSeek03, AsyncWrite as AsyncWrite03, Initializer,
+        IoSlice, IoSliceMut, SeekFrom,
     };
-    use std::io::Error;
+    use std::io::{BufRead, Error, ErrorKind, Seek};
     use tokio_io::{AsyncRead as AsyncRead01, AsyncWrite as AsyncWrite01};
 
     /// Extension trait for tokio-io [`AsyncRead`](tokio_io::AsyncRead)
@@ -379,6 +381,12 @@ mod io {
         /// Converts a tokio-io [`AsyncRead`](tokio_io::AsyncRead) into a futures-io 0.3
         /// [`AsyncRead`](futures_io::AsyncRead).
         ///
+        /// The wrapper keeps every IO trait of the wrapped object: it also
+        /// implements [`AsyncWrite`](futures_io::AsyncWrite),
+        /// [`AsyncBufRead`](futures_io::AsyncBufRead) and
+        /// [`AsyncSeek`](futures_io::AsyncSeek) if the object implements
+        /// tokio-io's `AsyncWrite`, or `std::io`'s `BufRead` and `Seek`.
+        ///
         /// ```
         /// #![feature(impl_trait_in_bindings)]
         /// # #![allow(incomplete_features)]
@@ -432,6 +440,15 @@ mod io {
     }
     impl<W: AsyncWrite01> AsyncWrite01CompatExt for W {}
 
+    // tokio-io objects implement the blocking `std::io` traits, and signal
+    // that they are not ready with a `WouldBlock` error.
+    fn io_to_poll_03<T>(x: Result<T, Error>) -> task03::Poll<Result<T, Error>> {
+        match x {
+            Err(ref e) if e.kind() == ErrorKind::WouldBlock => task03::Poll::Pending,
+            x => task03::Poll::Ready(x),
+        }
+    }
+
     impl<R: AsyncRead01> AsyncRead03 for Compat01As03<R> {
         unsafe fn initializer(&self) -> Initializer {
             // check if `prepare_uninitialized_buffer` needs zeroing
@@ -447,6 +464,38 @@ mod io {
         {
             poll_01_to_03(self.in_notify(cx, |x| x.poll_read(buf)))
         }
+
+        fn poll_read_vectored(mut self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &mut [IoSliceMut<'_>])
+            -> task03::Poll<Result<usize, Error>>
+        {
+            io_to_poll_03(self.in_notify(cx, |x| x.read_vectored(bufs)))
+        }
+    }
+
+    impl<R: AsyncRead01 + BufRead> AsyncBufRead03 for Compat01As03<R> {
+        fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>)
+            -> task03::Poll<Result<&[u8], Error>>
+        {
+            let this = self.get_mut();
+            let buf = ready!(io_to_poll_03(
+                this.in_notify(cx, |x| x.fill_buf().map(|buf| buf as *const [u8]))
+            ))?;
+            // Safety: the buffer is owned by the reader, which stays borrowed
+            // for as long as the returned slice.
+            task03::Poll::Ready(Ok(unsafe { &*buf }))
+        }
+
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            self.get_mut().get_mut().consume(amt)
+        }
+    }
+
+    impl<S: Seek> AsyncSeek03 for Compat01As03<S> {
+        fn poll_seek(mut self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom)
+            -> task03::Poll<Result<u64, Error>>
+        {
+            io_to_poll_03(self.in_notify(cx, |x| x.seek(pos)))
+        }
     }
 
     impl<W: AsyncWrite01> AsyncWrite03 for Compat01As03<W> {
@@ -456,6 +505,16 @@ mod io {
             poll_01_to_03(self.in_notify(cx, |x| x.poll_write(buf)))
         }
 
+        fn poll_write_vectored(mut self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>])
+            -> task03::Poll<Result<usize, Error>>
+        {
+            io_to_poll_03(self.in_notify(cx, |x| x.write_vectored(bufs)))
+        }
+
+        fn is_write_vectored(&self) -> bool {
+            true
+        }
+
         fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>)
             -> task03::Poll<Result<(), Error>>
         {
@@ -152,12 +152,18 @@ where
         item: Self::SinkItem,
     ) -> StartSend01<Self::SinkItem, Self::SinkError> {
         with_sink_context(self, |mut inner, cx| {
-            match inner.as_mut().poll_ready(cx)? {
-                task03::Poll::Ready(()) => {
-                    inner.start_send(item).map(|()| AsyncSink01::Ready)
+            if inner.as_mut().poll_ready(cx)?.is_pending() {
+                // A 0.1 sink has to attempt to complete its outstanding
+                // requests before refusing an item, as callers such as
+                // `Send` only call `poll_complete` once the item is taken.
+                if inner.as_mut().poll_flush(cx)?.is_pending() {
+                    return Ok(AsyncSink01::NotReady(item));
+                }
+                if inner.as_mut().poll_ready(cx)?.is_pending() {
+                    return Ok(AsyncSink01::NotReady(item));
                 }
-                task03::Poll::Pending => Ok(AsyncSink01::NotReady(item)),
             }
+            inner.start_send(item).map(|()| AsyncSink01::Ready)
         })
     }
 
@@ -239,7 +245,11 @@ where
 #[cfg(feature = "io-compat")]
 mod io {
     use super::*;
-    use futures_io::{AsyncRead as AsyncRead03, AsyncWrite as AsyncWrite03};
+    use futures_io::{
+        AsyncBufRead as AsyncBufRead03, AsyncRead as AsyncRead03,
+        AsyncSeek as AsyncSeek03, AsyncWrite as AsyncWrite03, IoSlice,
+        IoSliceMut, SeekFrom,
+    };
     use tokio_io::{AsyncRead as AsyncRead01, AsyncWrite as AsyncWrite01};
 
     fn poll_03_to_io<T>(x: task03::Poll<Result<T, std::io::Error>>)
@@ -259,6 +269,26 @@ mod io {
             let mut cx = Context::from_waker(&waker);
             poll_03_to_io(Pin::new(&mut self.inner).poll_read(&mut cx, buf))
         }
+
+        fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> std::io::Result<usize> {
+            let current = Current::new();
+            let waker = current.as_waker();
+            let mut cx = Context::from_waker(&waker);
+            poll_03_to_io(Pin::new(&mut self.inner).poll_read_vectored(&mut cx, bufs))
+        }
+    }
+
+    impl<R: AsyncBufRead03 + Unpin> std::io::BufRead for Compat<R> {
+        fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+            let current = Current::new();
+            let waker = current.as_waker();
+            let mut cx = Context::from_waker(&waker);
+            poll_03_to_io(Pin::new(&mut self.inner).poll_fill_buf(&mut cx))
+        }
+
+        fn consume(&mut self, amt: usize) {
+            Pin::new(&mut self.inner).consume(amt)
+        }
     }
 
     impl<R: AsyncRead03 + Unpin> AsyncRead01 for Compat<R> {
@@ -280,6 +310,13 @@ mod io {
             poll_03_to_io(Pin::new(&mut self.inner).poll_write(&mut cx, buf))
         }
 
+        fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> std::io::Result<usize> {
+            let current = Current::new();
+            let waker = current.as_waker();
+            let mut cx = Context::from_waker(&waker);
+            poll_03_to_io(Pin::new(&mut self.inner).poll_write_vectored(&mut cx, bufs))
+        }
+
         fn flush(&mut self) -> std::io::Result<()> {
             let current = Current::new();
             let waker = current.as_waker();
@@ -288,6 +325,15 @@ mod io {
         }
     }
 
+    impl<S: AsyncSeek03 + Unpin> std::io::Seek for Compat<S> {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            let current = Current::new();
+            let waker = current.as_waker();
+            let mut cx = Context::from_waker(&waker);
+            poll_03_to_io(Pin::new(&mut self.inner).poll_seek(&mut cx, pos))
+        }
+    }
+
     impl<W: AsyncWrite03 + Unpin> AsyncWrite01 for Compat<W> {
         fn shutdown(&mut self) -> std::io::Result<Async01<()>> {
             let current = Current::new();
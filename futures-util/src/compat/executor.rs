@@ -9,6 +9,8 @@ use futures_01::Future as Future01;
 use futures_01::future::{Executor as Executor01, ExecuteError as ExecuteError01};
 use futures_core::task::{Spawn as Spawn03, SpawnError as SpawnError03};
 use futures_core::future::FutureObj;
+#[cfg(feature = "channel")]
+use crate::future::RemoteHandle;
 
 /// A future that can run on a futures 0.1
 /// [`Executor`](futures_01::future::Executor).
@@ -92,3 +94,49 @@ where
         Ok(())
     }
 }
+
+/// Extension trait for futures 0.3 [`Spawn`](futures_core::task::Spawn),
+/// adding methods for spawning futures 0.1 tasks.
+pub trait SpawnCompatExt: Spawn03 {
+    /// Spawns a task that polls the given futures 0.1 future to completion,
+    /// discarding its result.
+    fn spawn01<Fut>(&mut self, future: Fut) -> Result<(), SpawnError03>
+    where
+        Fut: Future01 + Send + 'static,
+    {
+        self.spawn(future.compat().map(|_| ()))
+    }
+
+    /// Spawns a task that polls the given futures 0.1 future to completion
+    /// and returns a future that resolves to its result.
+    ///
+    /// Dropping the returned [`RemoteHandle`](crate::future::RemoteHandle)
+    /// cancels the task, unless [`forget`](crate::future::RemoteHandle::forget)
+    /// is called on it.
+    ///
+    /// ```
+    /// use futures_util::compat::SpawnCompatExt;
+    /// use futures::executor::{block_on, ThreadPool};
+    ///
+    /// let mut executor = ThreadPool::new().unwrap();
+    ///
+    /// let future01 = futures_01::future::ok::<u32, ()>(1);
+    /// let handle = executor.spawn01_with_handle(future01).unwrap();
+    /// assert_eq!(block_on(handle), Ok(1));
+    /// ```
+    #[cfg(feature = "channel")]
+    #[allow(clippy::type_complexity)]
+    fn spawn01_with_handle<Fut>(
+        &mut self,
+        future: Fut,
+    ) -> Result<RemoteHandle<Result<Fut::Item, Fut::Error>>, SpawnError03>
+    where
+        Fut: Future01 + Send + 'static,
+        Fut::Item: Send,
+        Fut::Error: Send,
+    {
+        self.spawn_with_handle(future.compat())
+    }
+}
+
+impl<Sp: Spawn03 + ?Sized> SpawnCompatExt for Sp {}
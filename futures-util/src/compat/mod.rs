@@ -4,7 +4,7 @@
 //! library is activated.
 
 mod executor;
-pub use self::executor::{Executor01CompatExt, Executor01Future, Executor01As03};
+pub use self::executor::{Executor01CompatExt, Executor01Future, Executor01As03, SpawnCompatExt};
 
 mod compat01as03;
 pub use self::compat01as03::{Compat01As03, Future01CompatExt, Stream01CompatExt};
@@ -13,6 +13,9 @@ pub use self::compat01as03::{Compat01As03Sink, Sink01CompatExt};
 #[cfg(feature = "io-compat")]
 pub use self::compat01as03::{AsyncRead01CompatExt, AsyncWrite01CompatExt};
 
+mod stream01;
+pub use self::stream01::{SplitErrorFuture, SplitItems, UnwrapOrElse};
+
 mod compat03as01;
 pub use self::compat03as01::Compat;
 #[cfg(feature = "sink")]
@@ -0,0 +1,201 @@
+use super::Compat01As03;
+use futures_01::Stream as Stream01;
+use futures_core::future::{FusedFuture, Future};
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll, Waker};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+impl<St: Stream01> Compat01As03<St> {
+    /// Splits the converted stream into a stream of its items and a future
+    /// which resolves to how the stream ended.
+    ///
+    /// The stream half yields the items of the 0.1 stream, and ends at the
+    /// first error, which the future half then resolves to. If the 0.1
+    /// stream ends without an error, the future resolves to `Ok(())`. The
+    /// future also resolves to `Ok(())` if the stream half is dropped before
+    /// the 0.1 stream ends.
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::StreamExt;
+    /// use futures_util::compat::Stream01CompatExt;
+    ///
+    /// let stream = futures_01::stream::iter_result(vec![Ok(1), Ok(2), Err("boom"), Ok(3)]);
+    /// let (items, result) = stream.compat().split_errors();
+    /// assert_eq!(items.collect::<Vec<i32>>().await, vec![1, 2]);
+    /// assert_eq!(result.await, Err("boom"));
+    /// # });
+    /// ```
+    pub fn split_errors(self) -> (SplitItems<St>, SplitErrorFuture<St::Error>) {
+        let shared = Arc::new(Mutex::new(Shared { result: None, waker: None }));
+        let items = SplitItems { stream: Some(self), shared: shared.clone() };
+        (items, SplitErrorFuture { shared: Some(shared) })
+    }
+
+    /// Converts the stream into one which yields the items of the 0.1 stream
+    /// directly, turning each error into an item with `f`.
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::StreamExt;
+    /// use futures_util::compat::Stream01CompatExt;
+    ///
+    /// let stream = futures_01::stream::iter_result(vec![Ok(1), Err(()), Ok(3)]);
+    /// let stream = stream.compat().unwrap_or_else(|()| 0);
+    /// assert_eq!(stream.collect::<Vec<i32>>().await, vec![1, 0, 3]);
+    /// # });
+    /// ```
+    pub fn unwrap_or_else<F>(self, f: F) -> UnwrapOrElse<St, F>
+    where
+        F: FnMut(St::Error) -> St::Item,
+    {
+        UnwrapOrElse { stream: self, f }
+    }
+}
+
+struct Shared<E> {
+    result: Option<Result<(), E>>,
+    waker: Option<Waker>,
+}
+
+/// Stream for the [`split_errors`](Compat01As03::split_errors) method.
+#[must_use = "streams do nothing unless polled"]
+pub struct SplitItems<St: Stream01> {
+    // `None` once the 0.1 stream has ended.
+    stream: Option<Compat01As03<St>>,
+    shared: Arc<Mutex<Shared<St::Error>>>,
+}
+
+impl<St: Stream01> SplitItems<St> {
+    fn finish(&mut self, result: Result<(), St::Error>) {
+        self.stream = None;
+        let waker = {
+            let mut shared = self.shared.lock().unwrap();
+            shared.result = Some(result);
+            shared.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<St: Stream01> Stream for SplitItems<St> {
+    type Item = St::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<St::Item>> {
+        let stream = match self.stream.as_mut() {
+            Some(stream) => stream,
+            None => return Poll::Ready(None),
+        };
+        match ready!(Pin::new(stream).poll_next(cx)) {
+            Some(Ok(item)) => return Poll::Ready(Some(item)),
+            Some(Err(e)) => self.finish(Err(e)),
+            None => self.finish(Ok(())),
+        }
+        Poll::Ready(None)
+    }
+}
+
+impl<St: Stream01> FusedStream for SplitItems<St> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_none()
+    }
+}
+
+impl<St: Stream01> Drop for SplitItems<St> {
+    fn drop(&mut self) {
+        if self.stream.is_some() {
+            self.finish(Ok(()));
+        }
+    }
+}
+
+impl<St: Stream01 + fmt::Debug> fmt::Debug for SplitItems<St> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SplitItems")
+            .field("stream", &self.stream)
+            .finish()
+    }
+}
+
+/// Future for the [`split_errors`](Compat01As03::split_errors) method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SplitErrorFuture<E> {
+    // `None` once the result has been taken.
+    shared: Option<Arc<Mutex<Shared<E>>>>,
+}
+
+impl<E> Future for SplitErrorFuture<E> {
+    type Output = Result<(), E>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), E>> {
+        let result = {
+            let shared = self.shared.as_ref().expect("polled SplitErrorFuture after completion");
+            let mut shared = shared.lock().unwrap();
+            match shared.result.take() {
+                Some(result) => result,
+                None => {
+                    shared.waker = Some(cx.waker().clone());
+                    return Poll::Pending;
+                }
+            }
+        };
+        self.shared = None;
+        Poll::Ready(result)
+    }
+}
+
+impl<E> FusedFuture for SplitErrorFuture<E> {
+    fn is_terminated(&self) -> bool {
+        self.shared.is_none()
+    }
+}
+
+impl<E> fmt::Debug for SplitErrorFuture<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SplitErrorFuture")
+            .field("is_terminated", &self.shared.is_none())
+            .finish()
+    }
+}
+
+/// Stream for the [`unwrap_or_else`](Compat01As03::unwrap_or_else) method.
+#[must_use = "streams do nothing unless polled"]
+pub struct UnwrapOrElse<St, F> {
+    stream: Compat01As03<St>,
+    f: F,
+}
+
+impl<St, F> Unpin for UnwrapOrElse<St, F> {}
+
+impl<St, F> UnwrapOrElse<St, F> {
+    /// Consumes this adapter, returning the converted stream.
+    pub fn into_inner(self) -> Compat01As03<St> {
+        self.stream
+    }
+}
+
+impl<St, F> Stream for UnwrapOrElse<St, F>
+where
+    St: Stream01,
+    F: FnMut(St::Error) -> St::Item,
+{
+    type Item = St::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<St::Item>> {
+        let this = &mut *self;
+        Poll::Ready(ready!(Pin::new(&mut this.stream).poll_next(cx))
+            .map(|res| res.unwrap_or_else(&mut this.f)))
+    }
+}
+
+impl<St: fmt::Debug, F> fmt::Debug for UnwrapOrElse<St, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnwrapOrElse")
+            .field("stream", &self.stream)
+            .finish()
+    }
+}
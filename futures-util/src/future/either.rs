@@ -235,6 +235,13 @@ mod if_std {
             }
         }
 
+        fn is_write_vectored(&self) -> bool {
+            match self {
+                Either::Left(x) => x.is_write_vectored(),
+                Either::Right(x) => x.is_write_vectored(),
+            }
+        }
+
         fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
             unsafe {
                 match self.get_unchecked_mut() {
@@ -89,6 +89,10 @@ impl<T> AsyncWrite for AllowStdIo<T> where T: io::Write {
         Poll::Ready(Ok(try_with_interrupt!(self.0.write_vectored(bufs))))
     }
 
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
     fn poll_flush(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
         try_with_interrupt!(self.0.flush());
         Poll::Ready(Ok(()))
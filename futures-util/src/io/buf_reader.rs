@@ -1,3 +1,4 @@
+use futures_core::future::Future;
 use futures_core::task::{Context, Poll};
 use futures_io::{AsyncBufRead, AsyncRead, AsyncSeek, Initializer, IoSliceMut, SeekFrom};
 use pin_utils::{unsafe_pinned, unsafe_unpinned};
@@ -81,11 +82,23 @@ impl<R: AsyncRead> BufReader<R> {
 
     /// Consumes this `BufWriter`, returning the underlying reader.
     ///
-    /// Note that any leftover data in the internal buffer is lost.
+    /// Note that any leftover data in the internal buffer is lost. Use
+    /// [`into_parts`](BufReader::into_parts) to keep it.
     pub fn into_inner(self) -> R {
         self.inner
     }
 
+    /// Consumes this `BufReader`, returning the underlying reader and any
+    /// data that has been read from it but not yet consumed.
+    ///
+    /// This is useful when handing the reader over to a different protocol,
+    /// for example after an HTTP upgrade, where the returned bytes must be
+    /// processed before anything read from the underlying reader.
+    pub fn into_parts(self) -> (R, Vec<u8>) {
+        let unread = self.buf[self.pos..self.cap].to_vec();
+        (self.inner, unread)
+    }
+
     /// Returns a reference to the internally buffered data.
     ///
     /// Unlike `fill_buf`, this will not attempt to fill the buffer if it is empty.
@@ -93,6 +106,11 @@ impl<R: AsyncRead> BufReader<R> {
         &self.buf[self.pos..self.cap]
     }
 
+    /// Returns the number of bytes the internal buffer can hold at once.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
     /// Invalidates all data in the internal buffer.
     #[inline]
     fn discard_buffer(mut self: Pin<&mut Self>) {
@@ -126,6 +144,68 @@ impl<R: AsyncRead + AsyncSeek> BufReader<R> {
         }
         self.poll_seek(cx, SeekFrom::Current(offset)).map(|res| res.map(|_| ()))
     }
+
+    /// Creates a future which will seek relative to the current position.
+    ///
+    /// This is the async equivalent of std's `BufReader::seek_relative`. If
+    /// the new position lies within the buffer, the buffer is kept and no I/O
+    /// is performed, which makes backing up a few bytes cheap. Otherwise the
+    /// buffer is discarded and the underlying reader is seeked.
+    ///
+    /// The returned future does not resolve to the new position of the
+    /// underlying reader, so the caller must track this information
+    /// themselves if it is required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+    /// use std::io::Cursor;
+    ///
+    /// let mut reader = BufReader::new(Cursor::new(b"header:value"));
+    /// let mut buf = vec![];
+    /// reader.read_until(b':', &mut buf).await?;
+    ///
+    /// // Back up to re-read the delimiter, without touching the cursor.
+    /// reader.seek_relative(-1).await?;
+    /// let mut byte = [0];
+    /// reader.read_exact(&mut byte).await?;
+    /// assert_eq!(byte, *b":");
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    pub fn seek_relative(&mut self, offset: i64) -> SeekRelative<'_, R>
+        where R: Unpin,
+    {
+        SeekRelative { reader: self, offset }
+    }
+}
+
+/// Future for the [`seek_relative`](BufReader::seek_relative) method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SeekRelative<'a, R> {
+    reader: &'a mut BufReader<R>,
+    offset: i64,
+}
+
+impl<R: Unpin> Unpin for SeekRelative<'_, R> {}
+
+impl<R: AsyncRead + fmt::Debug> fmt::Debug for SeekRelative<'_, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SeekRelative")
+            .field("reader", &self.reader)
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> Future for SeekRelative<'_, R> {
+    type Output = io::Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let offset = self.offset;
+        Pin::new(&mut *self.reader).poll_seek_relative(cx, offset)
+    }
 }
 
 impl<R: AsyncRead> AsyncRead for BufReader<R> {
@@ -218,6 +298,7 @@ impl<R: AsyncRead + AsyncSeek> AsyncSeek for BufReader<R> {
     /// at the same position.
     ///
     /// To seek without discarding the internal buffer, use
+    /// [`BufReader::seek_relative`](BufReader::seek_relative) or
     /// [`BufReader::poll_seek_relative`](BufReader::poll_seek_relative).
     ///
     /// See [`AsyncSeek`](futures_io::AsyncSeek) for more details.
@@ -1,7 +1,7 @@
 use futures_core::task::{Context, Poll};
 use futures_io::{AsyncSeek, AsyncWrite, IoSlice, SeekFrom};
 use pin_utils::{unsafe_pinned, unsafe_unpinned};
-use std::fmt;
+use std::{cmp, fmt};
 use std::io::{self, Write};
 use std::pin::Pin;
 use super::DEFAULT_BUF_SIZE;
@@ -52,7 +52,7 @@ impl<W: AsyncWrite> BufWriter<W> {
         }
     }
 
-    fn flush_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    pub(super) fn flush_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         let Self { inner, buf, written } = unsafe { self.get_unchecked_mut() };
         let mut inner = unsafe { Pin::new_unchecked(inner) };
 
@@ -81,6 +81,15 @@ impl<W: AsyncWrite> BufWriter<W> {
         Poll::Ready(ret)
     }
 
+    /// Copies as much of `buf` into the internal buffer as fits without
+    /// growing it, returning the number of bytes copied.
+    pub(super) fn write_to_buf(self: Pin<&mut Self>, buf: &[u8]) -> usize {
+        let this = self.buf();
+        let amt = cmp::min(this.capacity() - this.len(), buf.len());
+        this.extend_from_slice(&buf[..amt]);
+        amt
+    }
+
     /// Gets a reference to the underlying writer.
     pub fn get_ref(&self) -> &W {
         &self.inner
@@ -102,14 +111,31 @@ impl<W: AsyncWrite> BufWriter<W> {
 
     /// Consumes this `BufWriter`, returning the underlying writer.
     ///
-    /// Note that any leftover data in the internal buffer is lost.
+    /// Note that any leftover data in the internal buffer is lost. Use
+    /// [`into_parts`](BufWriter::into_parts) to keep it.
     pub fn into_inner(self) -> W {
         self.inner
     }
 
+    /// Consumes this `BufWriter`, returning the underlying writer and any
+    /// buffered data that has not been written to it yet.
+    ///
+    /// No I/O is performed, so it is up to the caller to write out the
+    /// returned data if it is still needed.
+    pub fn into_parts(mut self) -> (W, Vec<u8>) {
+        self.buf.drain(..self.written);
+        (self.inner, self.buf)
+    }
+
     /// Returns a reference to the internally buffered data.
     pub fn buffer(&self) -> &[u8] {
-        &self.buf
+        &self.buf[self.written..]
+    }
+
+    /// Returns the number of bytes the internal buffer can hold without
+    /// flushing.
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
     }
 }
 
@@ -145,6 +171,10 @@ impl<W: AsyncWrite> AsyncWrite for BufWriter<W> {
         }
     }
 
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         ready!(self.as_mut().flush_buf(cx))?;
         self.inner().poll_flush(cx)
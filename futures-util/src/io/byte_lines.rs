@@ -0,0 +1,85 @@
+use futures_core::stream::Stream;
+use futures_core::task::{Context, Poll};
+use futures_io::AsyncBufRead;
+use std::io;
+use std::mem;
+use std::pin::Pin;
+use super::read_until::read_until_internal;
+
+/// Stream for the [`byte_lines`](super::AsyncBufReadExt::byte_lines) method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ByteLines<R> {
+    reader: R,
+    buf: Vec<u8>,
+    spare: Option<Vec<u8>>,
+    read: usize,
+}
+
+impl<R: Unpin> Unpin for ByteLines<R> {}
+
+impl<R: AsyncBufRead> ByteLines<R> {
+    pub(super) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+            spare: None,
+            read: 0,
+        }
+    }
+
+    /// Hands a line previously yielded by this stream back for reuse.
+    ///
+    /// The next line is read into `buf` instead of a newly allocated buffer,
+    /// so a consumer which is done with each line before asking for the next
+    /// one only allocates when a line is longer than any line before it.
+    pub fn recycle(&mut self, mut buf: Vec<u8>) {
+        buf.clear();
+        self.spare = Some(buf);
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid reading from the underlying reader
+    /// directly, as doing so may corrupt the stream of lines.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Consumes this stream, returning the underlying reader.
+    ///
+    /// Any part of a line which has been read but not yet yielded is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: AsyncBufRead> Stream for ByteLines<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Self { reader, buf, spare, read } = unsafe { self.get_unchecked_mut() };
+        let reader = unsafe { Pin::new_unchecked(reader) };
+        if buf.is_empty() {
+            if let Some(spare) = spare.take() {
+                *buf = spare;
+            }
+        }
+        let n = ready!(read_until_internal(reader, cx, b'\n', buf, read))?;
+        if n == 0 && buf.is_empty() {
+            return Poll::Ready(None)
+        }
+        if buf.ends_with(b"\n") {
+            buf.pop();
+            if buf.ends_with(b"\r") {
+                buf.pop();
+            }
+        }
+        Poll::Ready(Some(Ok(mem::replace(buf, Default::default()))))
+    }
+}
@@ -1,9 +1,10 @@
 use futures_core::task::{Context, Poll};
-use futures_io::{AsyncBufRead, AsyncRead, Initializer, IoSliceMut};
+use futures_io::{AsyncBufRead, AsyncRead, AsyncSeek, Initializer, IoSliceMut, SeekFrom};
 use pin_utils::{unsafe_pinned, unsafe_unpinned};
 use std::fmt;
 use std::io;
 use std::pin::Pin;
+use super::seek_offset;
 
 /// Stream for the [`chain`](super::AsyncReadExt::chain) method.
 #[must_use = "streams do nothing unless polled"]
@@ -11,6 +12,10 @@ pub struct Chain<T, U> {
     first: T,
     second: U,
     done_first: bool,
+    // Length of `first`, learned on the first seek.
+    first_len: Option<u64>,
+    // Resolved current position of an in-progress `SeekFrom::Current` seek.
+    seek_current: Option<(SeekFrom, u64)>,
 }
 
 impl<T, U> Unpin for Chain<T, U>
@@ -34,6 +39,8 @@ where
             first,
             second,
             done_first: false,
+            first_len: None,
+            seek_current: None,
         }
     }
 
@@ -125,6 +132,7 @@ where
             first,
             second,
             done_first,
+            ..
         } = unsafe { self.get_unchecked_mut() };
         let first = unsafe { Pin::new_unchecked(first) };
         let second = unsafe { Pin::new_unchecked(second) };
@@ -148,3 +156,88 @@ where
         }
     }
 }
+
+/// Seeks in the concatenation of both readers.
+///
+/// Positions below the length of the first reader are positions in the
+/// first reader, later positions continue at the start of the second reader.
+/// Both readers are treated as a whole, from their start to their end, no
+/// matter where they were positioned when the `Chain` was created.
+///
+/// The length of the first reader is determined by seeking it to its end on
+/// the first seek and is cached afterwards, so it must not change while the
+/// `Chain` is in use.
+impl<T, U> AsyncSeek for Chain<T, U>
+where
+    T: AsyncRead + AsyncSeek,
+    U: AsyncRead + AsyncSeek,
+{
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let Self {
+            first,
+            second,
+            done_first,
+            first_len,
+            seek_current,
+        } = unsafe { self.get_unchecked_mut() };
+        let mut first = unsafe { Pin::new_unchecked(first) };
+        let mut second = unsafe { Pin::new_unchecked(second) };
+
+        if seek_current.map(|(p, _)| p) != Some(pos) {
+            *seek_current = None;
+        }
+
+        // The current position has to be known before learning the length of
+        // the first reader, as that moves it.
+        if let SeekFrom::Current(_) = pos {
+            if seek_current.is_none() && !*done_first {
+                let current = ready!(first.as_mut().poll_seek(cx, SeekFrom::Current(0)))?;
+                *seek_current = Some((pos, current));
+            }
+        }
+
+        let len = match *first_len {
+            Some(len) => len,
+            None => {
+                let len = ready!(first.as_mut().poll_seek(cx, SeekFrom::End(0)))?;
+                *first_len = Some(len);
+                len
+            }
+        };
+
+        let target = match pos {
+            SeekFrom::Start(n) => n,
+            SeekFrom::Current(n) => {
+                let current = match *seek_current {
+                    Some((_, current)) => current,
+                    None => {
+                        let current = ready!(second.as_mut().poll_seek(cx, SeekFrom::Current(0)))?;
+                        let current = seek_offset(len, current as i64)?;
+                        *seek_current = Some((pos, current));
+                        current
+                    }
+                };
+                seek_offset(current, n)?
+            }
+            SeekFrom::End(n) => {
+                let second_len = ready!(second.as_mut().poll_seek(cx, SeekFrom::End(0)))?;
+                seek_offset(seek_offset(len, second_len as i64)?, n)?
+            }
+        };
+
+        if target < len {
+            ready!(first.poll_seek(cx, SeekFrom::Start(target)))?;
+            ready!(second.poll_seek(cx, SeekFrom::Start(0)))?;
+            *done_first = false;
+        } else {
+            ready!(second.poll_seek(cx, SeekFrom::Start(target - len)))?;
+            *done_first = true;
+        }
+        *seek_current = None;
+        Poll::Ready(Ok(target))
+    }
+}
@@ -0,0 +1,177 @@
+use futures_core::task::{Context, Poll};
+use futures_io::{AsyncBufRead, AsyncRead, IoSliceMut};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+
+/// Reader for the [`chain_all`] function.
+#[must_use = "readers do nothing unless polled"]
+pub struct ChainAll<I: Iterator> {
+    current: Option<I::Item>,
+    readers: I,
+}
+
+impl<I> Unpin for ChainAll<I>
+where
+    I: Iterator,
+    I::Item: Unpin,
+{
+}
+
+/// Creates a reader which reads from each reader in `readers` in turn.
+///
+/// The returned reader reads all bytes from the first reader until EOF is
+/// encountered, then moves on to the next one, and so on until the last
+/// reader has been exhausted. Unlike nesting [`chain`](super::AsyncReadExt::chain)
+/// calls, the type of the returned reader does not depend on the number of
+/// readers, and readers are only taken from the iterator once the previous
+/// one has been exhausted.
+///
+/// # Examples
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::io::{self, AsyncReadExt};
+/// use std::io::Cursor;
+///
+/// let parts = vec![Cursor::new(vec![1, 2]), Cursor::new(vec![]), Cursor::new(vec![3])];
+///
+/// let mut reader = io::chain_all(parts);
+/// let mut buffer = Vec::new();
+/// reader.read_to_end(&mut buffer).await?;
+/// assert_eq!(buffer, [1, 2, 3]);
+/// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+/// ```
+pub fn chain_all<I>(readers: I) -> ChainAll<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: AsyncRead,
+{
+    ChainAll::new(readers.into_iter())
+}
+
+impl<I> ChainAll<I>
+where
+    I: Iterator,
+    I::Item: AsyncRead,
+{
+    unsafe_pinned!(current: Option<I::Item>);
+    unsafe_unpinned!(readers: I);
+
+    fn new(mut readers: I) -> Self {
+        Self {
+            current: readers.next(),
+            readers,
+        }
+    }
+
+    /// Moves on to the next reader, dropping the exhausted one.
+    fn advance(mut self: Pin<&mut Self>) {
+        let next = self.as_mut().readers().next();
+        self.current().set(next);
+    }
+
+    fn current_pin_mut(self: Pin<&mut Self>) -> Option<Pin<&mut I::Item>> {
+        unsafe { self.get_unchecked_mut().current.as_mut().map(|r| Pin::new_unchecked(r)) }
+    }
+
+    /// Consumes the `ChainAll`, returning the reader currently being read
+    /// from, if any, and the iterator over the remaining readers.
+    pub fn into_inner(self) -> (Option<I::Item>, I) {
+        (self.current, self.readers)
+    }
+
+    /// Gets references to the reader currently being read from, if any, and
+    /// to the iterator over the remaining readers.
+    pub fn get_ref(&self) -> (Option<&I::Item>, &I) {
+        (self.current.as_ref(), &self.readers)
+    }
+
+    /// Gets mutable references to the reader currently being read from, if
+    /// any, and to the iterator over the remaining readers.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying readers as doing so may corrupt the internal state of this
+    /// `ChainAll`.
+    pub fn get_mut(&mut self) -> (Option<&mut I::Item>, &mut I) {
+        (self.current.as_mut(), &mut self.readers)
+    }
+}
+
+impl<I> fmt::Debug for ChainAll<I>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChainAll")
+            .field("current", &self.current)
+            .field("readers", &self.readers)
+            .finish()
+    }
+}
+
+impl<I> AsyncRead for ChainAll<I>
+where
+    I: Iterator,
+    I::Item: AsyncRead,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        while let Some(reader) = self.as_mut().current_pin_mut() {
+            match ready!(reader.poll_read(cx, buf)?) {
+                0 if !buf.is_empty() => self.as_mut().advance(),
+                n => return Poll::Ready(Ok(n)),
+            }
+        }
+        Poll::Ready(Ok(0))
+    }
+
+    fn poll_read_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<usize>> {
+        while let Some(reader) = self.as_mut().current_pin_mut() {
+            let n = ready!(reader.poll_read_vectored(cx, bufs)?);
+            if n == 0 && bufs.iter().any(|b| !b.is_empty()) {
+                self.as_mut().advance()
+            } else {
+                return Poll::Ready(Ok(n));
+            }
+        }
+        Poll::Ready(Ok(0))
+    }
+}
+
+impl<I> AsyncBufRead for ChainAll<I>
+where
+    I: Iterator,
+    I::Item: AsyncBufRead,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        while let Some(reader) = &mut this.current {
+            let reader = unsafe { Pin::new_unchecked(reader) };
+            let buf = ready!(reader.poll_fill_buf(cx)?);
+            if !buf.is_empty() {
+                // Safety: `buf` borrows from `this.current`, which is not
+                // touched again before returning. The borrow checker cannot
+                // see that the borrow ends on the other path through the loop.
+                return Poll::Ready(Ok(unsafe { &*(buf as *const [u8]) }));
+            }
+            this.current = this.readers.next();
+        }
+        Poll::Ready(Ok(&[]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        if let Some(reader) = self.current_pin_mut() {
+            reader.consume(amt)
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use futures_core::task::{Context, Poll};
+use futures_io::{AsyncRead, AsyncWrite, Initializer, IoSlice, IoSliceMut};
+use pin_utils::unsafe_pinned;
+use std::cmp;
+use std::io;
+use std::pin::Pin;
+use super::DEFAULT_BUF_SIZE;
+
+/// Writer for the
+/// [`coalesce_vectored`](super::AsyncWriteExt::coalesce_vectored) method.
+#[derive(Debug)]
+#[must_use = "writers do nothing unless polled"]
+pub struct CoalesceVectored<W> {
+    inner: W,
+    // Scratch space to gather small buffers in, only used for the duration
+    // of a single write.
+    buf: Vec<u8>,
+}
+
+impl<W: Unpin> Unpin for CoalesceVectored<W> {}
+
+impl<W: AsyncWrite> CoalesceVectored<W> {
+    unsafe_pinned!(inner: W);
+
+    pub(super) fn new(inner: W) -> Self {
+        Self { inner, buf: Vec::new() }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Gets a pinned mutable reference to the underlying writer.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut W> {
+        self.inner()
+    }
+
+    /// Consumes this writer, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for CoalesceVectored<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.inner().poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        let Self { inner, buf } = unsafe { self.get_unchecked_mut() };
+        let inner = unsafe { Pin::new_unchecked(inner) };
+        if inner.is_write_vectored() {
+            return inner.poll_write_vectored(cx, bufs);
+        }
+
+        let mut non_empty = bufs.iter().filter(|b| !b.is_empty());
+        let first = match non_empty.next() {
+            Some(first) => first,
+            None => return Poll::Ready(Ok(0)),
+        };
+        // Copying only pays off when there is more than one buffer to write
+        // and they fit into the scratch space together.
+        if first.len() >= DEFAULT_BUF_SIZE || non_empty.next().is_none() {
+            return inner.poll_write(cx, first);
+        }
+
+        buf.clear();
+        for slice in bufs {
+            let n = cmp::min(slice.len(), DEFAULT_BUF_SIZE - buf.len());
+            buf.extend_from_slice(&slice[..n]);
+            if buf.len() == DEFAULT_BUF_SIZE {
+                break;
+            }
+        }
+        inner.poll_write(cx, buf)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner().poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner().poll_close(cx)
+    }
+}
+
+impl<W: AsyncWrite + AsyncRead> AsyncRead for CoalesceVectored<W> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.inner().poll_read(cx, buf)
+    }
+
+    fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &mut [IoSliceMut<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        self.inner().poll_read_vectored(cx, bufs)
+    }
+}
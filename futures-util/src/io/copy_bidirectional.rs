@@ -0,0 +1,237 @@
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_io::{AsyncRead, AsyncWrite};
+use std::cmp;
+use std::io;
+use std::pin::Pin;
+use super::DEFAULT_BUF_SIZE;
+
+/// Future for the [`copy_bidirectional`](super::copy_bidirectional) function.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CopyBidirectional<'a, A: ?Sized, B: ?Sized> {
+    a: &'a mut A,
+    b: &'a mut B,
+    a_to_b: TransferState,
+    b_to_a: TransferState,
+}
+
+impl<A: ?Sized, B: ?Sized> Unpin for CopyBidirectional<'_, A, B> {}
+
+/// Copies data in both directions between `a` and `b`.
+///
+/// This function returns a future that reads from `a` and writes the data to
+/// `b`, while concurrently reading from `b` and writing the data to `a`.
+///
+/// When one side reaches EOF, the data read so far is flushed to the other
+/// side, which is then closed (half-close) with
+/// [`poll_close`](futures_io::AsyncWrite::poll_close). The other direction
+/// keeps copying until it reaches EOF as well. The future completes once both
+/// directions are finished, yielding the number of bytes copied from `a` to
+/// `b` and from `b` to `a`, in that order.
+///
+/// If an error occurs in either direction the future resolves to that error
+/// and the bytes copied so far are lost.
+///
+/// # Examples
+///
+/// ```
+/// use futures::io::{self, AsyncRead, AsyncWrite};
+///
+/// async fn proxy<C, S>(mut client: C, mut server: S) -> io::Result<()>
+/// where
+///     C: AsyncRead + AsyncWrite + Unpin,
+///     S: AsyncRead + AsyncWrite + Unpin,
+/// {
+///     let (to_server, to_client) = io::copy_bidirectional(&mut client, &mut server).await?;
+///     println!("client wrote {} bytes and received {} bytes", to_server, to_client);
+///     Ok(())
+/// }
+/// ```
+pub fn copy_bidirectional<'a, A, B>(a: &'a mut A, b: &'a mut B) -> CopyBidirectional<'a, A, B>
+where
+    A: AsyncRead + AsyncWrite + Unpin + ?Sized,
+    B: AsyncRead + AsyncWrite + Unpin + ?Sized,
+{
+    CopyBidirectional {
+        a,
+        b,
+        a_to_b: TransferState::Running(CopyBuffer::new(DEFAULT_BUF_SIZE)),
+        b_to_a: TransferState::Running(CopyBuffer::new(DEFAULT_BUF_SIZE)),
+    }
+}
+
+#[derive(Debug)]
+enum TransferState {
+    Running(CopyBuffer),
+    ShuttingDown(u64),
+    Done(u64),
+}
+
+/// A reusable buffer used to move bytes from a reader to a writer.
+#[derive(Debug)]
+pub(super) struct CopyBuffer {
+    buf: Box<[u8]>,
+    pos: usize,
+    cap: usize,
+    amt: u64,
+    limit: Option<u64>,
+    read_done: bool,
+    // Whether data has been written since the writer was last flushed.
+    need_flush: bool,
+}
+
+impl CopyBuffer {
+    pub(super) fn new(size: usize) -> Self {
+        Self::with_limit(size, None)
+    }
+
+    /// Creates a buffer that stops reading once `limit` bytes have been
+    /// read, if a limit is given.
+    pub(super) fn with_limit(size: usize, limit: Option<u64>) -> Self {
+        assert!(size > 0, "copy buffer size must be non-zero");
+        Self {
+            buf: vec![0; size].into_boxed_slice(),
+            pos: 0,
+            cap: 0,
+            amt: 0,
+            limit,
+            read_done: false,
+            need_flush: false,
+        }
+    }
+
+    /// Copies data from `reader` into `writer` until `reader` reaches EOF and
+    /// everything read so far has been written to and flushed from `writer`.
+    ///
+    /// Resolves to the total number of bytes copied.
+    pub(super) fn poll_copy<R, W>(
+        &mut self,
+        cx: &mut Context<'_>,
+        reader: Pin<&mut R>,
+        writer: Pin<&mut W>,
+    ) -> Poll<io::Result<u64>>
+    where
+        R: AsyncRead + ?Sized,
+        W: AsyncWrite + ?Sized,
+    {
+        self.poll_copy_with(cx, reader, writer, |_| {})
+    }
+
+    /// Like `poll_copy`, but calls `on_write` with the total number of bytes
+    /// copied so far every time the writer accepts some data.
+    pub(super) fn poll_copy_with<R, W, F>(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut reader: Pin<&mut R>,
+        mut writer: Pin<&mut W>,
+        mut on_write: F,
+    ) -> Poll<io::Result<u64>>
+    where
+        R: AsyncRead + ?Sized,
+        W: AsyncWrite + ?Sized,
+        F: FnMut(u64),
+    {
+        loop {
+            // If our buffer is empty, then we need to read some data to
+            // continue.
+            if self.pos == self.cap && !self.read_done {
+                let max = match self.limit {
+                    Some(limit) => cmp::min(limit - self.amt, self.buf.len() as u64) as usize,
+                    None => self.buf.len(),
+                };
+                let n = if max == 0 {
+                    0
+                } else {
+                    match reader.as_mut().poll_read(cx, &mut self.buf[..max]) {
+                        Poll::Ready(res) => res?,
+                        Poll::Pending => {
+                            // Flush what has been written so far before
+                            // waiting for more data, so that it does not sit
+                            // in a buffered writer until the reader is done.
+                            if self.need_flush {
+                                ready!(writer.as_mut().poll_flush(cx))?;
+                                self.need_flush = false;
+                            }
+                            return Poll::Pending;
+                        }
+                    }
+                };
+                if n == 0 {
+                    self.read_done = true;
+                } else {
+                    self.pos = 0;
+                    self.cap = n;
+                }
+            }
+
+            // If our buffer has some data, let's write it out!
+            while self.pos < self.cap {
+                let i = ready!(writer.as_mut().poll_write(cx, &self.buf[self.pos..self.cap]))?;
+                if i == 0 {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "write zero byte into writer",
+                    )));
+                }
+                self.pos += i;
+                self.amt += i as u64;
+                self.need_flush = true;
+                on_write(self.amt);
+            }
+
+            // If we've written all the data and we've seen EOF, flush out the
+            // data and finish the transfer.
+            if self.pos == self.cap && self.read_done {
+                ready!(writer.as_mut().poll_flush(cx))?;
+                self.need_flush = false;
+                return Poll::Ready(Ok(self.amt));
+            }
+        }
+    }
+}
+
+fn transfer_one_direction<R, W>(
+    cx: &mut Context<'_>,
+    state: &mut TransferState,
+    reader: &mut R,
+    writer: &mut W,
+) -> Poll<io::Result<u64>>
+where
+    R: AsyncRead + Unpin + ?Sized,
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    loop {
+        match state {
+            TransferState::Running(buf) => {
+                let count = ready!(buf.poll_copy(cx, Pin::new(&mut *reader), Pin::new(&mut *writer)))?;
+                *state = TransferState::ShuttingDown(count);
+            }
+            TransferState::ShuttingDown(count) => {
+                ready!(Pin::new(&mut *writer).poll_close(cx))?;
+                *state = TransferState::Done(*count);
+            }
+            TransferState::Done(count) => return Poll::Ready(Ok(*count)),
+        }
+    }
+}
+
+impl<A, B> Future for CopyBidirectional<'_, A, B>
+where
+    A: AsyncRead + AsyncWrite + Unpin + ?Sized,
+    B: AsyncRead + AsyncWrite + Unpin + ?Sized,
+{
+    type Output = io::Result<(u64, u64)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self { a, b, a_to_b, b_to_a } = &mut *self;
+
+        let a_to_b = transfer_one_direction(cx, a_to_b, &mut **a, &mut **b)?;
+        let b_to_a = transfer_one_direction(cx, b_to_a, &mut **b, &mut **a)?;
+
+        match (a_to_b, b_to_a) {
+            (Poll::Ready(a_to_b), Poll::Ready(b_to_a)) => Poll::Ready(Ok((a_to_b, b_to_a))),
+            _ => Poll::Pending,
+        }
+    }
+}
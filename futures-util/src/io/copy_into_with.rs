@@ -0,0 +1,128 @@
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_io::{AsyncRead, AsyncWrite};
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use super::DEFAULT_BUF_SIZE;
+use super::copy_bidirectional::CopyBuffer;
+
+/// Options for the [`copy_into_with`](super::AsyncReadExt::copy_into_with)
+/// method.
+///
+/// By default the copy uses an 8 KB buffer, copies until EOF and does not
+/// report progress.
+pub struct CopyOptions {
+    buffer_size: usize,
+    max_bytes: Option<u64>,
+    progress: Option<Box<dyn FnMut(u64) + Send>>,
+}
+
+impl CopyOptions {
+    /// Creates a new set of options with the default configuration.
+    pub fn new() -> Self {
+        Self {
+            buffer_size: DEFAULT_BUF_SIZE,
+            max_bytes: None,
+            progress: None,
+        }
+    }
+
+    /// Sets the size of the intermediate buffer used for the copy.
+    ///
+    /// Larger buffers mean fewer, bigger reads and writes, which usually
+    /// improves throughput for large transfers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        assert!(size > 0, "copy buffer size must be non-zero");
+        self.buffer_size = size;
+        self
+    }
+
+    /// Sets the maximum number of bytes to copy.
+    ///
+    /// Once `max` bytes have been copied the copy finishes as if the reader
+    /// had reached EOF. Bytes past the limit are never read from the reader.
+    pub fn max_bytes(mut self, max: u64) -> Self {
+        self.max_bytes = Some(max);
+        self
+    }
+
+    /// Sets a callback which is invoked with the total number of bytes
+    /// copied so far every time the writer accepts a chunk of data.
+    pub fn progress<F>(mut self, f: F) -> Self
+        where F: FnMut(u64) + Send + 'static,
+    {
+        self.progress = Some(Box::new(f));
+        self
+    }
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for CopyOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CopyOptions")
+            .field("buffer_size", &self.buffer_size)
+            .field("max_bytes", &self.max_bytes)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// Future for the [`copy_into_with`](super::AsyncReadExt::copy_into_with) method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CopyIntoWith<'a, R, W: ?Sized> {
+    reader: R,
+    writer: &'a mut W,
+    buf: CopyBuffer,
+    progress: Option<Box<dyn FnMut(u64) + Send>>,
+}
+
+impl<R: Unpin, W: ?Sized> Unpin for CopyIntoWith<'_, R, W> {}
+
+impl<R: fmt::Debug, W: fmt::Debug + ?Sized> fmt::Debug for CopyIntoWith<'_, R, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CopyIntoWith")
+            .field("reader", &self.reader)
+            .field("writer", &self.writer)
+            .field("buf", &self.buf)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+impl<'a, R: AsyncRead, W: ?Sized> CopyIntoWith<'a, R, W> {
+    pub(super) fn new(reader: R, writer: &'a mut W, options: CopyOptions) -> Self {
+        CopyIntoWith {
+            reader,
+            writer,
+            buf: CopyBuffer::with_limit(options.buffer_size, options.max_bytes),
+            progress: options.progress,
+        }
+    }
+}
+
+impl<R, W> Future for CopyIntoWith<'_, R, W>
+    where R: AsyncRead,
+          W: AsyncWrite + Unpin + ?Sized,
+{
+    type Output = io::Result<u64>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self { reader, writer, buf, progress } = unsafe { self.get_unchecked_mut() };
+        let reader = unsafe { Pin::new_unchecked(reader) };
+        buf.poll_copy_with(cx, reader, Pin::new(&mut **writer), |amt| {
+            if let Some(progress) = progress {
+                progress(amt);
+            }
+        })
+    }
+}
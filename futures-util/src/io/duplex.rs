@@ -0,0 +1,175 @@
+use futures_core::task::{Context, Poll, Waker};
+use futures_io::{AsyncRead, AsyncWrite};
+use std::cmp;
+use std::collections::VecDeque;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Creates a pair of connected in-memory IO objects.
+///
+/// Bytes written to one end of the pair can be read from the other, in both
+/// directions. Each direction has an internal buffer holding up to
+/// `capacity` bytes; writes wait for the other end to read once it is full.
+///
+/// Closing or dropping one end makes reads on the other end return EOF
+/// once the buffered data has been read. Writes to an end whose peer has
+/// been dropped fail with an error of kind
+/// [`BrokenPipe`](io::ErrorKind::BrokenPipe).
+///
+/// This is useful for testing protocol code without sockets.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+///
+/// # Examples
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::io::{self, AsyncReadExt, AsyncWriteExt};
+///
+/// let (mut client, mut server) = io::duplex(64);
+///
+/// client.write_all(b"ping").await?;
+/// let mut buf = [0; 4];
+/// server.read_exact(&mut buf).await?;
+/// assert_eq!(&buf, b"ping");
+///
+/// server.write_all(b"pong").await?;
+/// server.close().await?;
+/// let mut reply = Vec::new();
+/// client.read_to_end(&mut reply).await?;
+/// assert_eq!(reply, b"pong");
+/// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+/// ```
+pub fn duplex(capacity: usize) -> (DuplexStream, DuplexStream) {
+    assert!(capacity > 0, "duplex capacity must be non-zero");
+    let one = Arc::new(Mutex::new(Pipe::new(capacity)));
+    let two = Arc::new(Mutex::new(Pipe::new(capacity)));
+    (
+        DuplexStream { read: one.clone(), write: two.clone() },
+        DuplexStream { read: two, write: one },
+    )
+}
+
+/// One end of an in-memory pipe created by [`duplex`].
+#[derive(Debug)]
+pub struct DuplexStream {
+    read: Arc<Mutex<Pipe>>,
+    write: Arc<Mutex<Pipe>>,
+}
+
+/// A unidirectional bounded byte buffer.
+#[derive(Debug)]
+struct Pipe {
+    buf: VecDeque<u8>,
+    capacity: usize,
+    read_waker: Option<Waker>,
+    write_waker: Option<Waker>,
+    // The writing end has been closed or dropped.
+    write_closed: bool,
+    // The reading end has been dropped.
+    read_closed: bool,
+}
+
+impl Pipe {
+    fn new(capacity: usize) -> Self {
+        Self {
+            buf: VecDeque::with_capacity(capacity),
+            capacity,
+            read_waker: None,
+            write_waker: None,
+            write_closed: false,
+            read_closed: false,
+        }
+    }
+
+    fn close_write(&mut self) {
+        self.write_closed = true;
+        if let Some(waker) = self.read_waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn close_read(&mut self) {
+        self.read_closed = true;
+        if let Some(waker) = self.write_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+fn lock(pipe: &Mutex<Pipe>) -> MutexGuard<'_, Pipe> {
+    // A panic while holding the lock cannot leave the pipe inconsistent.
+    pipe.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl AsyncRead for DuplexStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        let mut pipe = lock(&self.read);
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        if pipe.buf.is_empty() {
+            if pipe.write_closed {
+                return Poll::Ready(Ok(0));
+            }
+            pipe.read_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let n = cmp::min(buf.len(), pipe.buf.len());
+        for (dst, src) in buf.iter_mut().zip(pipe.buf.drain(..n)) {
+            *dst = src;
+        }
+        if let Some(waker) = pipe.write_waker.take() {
+            waker.wake();
+        }
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl AsyncWrite for DuplexStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        let mut pipe = lock(&self.write);
+        if pipe.read_closed || pipe.write_closed {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        }
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let available = pipe.capacity - pipe.buf.len();
+        if available == 0 {
+            pipe.write_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let n = cmp::min(buf.len(), available);
+        pipe.buf.extend(&buf[..n]);
+        if let Some(waker) = pipe.read_waker.take() {
+            waker.wake();
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        lock(&self.write).close_write();
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Drop for DuplexStream {
+    fn drop(&mut self) {
+        lock(&self.write).close_write();
+        lock(&self.read).close_read();
+    }
+}
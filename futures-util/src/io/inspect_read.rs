@@ -0,0 +1,135 @@
+use futures_core::task::{Context, Poll};
+use futures_io::{AsyncRead, AsyncSeek, AsyncWrite, Initializer, IoSlice, IoSliceMut, SeekFrom};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+
+/// Reader for the [`inspect_read`](super::AsyncReadExt::inspect_read) method.
+#[must_use = "readers do nothing unless polled"]
+pub struct InspectRead<R, F> {
+    reader: R,
+    f: F,
+}
+
+impl<R: Unpin, F> Unpin for InspectRead<R, F> {}
+
+impl<R: fmt::Debug, F> fmt::Debug for InspectRead<R, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InspectRead")
+            .field("reader", &self.reader)
+            .finish()
+    }
+}
+
+impl<R, F> InspectRead<R, F> {
+    unsafe_pinned!(reader: R);
+    unsafe_unpinned!(f: F);
+
+    pub(super) fn new(reader: R, f: F) -> Self {
+        Self { reader, f }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid reading from the underlying reader
+    /// directly, as the closure will not see the data read that way.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Gets a pinned mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid reading from the underlying reader
+    /// directly, as the closure will not see the data read that way.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut R> {
+        self.reader()
+    }
+
+    /// Consumes the `InspectRead`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R, F> AsyncRead for InspectRead<R, F>
+where
+    R: AsyncRead,
+    F: FnMut(&[u8]),
+{
+    unsafe fn initializer(&self) -> Initializer {
+        self.reader.initializer()
+    }
+
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let n = ready!(self.as_mut().reader().poll_read(cx, buf))?;
+        if n > 0 {
+            (self.as_mut().f())(&buf[..n]);
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_read_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let n = ready!(self.as_mut().reader().poll_read_vectored(cx, bufs))?;
+        let f = self.as_mut().f();
+        let mut remaining = n;
+        for buf in bufs.iter() {
+            if remaining == 0 {
+                break;
+            }
+            let len = remaining.min(buf.len());
+            if len > 0 {
+                f(&buf[..len]);
+            }
+            remaining -= len;
+        }
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl<R: AsyncWrite, F> AsyncWrite for InspectRead<R, F> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.reader().poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        self.reader().poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.reader.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.reader().poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.reader().poll_close(cx)
+    }
+}
+
+impl<R: AsyncSeek, F> AsyncSeek for InspectRead<R, F> {
+    fn poll_seek(self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom)
+        -> Poll<io::Result<u64>>
+    {
+        self.reader().poll_seek(cx, pos)
+    }
+}
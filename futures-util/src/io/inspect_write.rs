@@ -0,0 +1,136 @@
+use futures_core::task::{Context, Poll};
+use futures_io::{AsyncRead, AsyncSeek, AsyncWrite, Initializer, IoSlice, IoSliceMut, SeekFrom};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+
+/// Writer for the [`inspect_write`](super::AsyncWriteExt::inspect_write)
+/// method.
+#[must_use = "writers do nothing unless polled"]
+pub struct InspectWrite<W, F> {
+    writer: W,
+    f: F,
+}
+
+impl<W: Unpin, F> Unpin for InspectWrite<W, F> {}
+
+impl<W: fmt::Debug, F> fmt::Debug for InspectWrite<W, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InspectWrite")
+            .field("writer", &self.writer)
+            .finish()
+    }
+}
+
+impl<W, F> InspectWrite<W, F> {
+    unsafe_pinned!(writer: W);
+    unsafe_unpinned!(f: F);
+
+    pub(super) fn new(writer: W, f: F) -> Self {
+        Self { writer, f }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Care should be taken to avoid writing to the underlying writer
+    /// directly, as the closure will not see the data written that way.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Gets a pinned mutable reference to the underlying writer.
+    ///
+    /// Care should be taken to avoid writing to the underlying writer
+    /// directly, as the closure will not see the data written that way.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut W> {
+        self.writer()
+    }
+
+    /// Consumes the `InspectWrite`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W, F> AsyncWrite for InspectWrite<W, F>
+where
+    W: AsyncWrite,
+    F: FnMut(&[u8]),
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let n = ready!(self.as_mut().writer().poll_write(cx, buf))?;
+        if n > 0 {
+            (self.as_mut().f())(&buf[..n]);
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let n = ready!(self.as_mut().writer().poll_write_vectored(cx, bufs))?;
+        let f = self.as_mut().f();
+        let mut remaining = n;
+        for buf in bufs {
+            if remaining == 0 {
+                break;
+            }
+            let len = remaining.min(buf.len());
+            if len > 0 {
+                f(&buf[..len]);
+            }
+            remaining -= len;
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.writer.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.writer().poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.writer().poll_close(cx)
+    }
+}
+
+impl<W: AsyncRead, F> AsyncRead for InspectWrite<W, F> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.writer.initializer()
+    }
+
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.writer().poll_read(cx, buf)
+    }
+
+    fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &mut [IoSliceMut<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        self.writer().poll_read_vectored(cx, bufs)
+    }
+}
+
+impl<W: AsyncSeek, F> AsyncSeek for InspectWrite<W, F> {
+    fn poll_seek(self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom)
+        -> Poll<io::Result<u64>>
+    {
+        self.writer().poll_seek(cx, pos)
+    }
+}
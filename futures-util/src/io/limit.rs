@@ -0,0 +1,126 @@
+use futures_core::task::{Context, Poll};
+use futures_io::{AsyncWrite, IoSlice};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::cmp;
+use std::io;
+use std::pin::Pin;
+use super::LimitExceeded;
+
+/// Writer for the [`limit`](super::AsyncWriteExt::limit) method.
+#[derive(Debug)]
+#[must_use = "writers do nothing unless polled"]
+pub struct Limit<W> {
+    inner: W,
+    limit: u64,
+    written: u64,
+}
+
+impl<W: Unpin> Unpin for Limit<W> {}
+
+impl<W: AsyncWrite> Limit<W> {
+    unsafe_pinned!(inner: W);
+    unsafe_unpinned!(written: u64);
+
+    pub(super) fn new(inner: W, limit: u64) -> Self {
+        Self { inner, limit, written: 0 }
+    }
+
+    /// Returns the total number of bytes that may be written.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Sets the total number of bytes that may be written, including those
+    /// which have already been written.
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = limit
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.written
+    }
+
+    /// Returns the number of bytes that can still be written before writes
+    /// start to fail.
+    pub fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.written)
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer are not counted.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Gets a pinned mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer are not counted.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut W> {
+        self.inner()
+    }
+
+    /// Consumes the `Limit`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn limit_exceeded(&self) -> io::Error {
+        LimitExceeded::new(cmp::min(self.limit, usize::max_value() as u64) as usize).into()
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for Limit<W> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let remaining = self.remaining();
+        if remaining == 0 {
+            return Poll::Ready(Err(self.limit_exceeded()));
+        }
+        let max = cmp::min(buf.len() as u64, remaining) as usize;
+        let n = ready!(self.as_mut().inner().poll_write(cx, &buf[..max]))?;
+        *self.written() += n as u64;
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let total = bufs.iter().map(|b| b.len() as u64).sum::<u64>();
+        if total > self.remaining() {
+            // Trimming the slices would need an allocation, so fall back to
+            // writing the first non-empty buffer.
+            let buf = bufs.iter().find(|b| !b.is_empty()).map_or(&[][..], |b| &**b);
+            return self.poll_write(cx, buf);
+        }
+        let n = ready!(self.as_mut().inner().poll_write_vectored(cx, bufs))?;
+        *self.written() += n as u64;
+        Poll::Ready(Ok(n))
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner().poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner().poll_close(cx)
+    }
+}
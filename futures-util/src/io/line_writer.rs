@@ -0,0 +1,132 @@
+use futures_core::task::{Context, Poll};
+use futures_io::AsyncWrite;
+use pin_utils::unsafe_pinned;
+use std::io;
+use std::pin::Pin;
+use super::BufWriter;
+
+/// Wraps a writer and buffers output to it, flushing whenever a newline
+/// (`0x0a`, `'\n'`) is written.
+///
+/// This is the async equivalent of [`std::io::LineWriter`]. Like
+/// [`BufWriter`], it batches small writes, but it also makes sure that every
+/// complete line reaches the underlying writer as soon as it has been
+/// written. This keeps latency low for interactive or log-style output while
+/// avoiding a write to the underlying writer for every fragment of a line.
+///
+/// Data after the last newline of a write is buffered until the next newline
+/// is written, the buffer fills up, or the `LineWriter` is flushed or closed.
+/// When the `LineWriter` is dropped, the contents of its buffer will be
+/// discarded.
+///
+/// # Examples
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::io::{AsyncWriteExt, LineWriter};
+///
+/// let mut writer = LineWriter::new(Vec::new());
+///
+/// writer.write_all(b"partial").await?;
+/// assert_eq!(writer.get_ref(), b"");
+///
+/// writer.write_all(b" line\nnext").await?;
+/// assert_eq!(writer.get_ref(), b"partial line\n");
+///
+/// writer.flush().await?;
+/// assert_eq!(writer.get_ref(), b"partial line\nnext");
+/// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct LineWriter<W: AsyncWrite> {
+    inner: BufWriter<W>,
+}
+
+impl<W: AsyncWrite> LineWriter<W> {
+    unsafe_pinned!(inner: BufWriter<W>);
+
+    /// Creates a new `LineWriter` with a default buffer capacity. The default
+    /// is currently 1 KB, but may change in the future.
+    pub fn new(inner: W) -> Self {
+        Self::with_capacity(1024, inner)
+    }
+
+    /// Creates a new `LineWriter` with the specified buffer capacity.
+    pub fn with_capacity(cap: usize, inner: W) -> Self {
+        Self { inner: BufWriter::with_capacity(cap, inner) }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.get_mut()
+    }
+
+    /// Gets a pinned mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut W> {
+        self.inner().get_pin_mut()
+    }
+
+    /// Consumes this `LineWriter`, returning the underlying writer.
+    ///
+    /// Note that any leftover data in the internal buffer is lost.
+    pub fn into_inner(self) -> W {
+        self.inner.into_inner()
+    }
+
+    /// Returns a reference to the internally buffered data.
+    pub fn buffer(&self) -> &[u8] {
+        self.inner.buffer()
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for LineWriter<W> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let newline_idx = match memchr::memrchr(b'\n', buf) {
+            Some(i) => i,
+            None => {
+                // No new line is completed by this write, but a previously
+                // completed one may still be sitting in the buffer.
+                if self.inner.buffer().last() == Some(&b'\n') {
+                    ready!(self.as_mut().inner().flush_buf(cx))?;
+                }
+                return self.inner().poll_write(cx, buf);
+            }
+        };
+
+        // Write out everything up to and including the last newline, going
+        // straight to the underlying writer once the buffer is empty.
+        ready!(self.as_mut().inner().flush_buf(cx))?;
+        let lines = &buf[..=newline_idx];
+        let flushed = ready!(self.as_mut().inner().get_pin_mut().poll_write(cx, lines))?;
+        if flushed < lines.len() {
+            return Poll::Ready(Ok(flushed));
+        }
+
+        // The remainder is an incomplete line, so buffer as much of it as
+        // fits. Any I/O here would risk losing track of the bytes written
+        // above if it failed.
+        let buffered = self.inner().write_to_buf(&buf[flushed..]);
+        Poll::Ready(Ok(flushed + buffered))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner().poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner().poll_close(cx)
+    }
+}
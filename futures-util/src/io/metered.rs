@@ -0,0 +1,324 @@
+use futures_core::task::{Context, Poll};
+use futures_io::{
+    AsyncBufRead, AsyncRead, AsyncSeek, AsyncWrite, Initializer, IoSlice, IoSliceMut, SeekFrom,
+};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Default)]
+struct Counters {
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+    reads: AtomicU64,
+    writes: AtomicU64,
+    read_pending_nanos: AtomicU64,
+    write_pending_nanos: AtomicU64,
+}
+
+/// Measures the time an operation spends pending, from the first
+/// `Poll::Pending` until it completes.
+#[derive(Debug, Default)]
+struct PendingTimer {
+    since: Option<Instant>,
+}
+
+impl PendingTimer {
+    fn observe<T>(&mut self, res: &Poll<T>, total_nanos: &AtomicU64) {
+        match (res.is_ready(), self.since) {
+            (false, None) => self.since = Some(Instant::now()),
+            (true, Some(since)) => {
+                self.since = None;
+                let nanos = since.elapsed().as_nanos() as u64;
+                total_nanos.fetch_add(nanos, Ordering::Relaxed);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// I/O object which counts the bytes and operations passing through it, and
+/// the time spent waiting for the underlying object.
+///
+/// The counters can be read at any time, also from other threads, through
+/// a [`Meter`] obtained from [`meter`](Metered::meter). Updating them costs a
+/// few relaxed atomic operations per call, plus reading the clock when an
+/// operation starts or stops waiting.
+///
+/// Only successful operations are counted. Time spent pending is measured
+/// from the first time an operation returns `Poll::Pending` until it
+/// completes; flushing and closing count towards the writing side.
+///
+/// # Examples
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::io::{AsyncReadExt, AsyncWriteExt, Metered};
+/// use std::io::Cursor;
+///
+/// let mut io = Metered::new(Cursor::new([1, 2, 3, 0, 0]));
+/// let meter = io.meter();
+///
+/// let mut buf = [0; 3];
+/// io.read_exact(&mut buf).await?;
+/// io.write_all(&[4, 5]).await?;
+///
+/// let snapshot = meter.snapshot();
+/// assert_eq!(snapshot.bytes_read(), 3);
+/// assert_eq!(snapshot.bytes_written(), 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Metered<T> {
+    inner: T,
+    counters: Arc<Counters>,
+    read_timer: PendingTimer,
+    write_timer: PendingTimer,
+}
+
+impl<T: Unpin> Unpin for Metered<T> {}
+
+impl<T> Metered<T> {
+    unsafe_pinned!(inner: T);
+    unsafe_unpinned!(read_timer: PendingTimer);
+    unsafe_unpinned!(write_timer: PendingTimer);
+
+    /// Creates a new `Metered` wrapping `inner`, with all counters at zero.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            counters: Arc::default(),
+            read_timer: PendingTimer::default(),
+            write_timer: PendingTimer::default(),
+        }
+    }
+
+    /// Returns a handle for reading the counters of this object.
+    ///
+    /// The handle stays valid after this object has been dropped.
+    pub fn meter(&self) -> Meter {
+        Meter { counters: self.counters.clone() }
+    }
+
+    /// Gets a reference to the underlying I/O object.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying I/O object.
+    ///
+    /// Operations performed directly on the I/O object are not counted.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Gets a pinned mutable reference to the underlying I/O object.
+    ///
+    /// Operations performed directly on the I/O object are not counted.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut T> {
+        self.inner()
+    }
+
+    /// Consumes this `Metered`, returning the underlying I/O object.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    fn record_read(mut self: Pin<&mut Self>, res: Poll<io::Result<usize>>) -> Poll<io::Result<usize>> {
+        let counters = self.counters.clone();
+        self.as_mut().read_timer().observe(&res, &counters.read_pending_nanos);
+        if let Poll::Ready(Ok(n)) = res {
+            counters.bytes_read.fetch_add(n as u64, Ordering::Relaxed);
+            counters.reads.fetch_add(1, Ordering::Relaxed);
+        }
+        res
+    }
+
+    fn record_write(mut self: Pin<&mut Self>, res: Poll<io::Result<usize>>) -> Poll<io::Result<usize>> {
+        let counters = self.counters.clone();
+        self.as_mut().write_timer().observe(&res, &counters.write_pending_nanos);
+        if let Poll::Ready(Ok(n)) = res {
+            counters.bytes_written.fetch_add(n as u64, Ordering::Relaxed);
+            counters.writes.fetch_add(1, Ordering::Relaxed);
+        }
+        res
+    }
+}
+
+impl<T: AsyncRead> AsyncRead for Metered<T> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        let res = self.as_mut().inner().poll_read(cx, buf);
+        self.record_read(res)
+    }
+
+    fn poll_read_vectored(mut self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &mut [IoSliceMut<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        let res = self.as_mut().inner().poll_read_vectored(cx, bufs);
+        self.record_read(res)
+    }
+}
+
+/// Bytes are counted as read once they are consumed.
+impl<T: AsyncBufRead> AsyncBufRead for Metered<T> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let Self { inner, counters, read_timer, .. } = unsafe { self.get_unchecked_mut() };
+        let inner = unsafe { Pin::new_unchecked(inner) };
+        let res = inner.poll_fill_buf(cx);
+        read_timer.observe(&res, &counters.read_pending_nanos);
+        if let Poll::Ready(Ok(_)) = res {
+            counters.reads.fetch_add(1, Ordering::Relaxed);
+        }
+        res
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.counters.bytes_read.fetch_add(amt as u64, Ordering::Relaxed);
+        self.inner().consume(amt)
+    }
+}
+
+impl<T: AsyncWrite> AsyncWrite for Metered<T> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        let res = self.as_mut().inner().poll_write(cx, buf);
+        self.record_write(res)
+    }
+
+    fn poll_write_vectored(mut self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        let res = self.as_mut().inner().poll_write_vectored(cx, bufs);
+        self.record_write(res)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let res = self.as_mut().inner().poll_flush(cx);
+        let counters = self.counters.clone();
+        self.write_timer().observe(&res, &counters.write_pending_nanos);
+        res
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let res = self.as_mut().inner().poll_close(cx);
+        let counters = self.counters.clone();
+        self.write_timer().observe(&res, &counters.write_pending_nanos);
+        res
+    }
+}
+
+impl<T: AsyncSeek> AsyncSeek for Metered<T> {
+    fn poll_seek(self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom)
+        -> Poll<io::Result<u64>>
+    {
+        self.inner().poll_seek(cx, pos)
+    }
+}
+
+/// A handle to the counters of a [`Metered`] I/O object.
+///
+/// Handles are cheap to clone and can be sent to other threads, for example
+/// to a task that periodically reports statistics.
+#[derive(Debug, Clone)]
+pub struct Meter {
+    counters: Arc<Counters>,
+}
+
+impl Meter {
+    /// Returns the current values of the counters.
+    ///
+    /// The counters are read individually, so a snapshot taken while the I/O
+    /// object is in use may be slightly inconsistent.
+    pub fn snapshot(&self) -> MeterSnapshot {
+        let c = &self.counters;
+        MeterSnapshot {
+            bytes_read: c.bytes_read.load(Ordering::Relaxed),
+            bytes_written: c.bytes_written.load(Ordering::Relaxed),
+            reads: c.reads.load(Ordering::Relaxed),
+            writes: c.writes.load(Ordering::Relaxed),
+            read_pending: Duration::from_nanos(c.read_pending_nanos.load(Ordering::Relaxed)),
+            write_pending: Duration::from_nanos(c.write_pending_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// The counters of a [`Metered`] I/O object at one point in time, returned by
+/// [`Meter::snapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MeterSnapshot {
+    bytes_read: u64,
+    bytes_written: u64,
+    reads: u64,
+    writes: u64,
+    read_pending: Duration,
+    write_pending: Duration,
+}
+
+impl MeterSnapshot {
+    /// Returns the number of bytes read.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Returns the number of bytes written.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Returns the number of completed read operations.
+    pub fn reads(&self) -> u64 {
+        self.reads
+    }
+
+    /// Returns the number of completed write operations.
+    pub fn writes(&self) -> u64 {
+        self.writes
+    }
+
+    /// Returns the total time read operations spent waiting for the
+    /// underlying object.
+    pub fn read_pending(&self) -> Duration {
+        self.read_pending
+    }
+
+    /// Returns the total time write, flush and close operations spent
+    /// waiting for the underlying object.
+    pub fn write_pending(&self) -> Duration {
+        self.write_pending
+    }
+
+    /// Returns the average read throughput over `elapsed`, in bytes per
+    /// second.
+    pub fn read_throughput(&self, elapsed: Duration) -> f64 {
+        throughput(self.bytes_read, elapsed)
+    }
+
+    /// Returns the average write throughput over `elapsed`, in bytes per
+    /// second.
+    pub fn write_throughput(&self, elapsed: Duration) -> f64 {
+        throughput(self.bytes_written, elapsed)
+    }
+}
+
+fn throughput(bytes: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) / 1e9;
+    if secs == 0.0 {
+        0.0
+    } else {
+        bytes as f64 / secs
+    }
+}
@@ -10,8 +10,8 @@
 //! library is activated, and it is activated by default.
 
 pub use futures_io::{
-    AsyncRead, AsyncWrite, AsyncSeek, AsyncBufRead, Error, ErrorKind,
-    IoSlice, IoSliceMut, Result, SeekFrom,
+    AsyncRead, AsyncWrite, AsyncSeek, AsyncBufRead, AsyncReadAt, AsyncWriteAt,
+    Error, ErrorKind, IoSlice, IoSliceMut, Result, SeekFrom,
 };
 
 #[cfg(feature = "io-compat")] use crate::compat::Compat;
@@ -20,80 +20,186 @@ pub use futures_io::{
 // https://github.com/rust-lang/rust/blob/master/src/libstd/sys_common/io.rs#L1
 const DEFAULT_BUF_SIZE: usize = 8 * 1024;
 
+// Applies a relative seek `offset` to the position `base`, as used by the
+// `AsyncSeek` impls of adapters which compute positions themselves.
+fn seek_offset(base: u64, offset: i64) -> Result<u64> {
+    let pos = if offset >= 0 {
+        base.checked_add(offset as u64)
+    } else {
+        base.checked_sub(offset.wrapping_neg() as u64)
+    };
+    pos.ok_or_else(|| Error::new(
+        ErrorKind::InvalidInput,
+        "invalid seek to a negative or overflowing position",
+    ))
+}
+
 mod allow_std;
 pub use self::allow_std::AllowStdIo;
 
 mod buf_reader;
-pub use self::buf_reader::BufReader;
+pub use self::buf_reader::{BufReader, SeekRelative};
 
 mod buf_writer;
 pub use self::buf_writer::BufWriter;
 
+mod byte_lines;
+pub use self::byte_lines::ByteLines;
+
 mod chain;
 pub use self::chain::Chain;
 
+mod chain_all;
+pub use self::chain_all::{chain_all, ChainAll};
+
 mod close;
 pub use self::close::Close;
 
+mod coalesce_vectored;
+pub use self::coalesce_vectored::CoalesceVectored;
+
+mod copy_bidirectional;
+pub use self::copy_bidirectional::{copy_bidirectional, CopyBidirectional};
+
 mod copy_into;
 pub use self::copy_into::CopyInto;
 
+mod copy_into_with;
+pub use self::copy_into_with::{CopyIntoWith, CopyOptions};
+
 mod copy_buf_into;
 pub use self::copy_buf_into::CopyBufInto;
 
+mod duplex;
+pub use self::duplex::{duplex, DuplexStream};
+
 mod flush;
 pub use self::flush::Flush;
 
+mod inspect_read;
+pub use self::inspect_read::InspectRead;
+
+mod inspect_write;
+pub use self::inspect_write::InspectWrite;
+
 #[cfg(feature = "sink")]
 mod into_sink;
 #[cfg(feature = "sink")]
 pub use self::into_sink::IntoSink;
 
+mod limit;
+pub use self::limit::Limit;
+
+mod line_writer;
+pub use self::line_writer::LineWriter;
+
 mod lines;
 pub use self::lines::Lines;
 
+// The counters of the meter need 64-bit atomics.
+#[cfg_attr(feature = "cfg-target-has-atomic", cfg(target_has_atomic = "64"))]
+mod metered;
+#[cfg_attr(feature = "cfg-target-has-atomic", cfg(target_has_atomic = "64"))]
+pub use self::metered::{Meter, MeterSnapshot, Metered};
+
 mod read;
 pub use self::read::Read;
 
+mod read_at;
+pub use self::read_at::ReadAt;
+
 mod read_vectored;
 pub use self::read_vectored::ReadVectored;
 
 mod read_exact;
 pub use self::read_exact::ReadExact;
 
+mod read_exact_or_eof;
+pub use self::read_exact_or_eof::ReadExactOrEof;
+
+mod read_exact_at;
+pub use self::read_exact_at::ReadExactAt;
+
 mod read_line;
 pub use self::read_line::ReadLine;
 
+mod read_line_limited;
+pub use self::read_line_limited::ReadLineLimited;
+
 mod read_to_end;
 pub use self::read_to_end::ReadToEnd;
 
+mod read_to_end_with;
+pub use self::read_to_end_with::{ReadToEndOptions, ReadToEndWith};
+
 mod read_to_string;
 pub use self::read_to_string::ReadToString;
 
 mod read_until;
 pub use self::read_until::ReadUntil;
 
+mod read_until_any;
+pub use self::read_until_any::ReadUntilAny;
+
+mod read_until_limited;
+pub use self::read_until_limited::{LimitExceeded, ReadUntilLimited};
+
+mod reader_stream;
+pub use self::reader_stream::ReaderStream;
+
+mod rewind;
+pub use self::rewind::Rewind;
+
 mod seek;
 pub use self::seek::Seek;
 
+mod segmented_buf;
+pub use self::segmented_buf::SegmentedBuf;
+
 mod split;
-pub use self::split::{ReadHalf, WriteHalf};
+pub use self::split::{ReadHalf, ReuniteError, WriteHalf};
+
+mod split_by;
+pub use self::split_by::SplitBy;
 
 mod take;
 pub use self::take::Take;
 
+mod tee;
+pub use self::tee::Tee;
+
+mod timeout;
+pub use self::timeout::{TimeoutReader, TimeoutWriter};
+
+#[cfg(feature = "tokio-io-compat")]
+mod tokio_compat;
+#[cfg(feature = "tokio-io-compat")]
+pub use self::tokio_compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt, TokioCompat};
+
+mod unblock;
+pub use self::unblock::{Unblock, UnblockIntoInner};
+
 mod window;
 pub use self::window::Window;
 
 mod write;
 pub use self::write::Write;
 
+mod write_at;
+pub use self::write_at::WriteAt;
+
 mod write_vectored;
 pub use self::write_vectored::WriteVectored;
 
 mod write_all;
 pub use self::write_all::WriteAll;
 
+mod write_all_at;
+pub use self::write_all_at::WriteAllAt;
+
+mod write_all_vectored;
+pub use self::write_all_vectored::WriteAllVectored;
+
 /// An extension trait which adds utility methods to `AsyncRead` types.
 pub trait AsyncReadExt: AsyncRead {
     /// Creates an adaptor which will chain this stream with another.
@@ -162,6 +268,48 @@ pub trait AsyncReadExt: AsyncRead {
         CopyInto::new(self, writer)
     }
 
+    /// Creates a future which copies bytes from one object to another,
+    /// configured by the given [`CopyOptions`].
+    ///
+    /// This behaves like [`copy_into`](AsyncReadExt::copy_into), except that
+    /// the size of the intermediate buffer, the maximum number of bytes to
+    /// copy and a progress callback can be specified.
+    ///
+    /// On success the number of bytes copied is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::{AsyncReadExt, CopyOptions};
+    /// use std::io::Cursor;
+    /// use std::sync::{Arc, atomic::{AtomicU64, Ordering}};
+    ///
+    /// let reader = Cursor::new([1, 2, 3, 4, 5, 6]);
+    /// let mut writer = Vec::new();
+    ///
+    /// let copied = Arc::new(AtomicU64::new(0));
+    /// let copied2 = copied.clone();
+    /// let options = CopyOptions::new()
+    ///     .buffer_size(2)
+    ///     .max_bytes(5)
+    ///     .progress(move |n| copied2.store(n, Ordering::SeqCst));
+    ///
+    /// let bytes = reader.copy_into_with(&mut writer, options).await?;
+    ///
+    /// assert_eq!(bytes, 5);
+    /// assert_eq!(writer, [1, 2, 3, 4, 5]);
+    /// assert_eq!(copied.load(Ordering::SeqCst), 5);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn copy_into_with<W>(self, writer: &mut W, options: CopyOptions) -> CopyIntoWith<'_, Self, W>
+    where
+        Self: Sized,
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        CopyIntoWith::new(self, writer, options)
+    }
+
     /// Tries to read some bytes directly into the given `buf` in asynchronous
     /// manner, returning a future type.
     ///
@@ -252,6 +400,43 @@ pub trait AsyncReadExt: AsyncRead {
         ReadExact::new(self, buf)
     }
 
+    /// Creates a future which will read exactly enough bytes to fill `buf`,
+    /// stopping early without an error if end of file (EOF) is hit.
+    ///
+    /// The returned future resolves to the number of bytes read. This is
+    /// `buf.len()` unless EOF was reached first, and `0` if the reader was
+    /// already at EOF. Unlike [`read_exact`](AsyncReadExt::read_exact), this
+    /// lets a reader of fixed-size frames tell a clean EOF between frames
+    /// apart from a truncated frame without peeking ahead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::AsyncReadExt;
+    /// use std::io::Cursor;
+    ///
+    /// let mut reader = Cursor::new([1, 2, 3, 4, 5]);
+    /// let mut frame = [0; 2];
+    ///
+    /// assert_eq!(reader.read_exact_or_eof(&mut frame).await?, 2);
+    /// assert_eq!(reader.read_exact_or_eof(&mut frame).await?, 2);
+    /// // A truncated frame.
+    /// assert_eq!(reader.read_exact_or_eof(&mut frame).await?, 1);
+    /// assert_eq!(frame[..1], [5]);
+    /// // EOF on a frame boundary.
+    /// assert_eq!(reader.read_exact_or_eof(&mut frame).await?, 0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn read_exact_or_eof<'a>(
+        &'a mut self,
+        buf: &'a mut [u8],
+    ) -> ReadExactOrEof<'a, Self>
+        where Self: Unpin,
+    {
+        ReadExactOrEof::new(self, buf)
+    }
+
     /// Creates a future which will read all the bytes from this `AsyncRead`.
     ///
     /// On success the total number of bytes read is returned.
@@ -281,6 +466,51 @@ pub trait AsyncReadExt: AsyncRead {
         ReadToEnd::new(self, buf)
     }
 
+    /// Creates a future which will read all the bytes from this `AsyncRead`,
+    /// configured by the given [`ReadToEndOptions`].
+    ///
+    /// The options can reserve capacity in `buf` up front and put a hard
+    /// cap on the number of bytes read, which protects against unbounded
+    /// memory growth when reading from untrusted peers. If the cap is
+    /// exceeded the future fails with an error of kind
+    /// [`InvalidData`](ErrorKind::InvalidData); `buf` then holds the
+    /// first `max_bytes` bytes and the reader is left at an unspecified
+    /// position.
+    ///
+    /// On success the total number of bytes read is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::{AsyncReadExt, ReadToEndOptions};
+    /// use std::io::{Cursor, ErrorKind};
+    ///
+    /// let options = ReadToEndOptions::new().size_hint(4).max_bytes(4);
+    ///
+    /// let mut output = Vec::new();
+    /// let bytes = Cursor::new([1, 2, 3, 4]).read_to_end_with(&mut output, options).await?;
+    /// assert_eq!(bytes, 4);
+    /// assert_eq!(output, [1, 2, 3, 4]);
+    ///
+    /// let mut output = Vec::new();
+    /// let err = Cursor::new([1, 2, 3, 4, 5])
+    ///     .read_to_end_with(&mut output, options)
+    ///     .await
+    ///     .unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::InvalidData);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn read_to_end_with<'a>(
+        &'a mut self,
+        buf: &'a mut Vec<u8>,
+        options: ReadToEndOptions,
+    ) -> ReadToEndWith<'a, Self>
+        where Self: Unpin,
+    {
+        ReadToEndWith::new(self, buf, options)
+    }
+
     /// Creates a future which will read all the bytes from this `AsyncRead`.
     ///
     /// On success the total number of bytes read is returned.
@@ -372,10 +602,112 @@ pub trait AsyncReadExt: AsyncRead {
         Take::new(self, limit)
     }
 
+    /// Creates an AsyncRead adapter which copies all bytes read from this
+    /// reader into `writer` as a side effect.
+    ///
+    /// This is useful for capturing a transcript of, or computing a checksum
+    /// over, data while it is being processed. Reading pauses while the
+    /// writer cannot keep up, and errors from the writer are returned from
+    /// the read calls. All data is written and the writer is flushed by the
+    /// time the returned reader reports EOF.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::AsyncReadExt;
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(&b"hello"[..]);
+    /// let mut tee = reader.tee(Vec::new());
+    ///
+    /// let mut buffer = String::new();
+    /// tee.read_to_string(&mut buffer).await?;
+    ///
+    /// let (_, transcript) = tee.into_inner();
+    /// assert_eq!(buffer, "hello");
+    /// assert_eq!(transcript, b"hello");
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn tee<W>(self, writer: W) -> Tee<Self, W>
+        where Self: Sized,
+              W: AsyncWrite,
+    {
+        Tee::new(self, writer)
+    }
+
+    /// Calls a closure with every chunk of data read from this reader.
+    ///
+    /// The closure sees each buffer filled by a successful read, in order,
+    /// before the data is handed to the caller. This makes it easy to log or
+    /// hash data as it goes over the wire.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::AsyncReadExt;
+    /// use std::io::Cursor;
+    ///
+    /// let mut seen = Vec::new();
+    /// let mut reader = Cursor::new(&b"hello"[..]).inspect_read(|buf| seen.extend_from_slice(buf));
+    ///
+    /// let mut output = String::new();
+    /// reader.read_to_string(&mut output).await?;
+    /// drop(reader);
+    ///
+    /// assert_eq!(seen, b"hello");
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn inspect_read<F>(self, f: F) -> InspectRead<Self, F>
+        where F: FnMut(&[u8]),
+              Self: Sized,
+    {
+        InspectRead::new(self, f)
+    }
+
+    /// Converts this reader into a stream of chunks of at most `chunk_size`
+    /// bytes.
+    ///
+    /// This is the inverse of
+    /// [`TryStreamExt::into_async_read`](crate::try_stream::TryStreamExt::into_async_read).
+    /// Every successful read which returns data yields a chunk, and the stream
+    /// ends once the reader reaches EOF. Errors are yielded as they occur and
+    /// do not end the stream.
+    ///
+    /// Each chunk is a freshly allocated buffer, unless chunks that are no
+    /// longer needed are handed back with
+    /// [`ReaderStream::recycle`](ReaderStream::recycle).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::AsyncReadExt;
+    /// use futures::stream::TryStreamExt;
+    /// use std::io::Cursor;
+    ///
+    /// let reader = Cursor::new(&b"hello world"[..]);
+    /// let chunks: Vec<Vec<u8>> = reader.into_stream(4).try_collect().await?;
+    /// assert_eq!(chunks, [&b"hell"[..], b"o wo", b"rld"]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn into_stream(self, chunk_size: usize) -> ReaderStream<Self>
+        where Self: Sized,
+    {
+        ReaderStream::new(self, chunk_size)
+    }
+
     /// Wraps an [`AsyncRead`] in a compatibility wrapper that allows it to be
     /// used as a futures 0.1 / tokio-io 0.1 `AsyncRead`. If the wrapped type
     /// implements [`AsyncWrite`] as well, the result will also implement the
-    /// futures 0.1 / tokio 0.1 `AsyncWrite` trait.
+    /// futures 0.1 / tokio 0.1 `AsyncWrite` trait. Likewise, it implements
+    /// `std::io::BufRead` and `std::io::Seek` if the wrapped type implements
+    /// [`AsyncBufRead`] and [`AsyncSeek`].
     ///
     /// Requires the `io-compat` feature to enable.
     #[cfg(feature = "io-compat")]
@@ -384,6 +716,19 @@ pub trait AsyncReadExt: AsyncRead {
     {
         Compat::new(self)
     }
+
+    /// Wraps an [`AsyncRead`] in a compatibility wrapper that allows it to be
+    /// used as a tokio 1.x `AsyncRead`. If the wrapped type implements
+    /// [`AsyncBufRead`] or [`AsyncWrite`] as well, the result will also
+    /// implement tokio's `AsyncBufRead` or `AsyncWrite`.
+    ///
+    /// Requires the `tokio-io-compat` feature to enable.
+    #[cfg(feature = "tokio-io-compat")]
+    fn compat_tokio(self) -> TokioCompat<Self>
+        where Self: Sized,
+    {
+        TokioCompat::new(self)
+    }
 }
 
 impl<R: AsyncRead + ?Sized> AsyncReadExt for R {}
@@ -473,6 +818,152 @@ pub trait AsyncWriteExt: AsyncWrite {
         WriteAll::new(self, buf)
     }
 
+    /// Attempts to write multiple buffers into this writer.
+    ///
+    /// Creates a future that will write the entire contents of `bufs` into this
+    /// `AsyncWrite` using [vectored writes].
+    ///
+    /// The returned future will not complete until all the data has been
+    /// written.
+    ///
+    /// [vectored writes]: futures_io::AsyncWrite::poll_write_vectored
+    ///
+    /// # Notes
+    ///
+    /// Unlike `io::Write::write_vectored`, this takes a *mutable* reference to
+    /// a slice of `IoSlice`s, not an immutable one. That's because we need to
+    /// modify the slice to keep track of the bytes already written.
+    ///
+    /// Once this futures returns, the contents of `bufs` are unspecified, as
+    /// this depends on how many calls to `write_vectored` were necessary. It is
+    /// best to understand this function as taking ownership of `bufs` and to
+    /// not use `bufs` afterwards. The underlying buffers, to which the
+    /// `IoSlice`s point (but not the `IoSlice`s themselves), are unchanged and
+    /// can be reused.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::{AsyncWriteExt, IoSlice};
+    /// use std::io::Cursor;
+    ///
+    /// let mut writer = Cursor::new([0u8; 7]);
+    /// let bufs = &mut [
+    ///     IoSlice::new(&[1]),
+    ///     IoSlice::new(&[2, 3]),
+    ///     IoSlice::new(&[4, 5, 6]),
+    /// ];
+    ///
+    /// writer.write_all_vectored(bufs).await?;
+    /// // Note: the contents of `bufs` is now unspecified, see the Notes section.
+    ///
+    /// assert_eq!(writer.into_inner(), [1, 2, 3, 4, 5, 6, 0]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn write_all_vectored<'a>(
+        &'a mut self,
+        bufs: &'a mut [IoSlice<'a>],
+    ) -> WriteAllVectored<'a, Self>
+        where Self: Unpin,
+    {
+        WriteAllVectored::new(self, bufs)
+    }
+
+    /// Creates an AsyncWrite adapter which will write at most `limit` bytes
+    /// to the underlying writer.
+    ///
+    /// This is the write side equivalent of
+    /// [`take`](AsyncReadExt::take), useful for enforcing a quota on
+    /// uploads. Writes are shortened so that they do not cross the limit,
+    /// and once it has been reached, writing fails with an error of kind
+    /// [`InvalidData`](std::io::ErrorKind::InvalidData) wrapping a
+    /// [`LimitExceeded`]. The number of bytes written so far is available
+    /// from [`Limit::bytes_written`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::{AsyncWriteExt, LimitExceeded};
+    ///
+    /// let mut writer = Vec::new().limit(4);
+    ///
+    /// writer.write_all(b"abc").await?;
+    /// assert_eq!(writer.bytes_written(), 3);
+    ///
+    /// let err = writer.write_all(b"def").await.unwrap_err();
+    /// assert!(err.get_ref().unwrap().is::<LimitExceeded>());
+    /// assert_eq!(writer.into_inner(), b"abcd");
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn limit(self, limit: u64) -> Limit<Self>
+        where Self: Sized,
+    {
+        Limit::new(self, limit)
+    }
+
+    /// Coalesces the buffers of vectored writes to this writer if it does
+    /// not support vectored writes itself.
+    ///
+    /// Writers which don't override
+    /// [`poll_write_vectored`](AsyncWrite::poll_write_vectored) only write
+    /// the first buffer on every call, so writing many small buffers takes
+    /// one write each. If [`is_write_vectored`](AsyncWrite::is_write_vectored)
+    /// returns `false`, the returned writer copies small buffers into an
+    /// internal buffer and hands them to the writer in a single write
+    /// instead. Writers which support vectored writes are passed through
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::{AsyncWriteExt, IoSlice};
+    /// use std::io::Cursor;
+    ///
+    /// let mut writer = Cursor::new([0u8; 8]).coalesce_vectored();
+    /// let bufs = [IoSlice::new(b"ab"), IoSlice::new(b"cd"), IoSlice::new(b"ef")];
+    ///
+    /// assert_eq!(writer.write_vectored(&bufs).await?, 6);
+    /// assert_eq!(&writer.into_inner().into_inner()[..6], b"abcdef");
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn coalesce_vectored(self) -> CoalesceVectored<Self>
+        where Self: Sized,
+    {
+        CoalesceVectored::new(self)
+    }
+
+    /// Calls a closure with every chunk of data written to this writer.
+    ///
+    /// The closure sees the part of each buffer that the underlying writer
+    /// accepted, in order, after the write succeeds. This makes it easy to
+    /// log or hash data as it goes over the wire.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::AsyncWriteExt;
+    /// use std::io::Cursor;
+    ///
+    /// let mut written = 0;
+    /// let mut writer = Cursor::new([0u8; 8]).inspect_write(|buf| written += buf.len());
+    ///
+    /// writer.write_all(b"hello").await?;
+    /// drop(writer);
+    ///
+    /// assert_eq!(written, 5);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn inspect_write<F>(self, f: F) -> InspectWrite<Self, F>
+        where F: FnMut(&[u8]),
+              Self: Sized,
+    {
+        InspectWrite::new(self, f)
+    }
+
     /// Wraps an [`AsyncWrite`] in a compatibility wrapper that allows it to be
     /// used as a futures 0.1 / tokio-io 0.1 `AsyncWrite`.
     /// Requires the `io-compat` feature to enable.
@@ -483,6 +974,16 @@ pub trait AsyncWriteExt: AsyncWrite {
         Compat::new(self)
     }
 
+    /// Wraps an [`AsyncWrite`] in a compatibility wrapper that allows it to be
+    /// used as a tokio 1.x `AsyncWrite`.
+    /// Requires the `tokio-io-compat` feature to enable.
+    #[cfg(feature = "tokio-io-compat")]
+    fn compat_tokio_write(self) -> TokioCompat<Self>
+        where Self: Sized,
+    {
+        TokioCompat::new(self)
+    }
+
 
     /// Allow using an [`AsyncWrite`] as a [`Sink`](futures_sink::Sink)`<Item: AsRef<[u8]>>`.
     ///
@@ -532,6 +1033,59 @@ pub trait AsyncSeekExt: AsyncSeek {
     {
         Seek::new(self, pos)
     }
+
+    /// Creates a future which will return the current seek position from the
+    /// start of the stream.
+    ///
+    /// This is equivalent to `self.seek(SeekFrom::Current(0))`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::{AsyncReadExt, AsyncSeekExt};
+    /// use std::io::Cursor;
+    ///
+    /// let mut reader = Cursor::new([1, 2, 3, 4]);
+    /// let mut buf = [0; 3];
+    /// reader.read_exact(&mut buf).await?;
+    ///
+    /// assert_eq!(reader.stream_position().await?, 3);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn stream_position(&mut self) -> Seek<'_, Self>
+        where Self: Unpin,
+    {
+        Seek::new(self, SeekFrom::Current(0))
+    }
+
+    /// Creates a future which will rewind to the beginning of the stream.
+    ///
+    /// This is equivalent to `self.seek(SeekFrom::Start(0))`, discarding the
+    /// returned position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::{AsyncReadExt, AsyncSeekExt};
+    /// use std::io::Cursor;
+    ///
+    /// let mut reader = Cursor::new([1, 2, 3, 4]);
+    /// let mut first = Vec::new();
+    /// reader.read_to_end(&mut first).await?;
+    ///
+    /// reader.rewind().await?;
+    /// let mut second = Vec::new();
+    /// reader.read_to_end(&mut second).await?;
+    /// assert_eq!(first, second);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn rewind(&mut self) -> Rewind<'_, Self>
+        where Self: Unpin,
+    {
+        Rewind::new(self)
+    }
 }
 
 impl<S: AsyncSeek + ?Sized> AsyncSeekExt for S {}
@@ -624,6 +1178,92 @@ pub trait AsyncBufReadExt: AsyncBufRead {
         ReadUntil::new(self, byte, buf)
     }
 
+    /// Creates a future which will read bytes into `buf` until the delimiter
+    /// `byte` or EOF is reached, reading at most `limit` bytes.
+    ///
+    /// This behaves like [`read_until`](AsyncBufReadExt::read_until), but
+    /// guards against unbounded buffering when the input is untrusted, for
+    /// example when parsing data from the network. The delimiter counts
+    /// towards the limit.
+    ///
+    /// # Errors
+    ///
+    /// If `limit` bytes have been read without finding the delimiter and the
+    /// stream is not at EOF, the future resolves to an error of kind
+    /// [`InvalidData`](std::io::ErrorKind::InvalidData) wrapping a
+    /// [`LimitExceeded`]. In that case the `limit` bytes read so far have been
+    /// appended to `buf` and consumed from the reader. Other errors have the
+    /// same semantics as for [`read_until`](AsyncBufReadExt::read_until).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::{AsyncBufReadExt, LimitExceeded};
+    /// use std::io::Cursor;
+    ///
+    /// let mut cursor = Cursor::new(b"lorem-ipsum");
+    /// let mut buf = vec![];
+    ///
+    /// let num_bytes = cursor.read_until_limited(b'-', 6, &mut buf).await?;
+    /// assert_eq!(num_bytes, 6);
+    /// assert_eq!(buf, b"lorem-");
+    /// buf.clear();
+    ///
+    /// let err = cursor.read_until_limited(b'-', 3, &mut buf).await.unwrap_err();
+    /// assert!(err.get_ref().unwrap().is::<LimitExceeded>());
+    /// assert_eq!(buf, b"ips");
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn read_until_limited<'a>(
+        &'a mut self,
+        byte: u8,
+        limit: usize,
+        buf: &'a mut Vec<u8>,
+    ) -> ReadUntilLimited<'a, Self>
+        where Self: Unpin,
+    {
+        ReadUntilLimited::new(self, byte, limit, buf)
+    }
+
+    /// Creates a future which will read bytes into `buf` until any of the
+    /// bytes in `delimiters` or EOF is reached.
+    ///
+    /// This behaves like [`read_until`](AsyncBufReadExt::read_until), except
+    /// that it stops at the first occurrence of any of the given delimiters.
+    /// The delimiter that was found, if any, is the last byte appended to
+    /// `buf`. If `delimiters` is empty the whole stream is read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::AsyncBufReadExt;
+    /// use std::io::Cursor;
+    ///
+    /// let mut cursor = Cursor::new(b"key=value;next");
+    /// let mut buf = vec![];
+    ///
+    /// let num_bytes = cursor.read_until_any(b"=;", &mut buf).await?;
+    /// assert_eq!(num_bytes, 4);
+    /// assert_eq!(buf, b"key=");
+    /// buf.clear();
+    ///
+    /// let num_bytes = cursor.read_until_any(b"=;", &mut buf).await?;
+    /// assert_eq!(num_bytes, 6);
+    /// assert_eq!(buf, b"value;");
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn read_until_any<'a>(
+        &'a mut self,
+        delimiters: &'a [u8],
+        buf: &'a mut Vec<u8>,
+    ) -> ReadUntilAny<'a, Self>
+        where Self: Unpin,
+    {
+        ReadUntilAny::new(self, delimiters, buf)
+    }
+
     /// Creates a future which will read all the bytes associated with this I/O
     /// object into `buf` until a newline (the 0xA byte) or EOF is reached,
     /// This method is the async equivalent to [`BufRead::read_line`](std::io::BufRead::read_line).
@@ -682,6 +1322,47 @@ pub trait AsyncBufReadExt: AsyncBufRead {
         ReadLine::new(self, buf)
     }
 
+    /// Creates a future which will read a line into `buf`, reading at most
+    /// `limit` bytes.
+    ///
+    /// This behaves like [`read_line`](AsyncBufReadExt::read_line), but
+    /// guards against unbounded buffering when the input is untrusted. The
+    /// trailing newline counts towards the limit.
+    ///
+    /// # Errors
+    ///
+    /// If `limit` bytes have been read without finding a newline and the
+    /// stream is not at EOF, the future resolves to an error of kind
+    /// [`InvalidData`](std::io::ErrorKind::InvalidData) wrapping a
+    /// [`LimitExceeded`]. Other errors, including the handling of invalid
+    /// UTF-8, have the same semantics as for
+    /// [`read_line`](AsyncBufReadExt::read_line).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::{AsyncBufReadExt, LimitExceeded};
+    /// use std::io::Cursor;
+    ///
+    /// let mut cursor = Cursor::new(b"foo\na very long line\n");
+    /// let mut buf = String::new();
+    ///
+    /// let num_bytes = cursor.read_line_limited(8, &mut buf).await?;
+    /// assert_eq!(num_bytes, 4);
+    /// assert_eq!(buf, "foo\n");
+    /// buf.clear();
+    ///
+    /// let err = cursor.read_line_limited(8, &mut buf).await.unwrap_err();
+    /// assert!(err.get_ref().unwrap().is::<LimitExceeded>());
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn read_line_limited<'a>(&'a mut self, limit: usize, buf: &'a mut String) -> ReadLineLimited<'a, Self>
+        where Self: Unpin,
+    {
+        ReadLineLimited::new(self, limit, buf)
+    }
+
     /// Returns a stream over the lines of this reader.
     /// This method is the async equivalent to [`BufRead::lines`](std::io::BufRead::lines).
     ///
@@ -720,6 +1401,190 @@ pub trait AsyncBufReadExt: AsyncBufRead {
     {
         Lines::new(self)
     }
+
+    /// Returns a stream over the lines of this reader as raw bytes.
+    ///
+    /// This is like [`lines`](AsyncBufReadExt::lines), but it does not
+    /// require the data to be valid UTF-8. The stream yields instances of
+    /// [`io::Result`]`<`[`Vec<u8>`]`>`, none of which have a trailing newline
+    /// (the `0xA` byte) or a carriage return followed by a newline.
+    ///
+    /// Each line is a freshly allocated buffer, unless lines that are no
+    /// longer needed are handed back with
+    /// [`ByteLines::recycle`](ByteLines::recycle).
+    ///
+    /// [`io::Result`]: std::io::Result
+    /// [`Vec<u8>`]: std::vec::Vec
+    ///
+    /// # Errors
+    ///
+    /// Each line of the stream has the same error semantics as
+    /// [`AsyncBufReadExt::read_until`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::AsyncBufReadExt;
+    /// use futures::stream::StreamExt;
+    /// use std::io::Cursor;
+    ///
+    /// let cursor = Cursor::new(b"lorem\nip\xffsum\r\ndolor");
+    ///
+    /// let mut lines_stream = cursor.byte_lines().map(|l| l.unwrap());
+    /// assert_eq!(lines_stream.next().await, Some(b"lorem".to_vec()));
+    /// assert_eq!(lines_stream.next().await, Some(b"ip\xffsum".to_vec()));
+    /// assert_eq!(lines_stream.next().await, Some(b"dolor".to_vec()));
+    /// assert_eq!(lines_stream.next().await, None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn byte_lines(self) -> ByteLines<Self>
+        where Self: Sized,
+    {
+        ByteLines::new(self)
+    }
+
+    /// Returns a stream over the contents of this reader split on the byte
+    /// sequence `delimiter`.
+    ///
+    /// This is similar to [`BufRead::split`](std::io::BufRead::split), but the
+    /// delimiter may be longer than a single byte, such as `b"\r\n"`. The
+    /// stream yields instances of [`io::Result`]`<`[`Vec<u8>`]`>`, none of
+    /// which have the delimiter at the end. A delimiter split across two reads
+    /// of the underlying reader is still recognized.
+    ///
+    /// [`io::Result`]: std::io::Result
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delimiter` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::AsyncBufReadExt;
+    /// use futures::stream::StreamExt;
+    /// use std::io::Cursor;
+    ///
+    /// let cursor = Cursor::new(b"GET / HTTP/1.1\r\nHost: a\nb\r\n\r\n");
+    ///
+    /// let mut parts = cursor.split_by(b"\r\n").map(|l| l.unwrap());
+    /// assert_eq!(parts.next().await, Some(b"GET / HTTP/1.1".to_vec()));
+    /// assert_eq!(parts.next().await, Some(b"Host: a\nb".to_vec()));
+    /// assert_eq!(parts.next().await, Some(b"".to_vec()));
+    /// assert_eq!(parts.next().await, None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn split_by<D>(self, delimiter: D) -> SplitBy<Self>
+        where Self: Sized,
+              D: AsRef<[u8]>,
+    {
+        SplitBy::new(self, delimiter.as_ref())
+    }
 }
 
 impl<R: AsyncBufRead + ?Sized> AsyncBufReadExt for R {}
+
+/// An extension trait which adds utility methods to `AsyncReadAt` types.
+pub trait AsyncReadAtExt: AsyncReadAt {
+    /// Tries to read some bytes starting at `offset` into `buf`.
+    ///
+    /// The returned future will resolve to the number of bytes read once the
+    /// read operation is completed. Any cursor position of the object is not
+    /// used and left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::AsyncReadAtExt;
+    ///
+    /// let mut data = vec![1, 2, 3, 4];
+    /// let mut buf = [0u8; 3];
+    ///
+    /// let n = data.read_at(&mut buf, 2).await?;
+    /// assert_eq!(n, 2);
+    /// assert_eq!(buf[..n], [3, 4]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn read_at<'a>(&'a mut self, buf: &'a mut [u8], offset: u64) -> ReadAt<'a, Self>
+        where Self: Unpin,
+    {
+        ReadAt::new(self, buf, offset)
+    }
+
+    /// Creates a future which will read exactly enough bytes starting at
+    /// `offset` to fill `buf`, returning an error if the end of the data is
+    /// reached before.
+    ///
+    /// The returned future will resolve once the read operation is completed.
+    /// In the case of an error the buffer and the object will be discarded,
+    /// with the error yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::{self, AsyncReadAtExt};
+    ///
+    /// let mut data = vec![1, 2, 3, 4];
+    /// let mut buf = [0u8; 2];
+    ///
+    /// data.read_exact_at(&mut buf, 1).await?;
+    /// assert_eq!(buf, [2, 3]);
+    ///
+    /// let result = data.read_exact_at(&mut buf, 3).await;
+    /// assert_eq!(result.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn read_exact_at<'a>(&'a mut self, buf: &'a mut [u8], offset: u64) -> ReadExactAt<'a, Self>
+        where Self: Unpin,
+    {
+        ReadExactAt::new(self, buf, offset)
+    }
+}
+
+impl<R: AsyncReadAt + ?Sized> AsyncReadAtExt for R {}
+
+/// An extension trait which adds utility methods to `AsyncWriteAt` types.
+pub trait AsyncWriteAtExt: AsyncWriteAt {
+    /// Tries to write some bytes from `buf` starting at `offset`.
+    ///
+    /// The returned future will resolve to the number of bytes written once
+    /// the write operation is completed. Any cursor position of the object is
+    /// not used and left unchanged.
+    fn write_at<'a>(&'a mut self, buf: &'a [u8], offset: u64) -> WriteAt<'a, Self>
+        where Self: Unpin,
+    {
+        WriteAt::new(self, buf, offset)
+    }
+
+    /// Creates a future which will write all of `buf` starting at `offset`.
+    ///
+    /// The returned future will not complete until all the data has been
+    /// written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::io::AsyncWriteAtExt;
+    ///
+    /// let mut data = vec![1, 2, 3, 4];
+    ///
+    /// data.write_all_at(&[5, 6], 1).await?;
+    /// assert_eq!(data, [1, 5, 6, 4]);
+    ///
+    /// data.write_all_at(&[7], 5).await?;
+    /// assert_eq!(data, [1, 5, 6, 4, 0, 7]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    fn write_all_at<'a>(&'a mut self, buf: &'a [u8], offset: u64) -> WriteAllAt<'a, Self>
+        where Self: Unpin,
+    {
+        WriteAllAt::new(self, buf, offset)
+    }
+}
+
+impl<W: AsyncWriteAt + ?Sized> AsyncWriteAtExt for W {}
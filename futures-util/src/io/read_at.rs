@@ -0,0 +1,31 @@
+use crate::io::AsyncReadAt;
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use std::io;
+use std::pin::Pin;
+
+/// Future for the [`read_at`](super::AsyncReadAtExt::read_at) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadAt<'a, R: ?Sized + Unpin> {
+    reader: &'a mut R,
+    buf: &'a mut [u8],
+    offset: u64,
+}
+
+impl<R: ?Sized + Unpin> Unpin for ReadAt<'_, R> {}
+
+impl<'a, R: AsyncReadAt + ?Sized + Unpin> ReadAt<'a, R> {
+    pub(super) fn new(reader: &'a mut R, buf: &'a mut [u8], offset: u64) -> Self {
+        ReadAt { reader, buf, offset }
+    }
+}
+
+impl<R: AsyncReadAt + ?Sized + Unpin> Future for ReadAt<'_, R> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        Pin::new(&mut this.reader).poll_read_at(cx, this.buf, this.offset)
+    }
+}
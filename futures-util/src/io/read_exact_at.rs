@@ -0,0 +1,43 @@
+use crate::io::AsyncReadAt;
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use std::io;
+use std::mem;
+use std::pin::Pin;
+
+/// Future for the [`read_exact_at`](super::AsyncReadAtExt::read_exact_at) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadExactAt<'a, R: ?Sized + Unpin> {
+    reader: &'a mut R,
+    buf: &'a mut [u8],
+    offset: u64,
+}
+
+impl<R: ?Sized + Unpin> Unpin for ReadExactAt<'_, R> {}
+
+impl<'a, R: AsyncReadAt + ?Sized + Unpin> ReadExactAt<'a, R> {
+    pub(super) fn new(reader: &'a mut R, buf: &'a mut [u8], offset: u64) -> Self {
+        ReadExactAt { reader, buf, offset }
+    }
+}
+
+impl<R: AsyncReadAt + ?Sized + Unpin> Future for ReadExactAt<'_, R> {
+    type Output = io::Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        while !this.buf.is_empty() {
+            let n = ready!(Pin::new(&mut this.reader).poll_read_at(cx, this.buf, this.offset))?;
+            {
+                let (_, rest) = mem::replace(&mut this.buf, &mut []).split_at_mut(n);
+                this.buf = rest;
+            }
+            this.offset += n as u64;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::UnexpectedEof.into()))
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
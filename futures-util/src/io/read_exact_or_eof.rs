@@ -0,0 +1,39 @@
+use crate::io::AsyncRead;
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use std::io;
+use std::pin::Pin;
+
+/// Future for the [`read_exact_or_eof`](super::AsyncReadExt::read_exact_or_eof)
+/// method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadExactOrEof<'a, R: ?Sized + Unpin> {
+    reader: &'a mut R,
+    buf: &'a mut [u8],
+    filled: usize,
+}
+
+impl<R: ?Sized + Unpin> Unpin for ReadExactOrEof<'_, R> {}
+
+impl<'a, R: AsyncRead + ?Sized + Unpin> ReadExactOrEof<'a, R> {
+    pub(super) fn new(reader: &'a mut R, buf: &'a mut [u8]) -> Self {
+        ReadExactOrEof { reader, buf, filled: 0 }
+    }
+}
+
+impl<R: AsyncRead + ?Sized + Unpin> Future for ReadExactOrEof<'_, R> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        while this.filled < this.buf.len() {
+            let n = ready!(Pin::new(&mut this.reader).poll_read(cx, &mut this.buf[this.filled..]))?;
+            if n == 0 {
+                break;
+            }
+            this.filled += n;
+        }
+        Poll::Ready(Ok(this.filled))
+    }
+}
@@ -0,0 +1,42 @@
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_io::AsyncBufRead;
+use std::io;
+use std::mem;
+use std::pin::Pin;
+use super::read_line::read_line_limited_internal;
+
+/// Future for the
+/// [`read_line_limited`](super::AsyncBufReadExt::read_line_limited) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadLineLimited<'a, R: ?Sized + Unpin> {
+    reader: &'a mut R,
+    limit: usize,
+    buf: &'a mut String,
+    bytes: Vec<u8>,
+    read: usize,
+}
+
+impl<R: ?Sized + Unpin> Unpin for ReadLineLimited<'_, R> {}
+
+impl<'a, R: AsyncBufRead + ?Sized + Unpin> ReadLineLimited<'a, R> {
+    pub(super) fn new(reader: &'a mut R, limit: usize, buf: &'a mut String) -> Self {
+        Self {
+            reader,
+            limit,
+            bytes: unsafe { mem::replace(buf.as_mut_vec(), Default::default()) },
+            buf,
+            read: 0,
+        }
+    }
+}
+
+impl<R: AsyncBufRead + ?Sized + Unpin> Future for ReadLineLimited<'_, R> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self { reader, limit, buf, bytes, read } = &mut *self;
+        read_line_limited_internal(Pin::new(reader), cx, *limit, buf, bytes, read)
+    }
+}
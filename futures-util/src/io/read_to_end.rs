@@ -1,9 +1,11 @@
 use futures_core::future::Future;
 use futures_core::task::{Context, Poll};
 use futures_io::AsyncRead;
+use std::cmp;
 use std::io;
 use std::pin::Pin;
 use std::vec::Vec;
+use super::LimitExceeded;
 
 /// Future for the [`read_to_end`](super::AsyncReadExt::read_to_end) method.
 #[derive(Debug)]
@@ -45,14 +47,38 @@ impl Drop for Guard<'_> {
 // Because we're extending the buffer with uninitialized data for trusted
 // readers, we need to make sure to truncate that if any of this panics.
 pub(super) fn read_to_end_internal<R: AsyncRead + ?Sized>(
+    rd: Pin<&mut R>,
+    cx: &mut Context<'_>,
+    buf: &mut Vec<u8>,
+    start_len: usize,
+) -> Poll<io::Result<usize>> {
+    read_to_end_limited_internal(rd, cx, buf, start_len, usize::max_value())
+}
+
+// Like `read_to_end_internal`, but fails with `LimitExceeded` as soon as the
+// reader turns out to hold more than `limit` bytes.
+pub(super) fn read_to_end_limited_internal<R: AsyncRead + ?Sized>(
     mut rd: Pin<&mut R>,
     cx: &mut Context<'_>,
     buf: &mut Vec<u8>,
     start_len: usize,
+    limit: usize,
 ) -> Poll<io::Result<usize>> {
     let mut g = Guard { len: buf.len(), buf };
     let ret;
     loop {
+        let read = g.len - start_len;
+        if read == limit {
+            // Only EOF is acceptable now, anything else is over the limit.
+            let mut probe = [0; 1];
+            ret = match ready!(rd.as_mut().poll_read(cx, &mut probe)) {
+                Ok(0) => Poll::Ready(Ok(read)),
+                Ok(_) => Poll::Ready(Err(LimitExceeded::new(limit).into())),
+                Err(e) => Poll::Ready(Err(e)),
+            };
+            break;
+        }
+
         if g.len == g.buf.len() {
             unsafe {
                 g.buf.reserve(32);
@@ -62,9 +88,10 @@ pub(super) fn read_to_end_internal<R: AsyncRead + ?Sized>(
             }
         }
 
-        match ready!(rd.as_mut().poll_read(cx, &mut g.buf[g.len..])) {
+        let end = cmp::min(g.buf.len(), g.len.saturating_add(limit - read));
+        match ready!(rd.as_mut().poll_read(cx, &mut g.buf[g.len..end])) {
             Ok(0) => {
-                ret = Poll::Ready(Ok(g.len - start_len));
+                ret = Poll::Ready(Ok(read));
                 break;
             }
             Ok(n) => g.len += n,
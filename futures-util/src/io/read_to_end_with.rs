@@ -0,0 +1,84 @@
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_io::AsyncRead;
+use std::io;
+use std::pin::Pin;
+use std::vec::Vec;
+use super::read_to_end::read_to_end_limited_internal;
+
+/// Options for the [`read_to_end_with`](super::AsyncReadExt::read_to_end_with)
+/// method.
+///
+/// By default no capacity is reserved up front and there is no limit on the
+/// number of bytes read.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReadToEndOptions {
+    size_hint: usize,
+    max_bytes: Option<usize>,
+}
+
+impl ReadToEndOptions {
+    /// Creates a new set of options with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of bytes to reserve in the buffer before reading.
+    ///
+    /// When the expected size of the data is known, for example from a
+    /// length header, this avoids repeatedly growing the buffer. The hint is
+    /// capped to [`max_bytes`](ReadToEndOptions::max_bytes) so that an
+    /// untrusted hint cannot cause a large allocation by itself.
+    pub fn size_hint(mut self, size_hint: usize) -> Self {
+        self.size_hint = size_hint;
+        self
+    }
+
+    /// Sets the maximum number of bytes to read.
+    ///
+    /// If the reader holds more than `max` bytes the read fails with a
+    /// [`LimitExceeded`](super::LimitExceeded) error, converted into an
+    /// [`io::Error`] of kind [`InvalidData`](io::ErrorKind::InvalidData).
+    pub fn max_bytes(mut self, max: usize) -> Self {
+        self.max_bytes = Some(max);
+        self
+    }
+}
+
+/// Future for the [`read_to_end_with`](super::AsyncReadExt::read_to_end_with)
+/// method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadToEndWith<'a, R: ?Sized + Unpin> {
+    reader: &'a mut R,
+    buf: &'a mut Vec<u8>,
+    start_len: usize,
+    limit: usize,
+}
+
+impl<R: ?Sized + Unpin> Unpin for ReadToEndWith<'_, R> {}
+
+impl<'a, R: AsyncRead + ?Sized + Unpin> ReadToEndWith<'a, R> {
+    pub(super) fn new(reader: &'a mut R, buf: &'a mut Vec<u8>, options: ReadToEndOptions) -> Self {
+        let limit = options.max_bytes.unwrap_or(usize::max_value());
+        buf.reserve(options.size_hint.min(limit));
+        let start_len = buf.len();
+        Self {
+            reader,
+            buf,
+            start_len,
+            limit,
+        }
+    }
+}
+
+impl<A> Future for ReadToEndWith<'_, A>
+    where A: AsyncRead + ?Sized + Unpin,
+{
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        read_to_end_limited_internal(Pin::new(&mut this.reader), cx, this.buf, this.start_len, this.limit)
+    }
+}
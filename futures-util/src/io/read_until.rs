@@ -1,9 +1,11 @@
 use futures_core::future::Future;
 use futures_core::task::{Context, Poll};
 use futures_io::AsyncBufRead;
+use std::cmp;
 use std::io;
 use std::mem;
 use std::pin::Pin;
+use super::LimitExceeded;
 
 /// Future for the [`read_until`](super::AsyncBufReadExt::read_until) method.
 #[derive(Debug)]
@@ -24,15 +26,32 @@ impl<'a, R: AsyncBufRead + ?Sized + Unpin> ReadUntil<'a, R> {
 }
 
 pub(super) fn read_until_internal<R: AsyncBufRead + ?Sized>(
+    reader: Pin<&mut R>,
+    cx: &mut Context<'_>,
+    byte: u8,
+    buf: &mut Vec<u8>,
+    read: &mut usize,
+) -> Poll<io::Result<usize>> {
+    read_until_limited_internal(reader, cx, byte, usize::max_value(), buf, read)
+}
+
+pub(super) fn read_until_limited_internal<R: AsyncBufRead + ?Sized>(
     mut reader: Pin<&mut R>,
     cx: &mut Context<'_>,
     byte: u8,
+    limit: usize,
     buf: &mut Vec<u8>,
     read: &mut usize,
 ) -> Poll<io::Result<usize>> {
     loop {
         let (done, used) = {
             let available = ready!(reader.as_mut().poll_fill_buf(cx))?;
+            let remaining = limit - *read;
+            if remaining == 0 && !available.is_empty() {
+                *read = 0;
+                return Poll::Ready(Err(LimitExceeded::new(limit).into()));
+            }
+            let available = &available[..cmp::min(available.len(), remaining)];
             if let Some(i) = memchr::memchr(byte, available) {
                 buf.extend_from_slice(&available[..=i]);
                 (true, i + 1)
@@ -0,0 +1,69 @@
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_io::AsyncBufRead;
+use std::io;
+use std::mem;
+use std::pin::Pin;
+
+/// Future for the [`read_until_any`](super::AsyncBufReadExt::read_until_any) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadUntilAny<'a, R: ?Sized + Unpin> {
+    reader: &'a mut R,
+    delimiters: &'a [u8],
+    buf: &'a mut Vec<u8>,
+    read: usize,
+}
+
+impl<R: ?Sized + Unpin> Unpin for ReadUntilAny<'_, R> {}
+
+impl<'a, R: AsyncBufRead + ?Sized + Unpin> ReadUntilAny<'a, R> {
+    pub(super) fn new(reader: &'a mut R, delimiters: &'a [u8], buf: &'a mut Vec<u8>) -> Self {
+        Self { reader, delimiters, buf, read: 0 }
+    }
+}
+
+fn find_any(delimiters: &[u8], haystack: &[u8]) -> Option<usize> {
+    match *delimiters {
+        [] => None,
+        [a] => memchr::memchr(a, haystack),
+        [a, b] => memchr::memchr2(a, b, haystack),
+        [a, b, c] => memchr::memchr3(a, b, c, haystack),
+        _ => haystack.iter().position(|b| delimiters.contains(b)),
+    }
+}
+
+fn read_until_any_internal<R: AsyncBufRead + ?Sized>(
+    mut reader: Pin<&mut R>,
+    cx: &mut Context<'_>,
+    delimiters: &[u8],
+    buf: &mut Vec<u8>,
+    read: &mut usize,
+) -> Poll<io::Result<usize>> {
+    loop {
+        let (done, used) = {
+            let available = ready!(reader.as_mut().poll_fill_buf(cx))?;
+            if let Some(i) = find_any(delimiters, available) {
+                buf.extend_from_slice(&available[..=i]);
+                (true, i + 1)
+            } else {
+                buf.extend_from_slice(available);
+                (false, available.len())
+            }
+        };
+        reader.as_mut().consume(used);
+        *read += used;
+        if done || used == 0 {
+            return Poll::Ready(Ok(mem::replace(read, 0)));
+        }
+    }
+}
+
+impl<R: AsyncBufRead + ?Sized + Unpin> Future for ReadUntilAny<'_, R> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self { reader, delimiters, buf, read } = &mut *self;
+        read_until_any_internal(Pin::new(reader), cx, delimiters, buf, read)
+    }
+}
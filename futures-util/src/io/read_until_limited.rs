@@ -0,0 +1,85 @@
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_io::AsyncBufRead;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use super::read_until::read_until_limited_internal;
+
+/// Future for the
+/// [`read_until_limited`](super::AsyncBufReadExt::read_until_limited) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadUntilLimited<'a, R: ?Sized + Unpin> {
+    reader: &'a mut R,
+    byte: u8,
+    limit: usize,
+    buf: &'a mut Vec<u8>,
+    read: usize,
+}
+
+impl<R: ?Sized + Unpin> Unpin for ReadUntilLimited<'_, R> {}
+
+impl<'a, R: AsyncBufRead + ?Sized + Unpin> ReadUntilLimited<'a, R> {
+    pub(super) fn new(reader: &'a mut R, byte: u8, limit: usize, buf: &'a mut Vec<u8>) -> Self {
+        Self { reader, byte, limit, buf, read: 0 }
+    }
+}
+
+impl<R: AsyncBufRead + ?Sized + Unpin> Future for ReadUntilLimited<'_, R> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self { reader, byte, limit, buf, read } = &mut *self;
+        read_until_limited_internal(Pin::new(reader), cx, *byte, *limit, buf, read)
+    }
+}
+
+/// The error returned when a length-limited read such as
+/// [`read_until_limited`](super::AsyncBufReadExt::read_until_limited) or
+/// [`read_line_limited`](super::AsyncBufReadExt::read_line_limited) exceeds
+/// its limit, or when writing to a [`Limit`](super::Limit) writer which has
+/// reached its limit.
+///
+/// It is surfaced as an [`io::Error`] of kind
+/// [`InvalidData`](io::ErrorKind::InvalidData) wrapping this type, and can be
+/// told apart from other errors of that kind by downcasting:
+///
+/// ```
+/// use futures::io::LimitExceeded;
+/// # let err: std::io::Error = std::io::Error::new(std::io::ErrorKind::InvalidData, "");
+///
+/// if let Some(limit) = err.get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>()) {
+///     println!("line longer than {} bytes", limit.limit());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitExceeded {
+    limit: usize,
+}
+
+impl LimitExceeded {
+    pub(super) fn new(limit: usize) -> Self {
+        Self { limit }
+    }
+
+    /// Returns the limit that was exceeded.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "read limit of {} bytes exceeded", self.limit)
+    }
+}
+
+impl Error for LimitExceeded {}
+
+impl From<LimitExceeded> for io::Error {
+    fn from(err: LimitExceeded) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
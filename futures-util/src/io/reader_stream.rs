@@ -0,0 +1,114 @@
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+use futures_io::AsyncRead;
+use pin_utils::unsafe_pinned;
+use std::io;
+use std::mem;
+use std::pin::Pin;
+
+/// Stream for the [`into_stream`](super::AsyncReadExt::into_stream) method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ReaderStream<R> {
+    reader: R,
+    chunk_size: usize,
+    buf: Vec<u8>,
+    spare: Option<Vec<u8>>,
+    done: bool,
+}
+
+impl<R: Unpin> Unpin for ReaderStream<R> {}
+
+impl<R: AsyncRead> ReaderStream<R> {
+    unsafe_pinned!(reader: R);
+
+    pub(super) fn new(reader: R, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be non-zero");
+        Self {
+            reader,
+            chunk_size,
+            buf: Vec::new(),
+            spare: None,
+            done: false,
+        }
+    }
+
+    /// Hands a chunk previously yielded by this stream back for reuse.
+    ///
+    /// The next chunk is read into `buf` instead of a newly allocated
+    /// buffer, so a consumer which is done with each chunk before asking
+    /// for the next can stream any amount of data with a single allocation.
+    /// Buffers with a capacity smaller than the chunk size are ignored.
+    pub fn recycle(&mut self, mut buf: Vec<u8>) {
+        if buf.capacity() >= self.chunk_size {
+            buf.clear();
+            self.spare = Some(buf);
+        }
+    }
+
+    /// Returns the maximum size of the chunks yielded by this stream.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid reading from the underlying reader
+    /// directly, as doing so may corrupt the stream of chunks.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Gets a pinned mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid reading from the underlying reader
+    /// directly, as doing so may corrupt the stream of chunks.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut R> {
+        self.reader()
+    }
+
+    /// Consumes this stream, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: AsyncRead> Stream for ReaderStream<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Self { reader, chunk_size, buf, spare, done } = unsafe { self.get_unchecked_mut() };
+        let reader = unsafe { Pin::new_unchecked(reader) };
+        if *done {
+            return Poll::Ready(None);
+        }
+
+        if buf.is_empty() {
+            if let Some(spare) = spare.take() {
+                *buf = spare;
+            }
+            buf.resize(*chunk_size, 0);
+        }
+        let n = match ready!(reader.poll_read(cx, buf)) {
+            Ok(n) => n,
+            Err(e) => return Poll::Ready(Some(Err(e))),
+        };
+        if n == 0 {
+            *done = true;
+            return Poll::Ready(None);
+        }
+        buf.truncate(n);
+        Poll::Ready(Some(Ok(mem::replace(buf, Default::default()))))
+    }
+}
+
+impl<R: AsyncRead> FusedStream for ReaderStream<R> {
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
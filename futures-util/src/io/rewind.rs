@@ -0,0 +1,29 @@
+use crate::io::{AsyncSeek, SeekFrom};
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use std::io;
+use std::pin::Pin;
+
+/// Future for the [`rewind`](crate::io::AsyncSeekExt::rewind) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Rewind<'a, S: ?Sized + Unpin> {
+    seek: &'a mut S,
+}
+
+impl<S: ?Sized + Unpin> Unpin for Rewind<'_, S> {}
+
+impl<'a, S: AsyncSeek + ?Sized + Unpin> Rewind<'a, S> {
+    pub(super) fn new(seek: &'a mut S) -> Self {
+        Self { seek }
+    }
+}
+
+impl<S: AsyncSeek + ?Sized + Unpin> Future for Rewind<'_, S> {
+    type Output = io::Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        ready!(Pin::new(&mut *self.seek).poll_seek(cx, SeekFrom::Start(0)))?;
+        Poll::Ready(Ok(()))
+    }
+}
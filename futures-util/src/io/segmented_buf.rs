@@ -0,0 +1,185 @@
+use futures_core::task::{Context, Poll};
+use futures_io::{AsyncBufRead, AsyncRead, AsyncWrite, Initializer, IoSlice};
+use std::cmp;
+use std::collections::VecDeque;
+use std::io;
+use std::pin::Pin;
+
+/// An in-memory byte queue made up of separately allocated segments.
+///
+/// Data is read from the front and written to the back, so a `SegmentedBuf`
+/// can be used as an in-memory pipe. Unlike a `VecDeque<u8>` it never moves
+/// data around: owned chunks such as `Vec<u8>` or reference-counted buffers
+/// can be appended with [`push_back`](SegmentedBuf::push_back) without being
+/// copied, and every write through [`AsyncWrite`] becomes a segment of its
+/// own.
+///
+/// # Examples
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::io::{AsyncReadExt, AsyncWriteExt, SegmentedBuf};
+///
+/// let mut buf = SegmentedBuf::new();
+/// buf.push_back(b"hello".to_vec());
+/// buf.write_all(b" world").await?;
+/// assert_eq!(buf.len(), 11);
+///
+/// let mut output = String::new();
+/// buf.read_to_string(&mut output).await?;
+/// assert_eq!(output, "hello world");
+/// assert!(buf.is_empty());
+/// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct SegmentedBuf<T = Vec<u8>> {
+    segments: VecDeque<T>,
+    // Number of bytes already read from the front segment.
+    pos: usize,
+    len: usize,
+}
+
+impl<T> Unpin for SegmentedBuf<T> {}
+
+impl<T: AsRef<[u8]>> SegmentedBuf<T> {
+    /// Creates an empty `SegmentedBuf`.
+    pub fn new() -> Self {
+        Self { segments: VecDeque::new(), pos: 0, len: 0 }
+    }
+
+    /// Appends a segment to the back of the buffer without copying it.
+    ///
+    /// Empty segments are dropped.
+    pub fn push_back(&mut self, segment: T) {
+        let len = segment.as_ref().len();
+        if len > 0 {
+            self.len += len;
+            self.segments.push_back(segment);
+        }
+    }
+
+    /// Returns the number of unread bytes in the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no unread bytes in the buffer.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the unread parts of the segments, in order.
+    pub fn chunks(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        let pos = self.pos;
+        self.segments.iter().enumerate().map(move |(i, segment)| {
+            let segment = segment.as_ref();
+            if i == 0 { &segment[pos..] } else { segment }
+        })
+    }
+
+    /// Consumes the buffer, returning the remaining segments and the number
+    /// of bytes already read from the first one.
+    pub fn into_inner(self) -> (VecDeque<T>, usize) {
+        (self.segments, self.pos)
+    }
+
+    fn advance(&mut self, mut amt: usize) {
+        amt = cmp::min(amt, self.len);
+        self.len -= amt;
+        while amt > 0 {
+            let remaining = self.segments[0].as_ref().len() - self.pos;
+            if amt < remaining {
+                self.pos += amt;
+                return;
+            }
+            amt -= remaining;
+            self.segments.pop_front();
+            self.pos = 0;
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>> Default for SegmentedBuf<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: AsRef<[u8]>> From<VecDeque<T>> for SegmentedBuf<T> {
+    fn from(segments: VecDeque<T>) -> Self {
+        let mut buf = Self::new();
+        for segment in segments {
+            buf.push_back(segment);
+        }
+        buf
+    }
+}
+
+impl<T: AsRef<[u8]>> AsyncRead for SegmentedBuf<T> {
+    unsafe fn initializer(&self) -> Initializer {
+        Initializer::nop()
+    }
+
+    fn poll_read(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        let this = &mut *self;
+        let mut n = 0;
+        for chunk in this.chunks() {
+            if n == buf.len() {
+                break;
+            }
+            let amt = cmp::min(chunk.len(), buf.len() - n);
+            buf[n..n + amt].copy_from_slice(&chunk[..amt]);
+            n += amt;
+        }
+        this.advance(n);
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl<T: AsRef<[u8]>> AsyncBufRead for SegmentedBuf<T> {
+    fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        Poll::Ready(Ok(this.chunks().next().unwrap_or(&[])))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.get_mut().advance(amt)
+    }
+}
+
+impl<T> AsyncWrite for SegmentedBuf<T>
+where
+    T: AsRef<[u8]> + for<'a> From<&'a [u8]>,
+{
+    fn poll_write(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.push_back(T::from(buf));
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_write_vectored(mut self: Pin<&mut Self>, _: &mut Context<'_>, bufs: &[IoSlice<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        let mut n = 0;
+        for buf in bufs {
+            self.push_back(T::from(buf));
+            n += buf.len();
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
@@ -1,6 +1,9 @@
 use crate::lock::BiLock;
 use futures_core::task::{Context, Poll};
 use futures_io::{AsyncRead, AsyncWrite, IoSlice, IoSliceMut};
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
 use std::io;
 use std::pin::Pin;
 
@@ -14,6 +17,9 @@ pub struct ReadHalf<T> {
 #[derive(Debug)]
 pub struct WriteHalf<T> {
     handle: BiLock<T>,
+    // Whether the split object writes vectors efficiently, which cannot be
+    // asked through the lock.
+    vectored: bool,
 }
 
 fn lock_and_then<T, U, E, F>(
@@ -28,8 +34,50 @@ fn lock_and_then<T, U, E, F>(
 }
 
 pub(super) fn split<T: AsyncRead + AsyncWrite>(t: T) -> (ReadHalf<T>, WriteHalf<T>) {
+    let vectored = t.is_write_vectored();
     let (a, b) = BiLock::new(t);
-    (ReadHalf { handle: a }, WriteHalf { handle: b })
+    (ReadHalf { handle: a }, WriteHalf { handle: b, vectored })
+}
+
+impl<T> ReadHalf<T> {
+    /// Returns `true` if this `ReadHalf` and the given `WriteHalf` originate
+    /// from the same call to `AsyncReadExt::split`.
+    pub fn is_pair_of(&self, other: &WriteHalf<T>) -> bool {
+        self.handle.is_pair_of(&other.handle)
+    }
+
+    /// Attempts to put the two halves of a split object back together.
+    /// Succeeds only if the `ReadHalf<T>` and `WriteHalf<T>` are a matching
+    /// pair originating from the same call to `AsyncReadExt::split`.
+    ///
+    /// On failure both halves are handed back in the error.
+    pub fn reunite(self, other: WriteHalf<T>) -> Result<T, ReuniteError<T>>
+        where T: Unpin,
+    {
+        let vectored = other.vectored;
+        self.handle.reunite(other.handle).map_err(|err| {
+            ReuniteError(ReadHalf { handle: err.0 }, WriteHalf { handle: err.1, vectored })
+        })
+    }
+}
+
+impl<T> WriteHalf<T> {
+    /// Returns `true` if this `WriteHalf` and the given `ReadHalf` originate
+    /// from the same call to `AsyncReadExt::split`.
+    pub fn is_pair_of(&self, other: &ReadHalf<T>) -> bool {
+        self.handle.is_pair_of(&other.handle)
+    }
+
+    /// Attempts to put the two halves of a split object back together.
+    /// Succeeds only if the `WriteHalf<T>` and `ReadHalf<T>` are a matching
+    /// pair originating from the same call to `AsyncReadExt::split`.
+    ///
+    /// On failure both halves are handed back in the error.
+    pub fn reunite(self, other: ReadHalf<T>) -> Result<T, ReuniteError<T>>
+        where T: Unpin,
+    {
+        other.reunite(self)
+    }
 }
 
 impl<R: AsyncRead> AsyncRead for ReadHalf<R> {
@@ -59,6 +107,10 @@ impl<W: AsyncWrite> AsyncWrite for WriteHalf<W> {
         lock_and_then(&self.handle, cx, |l, cx| l.poll_write_vectored(cx, bufs))
     }
 
+    fn is_write_vectored(&self) -> bool {
+        self.vectored
+    }
+
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         lock_and_then(&self.handle, cx, |l, cx| l.poll_flush(cx))
     }
@@ -67,3 +119,26 @@ impl<W: AsyncWrite> AsyncWrite for WriteHalf<W> {
         lock_and_then(&self.handle, cx, |l, cx| l.poll_close(cx))
     }
 }
+
+/// Error indicating a `ReadHalf<T>` and `WriteHalf<T>` were not two halves
+/// of a `AsyncRead + AsyncWrite`, and thus could not be `reunite`d.
+///
+/// The two halves are returned unchanged, so they can still be used or
+/// paired up with their actual counterparts.
+pub struct ReuniteError<T>(pub ReadHalf<T>, pub WriteHalf<T>);
+
+impl<T> fmt::Debug for ReuniteError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ReuniteError")
+            .field(&"...")
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for ReuniteError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "tried to reunite a ReadHalf and WriteHalf that don't form a pair")
+    }
+}
+
+impl<T: Any> Error for ReuniteError<T> {}
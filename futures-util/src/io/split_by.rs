@@ -0,0 +1,86 @@
+use futures_core::stream::Stream;
+use futures_core::task::{Context, Poll};
+use futures_io::AsyncBufRead;
+use std::cmp;
+use std::io;
+use std::mem;
+use std::pin::Pin;
+
+/// Stream for the [`split_by`](super::AsyncBufReadExt::split_by) method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct SplitBy<R> {
+    reader: R,
+    delimiter: Box<[u8]>,
+    buf: Vec<u8>,
+    read: usize,
+}
+
+impl<R: Unpin> Unpin for SplitBy<R> {}
+
+impl<R: AsyncBufRead> SplitBy<R> {
+    pub(super) fn new(reader: R, delimiter: &[u8]) -> Self {
+        assert!(!delimiter.is_empty(), "split delimiter must not be empty");
+        Self {
+            reader,
+            delimiter: delimiter.into(),
+            buf: Vec::new(),
+            read: 0,
+        }
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.len() == 1 {
+        return memchr::memchr(needle[0], haystack);
+    }
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+fn read_until_seq_internal<R: AsyncBufRead + ?Sized>(
+    mut reader: Pin<&mut R>,
+    cx: &mut Context<'_>,
+    delimiter: &[u8],
+    buf: &mut Vec<u8>,
+    read: &mut usize,
+) -> Poll<io::Result<usize>> {
+    loop {
+        let (done, used) = {
+            let available = ready!(reader.as_mut().poll_fill_buf(cx))?;
+            let start = buf.len();
+            // The delimiter may straddle the end of the previous chunk, so
+            // start searching a little before the newly read bytes.
+            let from = start - cmp::min(*read, delimiter.len() - 1);
+            buf.extend_from_slice(available);
+            if let Some(i) = find(&buf[from..], delimiter) {
+                let end = from + i + delimiter.len();
+                buf.truncate(end);
+                (true, end - start)
+            } else {
+                (false, available.len())
+            }
+        };
+        reader.as_mut().consume(used);
+        *read += used;
+        if done || used == 0 {
+            return Poll::Ready(Ok(mem::replace(read, 0)));
+        }
+    }
+}
+
+impl<R: AsyncBufRead> Stream for SplitBy<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Self { reader, delimiter, buf, read } = unsafe { self.get_unchecked_mut() };
+        let reader = unsafe { Pin::new_unchecked(reader) };
+        let n = ready!(read_until_seq_internal(reader, cx, delimiter, buf, read))?;
+        if n == 0 && buf.is_empty() {
+            return Poll::Ready(None)
+        }
+        if buf.ends_with(delimiter) {
+            buf.truncate(buf.len() - delimiter.len());
+        }
+        Poll::Ready(Some(Ok(mem::replace(buf, Default::default()))))
+    }
+}
@@ -1,7 +1,9 @@
 use futures_core::task::{Context, Poll};
-use futures_io::AsyncRead;
+use futures_io::{AsyncRead, AsyncSeek, SeekFrom};
+use std::convert::TryFrom;
 use std::io;
 use std::pin::Pin;
+use super::seek_offset;
 
 /// Future for the [`take`](super::AsyncReadExt::take) method.
 #[derive(Debug)]
@@ -9,13 +11,17 @@ use std::pin::Pin;
 pub struct Take<R: Unpin> {
     inner: R,
     limit: u64,
+    // Length of the window that `limit` counts down in, used for seeking.
+    len: u64,
+    // How far past the end of the window the reader has been seeked.
+    past_end: u64,
 }
 
 impl<R: Unpin> Unpin for Take<R> { }
 
 impl<R: AsyncRead + Unpin> Take<R> {
     pub(super) fn new(inner: R, limit: u64) -> Self {
-        Take { inner, limit }
+        Take { inner, limit, len: limit, past_end: 0 }
     }
 
     /// Returns the remaining number of bytes that can be
@@ -51,6 +57,9 @@ impl<R: AsyncRead + Unpin> Take<R> {
     /// the amount of bytes read and the previous limit value don't matter when
     /// calling this method.
     ///
+    /// The new limit also starts a new window for seeking, beginning at the
+    /// current position of the underlying reader.
+    ///
     /// # Examples
     ///
     /// ```
@@ -74,7 +83,9 @@ impl<R: AsyncRead + Unpin> Take<R> {
     /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
     /// ```
     pub fn set_limit(&mut self, limit: u64) {
-        self.limit = limit
+        self.limit = limit;
+        self.len = limit;
+        self.past_end = 0;
     }
 
     /// Consumes the `Take`, returning the wrapped reader.
@@ -169,3 +180,35 @@ impl<R: AsyncRead + Unpin> AsyncRead for Take<R> {
         Poll::Ready(Ok(n))
     }
 }
+
+/// Seeks within the window of bytes this `Take` may read.
+///
+/// Positions are relative to the start of the window, which is where the
+/// underlying reader was when the `Take` was created or the limit was last
+/// set, and `SeekFrom::End` is relative to the end of the window. Seeking
+/// past the end of the window is allowed, reads from there return EOF.
+impl<R: AsyncRead + AsyncSeek + Unpin> AsyncSeek for Take<R> {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let this = &mut *self;
+        let current = this.len - this.limit + this.past_end;
+        let target = match pos {
+            SeekFrom::Start(n) => n,
+            SeekFrom::Current(n) => seek_offset(current, n)?,
+            SeekFrom::End(n) => seek_offset(this.len, n)?,
+        };
+        let delta = if target >= current {
+            i64::try_from(target - current)
+        } else {
+            i64::try_from(current - target).map(|n| -n)
+        }.map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "seek offset out of range"))?;
+
+        ready!(Pin::new(&mut this.inner).poll_seek(cx, SeekFrom::Current(delta)))?;
+        this.limit = this.len.saturating_sub(target);
+        this.past_end = target.saturating_sub(this.len);
+        Poll::Ready(Ok(target))
+    }
+}
@@ -0,0 +1,98 @@
+use futures_core::task::{Context, Poll};
+use futures_io::{AsyncRead, AsyncWrite};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::io;
+use std::pin::Pin;
+
+/// Reader for the [`tee`](super::AsyncReadExt::tee) method.
+#[derive(Debug)]
+#[must_use = "readers do nothing unless polled"]
+pub struct Tee<R, W> {
+    reader: R,
+    writer: W,
+    // Bytes handed out by `poll_read` that have not been written yet.
+    pending: Vec<u8>,
+}
+
+impl<R: Unpin, W: Unpin> Unpin for Tee<R, W> {}
+
+impl<R, W> Tee<R, W>
+where
+    R: AsyncRead,
+    W: AsyncWrite,
+{
+    unsafe_pinned!(reader: R);
+    unsafe_pinned!(writer: W);
+    unsafe_unpinned!(pending: Vec<u8>);
+
+    pub(super) fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer, pending: Vec::new() }
+    }
+
+    /// Gets references to the underlying reader and writer.
+    pub fn get_ref(&self) -> (&R, &W) {
+        (&self.reader, &self.writer)
+    }
+
+    /// Gets mutable references to the underlying reader and writer.
+    ///
+    /// Care should be taken to avoid reading from the reader or writing to
+    /// the writer directly, as doing so leaves the writer with an incomplete
+    /// or out of order copy of the data.
+    pub fn get_mut(&mut self) -> (&mut R, &mut W) {
+        (&mut self.reader, &mut self.writer)
+    }
+
+    /// Consumes the `Tee`, returning the underlying reader and writer.
+    ///
+    /// Data which has been read but not yet written to the writer is lost.
+    /// Reading until EOF guarantees that all data has been written.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+
+    /// Writes as much of the pending data as possible without waiting.
+    fn poll_write_pending(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.pending.is_empty() {
+            let Self { writer, pending, .. } = unsafe { self.as_mut().get_unchecked_mut() };
+            let writer = unsafe { Pin::new_unchecked(writer) };
+            let n = ready!(writer.poll_write(cx, pending))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write the tee'd data",
+                )));
+            }
+            pending.drain(..n);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R, W> AsyncRead for Tee<R, W>
+where
+    R: AsyncRead,
+    W: AsyncWrite,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        // The writer must keep up with the reader, so data is only read once
+        // everything read before has been handed to the writer.
+        ready!(self.as_mut().poll_write_pending(cx))?;
+
+        let n = ready!(self.as_mut().reader().poll_read(cx, buf))?;
+        if n == 0 && !buf.is_empty() {
+            ready!(self.as_mut().writer().poll_flush(cx))?;
+            return Poll::Ready(Ok(0));
+        }
+
+        // The bytes now belong to the caller, so errors and back pressure of
+        // the writer are reported by the next call.
+        self.as_mut().pending().extend_from_slice(&buf[..n]);
+        let _ = self.poll_write_pending(cx);
+        Poll::Ready(Ok(n))
+    }
+}
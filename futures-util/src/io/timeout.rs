@@ -0,0 +1,316 @@
+use crate::timer::Delay;
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_io::{
+    AsyncBufRead, AsyncRead, AsyncSeek, AsyncWrite, Initializer, IoSlice, IoSliceMut, SeekFrom,
+};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::io;
+use std::pin::Pin;
+use std::time::Duration;
+
+/// Tracks how long an I/O object has been unable to make progress.
+#[derive(Debug)]
+struct Deadline {
+    timeout: Duration,
+    delay: Delay,
+    active: bool,
+}
+
+impl Deadline {
+    fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            delay: Delay::new(timeout),
+            active: false,
+        }
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+        self.active = false;
+    }
+
+    /// Passes a ready result through, or turns a pending one into a
+    /// `TimedOut` error if the operation has been pending for too long.
+    fn poll_check<T>(&mut self, cx: &mut Context<'_>, res: Poll<io::Result<T>>) -> Poll<io::Result<T>> {
+        if res.is_ready() {
+            self.active = false;
+            return res;
+        }
+        if !self.active {
+            self.delay.reset(self.timeout);
+            self.active = true;
+        }
+        ready!(Pin::new(&mut self.delay).poll(cx));
+        self.active = false;
+        Poll::Ready(Err(io::Error::new(io::ErrorKind::TimedOut, "I/O operation timed out")))
+    }
+}
+
+/// Reader which fails a read that has been pending for longer than a
+/// timeout.
+///
+/// The timeout is an idle timeout: it starts when a read first returns
+/// `Poll::Pending` and is cleared as soon as a read completes, so a peer that
+/// keeps sending data slowly but steadily is never timed out. When it
+/// elapses, the pending read fails with an error of kind
+/// [`TimedOut`](io::ErrorKind::TimedOut). The reader is not affected and
+/// may be read from again.
+///
+/// Writes, flushes and seeks are passed through unchanged; wrap the reader
+/// in a [`TimeoutWriter`] as well to time out writes too.
+///
+/// # Examples
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::io::{AsyncReadExt, TimeoutReader};
+/// use std::time::Duration;
+///
+/// let mut reader = TimeoutReader::new(&b"hello"[..], Duration::from_secs(30));
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf).await?;
+/// assert_eq!(buf, b"hello");
+/// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct TimeoutReader<R> {
+    inner: R,
+    deadline: Deadline,
+}
+
+impl<R: Unpin> Unpin for TimeoutReader<R> {}
+
+impl<R> TimeoutReader<R> {
+    unsafe_pinned!(inner: R);
+    unsafe_unpinned!(deadline: Deadline);
+
+    /// Creates a new `TimeoutReader` which fails reads that are pending for
+    /// longer than `timeout`.
+    pub fn new(inner: R, timeout: Duration) -> Self {
+        Self { inner, deadline: Deadline::new(timeout) }
+    }
+
+    /// Returns the current timeout.
+    pub fn timeout(&self) -> Duration {
+        self.deadline.timeout
+    }
+
+    /// Sets the timeout, restarting the timer of a pending read.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.deadline.set_timeout(timeout)
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Gets a pinned mutable reference to the underlying reader.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut R> {
+        self.inner()
+    }
+
+    /// Consumes this `TimeoutReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for TimeoutReader<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        let res = self.as_mut().inner().poll_read(cx, buf);
+        self.deadline().poll_check(cx, res)
+    }
+
+    fn poll_read_vectored(mut self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &mut [IoSliceMut<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        let res = self.as_mut().inner().poll_read_vectored(cx, bufs);
+        self.deadline().poll_check(cx, res)
+    }
+}
+
+impl<R: AsyncBufRead> AsyncBufRead for TimeoutReader<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let Self { inner, deadline } = unsafe { self.get_unchecked_mut() };
+        let inner = unsafe { Pin::new_unchecked(inner) };
+        let res = inner.poll_fill_buf(cx);
+        deadline.poll_check(cx, res)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.inner().consume(amt)
+    }
+}
+
+impl<R: AsyncWrite> AsyncWrite for TimeoutReader<R> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.inner().poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        self.inner().poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner().poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner().poll_close(cx)
+    }
+}
+
+impl<R: AsyncSeek> AsyncSeek for TimeoutReader<R> {
+    fn poll_seek(self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom)
+        -> Poll<io::Result<u64>>
+    {
+        self.inner().poll_seek(cx, pos)
+    }
+}
+
+/// Writer which fails a write, flush or close that has been pending for
+/// longer than a timeout.
+///
+/// The timeout is an idle timeout: it starts when an operation first returns
+/// `Poll::Pending` and is cleared as soon as one completes. When it elapses,
+/// the pending operation fails with an error of kind
+/// [`TimedOut`](io::ErrorKind::TimedOut).
+///
+/// Reads and seeks are passed through unchanged; wrap the writer in a
+/// [`TimeoutReader`] as well to time out reads too.
+#[derive(Debug)]
+pub struct TimeoutWriter<W> {
+    inner: W,
+    deadline: Deadline,
+}
+
+impl<W: Unpin> Unpin for TimeoutWriter<W> {}
+
+impl<W> TimeoutWriter<W> {
+    unsafe_pinned!(inner: W);
+    unsafe_unpinned!(deadline: Deadline);
+
+    /// Creates a new `TimeoutWriter` which fails operations that are pending
+    /// for longer than `timeout`.
+    pub fn new(inner: W, timeout: Duration) -> Self {
+        Self { inner, deadline: Deadline::new(timeout) }
+    }
+
+    /// Returns the current timeout.
+    pub fn timeout(&self) -> Duration {
+        self.deadline.timeout
+    }
+
+    /// Sets the timeout, restarting the timer of a pending operation.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.deadline.set_timeout(timeout)
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Gets a pinned mutable reference to the underlying writer.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut W> {
+        self.inner()
+    }
+
+    /// Consumes this `TimeoutWriter`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for TimeoutWriter<W> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        let res = self.as_mut().inner().poll_write(cx, buf);
+        self.deadline().poll_check(cx, res)
+    }
+
+    fn poll_write_vectored(mut self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        let res = self.as_mut().inner().poll_write_vectored(cx, bufs);
+        self.deadline().poll_check(cx, res)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let res = self.as_mut().inner().poll_flush(cx);
+        self.deadline().poll_check(cx, res)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let res = self.as_mut().inner().poll_close(cx);
+        self.deadline().poll_check(cx, res)
+    }
+}
+
+impl<W: AsyncRead> AsyncRead for TimeoutWriter<W> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.inner().poll_read(cx, buf)
+    }
+
+    fn poll_read_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &mut [IoSliceMut<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        self.inner().poll_read_vectored(cx, bufs)
+    }
+}
+
+impl<W: AsyncBufRead> AsyncBufRead for TimeoutWriter<W> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        self.inner().poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.inner().consume(amt)
+    }
+}
+
+impl<W: AsyncSeek> AsyncSeek for TimeoutWriter<W> {
+    fn poll_seek(self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom)
+        -> Poll<io::Result<u64>>
+    {
+        self.inner().poll_seek(cx, pos)
+    }
+}
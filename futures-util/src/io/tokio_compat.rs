@@ -0,0 +1,179 @@
+use futures_core::task::{Context, Poll};
+use futures_io::{AsyncBufRead, AsyncRead, AsyncWrite, IoSlice};
+use pin_utils::unsafe_pinned;
+use std::io;
+use std::pin::Pin;
+use tokio_1::io::{
+    AsyncBufRead as TokioAsyncBufRead, AsyncRead as TokioAsyncRead,
+    AsyncWrite as TokioAsyncWrite, ReadBuf,
+};
+
+/// A compatibility wrapper between the IO traits of this library and those
+/// of tokio 1.x.
+///
+/// Wrapping a futures-io [`AsyncRead`], [`AsyncWrite`] or [`AsyncBufRead`]
+/// makes it implement the corresponding tokio trait, and wrapping a tokio
+/// reader or writer makes it implement the corresponding futures-io trait.
+///
+/// Created by the [`compat_tokio`](super::AsyncReadExt::compat_tokio),
+/// [`compat_tokio_write`](super::AsyncWriteExt::compat_tokio_write),
+/// [`compat_futures`](TokioAsyncReadCompatExt::compat_futures) and
+/// [`compat_futures_write`](TokioAsyncWriteCompatExt::compat_futures_write)
+/// methods.
+///
+/// Requires the `tokio-io-compat` feature to enable.
+#[derive(Debug)]
+pub struct TokioCompat<T> {
+    inner: T,
+}
+
+impl<T: Unpin> Unpin for TokioCompat<T> {}
+
+impl<T> TokioCompat<T> {
+    unsafe_pinned!(inner: T);
+
+    /// Wraps an IO object of either library.
+    pub fn new(inner: T) -> TokioCompat<T> {
+        TokioCompat { inner }
+    }
+
+    /// Gets a reference to the wrapped IO object.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the wrapped IO object.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Gets a pinned mutable reference to the wrapped IO object.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut T> {
+        self.inner()
+    }
+
+    /// Consumes the wrapper, returning the wrapped IO object.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> TokioAsyncRead for TokioCompat<R> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>)
+        -> Poll<io::Result<()>>
+    {
+        let n = ready!(self.inner().poll_read(cx, buf.initialize_unfilled()))?;
+        buf.advance(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite> TokioAsyncWrite for TokioCompat<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.inner().poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        self.inner().poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner().poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner().poll_close(cx)
+    }
+}
+
+impl<R: AsyncBufRead> TokioAsyncBufRead for TokioCompat<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        self.inner().poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.inner().consume(amt)
+    }
+}
+
+impl<R: TokioAsyncRead> AsyncRead for TokioCompat<R> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        let mut buf = ReadBuf::new(buf);
+        ready!(self.inner().poll_read(cx, &mut buf))?;
+        Poll::Ready(Ok(buf.filled().len()))
+    }
+}
+
+impl<W: TokioAsyncWrite> AsyncWrite for TokioCompat<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.inner().poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        self.inner().poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner().poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.inner().poll_shutdown(cx)
+    }
+}
+
+impl<R: TokioAsyncBufRead> AsyncBufRead for TokioCompat<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        self.inner().poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.inner().consume(amt)
+    }
+}
+
+/// Extension trait for tokio 1.x [`AsyncRead`](tokio_1::io::AsyncRead).
+pub trait TokioAsyncReadCompatExt: TokioAsyncRead {
+    /// Wraps a tokio 1.x `AsyncRead` in a compatibility wrapper that allows
+    /// it to be used as a futures-io [`AsyncRead`]. If the wrapped type
+    /// implements tokio's `AsyncBufRead` or `AsyncWrite` as well, the result
+    /// also implements [`AsyncBufRead`] or [`AsyncWrite`].
+    fn compat_futures(self) -> TokioCompat<Self>
+        where Self: Sized,
+    {
+        TokioCompat::new(self)
+    }
+}
+
+impl<R: TokioAsyncRead + ?Sized> TokioAsyncReadCompatExt for R {}
+
+/// Extension trait for tokio 1.x [`AsyncWrite`](tokio_1::io::AsyncWrite).
+pub trait TokioAsyncWriteCompatExt: TokioAsyncWrite {
+    /// Wraps a tokio 1.x `AsyncWrite` in a compatibility wrapper that allows
+    /// it to be used as a futures-io [`AsyncWrite`].
+    fn compat_futures_write(self) -> TokioCompat<Self>
+        where Self: Sized,
+    {
+        TokioCompat::new(self)
+    }
+}
+
+impl<W: TokioAsyncWrite + ?Sized> TokioAsyncWriteCompatExt for W {}
@@ -0,0 +1,476 @@
+use crate::task::AtomicWaker;
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_io::{AsyncRead, AsyncSeek, AsyncWrite, SeekFrom};
+use std::collections::VecDeque;
+use std::{cmp, fmt, mem};
+use std::io::{self, Read, Seek, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+use super::DEFAULT_BUF_SIZE;
+
+/// Runs blocking `std::io` operations on a dedicated thread pool.
+///
+/// [`AllowStdIo`](super::AllowStdIo) calls the blocking `Read`/`Write`/`Seek`
+/// methods of the wrapped object directly on the task's thread, stalling the
+/// executor while the call is in progress. `Unblock` instead moves the object
+/// to a background thread for the duration of each operation and wakes the
+/// task once it has completed, so files, pipes and other blocking IO objects
+/// can be used from async code.
+///
+/// The background threads are shared by all `Unblock` values. Threads are
+/// started on demand and exit after a period of inactivity.
+///
+/// Only one operation is in flight at a time. Each read transfers at most
+/// the configured capacity (8 KB by default) and each write hands at most
+/// that many bytes to the background thread. If an operation is abandoned
+/// (the caller stops polling after `Poll::Pending`) it still runs to
+/// completion; read data is kept for the next read, while the results of
+/// other operations are discarded.
+///
+/// # Examples
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::io::{AsyncReadExt, Unblock};
+/// use std::io::Cursor;
+///
+/// let mut reader = Unblock::new(Cursor::new(b"hello".to_vec()));
+/// let mut contents = String::new();
+/// reader.read_to_string(&mut contents).await?;
+///
+/// assert_eq!(contents, "hello");
+/// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+/// ```
+pub struct Unblock<T> {
+    io: Option<Box<T>>,
+    buf: Vec<u8>,
+    pos: usize,
+    cap: usize,
+    task: Option<BlockingTask<Done<T>>>,
+}
+
+impl<T> Unpin for Unblock<T> {}
+
+// The result of an operation which ran on the blocking pool.
+enum OpResult {
+    Read(io::Result<usize>),
+    Write(io::Result<usize>),
+    Flush(io::Result<()>),
+    Seek(io::Result<u64>),
+}
+
+type Done<T> = (Box<T>, Vec<u8>, OpResult);
+
+impl<T> Unblock<T> {
+    /// Wraps a blocking IO object, using the default capacity of 8 KB.
+    pub fn new(io: T) -> Self {
+        Self::with_capacity(DEFAULT_BUF_SIZE, io)
+    }
+
+    /// Wraps a blocking IO object, transferring at most `cap` bytes per
+    /// operation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cap` is zero.
+    pub fn with_capacity(cap: usize, io: T) -> Self {
+        assert!(cap > 0, "capacity must be non-zero");
+        Self {
+            io: Some(Box::new(io)),
+            buf: Vec::new(),
+            pos: 0,
+            cap,
+            task: None,
+        }
+    }
+
+    /// Gets a mutable reference to the underlying IO object, waiting for any
+    /// in-flight operation to complete first.
+    ///
+    /// Any data which has been read ahead but not yet returned is discarded.
+    pub fn poll_get_mut(&mut self, cx: &mut Context<'_>) -> Poll<&mut T> {
+        ready!(self.poll_finish(cx));
+        self.pos = 0;
+        self.buf.clear();
+        Poll::Ready(self.io.as_mut().unwrap())
+    }
+
+    /// Consumes this `Unblock`, returning a future which resolves to the
+    /// underlying IO object once any in-flight operation has completed.
+    pub fn into_inner(self) -> UnblockIntoInner<T> {
+        UnblockIntoInner { inner: Some(self) }
+    }
+
+    // Waits for the in-flight operation, if any, and reclaims the IO object.
+    fn poll_finish(&mut self, cx: &mut Context<'_>) -> Poll<Option<OpResult>> {
+        let result = match &mut self.task {
+            Some(task) => {
+                let (io, buf, result) = ready!(Pin::new(task).poll(cx));
+                self.io = Some(io);
+                self.buf = buf;
+                Some(result)
+            }
+            None => None,
+        };
+        self.task = None;
+        Poll::Ready(result)
+    }
+}
+
+impl<T: Send + 'static> Unblock<T> {
+    // Starts running `f` on the blocking pool, handing it the IO object and
+    // `buf`. Both are kept if the operation cannot be started.
+    fn start<F>(&mut self, buf: Vec<u8>, f: F) -> io::Result<()>
+    where
+        F: FnOnce(&mut T, &mut Vec<u8>) -> OpResult + Send + 'static,
+    {
+        let io = self.io.take().unwrap();
+        match spawn_job(io, buf, f) {
+            Ok(task) => {
+                self.task = Some(task);
+                Ok(())
+            }
+            Err((io, buf, e)) => {
+                self.io = Some(io);
+                self.buf = buf;
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Unblock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("Unblock");
+        match &self.io {
+            Some(io) => d.field("io", io),
+            None => d.field("io", &"<busy>"),
+        };
+        d.field("buffered", &(self.buf.len() - self.pos))
+            .field("cap", &self.cap)
+            .finish()
+    }
+}
+
+impl<T: Read + Send + 'static> AsyncRead for Unblock<T> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, out: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        let this = &mut *self;
+        loop {
+            // Hand out data that was read earlier first.
+            if this.task.is_none() && this.pos < this.buf.len() {
+                let n = cmp::min(out.len(), this.buf.len() - this.pos);
+                out[..n].copy_from_slice(&this.buf[this.pos..this.pos + n]);
+                this.pos += n;
+                return Poll::Ready(Ok(n));
+            }
+
+            match ready!(this.poll_finish(cx)) {
+                Some(OpResult::Read(res)) => {
+                    this.pos = 0;
+                    if res? == 0 {
+                        return Poll::Ready(Ok(0));
+                    }
+                    continue;
+                }
+                Some(_) => this.buf.clear(),
+                None => {}
+            }
+
+            if out.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+            let len = cmp::min(out.len(), this.cap);
+            let buf = mem::replace(&mut this.buf, Default::default());
+            this.pos = 0;
+            this.start(buf, move |io, buf| {
+                buf.clear();
+                buf.resize(len, 0);
+                let res = io.read(buf);
+                buf.truncate(*res.as_ref().unwrap_or(&0));
+                OpResult::Read(res)
+            })?;
+        }
+    }
+}
+
+impl<T: Write + Send + 'static> AsyncWrite for Unblock<T> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, data: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        let this = &mut *self;
+        loop {
+            match ready!(this.poll_finish(cx)) {
+                Some(OpResult::Write(res)) => {
+                    this.buf.clear();
+                    return Poll::Ready(res);
+                }
+                Some(_) => this.buf.clear(),
+                None => {}
+            }
+
+            if data.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+            let len = cmp::min(data.len(), this.cap);
+            let mut buf = mem::replace(&mut this.buf, Default::default());
+            buf.clear();
+            buf.extend_from_slice(&data[..len]);
+            this.pos = 0;
+            this.start(buf, |io, buf| {
+                OpResult::Write(io.write(buf))
+            })?;
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        loop {
+            match ready!(this.poll_finish(cx)) {
+                Some(OpResult::Flush(res)) => return Poll::Ready(res),
+                Some(OpResult::Write(res)) => {
+                    this.buf.clear();
+                    // Surface errors of writes which were abandoned.
+                    res?;
+                }
+                Some(_) => this.buf.clear(),
+                None => {}
+            }
+
+            let buf = mem::replace(&mut this.buf, Default::default());
+            this.pos = 0;
+            this.start(buf, |io, _| {
+                OpResult::Flush(io.flush())
+            })?;
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl<T: Seek + Send + 'static> AsyncSeek for Unblock<T> {
+    fn poll_seek(mut self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom)
+        -> Poll<io::Result<u64>>
+    {
+        let this = &mut *self;
+        loop {
+            match ready!(this.poll_finish(cx)) {
+                Some(OpResult::Seek(res)) => {
+                    this.buf.clear();
+                    this.pos = 0;
+                    return Poll::Ready(res);
+                }
+                Some(OpResult::Read(Ok(_))) => this.pos = 0,
+                Some(_) => this.buf.clear(),
+                None => {}
+            }
+
+            // The underlying object is ahead of our logical position by the
+            // amount of data that was read but not yet handed out.
+            let pos = match pos {
+                SeekFrom::Current(n) => {
+                    let ahead = (this.buf.len() - this.pos) as i64;
+                    SeekFrom::Current(n - ahead)
+                }
+                pos => pos,
+            };
+            let buf = mem::replace(&mut this.buf, Default::default());
+            this.pos = 0;
+            this.start(buf, move |io, _| {
+                OpResult::Seek(io.seek(pos))
+            })?;
+        }
+    }
+}
+
+// Moves the IO object and buffer to the blocking pool to run `f`, handing
+// them back together with the result once it completes, or right away if
+// the pool has no thread to run `f` on.
+#[allow(clippy::type_complexity)]
+fn spawn_job<T, F>(mut io: Box<T>, mut buf: Vec<u8>, f: F)
+    -> Result<BlockingTask<Done<T>>, (Box<T>, Vec<u8>, io::Error)>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut T, &mut Vec<u8>) -> OpResult + Send + 'static,
+{
+    let state = match pool().reserve() {
+        Ok(state) => state,
+        Err(e) => return Err((io, buf, e)),
+    };
+    Ok(spawn_blocking(state, move || {
+        let result = f(&mut io, &mut buf);
+        (io, buf, result)
+    }))
+}
+
+/// Future for the [`into_inner`](Unblock::into_inner) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct UnblockIntoInner<T> {
+    inner: Option<Unblock<T>>,
+}
+
+impl<T> Unpin for UnblockIntoInner<T> {}
+
+impl<T> Future for UnblockIntoInner<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let inner = self.inner.as_mut().expect("polled `UnblockIntoInner` after completion");
+        ready!(inner.poll_finish(cx));
+        let io = self.inner.take().unwrap().io.take().unwrap();
+        Poll::Ready(*io)
+    }
+}
+
+/*
+ *
+ * ===== blocking thread pool =====
+ *
+ */
+
+// Idle threads exit after this long without work.
+const IDLE_TIMEOUT: Duration = Duration::from_millis(500);
+
+// Upper bound on the number of threads used for blocking operations.
+const MAX_THREADS: usize = 512;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct Pool {
+    state: Mutex<PoolState>,
+    cvar: Condvar,
+}
+
+struct PoolState {
+    queue: VecDeque<Job>,
+    idle: usize,
+    threads: usize,
+}
+
+fn pool() -> &'static Pool {
+    static POOL: AtomicPtr<Pool> = AtomicPtr::new(ptr::null_mut());
+
+    let mut pool = POOL.load(Ordering::Acquire);
+    if pool.is_null() {
+        let new = Box::into_raw(Box::new(Pool {
+            state: Mutex::new(PoolState {
+                queue: VecDeque::new(),
+                idle: 0,
+                threads: 0,
+            }),
+            cvar: Condvar::new(),
+        }));
+        pool = match POOL.compare_exchange(ptr::null_mut(), new, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => new,
+            Err(existing) => {
+                // Someone else won the race; free our copy.
+                drop(unsafe { Box::from_raw(new) });
+                existing
+            }
+        };
+    }
+    // Safety: the pool is never freed once it has been published.
+    unsafe { &*pool }
+}
+
+impl Pool {
+    // Locks the pool for queueing a job, making sure that a thread is around
+    // to run it.
+    fn reserve(&'static self) -> io::Result<MutexGuard<'static, PoolState>> {
+        let mut state = self.state.lock().unwrap();
+        if state.idle > 0 {
+            self.cvar.notify_one();
+        } else if state.threads < MAX_THREADS {
+            state.threads += 1;
+            let spawned = thread::Builder::new()
+                .name("futures-unblock".to_string())
+                .spawn(move || self.work());
+            if spawned.is_err() {
+                // Fall back to the threads that already exist.
+                state.threads -= 1;
+                if state.threads == 0 {
+                    drop(state);
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "failed to spawn a thread for blocking IO",
+                    ));
+                }
+            }
+        }
+        Ok(state)
+    }
+
+    fn work(&self) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(job) = state.queue.pop_front() {
+                drop(state);
+                job();
+                state = self.state.lock().unwrap();
+                continue;
+            }
+
+            state.idle += 1;
+            let (guard, timeout) = self.cvar.wait_timeout(state, IDLE_TIMEOUT).unwrap();
+            state = guard;
+            state.idle -= 1;
+
+            if timeout.timed_out() && state.queue.is_empty() {
+                state.threads -= 1;
+                return;
+            }
+        }
+    }
+}
+
+struct Slot<T> {
+    value: Mutex<Option<thread::Result<T>>>,
+    waker: AtomicWaker,
+}
+
+// A handle to the result of a closure running on the blocking pool.
+struct BlockingTask<T> {
+    slot: Arc<Slot<T>>,
+}
+
+// Queues `f` on the pool locked by `state`.
+fn spawn_blocking<T, F>(mut state: MutexGuard<'static, PoolState>, f: F) -> BlockingTask<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let slot = Arc::new(Slot {
+        value: Mutex::new(None),
+        waker: AtomicWaker::new(),
+    });
+    let slot2 = slot.clone();
+    state.queue.push_back(Box::new(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(f));
+        *slot2.value.lock().unwrap() = Some(result);
+        slot2.waker.wake();
+    }));
+    BlockingTask { slot }
+}
+
+impl<T> Future for BlockingTask<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        self.slot.waker.register(cx.waker());
+        match self.slot.value.lock().unwrap().take() {
+            Some(Ok(value)) => Poll::Ready(value),
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => Poll::Pending,
+        }
+    }
+}
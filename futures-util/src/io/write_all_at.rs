@@ -0,0 +1,44 @@
+use crate::io::AsyncWriteAt;
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use std::io;
+use std::mem;
+use std::pin::Pin;
+
+/// Future for the [`write_all_at`](super::AsyncWriteAtExt::write_all_at) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WriteAllAt<'a, W: ?Sized + Unpin> {
+    writer: &'a mut W,
+    buf: &'a [u8],
+    offset: u64,
+}
+
+impl<W: ?Sized + Unpin> Unpin for WriteAllAt<'_, W> {}
+
+impl<'a, W: AsyncWriteAt + ?Sized + Unpin> WriteAllAt<'a, W> {
+    pub(super) fn new(writer: &'a mut W, buf: &'a [u8], offset: u64) -> Self {
+        WriteAllAt { writer, buf, offset }
+    }
+}
+
+impl<W: AsyncWriteAt + ?Sized + Unpin> Future for WriteAllAt<'_, W> {
+    type Output = io::Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        while !this.buf.is_empty() {
+            let n = ready!(Pin::new(&mut this.writer).poll_write_at(cx, this.buf, this.offset))?;
+            {
+                let (_, rest) = mem::replace(&mut this.buf, &[]).split_at(n);
+                this.buf = rest;
+            }
+            this.offset += n as u64;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
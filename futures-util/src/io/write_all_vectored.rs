@@ -0,0 +1,74 @@
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_io::AsyncWrite;
+use futures_io::IoSlice;
+use std::io;
+use std::mem;
+use std::pin::Pin;
+
+/// Future for the
+/// [`write_all_vectored`](super::AsyncWriteExt::write_all_vectored) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WriteAllVectored<'a, W: ?Sized + Unpin> {
+    writer: &'a mut W,
+    bufs: &'a mut [IoSlice<'a>],
+}
+
+impl<W: ?Sized + Unpin> Unpin for WriteAllVectored<'_, W> {}
+
+impl<'a, W: AsyncWrite + ?Sized + Unpin> WriteAllVectored<'a, W> {
+    pub(super) fn new(writer: &'a mut W, bufs: &'a mut [IoSlice<'a>]) -> Self {
+        let mut this = WriteAllVectored { writer, bufs };
+        // Skip leading empty buffers so that a write of zero bytes signals
+        // `WriteZero` rather than a bogus short write.
+        advance(&mut this.bufs, 0);
+        this
+    }
+}
+
+impl<W: AsyncWrite + ?Sized + Unpin> Future for WriteAllVectored<'_, W> {
+    type Output = io::Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        while !this.bufs.is_empty() {
+            let n = ready!(Pin::new(&mut this.writer).poll_write_vectored(cx, this.bufs))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            advance(&mut this.bufs, n);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Advances `bufs` past the first `n` bytes, removing buffers which have been
+/// written completely and shrinking the first partially written one.
+fn advance<'a>(bufs: &mut &'a mut [IoSlice<'a>], n: usize) {
+    // Number of buffers to remove.
+    let mut remove = 0;
+    // Total length of all the to be removed buffers.
+    let mut accumulated_len = 0;
+    for buf in bufs.iter() {
+        if accumulated_len + buf.len() > n {
+            break;
+        } else {
+            accumulated_len += buf.len();
+            remove += 1;
+        }
+    }
+
+    *bufs = &mut mem::replace(bufs, &mut [])[remove..];
+    if let Some(first) = bufs.first_mut() {
+        let advance_by = n - accumulated_len;
+        let rest = &first[advance_by..];
+        // Safety: `rest` points into the same memory that `first` borrows for
+        // `'a`, the lifetime is only shortened by the `Deref` impl.
+        let rest: &'a [u8] = unsafe { std::slice::from_raw_parts(rest.as_ptr(), rest.len()) };
+        *first = IoSlice::new(rest);
+    } else {
+        assert_eq!(n, accumulated_len, "advancing io slices beyond their length");
+    }
+}
@@ -0,0 +1,31 @@
+use crate::io::AsyncWriteAt;
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use std::io;
+use std::pin::Pin;
+
+/// Future for the [`write_at`](super::AsyncWriteAtExt::write_at) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WriteAt<'a, W: ?Sized + Unpin> {
+    writer: &'a mut W,
+    buf: &'a [u8],
+    offset: u64,
+}
+
+impl<W: ?Sized + Unpin> Unpin for WriteAt<'_, W> {}
+
+impl<'a, W: AsyncWriteAt + ?Sized + Unpin> WriteAt<'a, W> {
+    pub(super) fn new(writer: &'a mut W, buf: &'a [u8], offset: u64) -> Self {
+        WriteAt { writer, buf, offset }
+    }
+}
+
+impl<W: AsyncWriteAt + ?Sized + Unpin> Future for WriteAt<'_, W> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        Pin::new(&mut this.writer).poll_write_at(cx, this.buf, this.offset)
+    }
+}
@@ -122,4 +122,7 @@ pub mod io;
 cfg_target_has_atomic! {
     #[cfg(feature = "alloc")]
     pub mod lock;
+
+    #[cfg(feature = "std")]
+    pub mod timer;
 }
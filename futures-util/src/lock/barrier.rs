@@ -0,0 +1,167 @@
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::mem;
+use std::pin::Pin;
+use std::sync::Mutex as StdMutex;
+
+/// A barrier which lets a number of tasks wait for each other.
+///
+/// The futures returned by [`wait`](Barrier::wait) complete once `n` of them
+/// have been polled, at which point the barrier resets and can be used
+/// again. One of the `n` tasks is picked as the leader of each round.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::future::join;
+/// use futures::lock::Barrier;
+///
+/// let barrier = Barrier::new(2);
+/// let (a, b) = block_on(join(barrier.wait(), barrier.wait()));
+/// assert!(a.is_leader() != b.is_leader());
+/// ```
+pub struct Barrier {
+    n: usize,
+    state: StdMutex<State>,
+}
+
+struct State {
+    arrived: usize,
+    // Incremented each time the barrier is released.
+    generation: u64,
+    wakers: BTreeMap<u64, Waker>,
+    next_key: u64,
+}
+
+impl Barrier {
+    /// Creates a new barrier which releases the tasks waiting on it once `n`
+    /// of them have arrived.
+    ///
+    /// A barrier with `n` of zero behaves like one with `n` of one.
+    pub fn new(n: usize) -> Barrier {
+        Barrier {
+            n,
+            state: StdMutex::new(State {
+                arrived: 0,
+                generation: 0,
+                wakers: BTreeMap::new(),
+                next_key: 0,
+            }),
+        }
+    }
+
+    /// Returns a future which completes once `n` tasks are waiting on the
+    /// barrier.
+    ///
+    /// A task arrives at the barrier when the future is first polled. If the
+    /// future is dropped before the barrier is released, the task no longer
+    /// counts as having arrived.
+    pub fn wait(&self) -> BarrierWaitFuture<'_> {
+        BarrierWaitFuture { barrier: Some(self), waiting: None }
+    }
+}
+
+impl fmt::Debug for Barrier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("Barrier")
+            .field("n", &self.n)
+            .field("arrived", &state.arrived)
+            .finish()
+    }
+}
+
+/// Future for the [`wait`](Barrier::wait) method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct BarrierWaitFuture<'a> {
+    // `None` indicates that the barrier was released.
+    barrier: Option<&'a Barrier>,
+    // The generation the task arrived in, and the key of its waker.
+    waiting: Option<(u64, u64)>,
+}
+
+impl fmt::Debug for BarrierWaitFuture<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BarrierWaitFuture")
+            .field("was_released", &self.barrier.is_none())
+            .field("arrived", &self.waiting.is_some())
+            .finish()
+    }
+}
+
+impl FusedFuture for BarrierWaitFuture<'_> {
+    fn is_terminated(&self) -> bool {
+        self.barrier.is_none()
+    }
+}
+
+impl Future for BarrierWaitFuture<'_> {
+    type Output = BarrierWaitResult;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<BarrierWaitResult> {
+        let this = &mut *self;
+        let barrier = this.barrier.expect("polled BarrierWaitFuture after completion");
+        let mut state = barrier.state.lock().unwrap();
+        match this.waiting {
+            Some((generation, key)) => {
+                if state.generation == generation {
+                    let waker = state.wakers.get_mut(&key).unwrap();
+                    if !waker.will_wake(cx.waker()) {
+                        *waker = cx.waker().clone();
+                    }
+                    return Poll::Pending;
+                }
+                this.waiting = None;
+                this.barrier = None;
+                Poll::Ready(BarrierWaitResult { is_leader: false })
+            }
+            None => {
+                state.arrived += 1;
+                if state.arrived < barrier.n {
+                    let key = state.next_key;
+                    state.next_key += 1;
+                    state.wakers.insert(key, cx.waker().clone());
+                    this.waiting = Some((state.generation, key));
+                    return Poll::Pending;
+                }
+                // The last task to arrive releases the others and leads.
+                state.arrived = 0;
+                state.generation += 1;
+                let wakers = mem::replace(&mut state.wakers, Default::default());
+                drop(state);
+                for (_, waker) in wakers {
+                    waker.wake();
+                }
+                this.barrier = None;
+                Poll::Ready(BarrierWaitResult { is_leader: true })
+            }
+        }
+    }
+}
+
+impl Drop for BarrierWaitFuture<'_> {
+    fn drop(&mut self) {
+        if let (Some(barrier), Some((generation, key))) = (self.barrier, self.waiting) {
+            let mut state = barrier.state.lock().unwrap();
+            if state.generation == generation {
+                state.arrived -= 1;
+                state.wakers.remove(&key);
+            }
+        }
+    }
+}
+
+/// The result of waiting on a [`Barrier`].
+#[derive(Debug, Clone, Copy)]
+pub struct BarrierWaitResult {
+    is_leader: bool,
+}
+
+impl BarrierWaitResult {
+    /// Returns `true` for exactly one of the tasks released by each round of
+    /// the barrier.
+    pub fn is_leader(&self) -> bool {
+        self.is_leader
+    }
+}
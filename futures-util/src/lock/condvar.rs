@@ -0,0 +1,272 @@
+use super::mutex::{Mutex, MutexGuard, MutexLockFuture};
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Mutex as StdMutex;
+
+/// A futures-aware condition variable, for use with a [`Mutex`].
+///
+/// A task holding the lock of a mutex can [`wait`](Condvar::wait) on the
+/// condition variable, which unlocks the mutex until another task calls
+/// [`notify_one`](Condvar::notify_one) or
+/// [`notify_all`](Condvar::notify_all), and then locks it again. The task
+/// starts waiting before the mutex is unlocked, so a notification sent by a
+/// task which then acquires the lock is never missed.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::future::join;
+/// use futures::lock::{Condvar, Mutex};
+///
+/// let ready = Mutex::new(false);
+/// let condvar = Condvar::new();
+///
+/// block_on(join(
+///     async {
+///         let guard = condvar.wait_until(ready.lock().await, |ready| *ready).await;
+///         assert!(*guard);
+///     },
+///     async {
+///         *ready.lock().await = true;
+///         condvar.notify_all();
+///     },
+/// ));
+/// ```
+pub struct Condvar {
+    state: StdMutex<State>,
+}
+
+struct State {
+    // Waiters, ordered by when they started waiting.
+    waiters: BTreeMap<u64, Waiter>,
+    next_key: u64,
+}
+
+struct Waiter {
+    // `None` until the waiting future is first polled.
+    waker: Option<Waker>,
+    notified: bool,
+}
+
+impl State {
+    // Notifies the waiter which has been waiting the longest, returning its
+    // waker.
+    fn notify_one(&mut self) -> Option<Waker> {
+        let waiter = self.waiters.values_mut().find(|waiter| !waiter.notified)?;
+        waiter.notified = true;
+        waiter.waker.clone()
+    }
+}
+
+impl Condvar {
+    /// Creates a new condition variable.
+    pub fn new() -> Condvar {
+        Condvar {
+            state: StdMutex::new(State {
+                waiters: BTreeMap::new(),
+                next_key: 0,
+            }),
+        }
+    }
+
+    /// Unlocks the mutex of `guard` and waits for a notification, returning
+    /// a future which locks the mutex again once one is received.
+    ///
+    /// As with other condition variables, the condition the task is waiting
+    /// for should be checked again once the future resolves, since another
+    /// task may have changed it in the meantime. [`wait_until`] does so.
+    ///
+    /// The future is cancel-safe: if it is dropped after receiving a
+    /// notification from [`notify_one`](Condvar::notify_one), the
+    /// notification is passed on to the next waiting task.
+    ///
+    /// [`wait_until`]: Condvar::wait_until
+    pub fn wait<'a, T: ?Sized>(&'a self, guard: MutexGuard<'a, T>) -> CondvarWaitFuture<'a, T> {
+        let key = {
+            let mut state = self.state.lock().unwrap();
+            let key = state.next_key;
+            state.next_key += 1;
+            state.waiters.insert(key, Waiter { waker: None, notified: false });
+            key
+        };
+        let mutex = guard.mutex;
+        drop(guard);
+        CondvarWaitFuture {
+            condvar: self,
+            mutex,
+            wait_key: Some(key),
+            lock: None,
+        }
+    }
+
+    /// Waits on the condition variable until `pred` returns `true` for the
+    /// value of the mutex of `guard`, returning a future which resolves to
+    /// the guard once it does.
+    ///
+    /// `pred` is called with the lock held, first right away, and then each
+    /// time the task is notified and has locked the mutex again.
+    pub fn wait_until<'a, T: ?Sized, F>(&'a self, guard: MutexGuard<'a, T>, pred: F) -> CondvarWaitUntilFuture<'a, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        CondvarWaitUntilFuture {
+            condvar: self,
+            guard: Some(guard),
+            wait: None,
+            pred,
+        }
+    }
+
+    /// Wakes the task which has been waiting the longest.
+    pub fn notify_one(&self) {
+        let waker = self.state.lock().unwrap().notify_one();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Wakes all the tasks which are waiting.
+    pub fn notify_all(&self) {
+        let wakers: Vec<Waker> = {
+            let mut state = self.state.lock().unwrap();
+            state.waiters.values_mut()
+                .filter(|waiter| !waiter.notified)
+                .filter_map(|waiter| {
+                    waiter.notified = true;
+                    waiter.waker.clone()
+                })
+                .collect()
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+impl Default for Condvar {
+    fn default() -> Condvar {
+        Condvar::new()
+    }
+}
+
+impl fmt::Debug for Condvar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("Condvar")
+            .field("waiters", &state.waiters.len())
+            .finish()
+    }
+}
+
+/// Future for the [`wait`](Condvar::wait) method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CondvarWaitFuture<'a, T: ?Sized> {
+    condvar: &'a Condvar,
+    mutex: &'a Mutex<T>,
+    // `None` once a notification was received.
+    wait_key: Option<u64>,
+    lock: Option<MutexLockFuture<'a, T>>,
+}
+
+impl<T: ?Sized> fmt::Debug for CondvarWaitFuture<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CondvarWaitFuture")
+            .field("was_notified", &self.wait_key.is_none())
+            .field("lock", &self.lock)
+            .finish()
+    }
+}
+
+impl<T: ?Sized> FusedFuture for CondvarWaitFuture<'_, T> {
+    fn is_terminated(&self) -> bool {
+        self.wait_key.is_none() && self.lock.as_ref().map_or(false, FusedFuture::is_terminated)
+    }
+}
+
+impl<'a, T: ?Sized> Future for CondvarWaitFuture<'a, T> {
+    type Output = MutexGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        if let Some(key) = this.wait_key {
+            let mut state = this.condvar.state.lock().unwrap();
+            let waiter = state.waiters.get_mut(&key).unwrap();
+            if !waiter.notified {
+                match &waiter.waker {
+                    Some(waker) if waker.will_wake(cx.waker()) => {}
+                    _ => waiter.waker = Some(cx.waker().clone()),
+                }
+                return Poll::Pending;
+            }
+            state.waiters.remove(&key);
+            this.wait_key = None;
+            this.lock = Some(this.mutex.lock());
+        }
+        let lock = this.lock.as_mut().expect("polled CondvarWaitFuture after completion");
+        Pin::new(lock).poll(cx)
+    }
+}
+
+impl<T: ?Sized> Drop for CondvarWaitFuture<'_, T> {
+    fn drop(&mut self) {
+        if let Some(key) = self.wait_key {
+            let waker = {
+                let mut state = self.condvar.state.lock().unwrap();
+                let waiter = state.waiters.remove(&key).unwrap();
+                // A notification which was received but not acted upon is
+                // passed on, so that it is not lost.
+                if waiter.notified {
+                    state.notify_one()
+                } else {
+                    None
+                }
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Future for the [`wait_until`](Condvar::wait_until) method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CondvarWaitUntilFuture<'a, T: ?Sized, F> {
+    condvar: &'a Condvar,
+    // Held while `pred` is being checked.
+    guard: Option<MutexGuard<'a, T>>,
+    wait: Option<CondvarWaitFuture<'a, T>>,
+    pred: F,
+}
+
+impl<T: ?Sized, F> fmt::Debug for CondvarWaitUntilFuture<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CondvarWaitUntilFuture")
+            .field("wait", &self.wait)
+            .finish()
+    }
+}
+
+impl<T: ?Sized, F> Unpin for CondvarWaitUntilFuture<'_, T, F> {}
+
+impl<'a, T: ?Sized, F> Future for CondvarWaitUntilFuture<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Output = MutexGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        loop {
+            if let Some(wait) = this.wait.as_mut() {
+                let guard = ready!(Pin::new(wait).poll(cx));
+                this.wait = None;
+                this.guard = Some(guard);
+            }
+            let mut guard = this.guard.take().expect("polled CondvarWaitUntilFuture after completion");
+            if (this.pred)(&mut *guard) {
+                return Poll::Ready(guard);
+            }
+            this.wait = Some(this.condvar.wait(guard));
+        }
+    }
+}
@@ -1,9 +1,49 @@
 //! Futures-powered synchronization primitives.
 
+#[cfg(feature = "std")]
+mod barrier;
+#[cfg(feature = "std")]
+pub use self::barrier::{Barrier, BarrierWaitFuture, BarrierWaitResult};
+
+#[cfg(feature = "std")]
+mod condvar;
+#[cfg(feature = "std")]
+pub use self::condvar::{Condvar, CondvarWaitFuture, CondvarWaitUntilFuture};
+
 #[cfg(feature = "std")]
 mod mutex;
 #[cfg(feature = "std")]
-pub use self::mutex::{Mutex, MutexLockFuture, MutexGuard};
+pub use self::mutex::{
+    LockTimeoutError, MappedMutexGuard, Mutex, MutexLockFuture, MutexLockTimeoutFuture, MutexGuard,
+    OwnedMutexGuard, OwnedMutexLockFuture,
+};
+
+#[cfg(feature = "std")]
+mod notify;
+#[cfg(feature = "std")]
+pub use self::notify::{Notified, Notify};
+
+#[cfg(feature = "std")]
+mod once_cell;
+#[cfg(feature = "std")]
+pub use self::once_cell::{GetOrInit, Lazy, OnceCell};
+
+#[cfg(feature = "std")]
+mod rwlock;
+#[cfg(feature = "std")]
+pub use self::rwlock::{
+    MappedRwLockReadGuard, MappedRwLockWriteGuard,
+    OwnedRwLockReadFuture, OwnedRwLockReadGuard, OwnedRwLockWriteFuture, OwnedRwLockWriteGuard,
+    RwLock, RwLockReadFuture, RwLockReadGuard, RwLockWriteFuture, RwLockWriteGuard,
+};
+
+#[cfg(feature = "std")]
+mod semaphore;
+#[cfg(feature = "std")]
+pub use self::semaphore::{
+    OwnedSemaphoreAcquireFuture, OwnedSemaphorePermit, Semaphore, SemaphoreAcquireFuture,
+    SemaphorePermit,
+};
 
 #[cfg(any(feature = "sink", feature = "io"))]
 #[allow(unreachable_pub)]
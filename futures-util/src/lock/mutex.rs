@@ -1,17 +1,23 @@
+use crate::timer::Delay;
 use futures_core::future::{FusedFuture, Future};
 use futures_core::task::{Context, Poll, Waker};
-use slab::Slab;
-use std::{fmt, mem};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::cell::UnsafeCell;
 use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
-use std::sync::Mutex as StdMutex;
+use std::sync::{Arc, Mutex as StdMutex};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
 /// A futures-aware mutex.
+///
+/// The lock is fair: when it is unlocked while tasks are waiting for it, it
+/// is handed directly to the task which has been waiting the longest, so
+/// tasks which keep locking and unlocking it cannot starve the others.
 pub struct Mutex<T: ?Sized> {
     state: AtomicUsize,
-    waiters: StdMutex<Slab<Waiter>>,
+    waiters: StdMutex<Waiters>,
     value: UnsafeCell<T>,
 }
 
@@ -31,9 +37,18 @@ impl<T: Default> Default for Mutex<T> {
     }
 }
 
+struct Waiters {
+    // Keys increase with each waiter, so this is ordered oldest first.
+    map: BTreeMap<usize, Waiter>,
+    next_key: usize,
+    // Number of waiters which have not been handed the lock yet.
+    waiting: usize,
+}
+
 enum Waiter {
     Waiting(Waker),
-    Woken,
+    // The lock was handed to this waiter, which now holds it.
+    Granted,
 }
 
 impl Waiter {
@@ -43,13 +58,6 @@ impl Waiter {
             _ => *self = Waiter::Waiting(waker.clone()),
         }
     }
-
-    fn wake(&mut self) {
-        match mem::replace(self, Waiter::Woken) {
-            Waiter::Waiting(waker) => waker.wake(),
-            Waiter::Woken => {},
-        }
-    }
 }
 
 #[allow(clippy::identity_op)] // https://github.com/rust-lang/rust-clippy/issues/3445
@@ -62,7 +70,11 @@ impl<T> Mutex<T> {
         Mutex {
             state: AtomicUsize::new(0),
             value: UnsafeCell::new(t),
-            waiters: StdMutex::new(Slab::new()),
+            waiters: StdMutex::new(Waiters {
+                map: BTreeMap::new(),
+                next_key: 0,
+                waiting: 0,
+            }),
         }
     }
 }
@@ -72,18 +84,34 @@ impl<T: ?Sized> Mutex<T> {
     ///
     /// If the lock is currently held, this will return `None`.
     pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
-        let old_state = self.state.fetch_or(IS_LOCKED, Ordering::Acquire);
-        if (old_state & IS_LOCKED) == 0 {
+        if self.try_acquire() {
             Some(MutexGuard { mutex: self })
         } else {
             None
         }
     }
 
+    /// Attempt to acquire the lock immediately, returning a guard which keeps
+    /// the mutex alive through an `Arc`.
+    ///
+    /// If the lock is currently held, the `Arc` is handed back as the error.
+    pub fn try_lock_owned(self: Arc<Self>) -> Result<OwnedMutexGuard<T>, Arc<Self>> {
+        if self.try_acquire() {
+            Ok(OwnedMutexGuard { mutex: self })
+        } else {
+            Err(self)
+        }
+    }
+
     /// Acquire the lock asynchronously.
     ///
     /// This method returns a future that will resolve once the lock has been
     /// successfully acquired.
+    ///
+    /// The future is cancel-safe: dropping it before it resolves removes the
+    /// task from the waiters, and if the lock had already been handed to the
+    /// task, it is handed on to the next waiter, so no waiter is left
+    /// waiting for a lock which is free.
     pub fn lock(&self) -> MutexLockFuture<'_, T> {
         MutexLockFuture {
             mutex: Some(self),
@@ -91,30 +119,165 @@ impl<T: ?Sized> Mutex<T> {
         }
     }
 
-    fn remove_waker(&self, wait_key: usize, wake_another: bool) {
-        if wait_key != WAIT_KEY_NONE {
+    /// Acquire the lock asynchronously, waiting at most `dur` for it.
+    ///
+    /// If the deadline passes first, the returned future resolves to a
+    /// [`LockTimeoutError`], and the task stops waiting for the lock in the
+    /// same way as when a [`lock`](Mutex::lock) future is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::lock::Mutex;
+    /// use std::time::Duration;
+    ///
+    /// let mutex = Mutex::new(0);
+    /// let guard = mutex.lock().await;
+    /// assert!(mutex.lock_timeout(Duration::from_millis(10)).await.is_err());
+    /// drop(guard);
+    /// assert!(mutex.lock_timeout(Duration::from_millis(10)).await.is_ok());
+    /// # });
+    /// ```
+    pub fn lock_timeout(&self, dur: Duration) -> MutexLockTimeoutFuture<'_, T> {
+        MutexLockTimeoutFuture {
+            lock: Some(self.lock()),
+            delay: Delay::new(dur),
+        }
+    }
+
+    /// Returns the number of tasks waiting for the lock.
+    ///
+    /// The count is only a snapshot, as tasks may start or stop waiting at
+    /// any time, but it is useful for diagnosing deadlocks and contention.
+    pub fn waiters(&self) -> usize {
+        self.waiters.lock().unwrap().waiting
+    }
+
+    /// Acquire the lock asynchronously, resolving to a guard which keeps the
+    /// mutex alive through an `Arc`.
+    ///
+    /// Unlike the guard of [`lock`](Mutex::lock), the guard does not borrow
+    /// the mutex, so it can be held across task boundaries or stored in
+    /// `'static` state.
+    pub fn lock_owned(self: Arc<Self>) -> OwnedMutexLockFuture<T> {
+        OwnedMutexLockFuture {
+            mutex: Some(self),
+            wait_key: WAIT_KEY_NONE,
+        }
+    }
+
+    fn try_acquire(&self) -> bool {
+        let old_state = self.state.fetch_or(IS_LOCKED, Ordering::Acquire);
+        (old_state & IS_LOCKED) == 0
+    }
+
+    fn poll_acquire(&self, wait_key: &mut usize, cx: &mut Context<'_>) -> Poll<()> {
+        if *wait_key != WAIT_KEY_NONE {
+            let mut waiters = self.waiters.lock().unwrap();
+            let waiter = waiters.map.get_mut(wait_key).unwrap();
+            if let Waiter::Waiting(_) = waiter {
+                waiter.register(cx.waker());
+                return Poll::Pending;
+            }
+            // The lock was handed to us by `unlock`.
+            waiters.map.remove(wait_key);
+            *wait_key = WAIT_KEY_NONE;
+            return Poll::Ready(());
+        }
+
+        if self.try_acquire() {
+            return Poll::Ready(());
+        }
+
+        let mut waiters = self.waiters.lock().unwrap();
+        let key = waiters.next_key;
+        waiters.next_key += 1;
+        waiters.map.insert(key, Waiter::Waiting(cx.waker().clone()));
+        waiters.waiting += 1;
+        if waiters.waiting == 1 {
+            self.state.fetch_or(HAS_WAITERS, Ordering::Relaxed); // released by mutex unlock
+        }
+
+        // Ensure that we haven't raced the unlock path, which only hands the
+        // lock over once it sees `HAS_WAITERS`, by attempting to acquire the
+        // lock again.
+        if self.try_acquire() {
+            waiters.map.remove(&key);
+            self.stop_waiting(&mut waiters);
+            return Poll::Ready(());
+        }
+
+        *wait_key = key;
+        Poll::Pending
+    }
+
+    fn unlock(&self) {
+        if self.state.compare_exchange(IS_LOCKED, 0, Ordering::AcqRel, Ordering::Relaxed).is_ok() {
+            return;
+        }
+        let waker = {
             let mut waiters = self.waiters.lock().unwrap();
-            match waiters.remove(wait_key) {
-                Waiter::Waiting(_) => {},
-                Waiter::Woken => {
-                    // We were awoken, but then dropped before we could
-                    // wake up to acquire the lock. Wake up another
-                    // waiter.
-                    if wake_another {
-                        if let Some((_i, waiter)) = waiters.iter_mut().next() {
-                            waiter.wake();
-                        }
+            self.hand_over(&mut waiters)
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    // Hands the lock, which the caller holds, to the waiter which has been
+    // waiting the longest, returning its waker. Unlocks the mutex if no one
+    // is waiting.
+    fn hand_over(&self, waiters: &mut Waiters) -> Option<Waker> {
+        let next = waiters.map.values_mut().find(|waiter| match waiter {
+            Waiter::Waiting(_) => true,
+            Waiter::Granted => false,
+        });
+        match next {
+            Some(waiter) => {
+                let waker = match std::mem::replace(waiter, Waiter::Granted) {
+                    Waiter::Waiting(waker) => waker,
+                    Waiter::Granted => unreachable!(),
+                };
+                self.stop_waiting(waiters);
+                Some(waker)
+            }
+            None => {
+                self.state.fetch_and(!IS_LOCKED, Ordering::Release);
+                None
+            }
+        }
+    }
+
+    fn stop_waiting(&self, waiters: &mut Waiters) {
+        waiters.waiting -= 1;
+        if waiters.waiting == 0 {
+            self.state.fetch_and(!HAS_WAITERS, Ordering::Relaxed); // released by mutex unlock
+        }
+    }
+
+    fn remove_waker(&self, wait_key: usize) {
+        if wait_key != WAIT_KEY_NONE {
+            let waker = {
+                let mut waiters = self.waiters.lock().unwrap();
+                match waiters.map.remove(&wait_key).unwrap() {
+                    Waiter::Waiting(_) => {
+                        self.stop_waiting(&mut waiters);
+                        None
                     }
+                    // We were handed the lock, but then dropped before we
+                    // could take it. Hand it on to the next waiter.
+                    Waiter::Granted => self.hand_over(&mut waiters),
                 }
-            }
-            if waiters.is_empty() {
-                self.state.fetch_and(!HAS_WAITERS, Ordering::Relaxed); // released by mutex unlock
+            };
+            if let Some(waker) = waker {
+                waker.wake();
             }
         }
     }
 }
 
-// Sentinel for when no slot in the `Slab` has been dedicated to this object.
+// Sentinel for when no waiter has been registered for this object.
 const WAIT_KEY_NONE: usize = usize::max_value();
 
 /// A future which resolves when the target mutex has been successfully acquired.
@@ -151,34 +314,9 @@ impl<'a, T: ?Sized> Future for MutexLockFuture<'a, T> {
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let mutex = self.mutex.expect("polled MutexLockFuture after completion");
-
-        if let Some(lock) = mutex.try_lock() {
-            mutex.remove_waker(self.wait_key, false);
-            self.mutex = None;
-            return Poll::Ready(lock);
-        }
-
-        {
-            let mut waiters = mutex.waiters.lock().unwrap();
-            if self.wait_key == WAIT_KEY_NONE {
-                self.wait_key = waiters.insert(Waiter::Waiting(cx.waker().clone()));
-                if waiters.len() == 1 {
-                    mutex.state.fetch_or(HAS_WAITERS, Ordering::Relaxed); // released by mutex unlock
-                }
-            } else {
-                waiters[self.wait_key].register(cx.waker());
-            }
-        }
-
-        // Ensure that we haven't raced `MutexGuard::drop`'s unlock path by
-        // attempting to acquire the lock again.
-        if let Some(lock) = mutex.try_lock() {
-            mutex.remove_waker(self.wait_key, false);
-            self.mutex = None;
-            return Poll::Ready(lock);
-        }
-
-        Poll::Pending
+        ready!(mutex.poll_acquire(&mut self.wait_key, cx));
+        self.mutex = None;
+        Poll::Ready(MutexGuard { mutex })
     }
 }
 
@@ -187,18 +325,71 @@ impl<T: ?Sized> Drop for MutexLockFuture<'_, T> {
         if let Some(mutex) = self.mutex {
             // This future was dropped before it acquired the mutex.
             //
-            // Remove ourselves from the map, waking up another waiter if we
-            // had been awoken to acquire the lock.
-            mutex.remove_waker(self.wait_key, true);
+            // Remove ourselves from the waiters, handing the lock on to the
+            // next waiter if it had been handed to us.
+            mutex.remove_waker(self.wait_key);
         }
     }
 }
 
+/// A future which resolves when the target mutex has been successfully
+/// acquired, or to an error once its deadline passes.
+pub struct MutexLockTimeoutFuture<'a, T: ?Sized> {
+    // `None` indicates that the future has completed.
+    lock: Option<MutexLockFuture<'a, T>>,
+    delay: Delay,
+}
+
+impl<T: ?Sized> fmt::Debug for MutexLockTimeoutFuture<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MutexLockTimeoutFuture")
+            .field("lock", &self.lock)
+            .field("delay", &self.delay)
+            .finish()
+    }
+}
+
+impl<T: ?Sized> FusedFuture for MutexLockTimeoutFuture<'_, T> {
+    fn is_terminated(&self) -> bool {
+        self.lock.is_none()
+    }
+}
+
+impl<'a, T: ?Sized> Future for MutexLockTimeoutFuture<'a, T> {
+    type Output = Result<MutexGuard<'a, T>, LockTimeoutError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let lock = this.lock.as_mut().expect("polled MutexLockTimeoutFuture after completion");
+        if let Poll::Ready(guard) = Pin::new(lock).poll(cx) {
+            this.lock = None;
+            return Poll::Ready(Ok(guard));
+        }
+        ready!(Pin::new(&mut this.delay).poll(cx));
+        // Stops waiting for the lock.
+        this.lock = None;
+        Poll::Ready(Err(LockTimeoutError(())))
+    }
+}
+
+/// The error returned by [`lock_timeout`](Mutex::lock_timeout) when the
+/// deadline passed before the lock was acquired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockTimeoutError(());
+
+impl fmt::Display for LockTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "lock timed out")
+    }
+}
+
+impl std::error::Error for LockTimeoutError {}
+
 /// An RAII guard returned by the `lock` and `try_lock` methods.
 /// When this structure is dropped (falls out of scope), the lock will be
 /// unlocked.
 pub struct MutexGuard<'a, T: ?Sized> {
-    mutex: &'a Mutex<T>,
+    pub(super) mutex: &'a Mutex<T>,
 }
 
 impl<T: ?Sized + fmt::Debug> fmt::Debug for MutexGuard<'_, T> {
@@ -210,15 +401,40 @@ impl<T: ?Sized + fmt::Debug> fmt::Debug for MutexGuard<'_, T> {
     }
 }
 
+impl<'a, T: ?Sized> MutexGuard<'a, T> {
+    /// Returns a guard for a part of the locked value, such as one of its
+    /// fields, which keeps the mutex locked until it is dropped.
+    ///
+    /// This is an associated function, as `MutexGuard` dereferences to the
+    /// locked value, whose methods it would otherwise shadow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::lock::{Mutex, MutexGuard};
+    ///
+    /// let mutex = Mutex::new((1, String::from("a")));
+    /// let mut name = MutexGuard::map(mutex.lock().await, |v| &mut v.1);
+    /// name.push('b');
+    /// drop(name);
+    /// assert_eq!(mutex.lock().await.1, "ab");
+    /// # });
+    /// ```
+    pub fn map<U: ?Sized, F>(this: MutexGuard<'a, T>, f: F) -> MappedMutexGuard<'a, T, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let mutex = this.mutex;
+        let value = f(unsafe { &mut *mutex.value.get() }) as *mut U;
+        std::mem::forget(this);
+        MappedMutexGuard { mutex, value }
+    }
+}
+
 impl<T: ?Sized> Drop for MutexGuard<'_, T> {
     fn drop(&mut self) {
-        let old_state = self.mutex.state.fetch_and(!IS_LOCKED, Ordering::AcqRel);
-        if (old_state & HAS_WAITERS) != 0 {
-            let mut waiters = self.mutex.waiters.lock().unwrap();
-            if let Some((_i, waiter)) = waiters.iter_mut().next() {
-                waiter.wake();
-            }
-        }
+        self.mutex.unlock();
     }
 }
 
@@ -235,6 +451,142 @@ impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
     }
 }
 
+/// An RAII guard for a part of the value of a mutex, returned by
+/// [`MutexGuard::map`]. When this structure is dropped (falls out of scope),
+/// the lock will be unlocked.
+pub struct MappedMutexGuard<'a, T: ?Sized, U: ?Sized> {
+    mutex: &'a Mutex<T>,
+    value: *mut U,
+}
+
+impl<'a, T: ?Sized, U: ?Sized> MappedMutexGuard<'a, T, U> {
+    /// Returns a guard for a part of the already mapped value.
+    pub fn map<V: ?Sized, F>(this: MappedMutexGuard<'a, T, U>, f: F) -> MappedMutexGuard<'a, T, V>
+    where
+        F: FnOnce(&mut U) -> &mut V,
+    {
+        let mutex = this.mutex;
+        let value = f(unsafe { &mut *this.value }) as *mut V;
+        std::mem::forget(this);
+        MappedMutexGuard { mutex, value }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized + fmt::Debug> fmt::Debug for MappedMutexGuard<'_, T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedMutexGuard")
+            .field("value", &&**self)
+            .field("mutex", &self.mutex)
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Drop for MappedMutexGuard<'_, T, U> {
+    fn drop(&mut self) {
+        self.mutex.unlock();
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Deref for MappedMutexGuard<'_, T, U> {
+    type Target = U;
+    fn deref(&self) -> &U {
+        unsafe { &*self.value }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> DerefMut for MappedMutexGuard<'_, T, U> {
+    fn deref_mut(&mut self) -> &mut U {
+        unsafe { &mut *self.value }
+    }
+}
+
+/// A future which resolves when the target mutex has been successfully
+/// acquired, with a guard owning an `Arc` of the mutex.
+pub struct OwnedMutexLockFuture<T: ?Sized> {
+    // `None` indicates that the mutex was successfully acquired.
+    mutex: Option<Arc<Mutex<T>>>,
+    wait_key: usize,
+}
+
+impl<T: ?Sized> fmt::Debug for OwnedMutexLockFuture<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedMutexLockFuture")
+            .field("was_acquired", &self.mutex.is_none())
+            .field("mutex", &self.mutex)
+            .field("wait_key", &(
+                    if self.wait_key == WAIT_KEY_NONE {
+                        None
+                    } else {
+                        Some(self.wait_key)
+                    }
+                ))
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Unpin for OwnedMutexLockFuture<T> {}
+
+impl<T: ?Sized> FusedFuture for OwnedMutexLockFuture<T> {
+    fn is_terminated(&self) -> bool {
+        self.mutex.is_none()
+    }
+}
+
+impl<T: ?Sized> Future for OwnedMutexLockFuture<T> {
+    type Output = OwnedMutexGuard<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mutex = this.mutex.as_ref().expect("polled OwnedMutexLockFuture after completion");
+        ready!(mutex.poll_acquire(&mut this.wait_key, cx));
+        Poll::Ready(OwnedMutexGuard { mutex: this.mutex.take().unwrap() })
+    }
+}
+
+impl<T: ?Sized> Drop for OwnedMutexLockFuture<T> {
+    fn drop(&mut self) {
+        if let Some(mutex) = &self.mutex {
+            // This future was dropped before it acquired the mutex.
+            mutex.remove_waker(self.wait_key);
+        }
+    }
+}
+
+/// An RAII guard returned by the `lock_owned` and `try_lock_owned` methods.
+/// When this structure is dropped (falls out of scope), the lock will be
+/// unlocked.
+pub struct OwnedMutexGuard<T: ?Sized> {
+    mutex: Arc<Mutex<T>>,
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for OwnedMutexGuard<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedMutexGuard")
+            .field("value", &&**self)
+            .field("mutex", &self.mutex)
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Drop for OwnedMutexGuard<T> {
+    fn drop(&mut self) {
+        self.mutex.unlock();
+    }
+}
+
+impl<T: ?Sized> Deref for OwnedMutexGuard<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for OwnedMutexGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
+
 // Mutexes can be moved freely between threads and acquired on any thread so long
 // as the inner value can be safely sent between threads.
 unsafe impl<T: ?Sized + Send> Send for Mutex<T> {}
@@ -250,3 +602,15 @@ unsafe impl<T: ?Sized> Sync for MutexLockFuture<'_, T> {}
 // lock is essentially spinlock-equivalent (attempt to flip an atomic bool)
 unsafe impl<T: ?Sized + Send> Send for MutexGuard<'_, T> {}
 unsafe impl<T: ?Sized + Sync> Sync for MutexGuard<'_, T> {}
+
+unsafe impl<T: ?Sized + Send> Send for OwnedMutexLockFuture<T> {}
+unsafe impl<T: ?Sized> Sync for OwnedMutexLockFuture<T> {}
+
+unsafe impl<T: ?Sized + Send> Send for OwnedMutexGuard<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for OwnedMutexGuard<T> {}
+
+unsafe impl<T: ?Sized + Send> Send for MutexLockTimeoutFuture<'_, T> {}
+unsafe impl<T: ?Sized> Sync for MutexLockTimeoutFuture<'_, T> {}
+
+unsafe impl<T: ?Sized + Send, U: ?Sized + Send> Send for MappedMutexGuard<'_, T, U> {}
+unsafe impl<T: ?Sized + Sync, U: ?Sized + Sync> Sync for MappedMutexGuard<'_, T, U> {}
@@ -0,0 +1,226 @@
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Mutex as StdMutex;
+
+/// Notifies tasks waiting for an event.
+///
+/// Tasks wait with [`notified`](Notify::notified), and are woken by
+/// [`notify_one`](Notify::notify_one), which wakes the task which has been
+/// waiting the longest, or by [`notify_waiters`](Notify::notify_waiters),
+/// which wakes all of them.
+///
+/// If `notify_one` is called while no task is waiting, a single permit is
+/// stored, and the next call to `notified` completes immediately by
+/// consuming it. This means a notification which is sent between a task
+/// checking its condition and starting to wait is not lost.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::lock::Notify;
+///
+/// let notify = Notify::new();
+/// notify.notify_one();
+///
+/// // The permit stored above completes this right away.
+/// block_on(notify.notified());
+/// ```
+pub struct Notify {
+    state: StdMutex<State>,
+}
+
+struct State {
+    permit: bool,
+    // Incremented by each call to `notify_waiters`.
+    generation: u64,
+    // Waiters, ordered by when they started waiting.
+    waiters: BTreeMap<u64, Waiter>,
+    next_key: u64,
+}
+
+struct Waiter {
+    waker: Waker,
+    notified: Option<Notification>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Notification {
+    One,
+    All,
+}
+
+impl State {
+    // Notifies the waiter which has been waiting the longest, or stores a
+    // permit if there is none, returning the waker to wake.
+    fn notify_one(&mut self) -> Option<Waker> {
+        match self.waiters.values_mut().find(|waiter| waiter.notified.is_none()) {
+            Some(waiter) => {
+                waiter.notified = Some(Notification::One);
+                Some(waiter.waker.clone())
+            }
+            None => {
+                self.permit = true;
+                None
+            }
+        }
+    }
+}
+
+impl Notify {
+    /// Creates a new `Notify` without a stored permit.
+    pub fn new() -> Notify {
+        Notify {
+            state: StdMutex::new(State {
+                permit: false,
+                generation: 0,
+                waiters: BTreeMap::new(),
+                next_key: 0,
+            }),
+        }
+    }
+
+    /// Returns a future which completes when this `Notify` is notified.
+    ///
+    /// The future counts as waiting for [`notify_waiters`] from the moment it
+    /// is created, and for [`notify_one`] from when it is first polled.
+    ///
+    /// [`notify_one`]: Notify::notify_one
+    /// [`notify_waiters`]: Notify::notify_waiters
+    pub fn notified(&self) -> Notified<'_> {
+        let generation = self.state.lock().unwrap().generation;
+        Notified { notify: Some(self), generation, wait_key: None }
+    }
+
+    /// Wakes the task which has been waiting the longest, or stores a permit
+    /// for the next call to [`notified`](Notify::notified) if no task is
+    /// waiting.
+    ///
+    /// At most one permit is stored, however many times this is called.
+    pub fn notify_one(&self) {
+        let waker = self.state.lock().unwrap().notify_one();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Wakes all the tasks which are waiting, including the futures returned
+    /// by [`notified`](Notify::notified) which have not been polled yet.
+    ///
+    /// Unlike [`notify_one`](Notify::notify_one), this does not store a
+    /// permit if no task is waiting.
+    pub fn notify_waiters(&self) {
+        let wakers: Vec<Waker> = {
+            let mut state = self.state.lock().unwrap();
+            state.generation += 1;
+            state.waiters.values_mut()
+                .filter(|waiter| waiter.notified.is_none())
+                .map(|waiter| {
+                    waiter.notified = Some(Notification::All);
+                    waiter.waker.clone()
+                })
+                .collect()
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+impl Default for Notify {
+    fn default() -> Notify {
+        Notify::new()
+    }
+}
+
+impl fmt::Debug for Notify {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("Notify")
+            .field("permit", &state.permit)
+            .field("waiters", &state.waiters.len())
+            .finish()
+    }
+}
+
+/// Future for the [`notified`](Notify::notified) method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Notified<'a> {
+    // `None` indicates that the notification was received.
+    notify: Option<&'a Notify>,
+    generation: u64,
+    wait_key: Option<u64>,
+}
+
+impl fmt::Debug for Notified<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Notified")
+            .field("was_notified", &self.notify.is_none())
+            .field("wait_key", &self.wait_key)
+            .finish()
+    }
+}
+
+impl FusedFuture for Notified<'_> {
+    fn is_terminated(&self) -> bool {
+        self.notify.is_none()
+    }
+}
+
+impl Future for Notified<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+        let notify = this.notify.expect("polled Notified after completion");
+        let mut state = notify.state.lock().unwrap();
+        match this.wait_key {
+            None => {
+                if state.generation != this.generation {
+                    // `notify_waiters` was called since this was created.
+                } else if state.permit {
+                    state.permit = false;
+                } else {
+                    let key = state.next_key;
+                    state.next_key += 1;
+                    state.waiters.insert(key, Waiter { waker: cx.waker().clone(), notified: None });
+                    this.wait_key = Some(key);
+                    return Poll::Pending;
+                }
+            }
+            Some(key) => {
+                let waiter = state.waiters.get_mut(&key).unwrap();
+                if waiter.notified.is_none() {
+                    if !waiter.waker.will_wake(cx.waker()) {
+                        waiter.waker = cx.waker().clone();
+                    }
+                    return Poll::Pending;
+                }
+                state.waiters.remove(&key);
+                this.wait_key = None;
+            }
+        }
+        this.notify = None;
+        Poll::Ready(())
+    }
+}
+
+impl Drop for Notified<'_> {
+    fn drop(&mut self) {
+        if let (Some(notify), Some(key)) = (self.notify, self.wait_key) {
+            let waker = {
+                let mut state = notify.state.lock().unwrap();
+                let waiter = state.waiters.remove(&key).unwrap();
+                // A `notify_one` which was received but not consumed is
+                // passed on, so that it is not lost.
+                if waiter.notified == Some(Notification::One) {
+                    state.notify_one()
+                } else {
+                    None
+                }
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use super::semaphore::{Semaphore, SemaphoreAcquireFuture, SemaphorePermit};
+use futures_core::future::{BoxFuture, Future};
+use futures_core::task::{Context, Poll};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A cell which is written to at most once, by an asynchronous initializer.
+///
+/// However many tasks call [`get_or_init`](OnceCell::get_or_init) at once,
+/// only one initializer runs at a time, and once one has completed the
+/// others are not run at all. If the future of an initializer is dropped
+/// before it completes, the initializer of another caller runs instead.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::lock::OnceCell;
+///
+/// let config = OnceCell::new();
+///
+/// block_on(async {
+///     let value = config.get_or_init(|| async { "fetched".to_string() }).await;
+///     assert_eq!(value, "fetched");
+///     assert_eq!(config.get().map(String::as_str), Some("fetched"));
+/// });
+/// ```
+pub struct OnceCell<T> {
+    initialized: AtomicBool,
+    value: UnsafeCell<Option<T>>,
+    // Held by the task which is running its initializer.
+    semaphore: Semaphore,
+}
+
+impl<T> OnceCell<T> {
+    /// Creates a new, empty cell.
+    pub fn new() -> OnceCell<T> {
+        OnceCell {
+            initialized: AtomicBool::new(false),
+            value: UnsafeCell::new(None),
+            semaphore: Semaphore::new(1),
+        }
+    }
+
+    /// Returns a reference to the value of the cell, or `None` if it has not
+    /// been initialized yet.
+    pub fn get(&self) -> Option<&T> {
+        if self.initialized.load(Ordering::Acquire) {
+            // Safety: the value is never written to again once initialized.
+            unsafe { (*self.value.get()).as_ref() }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the value of the cell, or `None` if it
+    /// has not been initialized yet.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.value.get_mut().as_mut()
+    }
+
+    /// Sets the value of the cell.
+    ///
+    /// Returns `value` back if the cell has been initialized already, or if
+    /// an initializer is running.
+    pub fn set(&self, value: T) -> Result<(), T> {
+        let permit = match self.semaphore.try_acquire(1) {
+            Some(permit) => permit,
+            None => return Err(value),
+        };
+        if self.get().is_some() {
+            return Err(value);
+        }
+        // Safety: the permit is held, so no one else is writing to the cell.
+        unsafe { self.initialize(value, permit) };
+        Ok(())
+    }
+
+    /// Returns a future which resolves to a reference to the value of the
+    /// cell, initializing it with the future returned by `init` if it has
+    /// not been initialized yet.
+    pub fn get_or_init<F, Fut>(&self, init: F) -> GetOrInit<'_, T, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        GetOrInit { cell: self, init: Some(init), acquire: None, permit: None, future: None }
+    }
+
+    /// Consumes the cell, returning its value if it has been initialized.
+    pub fn into_inner(self) -> Option<T> {
+        self.value.into_inner()
+    }
+
+    // Safety: `permit` must be a permit of `self.semaphore`, and the cell
+    // must not have been initialized yet.
+    unsafe fn initialize(&self, value: T, permit: SemaphorePermit<'_>) -> &T {
+        *self.value.get() = Some(value);
+        self.initialized.store(true, Ordering::Release);
+        drop(permit);
+        self.get().unwrap()
+    }
+}
+
+impl<T> Default for OnceCell<T> {
+    fn default() -> OnceCell<T> {
+        OnceCell::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OnceCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnceCell")
+            .field("value", &self.get())
+            .finish()
+    }
+}
+
+impl<T> From<T> for OnceCell<T> {
+    fn from(value: T) -> OnceCell<T> {
+        OnceCell {
+            initialized: AtomicBool::new(true),
+            value: UnsafeCell::new(Some(value)),
+            semaphore: Semaphore::new(1),
+        }
+    }
+}
+
+// The cell hands out shared references to its value on any thread, and the
+// value can be written from any thread.
+unsafe impl<T: Send> Send for OnceCell<T> {}
+unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
+
+/// Future for the [`get_or_init`](OnceCell::get_or_init) method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct GetOrInit<'a, T, F, Fut> {
+    cell: &'a OnceCell<T>,
+    init: Option<F>,
+    acquire: Option<SemaphoreAcquireFuture<'a>>,
+    permit: Option<SemaphorePermit<'a>>,
+    future: Option<Fut>,
+}
+
+impl<'a, T, F, Fut> GetOrInit<'a, T, F, Fut> {
+    unsafe_unpinned!(init: Option<F>);
+    unsafe_unpinned!(acquire: Option<SemaphoreAcquireFuture<'a>>);
+    unsafe_unpinned!(permit: Option<SemaphorePermit<'a>>);
+    unsafe_pinned!(future: Option<Fut>);
+}
+
+impl<T, F, Fut: Unpin> Unpin for GetOrInit<'_, T, F, Fut> {}
+
+impl<'a, T, F, Fut> Future for GetOrInit<'a, T, F, Fut>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    type Output = &'a T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<&'a T> {
+        let cell = self.cell;
+        if let Some(future) = self.as_mut().future().as_pin_mut() {
+            let value = ready!(future.poll(cx));
+            self.as_mut().future().set(None);
+            let permit = self.as_mut().permit().take().unwrap();
+            // Safety: the permit is held, and the cell was checked to be
+            // uninitialized after acquiring it.
+            return Poll::Ready(unsafe { cell.initialize(value, permit) });
+        }
+        if let Some(value) = cell.get() {
+            return Poll::Ready(value);
+        }
+
+        let acquire = self.as_mut().acquire()
+            .get_or_insert_with(|| cell.semaphore.acquire(1));
+        let permit = ready!(Pin::new(acquire).poll(cx));
+        *self.as_mut().acquire() = None;
+        // Another initializer may have completed while this one was waiting.
+        if let Some(value) = cell.get() {
+            return Poll::Ready(value);
+        }
+
+        let init = self.as_mut().init().take().expect("polled GetOrInit after completion");
+        *self.as_mut().permit() = Some(permit);
+        self.as_mut().future().set(Some(init()));
+        self.poll(cx)
+    }
+}
+
+impl<T, F, Fut> fmt::Debug for GetOrInit<'_, T, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GetOrInit")
+            .field("initializing", &self.future.is_some())
+            .finish()
+    }
+}
+
+/// A value which is initialized asynchronously on first access.
+///
+/// The initializer is run through a [`OnceCell`], so it runs only once even
+/// if the value is forced by many tasks at once.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::lock::Lazy;
+///
+/// let pool_size = Lazy::new(|| async { 4 });
+///
+/// block_on(async {
+///     assert_eq!(*pool_size.force().await, 4);
+/// });
+/// ```
+pub struct Lazy<T, F = fn() -> BoxFuture<'static, T>> {
+    cell: OnceCell<T>,
+    init: F,
+}
+
+impl<T, F> Lazy<T, F> {
+    /// Creates a new lazy value, which is initialized with the future
+    /// returned by `init`.
+    pub fn new(init: F) -> Lazy<T, F> {
+        Lazy { cell: OnceCell::new(), init }
+    }
+
+    /// Returns a reference to the value, or `None` if it has not been
+    /// initialized yet.
+    pub fn get(&self) -> Option<&T> {
+        self.cell.get()
+    }
+}
+
+impl<T, F, Fut> Lazy<T, F>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = T>,
+{
+    /// Returns a future which resolves to a reference to the value,
+    /// initializing it if it has not been initialized yet.
+    pub fn force(&self) -> GetOrInit<'_, T, &F, Fut> {
+        self.cell.get_or_init(&self.init)
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for Lazy<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lazy")
+            .field("value", &self.cell.get())
+            .finish()
+    }
+}
@@ -0,0 +1,630 @@
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{Context, Poll, Waker};
+use std::cell::UnsafeCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex as StdMutex};
+
+/// A futures-aware read-write lock.
+///
+/// Any number of readers can hold the lock at once, or a single writer.
+/// The lock is fair: tasks acquire it in the order they started waiting,
+/// and once a writer is waiting, readers which arrive after it wait for it
+/// too, so a steady stream of readers cannot starve writers.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::lock::RwLock;
+///
+/// let lock = RwLock::new(1);
+/// block_on(async {
+///     {
+///         let r1 = lock.read().await;
+///         let r2 = lock.read().await;
+///         assert_eq!(*r1 + *r2, 2);
+///     }
+///     *lock.write().await += 1;
+///     assert_eq!(*lock.read().await, 2);
+/// });
+/// ```
+pub struct RwLock<T: ?Sized> {
+    raw: RawRwLock,
+    value: UnsafeCell<T>,
+}
+
+// The part of the lock which does not depend on `T`.
+struct RawRwLock {
+    state: StdMutex<State>,
+}
+
+struct State {
+    // Number of readers holding the lock, and whether a writer holds it.
+    // Waiters which have been granted the lock count as holding it.
+    readers: usize,
+    writer: bool,
+    // Waiters, ordered by when they started waiting.
+    waiters: BTreeMap<u64, Waiter>,
+    next_key: u64,
+    // Number of waiters which have not been granted the lock yet.
+    queued: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Access {
+    Read,
+    Write,
+}
+
+struct Waiter {
+    access: Access,
+    waker: Waker,
+    granted: bool,
+}
+
+impl State {
+    fn try_grant(&mut self, access: Access) -> bool {
+        match access {
+            Access::Read if !self.writer => {
+                self.readers += 1;
+                true
+            }
+            Access::Write if !self.writer && self.readers == 0 => {
+                self.writer = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn release(&mut self, access: Access) {
+        match access {
+            Access::Read => self.readers -= 1,
+            Access::Write => self.writer = false,
+        }
+    }
+
+    // Grants the lock to the waiters at the front of the queue which can
+    // take it now, returning their wakers.
+    fn dispatch(&mut self) -> Vec<Waker> {
+        let mut wakers = Vec::new();
+        let keys: Vec<u64> = self.waiters.iter()
+            .filter(|(_, waiter)| !waiter.granted)
+            .map(|(key, _)| *key)
+            .collect();
+        for key in keys {
+            let access = self.waiters[&key].access;
+            if !self.try_grant(access) {
+                break;
+            }
+            let waiter = self.waiters.get_mut(&key).unwrap();
+            waiter.granted = true;
+            self.queued -= 1;
+            wakers.push(waiter.waker.clone());
+        }
+        wakers
+    }
+}
+
+impl RawRwLock {
+    fn new() -> RawRwLock {
+        RawRwLock {
+            state: StdMutex::new(State {
+                readers: 0,
+                writer: false,
+                waiters: BTreeMap::new(),
+                next_key: 0,
+                queued: 0,
+            }),
+        }
+    }
+
+    fn try_acquire(&self, access: Access) -> bool {
+        let mut state = self.state.lock().unwrap();
+        // Skipping the queue would be unfair to the waiters.
+        state.queued == 0 && state.try_grant(access)
+    }
+
+    fn poll_acquire(&self, access: Access, wait_key: &mut Option<u64>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        match *wait_key {
+            None => {
+                if state.queued == 0 && state.try_grant(access) {
+                    return Poll::Ready(());
+                }
+                let key = state.next_key;
+                state.next_key += 1;
+                state.queued += 1;
+                state.waiters.insert(key, Waiter { access, waker: cx.waker().clone(), granted: false });
+                *wait_key = Some(key);
+                Poll::Pending
+            }
+            Some(key) => {
+                let waiter = state.waiters.get_mut(&key).unwrap();
+                if waiter.granted {
+                    state.waiters.remove(&key);
+                    *wait_key = None;
+                    return Poll::Ready(());
+                }
+                if !waiter.waker.will_wake(cx.waker()) {
+                    waiter.waker = cx.waker().clone();
+                }
+                Poll::Pending
+            }
+        }
+    }
+
+    // Removes a waiter whose future was dropped, releasing the lock if it
+    // had been granted it in the meantime.
+    fn cancel(&self, access: Access, key: u64) {
+        let wakers = {
+            let mut state = self.state.lock().unwrap();
+            let waiter = state.waiters.remove(&key).unwrap();
+            if waiter.granted {
+                state.release(access);
+            } else {
+                state.queued -= 1;
+            }
+            // A writer which gave up may have been holding up the readers
+            // behind it.
+            state.dispatch()
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    fn release(&self, access: Access) {
+        let wakers = {
+            let mut state = self.state.lock().unwrap();
+            state.release(access);
+            state.dispatch()
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+impl<T> RwLock<T> {
+    /// Creates a new futures-aware read-write lock.
+    pub fn new(t: T) -> RwLock<T> {
+        RwLock {
+            raw: RawRwLock::new(),
+            value: UnsafeCell::new(t),
+        }
+    }
+
+    /// Consumes the lock, returning the underlying data.
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: ?Sized> RwLock<T> {
+    /// Attempts to acquire the lock for reading immediately.
+    ///
+    /// Returns `None` if a writer holds the lock or is waiting for it.
+    pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+        if self.raw.try_acquire(Access::Read) {
+            Some(RwLockReadGuard { lock: self })
+        } else {
+            None
+        }
+    }
+
+    /// Attempts to acquire the lock for writing immediately.
+    ///
+    /// Returns `None` if the lock is held, or if other tasks are waiting
+    /// for it.
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        if self.raw.try_acquire(Access::Write) {
+            Some(RwLockWriteGuard { lock: self })
+        } else {
+            None
+        }
+    }
+
+    /// Acquires the lock for reading asynchronously.
+    ///
+    /// This method returns a future that will resolve once the lock has been
+    /// successfully acquired.
+    pub fn read(&self) -> RwLockReadFuture<'_, T> {
+        RwLockReadFuture { lock: Some(self), wait_key: None }
+    }
+
+    /// Acquires the lock for writing asynchronously.
+    ///
+    /// This method returns a future that will resolve once the lock has been
+    /// successfully acquired.
+    pub fn write(&self) -> RwLockWriteFuture<'_, T> {
+        RwLockWriteFuture { lock: Some(self), wait_key: None }
+    }
+
+    /// Attempts to acquire the lock for reading immediately, returning a
+    /// guard which keeps the lock alive through an `Arc`.
+    ///
+    /// If the lock is currently held for writing, the `Arc` is handed back
+    /// as the error.
+    pub fn try_read_owned(self: Arc<Self>) -> Result<OwnedRwLockReadGuard<T>, Arc<Self>> {
+        if self.raw.try_acquire(Access::Read) {
+            Ok(OwnedRwLockReadGuard { lock: self })
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Attempts to acquire the lock for writing immediately, returning a
+    /// guard which keeps the lock alive through an `Arc`.
+    ///
+    /// If the lock is currently held, the `Arc` is handed back as the error.
+    pub fn try_write_owned(self: Arc<Self>) -> Result<OwnedRwLockWriteGuard<T>, Arc<Self>> {
+        if self.raw.try_acquire(Access::Write) {
+            Ok(OwnedRwLockWriteGuard { lock: self })
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Acquires the lock for reading asynchronously, resolving to a guard
+    /// which keeps the lock alive through an `Arc`.
+    ///
+    /// Unlike the guard of [`read`](RwLock::read), the guard does not borrow
+    /// the lock, so it can be moved into spawned tasks.
+    pub fn read_owned(self: Arc<Self>) -> OwnedRwLockReadFuture<T> {
+        OwnedRwLockReadFuture { lock: Some(self), wait_key: None }
+    }
+
+    /// Acquires the lock for writing asynchronously, resolving to a guard
+    /// which keeps the lock alive through an `Arc`.
+    ///
+    /// Unlike the guard of [`write`](RwLock::write), the guard does not
+    /// borrow the lock, so it can be moved into spawned tasks.
+    pub fn write_owned(self: Arc<Self>) -> OwnedRwLockWriteFuture<T> {
+        OwnedRwLockWriteFuture { lock: Some(self), wait_key: None }
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    ///
+    /// Since this call borrows the lock mutably, no actual locking needs to
+    /// take place.
+    pub fn get_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.value.get() }
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for RwLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.raw.state.lock().unwrap();
+        f.debug_struct("RwLock")
+            .field("readers", &state.readers)
+            .field("is_write_locked", &state.writer)
+            .field("waiters", &state.queued)
+            .finish()
+    }
+}
+
+impl<T: Default> Default for RwLock<T> {
+    fn default() -> RwLock<T> {
+        RwLock::new(Default::default())
+    }
+}
+
+impl<T> From<T> for RwLock<T> {
+    fn from(t: T) -> RwLock<T> {
+        RwLock::new(t)
+    }
+}
+
+macro_rules! lock_future {
+    (
+        $(#[$attr:meta])*
+        $name:ident<$($lt:lifetime,)? T>($lock:ty) -> $guard:ty, $access:expr,
+        |$l:ident| $make:expr
+    ) => {
+        $(#[$attr])*
+        #[must_use = "futures do nothing unless you `.await` or poll them"]
+        pub struct $name<$($lt,)? T: ?Sized> {
+            // `None` indicates that the lock was successfully acquired.
+            lock: Option<$lock>,
+            wait_key: Option<u64>,
+        }
+
+        impl<$($lt,)? T: ?Sized> fmt::Debug for $name<$($lt,)? T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("was_acquired", &self.lock.is_none())
+                    .field("wait_key", &self.wait_key)
+                    .finish()
+            }
+        }
+
+        impl<$($lt,)? T: ?Sized> Unpin for $name<$($lt,)? T> {}
+
+        impl<$($lt,)? T: ?Sized> FusedFuture for $name<$($lt,)? T> {
+            fn is_terminated(&self) -> bool {
+                self.lock.is_none()
+            }
+        }
+
+        impl<$($lt,)? T: ?Sized> Future for $name<$($lt,)? T> {
+            type Output = $guard;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+                let lock = this.lock.as_ref()
+                    .expect(concat!("polled ", stringify!($name), " after completion"));
+                ready!(lock.raw.poll_acquire($access, &mut this.wait_key, cx));
+                let $l = this.lock.take().unwrap();
+                Poll::Ready($make)
+            }
+        }
+
+        impl<$($lt,)? T: ?Sized> Drop for $name<$($lt,)? T> {
+            fn drop(&mut self) {
+                if let (Some(lock), Some(key)) = (&self.lock, self.wait_key) {
+                    lock.raw.cancel($access, key);
+                }
+            }
+        }
+    };
+}
+
+lock_future! {
+    /// A future which resolves when the target lock has been successfully
+    /// acquired for reading.
+    RwLockReadFuture<'a, T>(&'a RwLock<T>) -> RwLockReadGuard<'a, T>, Access::Read,
+    |lock| RwLockReadGuard { lock }
+}
+
+lock_future! {
+    /// A future which resolves when the target lock has been successfully
+    /// acquired for writing.
+    RwLockWriteFuture<'a, T>(&'a RwLock<T>) -> RwLockWriteGuard<'a, T>, Access::Write,
+    |lock| RwLockWriteGuard { lock }
+}
+
+lock_future! {
+    /// A future which resolves when the target lock has been successfully
+    /// acquired for reading, with a guard owning an `Arc` of the lock.
+    OwnedRwLockReadFuture<T>(Arc<RwLock<T>>) -> OwnedRwLockReadGuard<T>, Access::Read,
+    |lock| OwnedRwLockReadGuard { lock }
+}
+
+lock_future! {
+    /// A future which resolves when the target lock has been successfully
+    /// acquired for writing, with a guard owning an `Arc` of the lock.
+    OwnedRwLockWriteFuture<T>(Arc<RwLock<T>>) -> OwnedRwLockWriteGuard<T>, Access::Write,
+    |lock| OwnedRwLockWriteGuard { lock }
+}
+
+/// An RAII guard returned by the `read` and `try_read` methods.
+/// When this structure is dropped (falls out of scope), the read lock will
+/// be released.
+pub struct RwLockReadGuard<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+/// An RAII guard returned by the `write` and `try_write` methods.
+/// When this structure is dropped (falls out of scope), the write lock will
+/// be released.
+pub struct RwLockWriteGuard<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+/// An RAII guard returned by the `read_owned` and `try_read_owned` methods.
+/// When this structure is dropped (falls out of scope), the read lock will
+/// be released.
+pub struct OwnedRwLockReadGuard<T: ?Sized> {
+    lock: Arc<RwLock<T>>,
+}
+
+/// An RAII guard returned by the `write_owned` and `try_write_owned`
+/// methods. When this structure is dropped (falls out of scope), the write
+/// lock will be released.
+pub struct OwnedRwLockWriteGuard<T: ?Sized> {
+    lock: Arc<RwLock<T>>,
+}
+
+macro_rules! guard {
+    ($name:ident<$($lt:lifetime,)? T>, $access:expr) => {
+        impl<$($lt,)? T: ?Sized> Drop for $name<$($lt,)? T> {
+            fn drop(&mut self) {
+                self.lock.raw.release($access);
+            }
+        }
+
+        impl<$($lt,)? T: ?Sized> Deref for $name<$($lt,)? T> {
+            type Target = T;
+            fn deref(&self) -> &T {
+                unsafe { &*self.lock.value.get() }
+            }
+        }
+
+        impl<$($lt,)? T: ?Sized + fmt::Debug> fmt::Debug for $name<$($lt,)? T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("value", &&**self)
+                    .finish()
+            }
+        }
+    };
+}
+
+guard!(RwLockReadGuard<'a, T>, Access::Read);
+guard!(RwLockWriteGuard<'a, T>, Access::Write);
+guard!(OwnedRwLockReadGuard<T>, Access::Read);
+guard!(OwnedRwLockWriteGuard<T>, Access::Write);
+
+impl<T: ?Sized> DerefMut for RwLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for OwnedRwLockWriteGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<'a, T: ?Sized> RwLockReadGuard<'a, T> {
+    /// Returns a guard for a part of the locked value, such as one of its
+    /// fields, which keeps the lock held for reading until it is dropped.
+    ///
+    /// This is an associated function, as the guard dereferences to the
+    /// locked value, whose methods it would otherwise shadow.
+    pub fn map<U: ?Sized, F>(this: RwLockReadGuard<'a, T>, f: F) -> MappedRwLockReadGuard<'a, T, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let lock = this.lock;
+        let value = f(unsafe { &*lock.value.get() }) as *const U;
+        std::mem::forget(this);
+        MappedRwLockReadGuard { lock, value }
+    }
+}
+
+impl<'a, T: ?Sized> RwLockWriteGuard<'a, T> {
+    /// Returns a guard for a part of the locked value, such as one of its
+    /// fields, which keeps the lock held for writing until it is dropped.
+    ///
+    /// This is an associated function, as the guard dereferences to the
+    /// locked value, whose methods it would otherwise shadow.
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::lock::{RwLock, RwLockWriteGuard};
+    ///
+    /// let lock = RwLock::new((1, vec![2]));
+    /// block_on(async {
+    ///     let mut list = RwLockWriteGuard::map(lock.write().await, |v| &mut v.1);
+    ///     list.push(3);
+    /// });
+    /// assert_eq!(lock.into_inner().1, [2, 3]);
+    /// ```
+    pub fn map<U: ?Sized, F>(this: RwLockWriteGuard<'a, T>, f: F) -> MappedRwLockWriteGuard<'a, T, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let lock = this.lock;
+        let value = f(unsafe { &mut *lock.value.get() }) as *mut U;
+        std::mem::forget(this);
+        MappedRwLockWriteGuard { lock, value }
+    }
+}
+
+/// An RAII guard for a part of the value of a lock held for reading,
+/// returned by [`RwLockReadGuard::map`]. When this structure is dropped
+/// (falls out of scope), the read lock will be released.
+pub struct MappedRwLockReadGuard<'a, T: ?Sized, U: ?Sized> {
+    lock: &'a RwLock<T>,
+    value: *const U,
+}
+
+/// An RAII guard for a part of the value of a lock held for writing,
+/// returned by [`RwLockWriteGuard::map`]. When this structure is dropped
+/// (falls out of scope), the write lock will be released.
+pub struct MappedRwLockWriteGuard<'a, T: ?Sized, U: ?Sized> {
+    lock: &'a RwLock<T>,
+    value: *mut U,
+}
+
+impl<'a, T: ?Sized, U: ?Sized> MappedRwLockReadGuard<'a, T, U> {
+    /// Returns a guard for a part of the already mapped value.
+    pub fn map<V: ?Sized, F>(this: MappedRwLockReadGuard<'a, T, U>, f: F) -> MappedRwLockReadGuard<'a, T, V>
+    where
+        F: FnOnce(&U) -> &V,
+    {
+        let lock = this.lock;
+        let value = f(unsafe { &*this.value }) as *const V;
+        std::mem::forget(this);
+        MappedRwLockReadGuard { lock, value }
+    }
+}
+
+impl<'a, T: ?Sized, U: ?Sized> MappedRwLockWriteGuard<'a, T, U> {
+    /// Returns a guard for a part of the already mapped value.
+    pub fn map<V: ?Sized, F>(this: MappedRwLockWriteGuard<'a, T, U>, f: F) -> MappedRwLockWriteGuard<'a, T, V>
+    where
+        F: FnOnce(&mut U) -> &mut V,
+    {
+        let lock = this.lock;
+        let value = f(unsafe { &mut *this.value }) as *mut V;
+        std::mem::forget(this);
+        MappedRwLockWriteGuard { lock, value }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Drop for MappedRwLockReadGuard<'_, T, U> {
+    fn drop(&mut self) {
+        self.lock.raw.release(Access::Read);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Drop for MappedRwLockWriteGuard<'_, T, U> {
+    fn drop(&mut self) {
+        self.lock.raw.release(Access::Write);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Deref for MappedRwLockReadGuard<'_, T, U> {
+    type Target = U;
+    fn deref(&self) -> &U {
+        unsafe { &*self.value }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Deref for MappedRwLockWriteGuard<'_, T, U> {
+    type Target = U;
+    fn deref(&self) -> &U {
+        unsafe { &*self.value }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> DerefMut for MappedRwLockWriteGuard<'_, T, U> {
+    fn deref_mut(&mut self) -> &mut U {
+        unsafe { &mut *self.value }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized + fmt::Debug> fmt::Debug for MappedRwLockReadGuard<'_, T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedRwLockReadGuard")
+            .field("value", &&**self)
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized + fmt::Debug> fmt::Debug for MappedRwLockWriteGuard<'_, T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedRwLockWriteGuard")
+            .field("value", &&**self)
+            .finish()
+    }
+}
+
+// The lock hands out shared references to readers on any thread, and a
+// mutable reference to a writer on any thread.
+unsafe impl<T: ?Sized + Send> Send for RwLock<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for RwLock<T> {}
+
+// The futures only touch the lock, not the value.
+unsafe impl<T: ?Sized + Send + Sync> Send for RwLockReadFuture<'_, T> {}
+unsafe impl<T: ?Sized> Sync for RwLockReadFuture<'_, T> {}
+unsafe impl<T: ?Sized + Send + Sync> Send for RwLockWriteFuture<'_, T> {}
+unsafe impl<T: ?Sized> Sync for RwLockWriteFuture<'_, T> {}
+unsafe impl<T: ?Sized + Send + Sync> Send for OwnedRwLockReadFuture<T> {}
+unsafe impl<T: ?Sized> Sync for OwnedRwLockReadFuture<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Send for OwnedRwLockWriteFuture<T> {}
+unsafe impl<T: ?Sized> Sync for OwnedRwLockWriteFuture<T> {}
+
+unsafe impl<T: ?Sized + Sync> Send for RwLockReadGuard<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for RwLockReadGuard<'_, T> {}
+unsafe impl<T: ?Sized + Send + Sync> Send for RwLockWriteGuard<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for RwLockWriteGuard<'_, T> {}
+unsafe impl<T: ?Sized + Send + Sync> Send for OwnedRwLockReadGuard<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for OwnedRwLockReadGuard<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Send for OwnedRwLockWriteGuard<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for OwnedRwLockWriteGuard<T> {}
+unsafe impl<T: ?Sized + Sync, U: ?Sized + Sync> Send for MappedRwLockReadGuard<'_, T, U> {}
+unsafe impl<T: ?Sized + Sync, U: ?Sized + Sync> Sync for MappedRwLockReadGuard<'_, T, U> {}
+unsafe impl<T: ?Sized + Send + Sync, U: ?Sized + Send> Send for MappedRwLockWriteGuard<'_, T, U> {}
+unsafe impl<T: ?Sized + Sync, U: ?Sized + Sync> Sync for MappedRwLockWriteGuard<'_, T, U> {}
@@ -0,0 +1,371 @@
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::mem;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex as StdMutex};
+
+/// A futures-aware counting semaphore.
+///
+/// A semaphore holds a number of permits, which tasks acquire before
+/// entering a section of code and release when leaving it, limiting how many
+/// tasks can be inside at once. Permits are handed out in the order tasks
+/// started waiting for them, so a task waiting for many permits is not
+/// starved by tasks which only need a few.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::lock::Semaphore;
+///
+/// let semaphore = Semaphore::new(3);
+/// block_on(async {
+///     let permit = semaphore.acquire(2).await;
+///     assert_eq!(semaphore.available_permits(), 1);
+///     assert!(semaphore.try_acquire(2).is_none());
+///     drop(permit);
+///     assert_eq!(semaphore.available_permits(), 3);
+/// });
+/// ```
+pub struct Semaphore {
+    state: StdMutex<State>,
+}
+
+struct State {
+    permits: usize,
+    // Waiters, ordered by when they started waiting.
+    waiters: BTreeMap<u64, Waiter>,
+    next_key: u64,
+    // Number of waiters which have not been granted their permits yet.
+    queued: usize,
+}
+
+struct Waiter {
+    permits: usize,
+    waker: Waker,
+    granted: bool,
+}
+
+impl State {
+    // Grants permits to the waiters at the front of the queue which can
+    // take them now, returning their wakers.
+    fn dispatch(&mut self) -> Vec<Waker> {
+        let mut wakers = Vec::new();
+        for waiter in self.waiters.values_mut().filter(|waiter| !waiter.granted) {
+            if waiter.permits > self.permits {
+                break;
+            }
+            self.permits -= waiter.permits;
+            self.queued -= 1;
+            waiter.granted = true;
+            wakers.push(waiter.waker.clone());
+        }
+        wakers
+    }
+}
+
+impl Semaphore {
+    /// Creates a new semaphore with the given number of permits.
+    pub fn new(permits: usize) -> Semaphore {
+        Semaphore {
+            state: StdMutex::new(State {
+                permits,
+                waiters: BTreeMap::new(),
+                next_key: 0,
+                queued: 0,
+            }),
+        }
+    }
+
+    /// Returns the number of permits which are available right now.
+    pub fn available_permits(&self) -> usize {
+        self.state.lock().unwrap().permits
+    }
+
+    /// Adds `n` permits to the semaphore, waking the tasks which can now
+    /// acquire theirs.
+    pub fn add_permits(&self, n: usize) {
+        let wakers = {
+            let mut state = self.state.lock().unwrap();
+            state.permits += n;
+            state.dispatch()
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    /// Attempts to acquire `n` permits immediately.
+    ///
+    /// Returns `None` if there are not enough permits available, or if other
+    /// tasks are waiting for permits.
+    pub fn try_acquire(&self, n: usize) -> Option<SemaphorePermit<'_>> {
+        if self.try_take(n) {
+            Some(SemaphorePermit { semaphore: self, permits: n })
+        } else {
+            None
+        }
+    }
+
+    /// Acquires `n` permits asynchronously.
+    ///
+    /// This method returns a future that will resolve once the permits have
+    /// been successfully acquired. They are released when the returned
+    /// [`SemaphorePermit`] is dropped.
+    pub fn acquire(&self, n: usize) -> SemaphoreAcquireFuture<'_> {
+        SemaphoreAcquireFuture { semaphore: Some(self), permits: n, wait_key: None }
+    }
+
+    /// Attempts to acquire `n` permits immediately, returning a permit which
+    /// keeps the semaphore alive through an `Arc`.
+    ///
+    /// If the permits are not available, the `Arc` is handed back as the
+    /// error.
+    pub fn try_acquire_owned(self: Arc<Self>, n: usize) -> Result<OwnedSemaphorePermit, Arc<Self>> {
+        if self.try_take(n) {
+            Ok(OwnedSemaphorePermit { semaphore: self, permits: n })
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Acquires `n` permits asynchronously, resolving to a permit which keeps
+    /// the semaphore alive through an `Arc`.
+    ///
+    /// Unlike the permit of [`acquire`](Semaphore::acquire), the permit does
+    /// not borrow the semaphore, so it can be moved into spawned tasks.
+    pub fn acquire_owned(self: Arc<Self>, n: usize) -> OwnedSemaphoreAcquireFuture {
+        OwnedSemaphoreAcquireFuture { semaphore: Some(self), permits: n, wait_key: None }
+    }
+
+    fn try_take(&self, n: usize) -> bool {
+        let mut state = self.state.lock().unwrap();
+        // Skipping the queue would be unfair to the waiters.
+        if state.queued == 0 && state.permits >= n {
+            state.permits -= n;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn poll_take(&self, n: usize, wait_key: &mut Option<u64>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        match *wait_key {
+            None => {
+                if state.queued == 0 && state.permits >= n {
+                    state.permits -= n;
+                    return Poll::Ready(());
+                }
+                let key = state.next_key;
+                state.next_key += 1;
+                state.queued += 1;
+                state.waiters.insert(key, Waiter { permits: n, waker: cx.waker().clone(), granted: false });
+                *wait_key = Some(key);
+                Poll::Pending
+            }
+            Some(key) => {
+                let waiter = state.waiters.get_mut(&key).unwrap();
+                if waiter.granted {
+                    state.waiters.remove(&key);
+                    *wait_key = None;
+                    return Poll::Ready(());
+                }
+                if !waiter.waker.will_wake(cx.waker()) {
+                    waiter.waker = cx.waker().clone();
+                }
+                Poll::Pending
+            }
+        }
+    }
+
+    // Removes a waiter whose future was dropped, returning its permits if
+    // it had been granted them in the meantime.
+    fn cancel(&self, key: u64) {
+        let wakers = {
+            let mut state = self.state.lock().unwrap();
+            let waiter = state.waiters.remove(&key).unwrap();
+            if waiter.granted {
+                state.permits += waiter.permits;
+            } else {
+                state.queued -= 1;
+            }
+            // A waiter which gave up may have been holding up the smaller
+            // requests behind it.
+            state.dispatch()
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+impl fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("Semaphore")
+            .field("permits", &state.permits)
+            .field("waiters", &state.queued)
+            .finish()
+    }
+}
+
+/// A future which resolves when the requested permits of the target
+/// semaphore have been successfully acquired.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SemaphoreAcquireFuture<'a> {
+    // `None` indicates that the permits were successfully acquired.
+    semaphore: Option<&'a Semaphore>,
+    permits: usize,
+    wait_key: Option<u64>,
+}
+
+impl fmt::Debug for SemaphoreAcquireFuture<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemaphoreAcquireFuture")
+            .field("was_acquired", &self.semaphore.is_none())
+            .field("permits", &self.permits)
+            .field("wait_key", &self.wait_key)
+            .finish()
+    }
+}
+
+impl FusedFuture for SemaphoreAcquireFuture<'_> {
+    fn is_terminated(&self) -> bool {
+        self.semaphore.is_none()
+    }
+}
+
+impl<'a> Future for SemaphoreAcquireFuture<'a> {
+    type Output = SemaphorePermit<'a>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let semaphore = this.semaphore.expect("polled SemaphoreAcquireFuture after completion");
+        ready!(semaphore.poll_take(this.permits, &mut this.wait_key, cx));
+        this.semaphore = None;
+        Poll::Ready(SemaphorePermit { semaphore, permits: this.permits })
+    }
+}
+
+impl Drop for SemaphoreAcquireFuture<'_> {
+    fn drop(&mut self) {
+        if let (Some(semaphore), Some(key)) = (self.semaphore, self.wait_key) {
+            semaphore.cancel(key);
+        }
+    }
+}
+
+/// A future which resolves when the requested permits of the target
+/// semaphore have been successfully acquired, with a permit owning an `Arc`
+/// of the semaphore.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct OwnedSemaphoreAcquireFuture {
+    // `None` indicates that the permits were successfully acquired.
+    semaphore: Option<Arc<Semaphore>>,
+    permits: usize,
+    wait_key: Option<u64>,
+}
+
+impl fmt::Debug for OwnedSemaphoreAcquireFuture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedSemaphoreAcquireFuture")
+            .field("was_acquired", &self.semaphore.is_none())
+            .field("permits", &self.permits)
+            .field("wait_key", &self.wait_key)
+            .finish()
+    }
+}
+
+impl FusedFuture for OwnedSemaphoreAcquireFuture {
+    fn is_terminated(&self) -> bool {
+        self.semaphore.is_none()
+    }
+}
+
+impl Future for OwnedSemaphoreAcquireFuture {
+    type Output = OwnedSemaphorePermit;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let semaphore = this.semaphore.as_ref()
+            .expect("polled OwnedSemaphoreAcquireFuture after completion");
+        ready!(semaphore.poll_take(this.permits, &mut this.wait_key, cx));
+        let semaphore = this.semaphore.take().unwrap();
+        Poll::Ready(OwnedSemaphorePermit { semaphore, permits: this.permits })
+    }
+}
+
+impl Drop for OwnedSemaphoreAcquireFuture {
+    fn drop(&mut self) {
+        if let (Some(semaphore), Some(key)) = (&self.semaphore, self.wait_key) {
+            semaphore.cancel(key);
+        }
+    }
+}
+
+/// Permits acquired from a [`Semaphore`] with `acquire` or `try_acquire`.
+/// When this structure is dropped (falls out of scope), the permits are
+/// returned to the semaphore.
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+    permits: usize,
+}
+
+impl SemaphorePermit<'_> {
+    /// Returns the number of permits held.
+    pub fn num_permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Consumes the permit without returning its permits to the semaphore,
+    /// permanently reducing the number of permits available.
+    pub fn forget(mut self) {
+        self.permits = 0;
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        self.semaphore.add_permits(mem::replace(&mut self.permits, 0));
+    }
+}
+
+impl fmt::Debug for SemaphorePermit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemaphorePermit")
+            .field("permits", &self.permits)
+            .finish()
+    }
+}
+
+/// Permits acquired from a [`Semaphore`] with `acquire_owned` or
+/// `try_acquire_owned`. When this structure is dropped (falls out of scope),
+/// the permits are returned to the semaphore.
+pub struct OwnedSemaphorePermit {
+    semaphore: Arc<Semaphore>,
+    permits: usize,
+}
+
+impl OwnedSemaphorePermit {
+    /// Returns the number of permits held.
+    pub fn num_permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Consumes the permit without returning its permits to the semaphore,
+    /// permanently reducing the number of permits available.
+    pub fn forget(mut self) {
+        self.permits = 0;
+    }
+}
+
+impl Drop for OwnedSemaphorePermit {
+    fn drop(&mut self) {
+        self.semaphore.add_permits(mem::replace(&mut self.permits, 0));
+    }
+}
+
+impl fmt::Debug for OwnedSemaphorePermit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedSemaphorePermit")
+            .field("permits", &self.permits)
+            .finish()
+    }
+}
@@ -0,0 +1,89 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use futures_core::task::{Context, Poll};
+use futures_io::AsyncWrite;
+use futures_sink::Sink;
+use pin_utils::unsafe_pinned;
+use std::io;
+
+/// Writer for the [`into_async_write`](super::SinkExt::into_async_write)
+/// method.
+#[must_use = "writers do nothing unless polled"]
+pub struct SinkWriter<Si, Item> {
+    sink: Si,
+    _phantom: PhantomData<fn(Item)>,
+}
+
+impl<Si: Unpin, Item> Unpin for SinkWriter<Si, Item> {}
+
+impl<Si: fmt::Debug, Item> fmt::Debug for SinkWriter<Si, Item> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SinkWriter")
+            .field("sink", &self.sink)
+            .finish()
+    }
+}
+
+impl<Si, Item> SinkWriter<Si, Item>
+where
+    Si: Sink<Item>,
+    Si::Error: Into<io::Error>,
+    Item: for<'a> From<&'a [u8]>,
+{
+    unsafe_pinned!(sink: Si);
+
+    pub(super) fn new(sink: Si) -> Self {
+        SinkWriter { sink, _phantom: PhantomData }
+    }
+
+    /// Get a shared reference to the inner sink.
+    pub fn get_ref(&self) -> &Si {
+        &self.sink
+    }
+
+    /// Get a mutable reference to the inner sink.
+    pub fn get_mut(&mut self) -> &mut Si {
+        &mut self.sink
+    }
+
+    /// Get a pinned mutable reference to the inner sink.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut Si> {
+        self.sink()
+    }
+
+    /// Consumes this writer, returning the underlying sink.
+    pub fn into_inner(self) -> Si {
+        self.sink
+    }
+}
+
+impl<Si, Item> AsyncWrite for SinkWriter<Si, Item>
+where
+    Si: Sink<Item>,
+    Si::Error: Into<io::Error>,
+    Item: for<'a> From<&'a [u8]>,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        // Sending an empty chunk would tell the sink nothing, and readers on
+        // the other end commonly treat one as end-of-stream.
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        ready!(self.as_mut().sink().poll_ready(cx)).map_err(Into::into)?;
+        self.as_mut().sink().start_send(Item::from(buf)).map_err(Into::into)?;
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.sink().poll_flush(cx).map_err(Into::into)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.sink().poll_close(cx).map_err(Into::into)
+    }
+}
@@ -50,6 +50,11 @@ mod buffer;
 #[cfg(feature = "alloc")]
 pub use self::buffer::Buffer;
 
+#[cfg(feature = "io")]
+mod into_async_write;
+#[cfg(feature = "io")]
+pub use self::into_async_write::SinkWriter;
+
 impl<T: ?Sized, Item> SinkExt<Item> for T where T: Sink<Item> {}
 
 /// An extension trait for `Sink`s that provides a variety of convenient
@@ -254,6 +259,49 @@ pub trait SinkExt<Item>: Sink<Item> {
         Either::Right(self)
     }
 
+    /// Adapts this sink of byte chunks into an
+    /// [`AsyncWrite`](futures_io::AsyncWrite).
+    ///
+    /// This is the inverse of
+    /// [`AsyncWriteExt::into_sink`](crate::io::AsyncWriteExt::into_sink).
+    /// Every non-empty write is converted into a single item and sent to
+    /// the sink, so the writer accepts the whole buffer once the sink is
+    /// ready. Flushing and closing the writer flush and close the sink.
+    ///
+    /// Requires the `io` feature to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::channel::mpsc;
+    /// use futures::io::AsyncWriteExt;
+    /// use futures::sink::SinkExt;
+    /// use futures::stream::StreamExt;
+    /// use std::io;
+    ///
+    /// let (tx, rx) = mpsc::unbounded::<Vec<u8>>();
+    /// let mut writer = tx
+    ///     .sink_map_err(|e| io::Error::new(io::ErrorKind::BrokenPipe, e))
+    ///     .into_async_write();
+    ///
+    /// writer.write_all(b"hello").await?;
+    /// writer.write_all(b" world").await?;
+    /// writer.close().await?;
+    ///
+    /// let chunks = rx.collect::<Vec<_>>().await;
+    /// assert_eq!(chunks, [&b"hello"[..], b" world"]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(()) }).unwrap();
+    /// ```
+    #[cfg(feature = "io")]
+    fn into_async_write(self) -> SinkWriter<Self, Item>
+        where Self: Sized,
+              Self::Error: Into<std::io::Error>,
+              Item: for<'a> From<&'a [u8]>,
+    {
+        SinkWriter::new(self)
+    }
+
     /// Wraps a [`Sink`] into a sink compatible with libraries using
     /// futures 0.1 `Sink`. Requires the `compat` feature to be enabled.
     #[cfg(feature = "compat")]
@@ -0,0 +1,71 @@
+//! Cooperative scheduling budget.
+//!
+//! An executor can give each poll of a task a budget with
+//! [`with_budget`], which futures spend with [`consume`] as they make
+//! progress. Once it is used up, `consume` returns [`Poll::Pending`] after
+//! waking the task, so a future which is always ready, such as a stream
+//! reading from a full buffer, yields back to the executor instead of
+//! starving the other tasks.
+//!
+//! This is opt-in on both sides: outside of `with_budget` the budget is
+//! unlimited, and futures which never call `consume` are not limited by it.
+//!
+//! ```
+//! use futures::task::{budget, noop_waker, Context};
+//!
+//! let waker = noop_waker();
+//! let mut cx = Context::from_waker(&waker);
+//!
+//! budget::with_budget(2, || {
+//!     assert!(budget::consume(&mut cx).is_ready());
+//!     assert!(budget::consume(&mut cx).is_ready());
+//!     assert!(budget::consume(&mut cx).is_pending());
+//! });
+//! assert!(budget::consume(&mut cx).is_ready());
+//! ```
+
+use futures_core::task::{Context, Poll};
+use std::cell::Cell;
+
+thread_local!(static BUDGET: Cell<Option<usize>> = Cell::new(None));
+
+/// Calls `f` with the budget of the current thread set to `budget`, such as
+/// to poll a task, and restores the previous budget afterwards.
+pub fn with_budget<R>(budget: usize, f: impl FnOnce() -> R) -> R {
+    // Restores the previous budget, even if `f` panics.
+    struct Reset(Option<usize>);
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            BUDGET.with(|b| b.set(self.0));
+        }
+    }
+
+    let _reset = Reset(BUDGET.with(|b| b.replace(Some(budget))));
+    f()
+}
+
+/// Spends one unit of the budget of the current task.
+///
+/// Returns [`Poll::Pending`] after waking the task if the budget is used up,
+/// in which case the caller should return `Poll::Pending` as well. Returns
+/// [`Poll::Ready`] if there is budget left, or if no budget is set.
+pub fn consume(cx: &mut Context<'_>) -> Poll<()> {
+    BUDGET.with(|b| match b.get() {
+        Some(0) => {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+        Some(n) => {
+            b.set(Some(n - 1));
+            Poll::Ready(())
+        }
+        None => Poll::Ready(()),
+    })
+}
+
+/// Returns the budget left for the current task, or `None` if no budget is
+/// set.
+pub fn remaining() -> Option<usize> {
+    BUDGET.with(Cell::get)
+}
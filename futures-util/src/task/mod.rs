@@ -16,13 +16,47 @@ cfg_target_has_atomic! {
     #[cfg(feature = "alloc")]
     pub use self::waker_ref::{waker_ref, WakerRef};
 
+    #[cfg(feature = "alloc")]
+    mod waker_fn;
+    #[cfg(feature = "alloc")]
+    pub use self::waker_fn::{waker_fn, waker_ref_fn};
+
+    #[cfg(feature = "alloc")]
+    mod wake_counter;
+    #[cfg(feature = "alloc")]
+    pub use self::wake_counter::{new_count_waker, AwokenCount};
+
+    #[cfg(feature = "std")]
+    mod scope;
+    #[cfg(feature = "std")]
+    pub use self::scope::{scope, Scope, ScopeFuture};
+
+    #[cfg(feature = "std")]
+    mod waker_set;
+    #[cfg(feature = "std")]
+    pub use self::waker_set::WakerSet;
+
     pub use futures_core::task::__internal::AtomicWaker;
+
+    #[cfg(feature = "atomic-waker-stats")]
+    pub use futures_core::task::__internal::AtomicWakerStats;
 }
 
 mod noop_waker;
 pub use self::noop_waker::noop_waker;
 #[cfg(feature = "std")]
-pub use self::noop_waker::noop_waker_ref;
+pub use self::noop_waker::{noop_context, noop_waker_ref};
+
+#[cfg(feature = "std")]
+pub mod budget;
+
+#[cfg(feature = "std")]
+mod task_local;
+#[cfg(feature = "std")]
+pub use self::task_local::{AccessError, LocalKey, TaskLocalExt, TaskLocalFuture};
+
+mod yield_now;
+pub use self::yield_now::{yield_now, YieldNow};
 
 mod spawn;
 pub use self::spawn::{SpawnExt, LocalSpawnExt};
@@ -1,5 +1,7 @@
 //! Utilities for creating zero-cost wakers that don't do anything.
 use futures_core::task::{RawWaker, RawWakerVTable, Waker};
+#[cfg(feature = "std")]
+use futures_core::task::Context;
 use core::ptr::null;
 #[cfg(feature = "std")]
 use core::cell::UnsafeCell;
@@ -52,3 +54,26 @@ pub fn noop_waker_ref() -> &'static Waker {
     }
     NOOP_WAKER_INSTANCE.with(|l| unsafe { &*l.get() })
 }
+
+/// Create a new [`Context`] whose [waker](Context::waker) does nothing when
+/// woken.
+///
+/// This is meant for polling futures by hand, such as in tests.
+///
+/// # Examples
+///
+/// ```
+/// use futures::future::Future;
+/// use futures::task::{noop_context, Poll};
+/// use futures::pin_mut;
+///
+/// let future = async { 5 };
+/// pin_mut!(future);
+///
+/// assert_eq!(future.poll(&mut noop_context()), Poll::Ready(5));
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+pub fn noop_context() -> Context<'static> {
+    Context::from_waker(noop_waker_ref())
+}
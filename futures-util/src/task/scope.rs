@@ -0,0 +1,169 @@
+use crate::stream::{FuturesUnordered, StreamExt};
+use futures_core::future::{BoxFuture, FusedFuture, Future};
+use futures_core::task::{Context, Poll};
+use pin_utils::unsafe_pinned;
+use std::fmt;
+use std::mem;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// Creates a future which runs the future returned by `f` along with the
+/// tasks it spawns on the [`Scope`] passed to `f`, and completes once they
+/// have all completed.
+///
+/// Unlike tasks spawned on an executor, the tasks spawned on a scope may
+/// borrow from the environment of the scope: they are owned by the returned
+/// future, so they cannot outlive it, and they have all completed once it
+/// resolves. The tasks are polled by the returned future itself, so they run
+/// concurrently with each other rather than in parallel, on whichever
+/// executor runs the scope. If the future is dropped before it completes,
+/// the tasks which have not completed yet are dropped with it.
+///
+/// The future resolves to the output of the future returned by `f`.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::task::scope;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// let total = AtomicUsize::new(0);
+/// let numbers = vec![1, 2, 3];
+///
+/// block_on(scope(|s| {
+///     let (total, numbers) = (&total, &numbers);
+///     async move {
+///         for n in numbers {
+///             s.spawn(async move {
+///                 total.fetch_add(*n, Ordering::SeqCst);
+///             });
+///         }
+///     }
+/// }));
+/// assert_eq!(total.load(Ordering::SeqCst), 6);
+/// ```
+pub fn scope<'env, F, Fut>(f: F) -> ScopeFuture<'env, Fut>
+where
+    F: FnOnce(Scope<'env>) -> Fut,
+    Fut: Future + 'env,
+{
+    let scope = Scope { incoming: Arc::new(Mutex::new(Vec::new())) };
+    let incoming = scope.incoming.clone();
+    ScopeFuture {
+        body: f(scope),
+        body_done: false,
+        output: None,
+        incoming,
+        tasks: FuturesUnordered::new(),
+    }
+}
+
+/// A handle to spawn tasks which may borrow from their environment, passed
+/// to the closure given to [`scope`].
+pub struct Scope<'env> {
+    incoming: Arc<Mutex<Vec<BoxFuture<'env, ()>>>>,
+}
+
+impl<'env> Scope<'env> {
+    /// Spawns a task onto the scope, which runs until it completes or the
+    /// scope is dropped.
+    ///
+    /// Tasks spawned after the scope has completed, through a handle which
+    /// escaped it, are never run.
+    pub fn spawn<Fut>(&self, future: Fut)
+    where
+        Fut: Future<Output = ()> + Send + 'env,
+    {
+        self.incoming.lock().unwrap().push(Box::pin(future));
+    }
+}
+
+impl Clone for Scope<'_> {
+    fn clone(&self) -> Self {
+        Scope { incoming: self.incoming.clone() }
+    }
+}
+
+impl fmt::Debug for Scope<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope").finish()
+    }
+}
+
+/// Future for the [`scope`] function.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ScopeFuture<'env, Fut: Future> {
+    body: Fut,
+    body_done: bool,
+    output: Option<Fut::Output>,
+    incoming: Arc<Mutex<Vec<BoxFuture<'env, ()>>>>,
+    tasks: FuturesUnordered<BoxFuture<'env, ()>>,
+}
+
+impl<'env, Fut: Future> ScopeFuture<'env, Fut> {
+    unsafe_pinned!(body: Fut);
+
+    // Moves the tasks spawned since the last call into `tasks`, returning
+    // whether there were any.
+    fn drain_incoming(&mut self) -> bool {
+        let incoming = mem::replace(&mut *self.incoming.lock().unwrap(), Default::default());
+        let any = !incoming.is_empty();
+        for task in incoming {
+            self.tasks.push(task);
+        }
+        any
+    }
+}
+
+impl<Fut: Future + Unpin> Unpin for ScopeFuture<'_, Fut> {}
+
+impl<Fut: Future> Future for ScopeFuture<'_, Fut> {
+    type Output = Fut::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Fut::Output> {
+        if !self.body_done {
+            if let Poll::Ready(output) = self.as_mut().body().poll(cx) {
+                // Safety: only `body` is pinned.
+                let this = unsafe { self.as_mut().get_unchecked_mut() };
+                this.body_done = true;
+                this.output = Some(output);
+            }
+        }
+
+        // Safety: only `body` is pinned, and it is not touched from here on.
+        let this = unsafe { self.get_unchecked_mut() };
+        loop {
+            // Tasks can spawn more tasks as they are polled.
+            this.drain_incoming();
+            if this.tasks.is_empty() {
+                break;
+            }
+            match this.tasks.poll_next_unpin(cx) {
+                Poll::Ready(_) => {}
+                Poll::Pending => {
+                    if !this.drain_incoming() {
+                        return Poll::Pending;
+                    }
+                }
+            }
+        }
+
+        if !this.body_done {
+            return Poll::Pending;
+        }
+        Poll::Ready(this.output.take().expect("ScopeFuture polled after completion"))
+    }
+}
+
+impl<Fut: Future> FusedFuture for ScopeFuture<'_, Fut> {
+    fn is_terminated(&self) -> bool {
+        self.body_done && self.output.is_none()
+    }
+}
+
+impl<Fut: Future> fmt::Debug for ScopeFuture<'_, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopeFuture")
+            .field("tasks", &self.tasks.len())
+            .finish()
+    }
+}
@@ -8,7 +8,7 @@ use crate::future::{FutureExt, RemoteHandle};
 #[cfg(feature = "alloc")]
 use futures_core::future::{Future, FutureObj, LocalFutureObj};
 #[cfg(feature = "alloc")]
-use futures_core::task::SpawnError;
+use futures_core::task::{SpawnConfig, SpawnError};
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
 
@@ -49,6 +49,32 @@ pub trait SpawnExt: Spawn {
         self.spawn_obj(FutureObj::new(Box::new(future)))
     }
 
+    /// Spawns a task that polls the given future with output `()` to
+    /// completion, passing the name and metadata in `config` on to the
+    /// executor.
+    ///
+    /// Executors which support it use the name of the task in diagnostics,
+    /// such as the task hooks of the executors in `futures::executor`.
+    /// Other executors ignore the config.
+    ///
+    /// ```
+    /// use futures::executor::ThreadPool;
+    /// use futures::task::{SpawnConfig, SpawnExt};
+    ///
+    /// let mut executor = ThreadPool::new().unwrap();
+    ///
+    /// let future = async { /* ... */ };
+    /// let config = SpawnConfig::new().with_name("worker").with_metadata(7u32);
+    /// executor.spawn_with_config(future, config).unwrap();
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn spawn_with_config<Fut>(&mut self, future: Fut, config: SpawnConfig) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_obj(FutureObj::new(Box::new(future)).with_config(config))
+    }
+
     /// Spawns a task that polls the given future to completion and returns a
     /// future that resolves to the spawned future's output.
     ///
@@ -127,6 +153,23 @@ pub trait LocalSpawnExt: LocalSpawn {
         self.spawn_local_obj(LocalFutureObj::new(Box::new(future)))
     }
 
+    /// Spawns a task that polls the given future with output `()` to
+    /// completion, passing the name and metadata in `config` on to the
+    /// executor.
+    ///
+    /// See [`SpawnExt::spawn_with_config`] for details.
+    #[cfg(feature = "alloc")]
+    fn spawn_local_with_config<Fut>(
+        &mut self,
+        future: Fut,
+        config: SpawnConfig,
+    ) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = ()> + 'static,
+    {
+        self.spawn_local_obj(LocalFutureObj::new(Box::new(future)).with_config(config))
+    }
+
     /// Spawns a task that polls the given future to completion and returns a
     /// future that resolves to the spawned future's output.
     ///
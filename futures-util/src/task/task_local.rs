@@ -0,0 +1,217 @@
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{Context, Poll};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::thread;
+
+/// Declares task-local values, which are set for the duration of a future
+/// with [`TaskLocalExt::scope`](crate::task::TaskLocalExt::scope).
+///
+/// Each value is declared as a `static` of type
+/// [`LocalKey`](crate::task::LocalKey), through which it is accessed while
+/// the scoped future is being polled. This lets context such as request IDs
+/// flow through combinator chains without being passed along explicitly.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::future::{self, FutureExt};
+/// use futures::task::TaskLocalExt;
+///
+/// futures::task_local! {
+///     static REQUEST_ID: u32;
+/// }
+///
+/// let fut = future::lazy(|_| REQUEST_ID.get()).map(|id| id + 1);
+/// assert_eq!(block_on(REQUEST_ID.scope(41, fut)), 42);
+/// ```
+#[macro_export]
+macro_rules! task_local {
+    () => {};
+
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty; $($rest:tt)*) => {
+        $crate::__task_local_inner!($(#[$attr])* $vis $name, $t);
+        $crate::task_local!($($rest)*);
+    };
+
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty) => {
+        $crate::__task_local_inner!($(#[$attr])* $vis $name, $t);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __task_local_inner {
+    ($(#[$attr:meta])* $vis:vis $name:ident, $t:ty) => {
+        $(#[$attr])*
+        $vis static $name: $crate::task::LocalKey<$t> = {
+            ::std::thread_local! {
+                static __KEY: ::std::cell::RefCell<::std::option::Option<$t>> =
+                    ::std::cell::RefCell::new(::std::option::Option::None);
+            }
+            $crate::task::LocalKey { inner: __KEY }
+        };
+    };
+}
+
+/// A key for a task-local value, declared with
+/// [`task_local!`](crate::task_local).
+///
+/// The value is only set while a future scoped with
+/// [`TaskLocalExt::scope`](TaskLocalExt::scope) is being polled.
+pub struct LocalKey<T: 'static> {
+    #[doc(hidden)]
+    pub inner: thread::LocalKey<RefCell<Option<T>>>,
+}
+
+impl<T: 'static> LocalKey<T> {
+    /// Calls `f` with a reference to the value of this task-local.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if it is not called from within a future scoped
+    /// with this key.
+    pub fn with<F, R>(&'static self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        match self.try_with(f) {
+            Ok(r) => r,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Calls `f` with a reference to the value of this task-local, or
+    /// returns an error if it is not set.
+    pub fn try_with<F, R>(&'static self, f: F) -> Result<R, AccessError>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.inner.try_with(|slot| {
+            slot.borrow().as_ref().map(f).ok_or(AccessError { _a: () })
+        }).unwrap_or(Err(AccessError { _a: () }))
+    }
+
+    /// Returns a clone of the value of this task-local.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if it is not called from within a future scoped
+    /// with this key.
+    pub fn get(&'static self) -> T
+    where
+        T: Clone,
+    {
+        self.with(T::clone)
+    }
+
+    // Swaps `value` with the value of this task-local.
+    fn swap(&'static self, value: &mut Option<T>) {
+        self.inner.with(|slot| {
+            std::mem::swap(&mut *slot.borrow_mut(), value);
+        })
+    }
+}
+
+impl<T: 'static> fmt::Debug for LocalKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("LocalKey { .. }")
+    }
+}
+
+/// Setting the value of a task-local for the duration of a future.
+pub trait TaskLocalExt<T: 'static> {
+    /// Wraps `future` so that this task-local is set to `value` whenever
+    /// it is being polled.
+    ///
+    /// Scopes can be nested, in which case the innermost value is visible,
+    /// and the outer one is restored once the inner future returns from
+    /// being polled.
+    fn scope<F>(&'static self, value: T, future: F) -> TaskLocalFuture<T, F>
+    where
+        F: Future;
+}
+
+impl<T: 'static> TaskLocalExt<T> for LocalKey<T> {
+    fn scope<F>(&'static self, value: T, future: F) -> TaskLocalFuture<T, F>
+    where
+        F: Future,
+    {
+        TaskLocalFuture { key: self, value: Some(value), future }
+    }
+}
+
+/// Future for the [`scope`](TaskLocalExt::scope) method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TaskLocalFuture<T: 'static, F> {
+    key: &'static LocalKey<T>,
+    value: Option<T>,
+    future: F,
+}
+
+impl<T: 'static, F> TaskLocalFuture<T, F> {
+    unsafe_pinned!(future: F);
+    unsafe_unpinned!(value: Option<T>);
+}
+
+impl<T: 'static, F: Unpin> Unpin for TaskLocalFuture<T, F> {}
+
+impl<T: 'static, F: Future> Future for TaskLocalFuture<T, F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // Restores the outer value of the task-local, even if the poll panics.
+        struct Reset<'a, T: 'static> {
+            key: &'static LocalKey<T>,
+            value: &'a mut Option<T>,
+        }
+
+        impl<T: 'static> Drop for Reset<'_, T> {
+            fn drop(&mut self) {
+                self.key.swap(self.value);
+            }
+        }
+
+        let key = self.key;
+        let mut value = self.as_mut().value().take();
+        key.swap(&mut value);
+        let res = {
+            let _reset = Reset { key, value: &mut value };
+            self.as_mut().future().poll(cx)
+        };
+        *self.as_mut().value() = value;
+        res
+    }
+}
+
+impl<T: 'static, F: FusedFuture> FusedFuture for TaskLocalFuture<T, F> {
+    fn is_terminated(&self) -> bool {
+        self.future.is_terminated()
+    }
+}
+
+impl<T: 'static + fmt::Debug, F: fmt::Debug> fmt::Debug for TaskLocalFuture<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskLocalFuture")
+            .field("value", &self.value)
+            .field("future", &self.future)
+            .finish()
+    }
+}
+
+/// The error returned by [`LocalKey::try_with`](LocalKey::try_with) when
+/// the task-local is not set.
+#[derive(Debug)]
+pub struct AccessError {
+    _a: (),
+}
+
+impl fmt::Display for AccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "task-local value not set")
+    }
+}
+
+impl Error for AccessError {}
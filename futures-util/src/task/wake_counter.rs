@@ -1,7 +1,7 @@
+use super::arc_wake::ArcWake;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicUsize, Ordering};
 use futures_core::task::Waker;
-use futures_util::task::{self, ArcWake};
-use std::sync::Arc;
-use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Number of times the waker was awoken.
 ///
@@ -37,12 +37,15 @@ impl ArcWake for WakerInner {
 
 /// Create a new [`Waker`] that counts the number of times it's awoken.
 ///
+/// This is meant for testing `poll` implementations, which can assert
+/// exactly how many times they woke their task.
+///
 /// [`Waker`]: futures_core::task::Waker
 ///
 /// # Examples
 ///
 /// ```
-/// use futures_test::task::new_count_waker;
+/// use futures::task::new_count_waker;
 ///
 /// let (waker, count) = new_count_waker();
 ///
@@ -55,5 +58,5 @@ impl ArcWake for WakerInner {
 /// ```
 pub fn new_count_waker() -> (Waker, AwokenCount) {
     let inner = Arc::new(WakerInner { count: AtomicUsize::new(0) });
-    (task::waker(inner.clone()), AwokenCount { inner })
+    (super::waker(inner.clone()), AwokenCount { inner })
 }
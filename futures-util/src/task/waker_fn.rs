@@ -0,0 +1,77 @@
+use super::arc_wake::ArcWake;
+use super::waker::waker_vtable;
+use super::waker_ref::WakerRef;
+use alloc::sync::Arc;
+use core::mem::ManuallyDrop;
+use core::task::{RawWaker, Waker};
+
+// Has the layout of `F`, so an `Arc<F>` can be used as an `Arc<WakerFn<F>>`.
+#[repr(transparent)]
+struct WakerFn<F>(F);
+
+impl<F> ArcWake for WakerFn<F>
+where
+    F: Fn() + Send + Sync,
+{
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        (arc_self.0)()
+    }
+}
+
+/// Creates a [`Waker`] which calls the closure `f` when woken.
+///
+/// # Examples
+///
+/// ```
+/// use futures::task::waker_fn;
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// let wakes = Arc::new(AtomicUsize::new(0));
+/// let wakes2 = wakes.clone();
+/// let waker = waker_fn(move || {
+///     wakes2.fetch_add(1, Ordering::SeqCst);
+/// });
+///
+/// waker.wake_by_ref();
+/// waker.wake();
+/// assert_eq!(wakes.load(Ordering::SeqCst), 2);
+/// ```
+pub fn waker_fn<F>(f: F) -> Waker
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    super::waker(Arc::new(WakerFn(f)))
+}
+
+/// Creates a reference to a [`Waker`] which calls the closure behind `f`
+/// when woken, without touching the reference count of `f`.
+///
+/// Cloning the waker increments the reference count, like cloning a waker
+/// created with [`waker_ref`](super::waker_ref).
+///
+/// # Examples
+///
+/// ```
+/// use futures::task::{waker_ref_fn, Context};
+/// use std::sync::Arc;
+///
+/// let wake = Arc::new(|| println!("woken"));
+/// let waker = waker_ref_fn(&wake);
+/// let mut cx = Context::from_waker(&waker);
+/// # let _ = &mut cx;
+/// ```
+#[inline]
+pub fn waker_ref_fn<F>(f: &Arc<F>) -> WakerRef<'_>
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    // Like `waker_ref`, this copies the pointer instead of using
+    // `Arc::into_raw`, as it does not keep a reference count.
+    let ptr = (&**f as *const F) as *const ();
+
+    let waker = ManuallyDrop::new(unsafe {
+        Waker::from_raw(RawWaker::new(ptr, waker_vtable::<WakerFn<F>>()))
+    });
+    WakerRef::new_unowned(waker)
+}
@@ -0,0 +1,159 @@
+use futures_core::task::Waker;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::mem;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A set of wakers, for futures which have many waiters to wake.
+///
+/// Where [`AtomicWaker`](super::AtomicWaker) holds a single waker, a
+/// `WakerSet` holds one for each waiting task, which makes it a building
+/// block for primitives like semaphores and broadcast channels. Waiters
+/// [`insert`](WakerSet::insert) their waker and keep the returned key, and
+/// are woken in the order they were inserted.
+///
+/// Waking is done outside of the internal lock, and waking an empty set does
+/// not take the lock at all. As with `AtomicWaker`, a waiter should check
+/// its condition again after inserting its waker, so that a wakeup which
+/// happens in between is not lost.
+///
+/// ```
+/// use futures::task::{noop_waker, WakerSet};
+///
+/// let set = WakerSet::new();
+/// let waker = noop_waker();
+///
+/// let a = set.insert(&waker);
+/// let b = set.insert(&waker);
+/// assert!(set.wake_one()); // wakes `a`
+/// assert!(!set.remove(a)); // `a` was woken already
+/// assert!(set.remove(b));
+/// assert!(set.is_empty());
+/// ```
+pub struct WakerSet {
+    inner: Mutex<Inner>,
+    len: AtomicUsize,
+}
+
+struct Inner {
+    next_key: usize,
+    // Keys increase with each insertion, so this is ordered oldest first.
+    wakers: BTreeMap<usize, Waker>,
+}
+
+impl WakerSet {
+    /// Creates an empty set.
+    pub fn new() -> WakerSet {
+        WakerSet {
+            inner: Mutex::new(Inner { next_key: 0, wakers: BTreeMap::new() }),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    /// Inserts `waker`, returning the key to update or remove it with.
+    pub fn insert(&self, waker: &Waker) -> usize {
+        let mut inner = self.inner.lock().unwrap();
+        let key = inner.next_key;
+        inner.next_key += 1;
+        inner.wakers.insert(key, waker.clone());
+        self.len.store(inner.wakers.len(), Ordering::SeqCst);
+        key
+    }
+
+    /// Replaces the waker stored under `key` with `waker`, such as when a
+    /// waiting future is polled again.
+    ///
+    /// Returns `false` if the waker has been woken, and hence removed, in
+    /// which case it has to be inserted again.
+    pub fn update(&self, key: usize, waker: &Waker) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.wakers.get_mut(&key) {
+            Some(w) => {
+                if !w.will_wake(waker) {
+                    *w = waker.clone();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the waker stored under `key`.
+    ///
+    /// Returns `false` if the waker has been woken already. A waiter which
+    /// was woken with [`wake_one`](WakerSet::wake_one), but gives up on
+    /// waiting, can use this to pass the wakeup on to another waiter.
+    pub fn remove(&self, key: usize) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let removed = inner.wakers.remove(&key).is_some();
+        self.len.store(inner.wakers.len(), Ordering::SeqCst);
+        removed
+    }
+
+    /// Removes and wakes the waker which was inserted first.
+    ///
+    /// Returns `false` if the set was empty.
+    pub fn wake_one(&self) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let waker = {
+            let mut inner = self.inner.lock().unwrap();
+            let waker = match inner.wakers.keys().next() {
+                Some(&key) => inner.wakers.remove(&key),
+                None => None,
+            };
+            self.len.store(inner.wakers.len(), Ordering::SeqCst);
+            waker
+        };
+        match waker {
+            Some(waker) => {
+                waker.wake();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes and wakes all the wakers, returning how many were woken.
+    pub fn wake_all(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let wakers = {
+            let mut inner = self.inner.lock().unwrap();
+            self.len.store(0, Ordering::SeqCst);
+            mem::replace(&mut inner.wakers, Default::default())
+        };
+        let n = wakers.len();
+        for (_, waker) in wakers {
+            waker.wake();
+        }
+        n
+    }
+
+    /// Returns the number of wakers in the set.
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::SeqCst)
+    }
+
+    /// Returns `true` if the set holds no wakers.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for WakerSet {
+    fn default() -> WakerSet {
+        WakerSet::new()
+    }
+}
+
+impl fmt::Debug for WakerSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WakerSet")
+            .field("len", &self.len())
+            .finish()
+    }
+}
@@ -0,0 +1,44 @@
+use core::pin::Pin;
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+
+/// Creates a future which yields to the executor once.
+///
+/// The future returns [`Poll::Pending`] the first time it is polled, waking
+/// its task right away, and completes the second time. Awaiting it in a long
+/// running loop gives the executor a chance to run other tasks in between.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::task::yield_now;
+///
+/// block_on(async {
+///     for _ in 0..3 {
+///         // Some expensive work ...
+///         yield_now().await;
+///     }
+/// });
+/// ```
+pub fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
+
+/// Future for the [`yield_now`] function.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
@@ -0,0 +1,230 @@
+use super::Delay;
+use futures_channel::{mpsc, oneshot};
+use futures_core::future::Future;
+use futures_core::stream::Stream;
+use futures_core::task::{Context, Poll};
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::time::Duration;
+
+/// Extension trait adding a deadline to sending on a bounded
+/// [`mpsc::Sender`](futures_channel::mpsc::Sender).
+pub trait SendTimeoutExt<T> {
+    /// Sends a message, waiting at most `dur` for capacity in the channel.
+    ///
+    /// If the deadline passes first, the returned future resolves to a
+    /// [`SendTimeoutError::Timeout`] holding the message, so it is not lost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::channel::mpsc;
+    /// use futures::timer::SendTimeoutExt;
+    /// use std::time::Duration;
+    ///
+    /// let (mut tx, _rx) = mpsc::channel(0);
+    /// tx.send_timeout(1, Duration::from_millis(10)).await.unwrap();
+    ///
+    /// let err = tx.send_timeout(2, Duration::from_millis(10)).await.unwrap_err();
+    /// assert!(err.is_timeout());
+    /// assert_eq!(err.into_inner(), 2);
+    /// # });
+    /// ```
+    fn send_timeout(&mut self, msg: T, dur: Duration) -> SendTimeout<'_, T>;
+}
+
+impl<T> SendTimeoutExt<T> for mpsc::Sender<T> {
+    fn send_timeout(&mut self, msg: T, dur: Duration) -> SendTimeout<'_, T> {
+        SendTimeout { sender: self, msg: Some(msg), delay: Delay::new(dur) }
+    }
+}
+
+/// Extension trait adding a deadline to receiving from a channel.
+///
+/// This is implemented for [`mpsc::Receiver`](futures_channel::mpsc::Receiver),
+/// [`mpsc::UnboundedReceiver`](futures_channel::mpsc::UnboundedReceiver) and
+/// [`oneshot::Receiver`](futures_channel::oneshot::Receiver).
+pub trait RecvTimeoutExt: Sized {
+    /// Receives a message, waiting at most `dur` for one to arrive.
+    ///
+    /// If the deadline passes first, the returned future resolves to an
+    /// [`Elapsed`] error. The receiver is left untouched, so a message which
+    /// arrives later can still be received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::channel::oneshot;
+    /// use futures::timer::RecvTimeoutExt;
+    /// use std::time::Duration;
+    ///
+    /// let (tx, mut rx) = oneshot::channel();
+    /// assert!(rx.recv_timeout(Duration::from_millis(10)).await.is_err());
+    ///
+    /// tx.send(1).unwrap();
+    /// assert_eq!(rx.recv_timeout(Duration::from_millis(10)).await, Ok(Ok(1)));
+    /// # });
+    /// ```
+    fn recv_timeout(&mut self, dur: Duration) -> RecvTimeout<'_, Self> {
+        RecvTimeout { receiver: self, delay: Delay::new(dur) }
+    }
+}
+
+impl<T> RecvTimeoutExt for mpsc::Receiver<T> {}
+impl<T> RecvTimeoutExt for mpsc::UnboundedReceiver<T> {}
+impl<T> RecvTimeoutExt for oneshot::Receiver<T> {}
+
+/// Future for the [`send_timeout`](SendTimeoutExt::send_timeout) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SendTimeout<'a, T> {
+    sender: &'a mut mpsc::Sender<T>,
+    msg: Option<T>,
+    delay: Delay,
+}
+
+// Pinning is never projected to the fields
+impl<T> Unpin for SendTimeout<'_, T> {}
+
+impl<T> Future for SendTimeout<'_, T> {
+    type Output = Result<(), SendTimeoutError<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let err = match this.sender.poll_ready(cx) {
+            Poll::Ready(Ok(())) => {
+                let msg = this.msg.take().expect("SendTimeout polled after completion");
+                return Poll::Ready(this.sender.try_send(msg).map_err(|e| {
+                    SendTimeoutError::Disconnected(e.into_inner())
+                }));
+            }
+            Poll::Ready(Err(_)) => SendTimeoutError::Disconnected,
+            Poll::Pending => {
+                ready!(Pin::new(&mut this.delay).poll(cx));
+                SendTimeoutError::Timeout
+            }
+        };
+        let msg = this.msg.take().expect("SendTimeout polled after completion");
+        Poll::Ready(Err(err(msg)))
+    }
+}
+
+/// Future for the [`recv_timeout`](RecvTimeoutExt::recv_timeout) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RecvTimeout<'a, R> {
+    receiver: &'a mut R,
+    delay: Delay,
+}
+
+impl<R> Unpin for RecvTimeout<'_, R> {}
+
+impl<R> RecvTimeout<'_, R> {
+    fn poll_timeout<T>(
+        &mut self,
+        cx: &mut Context<'_>,
+        poll: impl FnOnce(Pin<&mut R>, &mut Context<'_>) -> Poll<T>,
+    ) -> Poll<Result<T, Elapsed>>
+    where
+        R: Unpin,
+    {
+        if let Poll::Ready(t) = poll(Pin::new(self.receiver), cx) {
+            return Poll::Ready(Ok(t));
+        }
+        ready!(Pin::new(&mut self.delay).poll(cx));
+        Poll::Ready(Err(Elapsed(())))
+    }
+}
+
+impl<T> Future for RecvTimeout<'_, mpsc::Receiver<T>> {
+    type Output = Result<Option<T>, Elapsed>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.poll_timeout(cx, Stream::poll_next)
+    }
+}
+
+impl<T> Future for RecvTimeout<'_, mpsc::UnboundedReceiver<T>> {
+    type Output = Result<Option<T>, Elapsed>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.poll_timeout(cx, Stream::poll_next)
+    }
+}
+
+impl<T> Future for RecvTimeout<'_, oneshot::Receiver<T>> {
+    type Output = Result<Result<T, oneshot::Canceled>, Elapsed>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.poll_timeout(cx, Future::poll)
+    }
+}
+
+/// The error returned by [`send_timeout`](SendTimeoutExt::send_timeout),
+/// holding the message which could not be sent.
+#[derive(Clone, PartialEq, Eq)]
+pub enum SendTimeoutError<T> {
+    /// The deadline passed before the channel had capacity for the message.
+    Timeout(T),
+    /// The receiver has been dropped.
+    Disconnected(T),
+}
+
+impl<T> SendTimeoutError<T> {
+    /// Returns `true` if the deadline passed before the message was sent.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            SendTimeoutError::Timeout(_) => true,
+            SendTimeoutError::Disconnected(_) => false,
+        }
+    }
+
+    /// Returns `true` if the message was not sent because the receiver has
+    /// been dropped.
+    pub fn is_disconnected(&self) -> bool {
+        !self.is_timeout()
+    }
+
+    /// Returns the message that could not be sent.
+    pub fn into_inner(self) -> T {
+        match self {
+            SendTimeoutError::Timeout(msg) | SendTimeoutError::Disconnected(msg) => msg,
+        }
+    }
+}
+
+impl<T> fmt::Debug for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendTimeoutError::Timeout(_) => f.debug_tuple("Timeout").finish(),
+            SendTimeoutError::Disconnected(_) => f.debug_tuple("Disconnected").finish(),
+        }
+    }
+}
+
+impl<T> fmt::Display for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendTimeoutError::Timeout(_) => write!(f, "send timed out because the channel is full"),
+            SendTimeoutError::Disconnected(_) => write!(f, "send failed because receiver is gone"),
+        }
+    }
+}
+
+impl<T> Error for SendTimeoutError<T> {}
+
+/// The error returned by [`recv_timeout`](RecvTimeoutExt::recv_timeout) when
+/// the deadline passed before a message arrived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed(());
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "receive timed out")
+    }
+}
+
+impl Error for Elapsed {}
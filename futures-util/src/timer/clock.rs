@@ -0,0 +1,173 @@
+use crate::task::AtomicWaker;
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use super::global::{timer, Queue};
+
+thread_local!(static CURRENT: RefCell<Option<VirtualClock>> = RefCell::new(None));
+
+/// A clock whose time only moves when it is advanced, for testing code which
+/// uses timers.
+///
+/// While a thread has [entered](VirtualClock::enter) the clock, the
+/// [`Delay`](super::Delay)s created and polled on that thread go by the time
+/// of the clock, and wait for it to be advanced instead of for the timer
+/// thread. This makes tests of timeouts fast and reproducible.
+///
+/// Cloning a `VirtualClock` creates another handle to the same clock.
+///
+/// # Examples
+///
+/// ```
+/// use futures::future::FutureExt;
+/// use futures::task::noop_waker_ref;
+/// use futures::timer::{Delay, VirtualClock};
+/// use std::task::Context;
+/// use std::time::Duration;
+///
+/// let clock = VirtualClock::new();
+/// let _guard = clock.enter();
+/// let mut cx = Context::from_waker(noop_waker_ref());
+///
+/// let mut delay = Delay::new(Duration::from_secs(60));
+/// assert!(delay.poll_unpin(&mut cx).is_pending());
+///
+/// clock.advance(Duration::from_secs(60));
+/// assert!(delay.poll_unpin(&mut cx).is_ready());
+/// ```
+#[derive(Clone)]
+pub struct VirtualClock {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    start: Instant,
+    state: Mutex<State>,
+}
+
+struct State {
+    elapsed: Duration,
+    queue: Queue,
+}
+
+impl VirtualClock {
+    /// Creates a clock which starts at the current time.
+    pub fn new() -> VirtualClock {
+        VirtualClock {
+            inner: Arc::new(Inner {
+                start: Instant::now(),
+                state: Mutex::new(State { elapsed: Duration::from_secs(0), queue: Queue::new() }),
+            }),
+        }
+    }
+
+    /// Returns the current time of the clock.
+    pub fn now(&self) -> Instant {
+        self.inner.start + self.elapsed()
+    }
+
+    /// Returns how far the clock has been advanced.
+    pub fn elapsed(&self) -> Duration {
+        self.inner.state.lock().unwrap().elapsed
+    }
+
+    /// Advances the clock by `dur`, waking the tasks whose timers expire.
+    pub fn advance(&self, dur: Duration) {
+        self.advance_to(self.now() + dur)
+    }
+
+    /// Advances the clock to `when`, waking the tasks whose timers expire.
+    ///
+    /// The clock never goes backwards, so this does nothing if `when` has
+    /// passed already.
+    pub fn advance_to(&self, when: Instant) {
+        let mut expired = Vec::new();
+        let mut state = self.inner.state.lock().unwrap();
+        if when > self.inner.start + state.elapsed {
+            state.elapsed = when - self.inner.start;
+        }
+        state.queue.expire(when, &mut expired);
+        drop(state);
+        for waker in expired {
+            waker.wake();
+        }
+    }
+
+    /// Returns the earliest deadline of the timers waiting on the clock.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.inner.state.lock().unwrap().queue.next_deadline()
+    }
+
+    /// Makes the timers on the current thread go by this clock until the
+    /// returned guard is dropped.
+    pub fn enter(&self) -> ClockGuard {
+        let prev = CURRENT.with(|current| current.borrow_mut().replace(self.clone()));
+        ClockGuard { prev }
+    }
+
+    fn register(&self, when: Instant, waker: Arc<AtomicWaker>) -> Arc<AtomicBool> {
+        let mut state = self.inner.state.lock().unwrap();
+        if when <= self.inner.start + state.elapsed {
+            drop(state);
+            waker.wake();
+            Arc::new(AtomicBool::new(false))
+        } else {
+            state.queue.push(when, waker)
+        }
+    }
+}
+
+impl Default for VirtualClock {
+    fn default() -> VirtualClock {
+        VirtualClock::new()
+    }
+}
+
+impl fmt::Debug for VirtualClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VirtualClock")
+            .field("elapsed", &self.elapsed())
+            .finish()
+    }
+}
+
+/// A guard which makes the current thread use a [`VirtualClock`] until it is
+/// dropped.
+///
+/// This value is created by the [`enter`](VirtualClock::enter) method.
+#[must_use = "the clock is only used until the guard is dropped"]
+pub struct ClockGuard {
+    prev: Option<VirtualClock>,
+}
+
+impl Drop for ClockGuard {
+    fn drop(&mut self) {
+        let prev = self.prev.take();
+        CURRENT.with(|current| *current.borrow_mut() = prev);
+    }
+}
+
+impl fmt::Debug for ClockGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClockGuard").finish()
+    }
+}
+
+/// Returns the current time as seen by the timers of this thread.
+pub(super) fn now() -> Instant {
+    CURRENT.with(|current| match &*current.borrow() {
+        Some(clock) => clock.now(),
+        None => Instant::now(),
+    })
+}
+
+/// Arranges for `waker` to be woken once `when` has passed, on the clock of
+/// this thread, returning a flag which cancels the wakeup once set.
+pub(super) fn register(when: Instant, waker: Arc<AtomicWaker>) -> Arc<AtomicBool> {
+    CURRENT.with(|current| match &*current.borrow() {
+        Some(clock) => clock.register(when, waker),
+        None => timer().register(when, waker),
+    })
+}
@@ -0,0 +1,130 @@
+use crate::task::AtomicWaker;
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use super::clock;
+
+/// A future which completes once a deadline has passed.
+///
+/// Creating a `Delay` is cheap: the timer is only involved once the future
+/// is polled before its deadline. A `Delay` can be [`reset`](Delay::reset)
+/// to a new deadline, which makes it suitable for idle timeouts that are
+/// pushed back whenever there is activity.
+///
+/// # Examples
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::timer::Delay;
+/// use std::time::{Duration, Instant};
+///
+/// let start = Instant::now();
+/// Delay::new(Duration::from_millis(10)).await;
+/// assert!(start.elapsed() >= Duration::from_millis(10));
+/// # });
+/// ```
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Delay {
+    deadline: Instant,
+    waker: Option<Arc<AtomicWaker>>,
+    // The deadline of the most recent wakeup requested from the timer, and
+    // the flag which cancels it.
+    registered: Option<(Instant, Arc<AtomicBool>)>,
+}
+
+impl Delay {
+    /// Creates a new `Delay` which completes after `dur` has elapsed.
+    pub fn new(dur: Duration) -> Self {
+        Self::new_at(clock::now() + dur)
+    }
+
+    /// Creates a new `Delay` which completes at `deadline`.
+    pub fn new_at(deadline: Instant) -> Self {
+        Self {
+            deadline,
+            waker: None,
+            registered: None,
+        }
+    }
+
+    /// Returns the instant at which this `Delay` completes.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Resets this `Delay` to complete after `dur` has elapsed from now,
+    /// whether or not it has already completed.
+    pub fn reset(&mut self, dur: Duration) {
+        self.reset_at(clock::now() + dur)
+    }
+
+    /// Resets this `Delay` to complete at `deadline`, whether or not it has
+    /// already completed.
+    pub fn reset_at(&mut self, deadline: Instant) {
+        self.deadline = deadline;
+        // A pending wakeup which is due after the new deadline would come too
+        // late, so it is replaced right away. One which is due before it is
+        // kept, and leads to a new wakeup being requested when it comes.
+        let late = match &self.registered {
+            Some((when, _)) => deadline < *when,
+            None => false,
+        };
+        if late {
+            let waker = self.waker.clone().unwrap();
+            self.register(waker, deadline);
+        }
+    }
+
+    // Requests a wakeup at `deadline` from the timer, cancelling the previous
+    // one.
+    fn register(&mut self, waker: Arc<AtomicWaker>, deadline: Instant) {
+        self.cancel();
+        let cancelled = clock::register(deadline, waker);
+        self.registered = Some((deadline, cancelled));
+    }
+
+    fn cancel(&mut self) {
+        if let Some((_, cancelled)) = self.registered.take() {
+            cancelled.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let now = clock::now();
+        if now >= self.deadline {
+            return Poll::Ready(());
+        }
+
+        let this = &mut *self;
+        let deadline = this.deadline;
+        let waker = this.waker.get_or_insert_with(|| Arc::new(AtomicWaker::new())).clone();
+        waker.register(cx.waker());
+
+        // A wakeup that is still due before the deadline leads to this future
+        // being polled again, at which point it can ask for another one, so a
+        // new wakeup is only needed if there is no such pending one. This
+        // keeps frequently reset delays from flooding the timer.
+        let pending = match &this.registered {
+            Some((when, _)) => now < *when && *when <= deadline,
+            None => false,
+        };
+        if !pending {
+            this.register(waker, deadline);
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for Delay {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
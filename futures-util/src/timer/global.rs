@@ -0,0 +1,169 @@
+use crate::task::AtomicWaker;
+use std::cmp::{self, Ordering};
+use std::collections::BinaryHeap;
+use std::mem;
+use std::ptr;
+use std::sync::atomic::{self, AtomicBool, AtomicPtr};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Instant;
+
+/// The timer shared by all `Delay`s, driven by a background thread.
+pub(super) struct Timer {
+    queue: Mutex<Queue>,
+    cvar: Condvar,
+}
+
+/// A pending wakeup. Entries are ordered so that the earliest deadline is at
+/// the top of the (max-)heap.
+struct Entry {
+    when: Instant,
+    waker: Arc<AtomicWaker>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Entry {
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(atomic::Ordering::SeqCst)
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.when == other.when
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.when.cmp(&self.when)
+    }
+}
+
+// The queue is not pruned of cancelled wakeups while it is smaller than this.
+const MIN_PRUNE_LEN: usize = 64;
+
+/// The wakeups waiting on a clock, ordered by deadline.
+///
+/// Cancelled wakeups are skipped once they reach the front of the queue, and
+/// pruned from the rest of it whenever it has doubled in size since it was
+/// last pruned, so that they do not pile up when `Delay`s with far away
+/// deadlines are dropped or reset.
+pub(super) struct Queue {
+    heap: BinaryHeap<Entry>,
+    prune_at: usize,
+}
+
+impl Queue {
+    pub(super) fn new() -> Queue {
+        Queue { heap: BinaryHeap::new(), prune_at: MIN_PRUNE_LEN }
+    }
+
+    /// Queues a wakeup of `waker` at `when`, returning a flag which cancels
+    /// the wakeup once set.
+    pub(super) fn push(&mut self, when: Instant, waker: Arc<AtomicWaker>) -> Arc<AtomicBool> {
+        if self.heap.len() >= self.prune_at {
+            let entries = mem::replace(&mut self.heap, BinaryHeap::new()).into_vec();
+            self.heap = entries.into_iter().filter(|e| !e.is_cancelled()).collect();
+            self.prune_at = cmp::max(MIN_PRUNE_LEN, 2 * self.heap.len());
+        }
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.heap.push(Entry { when, waker, cancelled: cancelled.clone() });
+        cancelled
+    }
+
+    /// Returns the earliest deadline of the wakeups which have not been
+    /// cancelled.
+    pub(super) fn next_deadline(&mut self) -> Option<Instant> {
+        while self.heap.peek().map_or(false, Entry::is_cancelled) {
+            self.heap.pop();
+        }
+        self.heap.peek().map(|e| e.when)
+    }
+
+    /// Removes the wakeups which are due at `now`, adding the wakers of
+    /// those which have not been cancelled to `expired`.
+    pub(super) fn expire(&mut self, now: Instant, expired: &mut Vec<Arc<AtomicWaker>>) {
+        while self.heap.peek().map_or(false, |e| e.when <= now) {
+            let entry = self.heap.pop().unwrap();
+            if !entry.is_cancelled() {
+                expired.push(entry.waker);
+            }
+        }
+    }
+}
+
+pub(super) fn timer() -> &'static Timer {
+    static TIMER: AtomicPtr<Timer> = AtomicPtr::new(ptr::null_mut());
+
+    let mut timer = TIMER.load(atomic::Ordering::Acquire);
+    if timer.is_null() {
+        let new = Box::into_raw(Box::new(Timer {
+            queue: Mutex::new(Queue::new()),
+            cvar: Condvar::new(),
+        }));
+        timer = match TIMER.compare_exchange(
+            ptr::null_mut(),
+            new,
+            atomic::Ordering::AcqRel,
+            atomic::Ordering::Acquire,
+        ) {
+            Ok(_) => {
+                let timer: &'static Timer = unsafe { &*new };
+                thread::Builder::new()
+                    .name("futures-timer".to_string())
+                    .spawn(move || timer.run())
+                    .expect("failed to spawn the timer thread");
+                new
+            }
+            Err(existing) => {
+                drop(unsafe { Box::from_raw(new) });
+                existing
+            }
+        };
+    }
+    unsafe { &*timer }
+}
+
+impl Timer {
+    /// Arranges for `waker` to be woken once `when` has passed, returning a
+    /// flag which cancels the wakeup once set.
+    pub(super) fn register(&self, when: Instant, waker: Arc<AtomicWaker>) -> Arc<AtomicBool> {
+        let mut queue = self.queue.lock().unwrap();
+        let earliest = queue.next_deadline().map_or(true, |next| when < next);
+        let cancelled = queue.push(when, waker);
+        if earliest {
+            self.cvar.notify_one();
+        }
+        cancelled
+    }
+
+    fn run(&self) {
+        let mut queue = self.queue.lock().unwrap();
+        let mut expired = Vec::new();
+        loop {
+            let now = Instant::now();
+            queue.expire(now, &mut expired);
+            if !expired.is_empty() {
+                drop(queue);
+                for waker in expired.drain(..) {
+                    waker.wake();
+                }
+                queue = self.queue.lock().unwrap();
+                continue;
+            }
+            queue = match queue.next_deadline() {
+                Some(when) => self.cvar.wait_timeout(queue, when - now).unwrap().0,
+                None => self.cvar.wait(queue).unwrap(),
+            };
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Timers.
+//!
+//! This module contains [`Delay`], a future which completes at a given point
+//! in time. It is the building block for the timeout utilities elsewhere in
+//! this crate, such as the [`SendTimeoutExt`] and [`RecvTimeoutExt`] traits
+//! which add deadlines to channel operations.
+//!
+//! Timers are driven by a single background thread which is started the
+//! first time a `Delay` has to wait. The thread only sleeps and wakes tasks,
+//! so the timers work with any executor. For tests, a [`VirtualClock`] can
+//! stand in for the real time, so that timers only expire once the clock is
+//! advanced.
+//!
+//! This module is only available when the `std` feature of this
+//! library is activated, and it is activated by default.
+
+#[cfg(feature = "channel")]
+mod channel;
+#[cfg(feature = "channel")]
+pub use self::channel::{
+    Elapsed, RecvTimeout, RecvTimeoutExt, SendTimeout, SendTimeoutError, SendTimeoutExt,
+};
+
+mod clock;
+pub use self::clock::{ClockGuard, VirtualClock};
+
+mod delay;
+pub use self::delay::Delay;
+
+mod global;
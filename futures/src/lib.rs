@@ -67,6 +67,8 @@ pub use futures_util::pin_mut;
 #[cfg(feature = "std")]
 #[cfg(feature = "async-await")]
 pub use futures_util::{pending, poll}; // Async-await
+#[cfg(feature = "std")]
+pub use futures_util::task_local; // Task-local storage
 
 #[cfg_attr(
     feature = "cfg-target-has-atomic",
@@ -77,21 +79,42 @@ pub mod channel {
     //! Cross-task communication.
     //!
     //! Like threads, concurrent tasks sometimes need to communicate with each
-    //! other. This module contains two basic abstractions for doing so:
+    //! other. This module contains several abstractions for doing so:
     //!
     //! - [oneshot](crate::channel::oneshot), a way of sending a single value
     //!   from one task to another.
     //! - [mpsc](crate::channel::mpsc), a multi-producer, single-consumer
     //!   channel for sending values between tasks, analogous to the
     //!   similarly-named structure in the standard library.
+    //! - [mpmc](crate::channel::mpmc), a multi-producer, multi-consumer
+    //!   channel where each value is taken by one receiver, for distributing
+    //!   work among tasks.
+    //! - [broadcast](crate::channel::broadcast), a multi-producer,
+    //!   multi-consumer channel where every receiver sees every value.
+    //! - [spsc](crate::channel::spsc), a single-producer, single-consumer
+    //!   channel which is cheaper than mpsc when there is only one sender.
+    //! - [watch](crate::channel::watch), a channel which only keeps the latest
+    //!   value, for propagating state to any number of tasks.
     //!
     //! This module is only available when the `std` or `alloc` feature of this
     //! library is activated, and it is activated by default.
 
     pub use futures_channel::oneshot;
 
+    #[cfg(feature = "std")]
+    pub use futures_channel::broadcast;
+
+    #[cfg(feature = "std")]
+    pub use futures_channel::mpmc;
+
     #[cfg(feature = "std")]
     pub use futures_channel::mpsc;
+
+    #[cfg(feature = "std")]
+    pub use futures_channel::spsc;
+
+    #[cfg(feature = "std")]
+    pub use futures_channel::watch;
 }
 
 #[cfg(feature = "compat")]
@@ -112,6 +135,10 @@ pub mod compat {
         Future01CompatExt,
         Stream01CompatExt,
         Sink01CompatExt,
+        SpawnCompatExt,
+        SplitErrorFuture,
+        SplitItems,
+        UnwrapOrElse,
     };
 
     #[cfg(feature = "io-compat")]
@@ -182,10 +209,14 @@ pub mod executor {
 
     pub use futures_executor::{
         BlockingStream,
-        Enter, EnterError,
-        LocalSpawner, LocalPool,
-        ThreadPool, ThreadPoolBuilder,
-        block_on, block_on_stream, enter,
+        Enter, EnterError, GlobalSpawner,
+        JoinError, JoinHandle,
+        LocalSpawner, LocalPool, LocalPoolMetrics, PanicPolicy, Priority,
+        QueueFullPolicy, RemoteSpawner, SetGlobalSpawnerError,
+        Shutdown, SpawnBlocking, TaskHooks, TaskInfo, ThreadPool, ThreadPoolBuilder, ThreadPoolMetrics,
+        ThreadPriority, TickStats,
+        block_on, block_on_stream, block_on_timeout, enter, global_spawner, is_entered,
+        set_global_spawner, try_block_on,
     };
 }
 
@@ -294,16 +325,33 @@ pub mod io {
     //! library is activated, and it is activated by default.
 
     pub use futures_io::{
-        AsyncRead, AsyncWrite, AsyncSeek, AsyncBufRead, Error, ErrorKind,
-        Initializer, IoSlice, IoSliceMut, Result, SeekFrom,
+        AsyncRead, AsyncWrite, AsyncSeek, AsyncBufRead, AsyncReadAt,
+        AsyncWriteAt, Error, ErrorKind, Initializer, IoSlice, IoSliceMut, Result,
+        SeekFrom,
     };
 
     pub use futures_util::io::{
-        AsyncReadExt, AsyncWriteExt, AsyncSeekExt, AsyncBufReadExt, AllowStdIo,
-        BufReader, BufWriter, Chain, Close, CopyInto, CopyBufInto, Flush,
-        IntoSink, Lines, Read, ReadExact, ReadHalf, ReadLine, ReadToEnd,
-        ReadToString, ReadUntil, ReadVectored, Seek, Take, Window, Write,
-        WriteAll, WriteHalf, WriteVectored,
+        AsyncReadExt, AsyncWriteExt, AsyncSeekExt, AsyncBufReadExt,
+        AsyncReadAtExt, AsyncWriteAtExt, AllowStdIo, BufReader, BufWriter,
+        ByteLines, Chain, chain_all, ChainAll, Close, CoalesceVectored,
+        CopyInto, CopyBufInto, Flush, copy_bidirectional, CopyBidirectional,
+        CopyIntoWith, CopyOptions, duplex, DuplexStream, InspectRead,
+        InspectWrite, IntoSink, Limit, LimitExceeded, LineWriter, Lines, Read,
+        ReadAt, ReadExact, ReadExactAt, ReadExactOrEof, ReadHalf, ReadLine,
+        ReadLineLimited, ReadToEnd, ReadToEndOptions, ReadToEndWith,
+        ReadToString, ReadUntil, ReadUntilAny, ReadUntilLimited, ReadVectored,
+        ReaderStream, ReuniteError, Rewind, Seek, SeekRelative, SegmentedBuf,
+        SplitBy, Take, TimeoutReader, TimeoutWriter, Unblock, UnblockIntoInner,
+        Window, Write, WriteAt, WriteAll, WriteAllAt, WriteAllVectored,
+        WriteHalf, WriteVectored,
+    };
+
+    #[cfg_attr(feature = "cfg-target-has-atomic", cfg(target_has_atomic = "64"))]
+    pub use futures_util::io::{Meter, MeterSnapshot, Metered};
+
+    #[cfg(feature = "tokio-io-compat")]
+    pub use futures_util::io::{
+        TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt, TokioCompat,
     };
 }
 
@@ -314,7 +362,29 @@ pub mod lock {
     //! This module is only available when the `std` feature of this
     //! library is activated, and it is activated by default.
 
-    pub use futures_util::lock::{Mutex, MutexLockFuture, MutexGuard};
+    pub use futures_util::lock::{Barrier, BarrierWaitFuture, BarrierWaitResult};
+
+    pub use futures_util::lock::{Condvar, CondvarWaitFuture, CondvarWaitUntilFuture};
+
+    pub use futures_util::lock::{
+        LockTimeoutError, MappedMutexGuard, Mutex, MutexLockFuture, MutexLockTimeoutFuture,
+        MutexGuard, OwnedMutexGuard, OwnedMutexLockFuture,
+    };
+
+    pub use futures_util::lock::{Notified, Notify};
+
+    pub use futures_util::lock::{GetOrInit, Lazy, OnceCell};
+
+    pub use futures_util::lock::{
+        MappedRwLockReadGuard, MappedRwLockWriteGuard,
+        OwnedRwLockReadFuture, OwnedRwLockReadGuard, OwnedRwLockWriteFuture, OwnedRwLockWriteGuard,
+        RwLock, RwLockReadFuture, RwLockReadGuard, RwLockWriteFuture, RwLockWriteGuard,
+    };
+
+    pub use futures_util::lock::{
+        OwnedSemaphoreAcquireFuture, OwnedSemaphorePermit, Semaphore, SemaphoreAcquireFuture,
+        SemaphorePermit,
+    };
 }
 
 pub mod prelude {
@@ -372,6 +442,9 @@ pub mod sink {
         WithFlatMap,
     };
 
+    #[cfg(feature = "std")]
+    pub use futures_util::sink::SinkWriter;
+
     #[cfg(feature = "alloc")]
     pub use futures_util::sink::Buffer;
 }
@@ -481,10 +554,19 @@ pub mod task {
         Waker, RawWaker, RawWakerVTable
     };
 
-    pub use futures_util::task::noop_waker;
+    #[cfg(feature = "alloc")]
+    pub use futures_core::task::SpawnConfig;
+
+    pub use futures_util::task::{noop_waker, yield_now, YieldNow};
+
+    #[cfg(feature = "std")]
+    pub use futures_util::task::{noop_context, noop_waker_ref};
+
+    #[cfg(feature = "std")]
+    pub use futures_util::task::budget;
 
     #[cfg(feature = "std")]
-    pub use futures_util::task::noop_waker_ref;
+    pub use futures_util::task::{AccessError, LocalKey, TaskLocalExt, TaskLocalFuture};
 
     #[cfg(feature = "alloc")]
     pub use futures_util::task::{SpawnExt, LocalSpawnExt};
@@ -494,13 +576,57 @@ pub mod task {
         cfg(all(target_has_atomic = "cas", target_has_atomic = "ptr"))
     )]
     #[cfg(feature = "alloc")]
-    pub use futures_util::task::{waker, waker_ref, WakerRef, ArcWake};
+    pub use futures_util::task::{waker, waker_fn, waker_ref, waker_ref_fn, WakerRef, ArcWake};
+
+    #[cfg_attr(
+        feature = "cfg-target-has-atomic",
+        cfg(all(target_has_atomic = "cas", target_has_atomic = "ptr"))
+    )]
+    #[cfg(feature = "alloc")]
+    pub use futures_util::task::{new_count_waker, AwokenCount};
 
     #[cfg_attr(
         feature = "cfg-target-has-atomic",
         cfg(all(target_has_atomic = "cas", target_has_atomic = "ptr"))
     )]
     pub use futures_util::task::AtomicWaker;
+
+    #[cfg_attr(
+        feature = "cfg-target-has-atomic",
+        cfg(all(target_has_atomic = "cas", target_has_atomic = "ptr"))
+    )]
+    #[cfg(feature = "atomic-waker-stats")]
+    pub use futures_util::task::AtomicWakerStats;
+
+    #[cfg_attr(
+        feature = "cfg-target-has-atomic",
+        cfg(all(target_has_atomic = "cas", target_has_atomic = "ptr"))
+    )]
+    #[cfg(feature = "std")]
+    pub use futures_util::task::{scope, Scope, ScopeFuture, WakerSet};
+}
+
+#[cfg_attr(
+    feature = "cfg-target-has-atomic",
+    cfg(all(target_has_atomic = "cas", target_has_atomic = "ptr"))
+)]
+#[cfg(feature = "std")]
+pub mod timer {
+    //! Timers.
+    //!
+    //! This module contains [`Delay`](crate::timer::Delay), a future which
+    //! completes at a given point in time, driven by a background thread, and
+    //! extension traits adding deadlines to sending and receiving on channels.
+    //! A [`VirtualClock`](crate::timer::VirtualClock) can stand in for the
+    //! real time in tests.
+    //!
+    //! This module is only available when the `std` feature of this
+    //! library is activated, and it is activated by default.
+
+    pub use futures_util::timer::{
+        ClockGuard, Delay, VirtualClock,
+        Elapsed, RecvTimeout, RecvTimeoutExt, SendTimeout, SendTimeoutError, SendTimeoutExt,
+    };
 }
 
 pub mod never {
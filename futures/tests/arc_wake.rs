@@ -75,3 +75,38 @@ fn waker_ref_wake_same() {
     assert!(w1.will_wake(&w2));
     assert!(w2.will_wake(&w3));
 }
+
+#[test]
+fn waker_fn_counts_wakes() {
+    let wakes = Arc::new(Mutex::new(0));
+    let wakes2 = wakes.clone();
+    let w1 = task::waker_fn(move || *wakes2.lock().unwrap() += 1);
+    let w2 = w1.clone();
+
+    w1.wake_by_ref();
+    w1.wake();
+    assert_eq!(2, *wakes.lock().unwrap());
+    assert_eq!(2, Arc::strong_count(&wakes)); // wakes + closure
+    w2.wake();
+    assert_eq!(3, *wakes.lock().unwrap());
+    assert_eq!(1, Arc::strong_count(&wakes));
+}
+
+#[test]
+fn waker_ref_fn_refcount() {
+    let wakes = Arc::new(Mutex::new(0));
+    let wakes2 = wakes.clone();
+    let f = Arc::new(move || *wakes2.lock().unwrap() += 1);
+
+    let w1 = task::waker_ref_fn(&f);
+    assert_eq!(1, Arc::strong_count(&f));
+    w1.wake_by_ref();
+    assert_eq!(1, *wakes.lock().unwrap());
+
+    let w2 = w1.clone();
+    assert_eq!(2, Arc::strong_count(&f));
+    assert!(w1.will_wake(&w2));
+    w2.wake();
+    assert_eq!(2, *wakes.lock().unwrap());
+    assert_eq!(1, Arc::strong_count(&f));
+}
@@ -48,3 +48,38 @@ fn basic() {
 
     t.join().unwrap();
 }
+
+#[test]
+fn wake_by_ref_keeps_waker() {
+    use futures_test::task::new_count_waker;
+
+    let atomic_waker = AtomicWaker::new();
+    let (waker, count) = new_count_waker();
+    atomic_waker.wake_by_ref();
+
+    atomic_waker.register(&waker);
+    atomic_waker.wake_by_ref();
+    atomic_waker.wake_by_ref();
+    assert_eq!(count, 2);
+
+    atomic_waker.wake();
+    atomic_waker.wake();
+    assert_eq!(count, 3);
+    assert!(atomic_waker.take().is_none());
+}
+
+#[cfg(feature = "atomic-waker-stats")]
+#[test]
+fn stats() {
+    use futures_test::task::noop_waker_ref;
+
+    let atomic_waker = AtomicWaker::new();
+    atomic_waker.wake();
+    atomic_waker.register(noop_waker_ref());
+    atomic_waker.wake_by_ref();
+    atomic_waker.wake();
+    atomic_waker.wake();
+
+    let stats = atomic_waker.stats();
+    assert_eq!((stats.registrations(), stats.wakes(), stats.empty_wakes()), (1, 2, 2));
+}
@@ -0,0 +1,74 @@
+use futures::channel::mpsc;
+use futures::future::FutureExt;
+use futures::lock::Barrier;
+use futures::stream::StreamExt;
+use futures::task::{Context, SpawnExt};
+use futures_test::task::{panic_context, new_count_waker};
+use std::sync::Arc;
+
+#[test]
+fn barrier_releases_after_n() {
+    let barrier = Barrier::new(3);
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut a = barrier.wait();
+    let mut b = barrier.wait();
+    assert!(a.poll_unpin(&mut cx).is_pending());
+    assert!(b.poll_unpin(&mut cx).is_pending());
+
+    let c = barrier.wait().now_or_never().unwrap();
+    assert!(c.is_leader());
+    assert_eq!(counter, 2);
+    match (a.poll_unpin(&mut panic_context()), b.poll_unpin(&mut panic_context())) {
+        (std::task::Poll::Ready(a), std::task::Poll::Ready(b)) => {
+            assert!(!a.is_leader());
+            assert!(!b.is_leader());
+        }
+        _ => panic!("barrier not released"),
+    }
+}
+
+#[test]
+fn barrier_dropped_waiter_does_not_count() {
+    let barrier = Barrier::new(2);
+    let (waker, _counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut a = barrier.wait();
+    assert!(a.poll_unpin(&mut cx).is_pending());
+    drop(a);
+
+    let mut b = barrier.wait();
+    assert!(b.poll_unpin(&mut cx).is_pending());
+    assert!(barrier.wait().now_or_never().unwrap().is_leader());
+    assert!(b.poll_unpin(&mut panic_context()).is_ready());
+}
+
+#[test]
+fn barrier_reusable_across_tasks() {
+    let (tx, rx) = mpsc::unbounded();
+    let mut pool = futures::executor::ThreadPool::builder()
+        .pool_size(4)
+        .create()
+        .unwrap();
+
+    let num_tasks = 8;
+    let rounds = 10;
+    let barrier = Arc::new(Barrier::new(num_tasks));
+    for _ in 0..num_tasks {
+        let tx = tx.clone();
+        let barrier = barrier.clone();
+        pool.spawn(async move {
+            for _ in 0..rounds {
+                let res = barrier.wait().await;
+                tx.unbounded_send(res.is_leader()).unwrap();
+            }
+        }).unwrap();
+    }
+    drop(tx);
+
+    let results = pool.run(rx.collect::<Vec<bool>>());
+    assert_eq!(results.len(), num_tasks * rounds);
+    assert_eq!(results.iter().filter(|&&leader| leader).count(), rounds);
+}
@@ -15,3 +15,257 @@ fn can_use_01_futures_in_a_03_future_running_on_a_01_executor() {
     let mut runtime = Runtime::new().unwrap();
     runtime.block_on(f.boxed().compat()).unwrap();
 }
+
+#[test]
+fn sink_01_as_03_forwards_items() {
+    use futures::compat::{Sink01CompatExt, Stream01CompatExt};
+    use futures::executor::block_on;
+    use futures::future::join;
+
+    let (tx, rx) = tokio::sync::mpsc::channel::<i32>(1);
+    let mut tx = tx.sink_compat();
+    let send = async move {
+        for i in 0..5 {
+            tx.send(i).await.unwrap();
+        }
+    };
+    let recv = rx.compat().map(Result::unwrap).collect::<Vec<_>>();
+    let ((), items) = block_on(join(send, recv));
+    assert_eq!(items, vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn sink_03_as_01_forwards_items() {
+    use futures::channel::mpsc;
+    use futures::executor::block_on;
+    use tokio::prelude::{stream as stream01, Future as Future01, Sink as Sink01};
+
+    let (tx, rx) = mpsc::channel::<i32>(0);
+    let recv = std::thread::spawn(move || block_on(rx.collect::<Vec<_>>()));
+    let (_tx, _) = tx.compat().send_all(stream01::iter_ok(0..5)).wait().unwrap();
+    assert_eq!(recv.join().unwrap(), vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn sink_03_as_01_flushes_before_refusing_item() {
+    use futures::sink::Sink;
+    use futures::task::{Context, Poll};
+    use std::pin::Pin;
+    use tokio::prelude::{stream as stream01, Future as Future01, Sink as Sink01};
+
+    // Holds a single item, which is only delivered when flushed.
+    struct OneSlot {
+        slot: Option<i32>,
+        delivered: Vec<i32>,
+    }
+
+    impl Sink<i32> for OneSlot {
+        type Error = ();
+
+        fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+            if self.slot.is_some() {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            } else {
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        fn start_send(mut self: Pin<&mut Self>, item: i32) -> Result<(), ()> {
+            self.slot = Some(item);
+            Ok(())
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), ()>> {
+            if let Some(item) = self.slot.take() {
+                self.delivered.push(item);
+            }
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+            self.poll_flush(cx)
+        }
+    }
+
+    // The second item only fits once the first has been flushed.
+    let sink = OneSlot { slot: None, delivered: Vec::new() }.compat();
+    let (sink, _) = sink.send_all(stream01::iter_ok(vec![1, 2])).wait().unwrap();
+    assert_eq!(sink.get_ref().delivered, vec![1, 2]);
+}
+
+#[test]
+fn stream_01_split_errors() {
+    use futures::compat::Stream01CompatExt;
+    use futures::executor::block_on;
+    use futures::future::FutureExt;
+    use futures_test::task::noop_context;
+    use tokio::prelude::stream as stream01;
+
+    let stream = stream01::iter_result(vec![Ok(1), Err("boom"), Ok(2)]);
+    let (mut items, mut result) = stream.compat().split_errors();
+    let mut cx = noop_context();
+    assert!(result.poll_unpin(&mut cx).is_pending());
+    assert_eq!(block_on(items.next()), Some(1));
+    assert!(result.poll_unpin(&mut cx).is_pending());
+    assert_eq!(block_on(items.next()), None);
+    assert_eq!(block_on(items.next()), None);
+    assert_eq!(block_on(result), Err("boom"));
+
+    let stream = stream01::iter_ok::<_, ()>(vec![1, 2]);
+    let (items, result) = stream.compat().split_errors();
+    assert_eq!(block_on(items.collect::<Vec<_>>()), vec![1, 2]);
+    assert_eq!(block_on(result), Ok(()));
+
+    // Dropping the items ends the stream early.
+    let stream = stream01::iter_ok::<_, ()>(vec![1, 2]);
+    let (items, result) = stream.compat().split_errors();
+    drop(items);
+    assert_eq!(block_on(result), Ok(()));
+}
+
+#[test]
+fn stream_01_unwrap_or_else() {
+    use futures::compat::Stream01CompatExt;
+    use futures::executor::block_on;
+    use tokio::prelude::stream as stream01;
+
+    let stream = stream01::iter_result(vec![Ok(1), Err(2), Ok(3)]);
+    let stream = stream.compat().unwrap_or_else(|e| e * 10);
+    assert_eq!(block_on(stream.collect::<Vec<_>>()), vec![1, 20, 3]);
+}
+
+#[test]
+fn spawn_01_future_with_handle() {
+    use futures::compat::SpawnCompatExt;
+    use futures::executor::{block_on, ThreadPool};
+    use tokio::prelude::future as future01;
+
+    let mut executor = ThreadPool::new().unwrap();
+    let handle = executor.spawn01_with_handle(future01::ok::<u32, ()>(1)).unwrap();
+    assert_eq!(block_on(handle), Ok(1));
+    let handle = executor.spawn01_with_handle(future01::err::<(), u32>(2)).unwrap();
+    assert_eq!(block_on(handle), Err(2));
+}
+
+#[test]
+fn executor_01_as_03_spawns_with_handle() {
+    use futures::compat::Executor01CompatExt;
+    use futures::executor::block_on;
+    use futures::task::SpawnExt;
+
+    let runtime = Runtime::new().unwrap();
+    let mut spawner = runtime.executor().compat();
+    let handle = spawner.spawn_with_handle(async { 1 }).unwrap();
+    assert_eq!(block_on(handle), 1);
+}
+
+#[cfg(feature = "io-compat")]
+#[test]
+fn io_01_as_03_passes_through_vectored_io() {
+    use futures::compat::Compat01As03;
+    use futures::executor::block_on;
+    use futures::io::{AsyncReadExt, AsyncWriteExt, IoSlice, IoSliceMut};
+    use std::io::Cursor;
+
+    let mut reader = Compat01As03::new(Cursor::new(&b"hello world"[..]));
+    let (mut a, mut b) = ([0; 5], [0; 6]);
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    assert_eq!(block_on(reader.read_vectored(&mut bufs)).unwrap(), 11);
+    assert_eq!((&a, &b), (b"hello", b" world"));
+
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = Compat01As03::new(&mut cursor);
+    assert!(writer.is_write_vectored());
+    let bufs = [IoSlice::new(b"hello"), IoSlice::new(b" world")];
+    assert_eq!(block_on(writer.write_vectored(&bufs)).unwrap(), 11);
+    assert_eq!(cursor.into_inner(), b"hello world");
+}
+
+#[cfg(feature = "io-compat")]
+#[test]
+fn io_01_as_03_buf_read() {
+    use futures::compat::Compat01As03;
+    use futures::executor::block_on;
+    use futures::io::AsyncBufReadExt;
+    use std::io::Cursor;
+
+    let mut reader = Compat01As03::new(Cursor::new(&b"hello\nworld"[..]));
+    let mut line = String::new();
+    assert_eq!(block_on(reader.read_line(&mut line)).unwrap(), 6);
+    assert_eq!(line, "hello\n");
+    line.clear();
+    assert_eq!(block_on(reader.read_line(&mut line)).unwrap(), 5);
+    assert_eq!(line, "world");
+}
+
+#[cfg(feature = "io-compat")]
+#[test]
+fn io_03_as_01_passes_through_vectored_io_and_buf_read() {
+    use futures::compat::Compat;
+    use std::io::{BufRead, IoSlice, IoSliceMut, Read, Write};
+    use tokio::prelude::future::{lazy, Future as Future01};
+
+    // The 0.1 traits are only usable from within a 0.1 task.
+    lazy(|| {
+        let mut reader = Compat::new(&b"hello world"[..]);
+        let (mut a, mut b) = ([0; 5], [0; 6]);
+        let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+        assert_eq!(reader.read_vectored(&mut bufs).unwrap(), 11);
+        assert_eq!((&a, &b), (b"hello", b" world"));
+
+        let mut reader = Compat::new(&b"hello\nworld"[..]);
+        let mut line = String::new();
+        assert_eq!(reader.read_line(&mut line).unwrap(), 6);
+        assert_eq!(line, "hello\n");
+        assert_eq!(reader.fill_buf().unwrap(), b"world");
+
+        let mut writer = Compat::new(Vec::new());
+        let bufs = [IoSlice::new(b"hello"), IoSlice::new(b" world")];
+        assert_eq!(writer.write_vectored(&bufs).unwrap(), 11);
+        assert_eq!(writer.into_inner(), b"hello world");
+        Ok::<(), ()>(())
+    }).wait().unwrap();
+}
+
+#[cfg(feature = "io-compat")]
+#[test]
+fn io_01_as_03_keeps_all_traits() {
+    use futures::compat::Compat01As03;
+    use futures::executor::block_on;
+    use futures::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, SeekFrom};
+    use std::io::Cursor;
+
+    block_on(async {
+        let mut io = Compat01As03::new(Cursor::new(Vec::new()));
+        io.write_all(b"hello\nworld").await.unwrap();
+        assert_eq!(io.seek(SeekFrom::Start(0)).await.unwrap(), 0);
+
+        let mut line = String::new();
+        io.read_line(&mut line).await.unwrap();
+        assert_eq!(line, "hello\n");
+
+        assert_eq!(io.seek(SeekFrom::Current(1)).await.unwrap(), 7);
+        let mut rest = String::new();
+        io.read_to_string(&mut rest).await.unwrap();
+        assert_eq!(rest, "orld");
+    });
+}
+
+#[cfg(feature = "io-compat")]
+#[test]
+fn io_03_as_01_seek() {
+    use futures::compat::Compat;
+    use std::io::{Cursor, Read, Seek, SeekFrom};
+    use tokio::prelude::future::{lazy, Future as Future01};
+
+    // The 0.1 traits are only usable from within a 0.1 task.
+    lazy(|| {
+        let mut io = Compat::new(Cursor::new(&b"hello world"[..]));
+        assert_eq!(io.seek(SeekFrom::End(-5)).unwrap(), 6);
+        let mut buf = String::new();
+        io.read_to_string(&mut buf).unwrap();
+        assert_eq!(buf, "world");
+        Ok::<(), ()>(())
+    }).wait().unwrap();
+}
@@ -0,0 +1,97 @@
+use futures::channel::mpsc;
+use futures::future::FutureExt;
+use futures::lock::{Condvar, Mutex};
+use futures::stream::StreamExt;
+use futures::task::{Context, SpawnExt};
+use futures_test::task::{panic_context, new_count_waker};
+use std::sync::Arc;
+
+#[test]
+fn condvar_wait_unlocks_and_relocks() {
+    let mutex = Mutex::new(0);
+    let condvar = Condvar::new();
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut wait = condvar.wait(mutex.try_lock().unwrap());
+    assert!(wait.poll_unpin(&mut cx).is_pending());
+
+    // The mutex is unlocked while waiting.
+    *mutex.try_lock().unwrap() += 1;
+    condvar.notify_one();
+    assert_eq!(counter, 1);
+
+    let guard = mutex.try_lock().unwrap();
+    assert!(wait.poll_unpin(&mut cx).is_pending());
+    drop(guard);
+    match wait.poll_unpin(&mut panic_context()) {
+        std::task::Poll::Ready(guard) => assert_eq!(*guard, 1),
+        std::task::Poll::Pending => panic!("mutex not relocked"),
+    };
+}
+
+#[test]
+fn condvar_notify_before_first_poll() {
+    let mutex = Mutex::new(());
+    let condvar = Condvar::new();
+
+    let wait = condvar.wait(mutex.try_lock().unwrap());
+    condvar.notify_all();
+    assert!(wait.now_or_never().is_some());
+}
+
+#[test]
+fn condvar_dropped_waiter_passes_notification_on() {
+    let mutex = Mutex::new(());
+    let condvar = Condvar::new();
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut first = condvar.wait(mutex.try_lock().unwrap());
+    assert!(first.poll_unpin(&mut cx).is_pending());
+    let mut second = condvar.wait(mutex.try_lock().unwrap());
+    assert!(second.poll_unpin(&mut cx).is_pending());
+
+    condvar.notify_one();
+    drop(first);
+    assert_eq!(counter, 2);
+    assert!(second.poll_unpin(&mut panic_context()).is_ready());
+}
+
+#[test]
+fn condvar_producer_consumer() {
+    let (tx, mut rx) = mpsc::unbounded();
+    let mut pool = futures::executor::ThreadPool::builder()
+        .pool_size(4)
+        .create()
+        .unwrap();
+
+    let shared = Arc::new((Mutex::new(Vec::new()), Condvar::new()));
+    let num_items = 100;
+
+    let consumer = shared.clone();
+    pool.spawn(async move {
+        let (queue, condvar) = &*consumer;
+        for _ in 0..num_items {
+            let mut queue = condvar.wait_until(queue.lock().await, |q| !q.is_empty()).await;
+            let item = queue.remove(0);
+            drop(queue);
+            tx.unbounded_send(item).unwrap();
+        }
+    }).unwrap();
+
+    let producer = shared.clone();
+    pool.spawn(async move {
+        let (queue, condvar) = &*producer;
+        for i in 0..num_items {
+            queue.lock().await.push(i);
+            condvar.notify_one();
+        }
+    }).unwrap();
+
+    pool.run(async {
+        for i in 0..num_items {
+            assert_eq!(rx.next().await, Some(i));
+        }
+    })
+}
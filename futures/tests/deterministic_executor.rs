@@ -0,0 +1,59 @@
+use futures::channel::oneshot;
+use futures::task::{LocalSpawnExt, SpawnExt};
+use futures::timer::{Delay, RecvTimeoutExt};
+use futures_test::executor::DeterministicExecutor;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+fn interleaving(seed: u64) -> Vec<usize> {
+    let mut executor = DeterministicExecutor::new(seed);
+    let mut spawner = executor.spawner();
+    let order = Rc::new(RefCell::new(Vec::new()));
+    for i in 0..8 {
+        let order = order.clone();
+        spawner.spawn_local(async move { order.borrow_mut().push(i) }).unwrap();
+    }
+    executor.run();
+    let order = order.borrow().clone();
+    order
+}
+
+#[test]
+fn same_seed_same_order() {
+    for seed in 0..10 {
+        assert_eq!(interleaving(seed), interleaving(seed));
+    }
+    let orders = (0..10).map(interleaving).collect::<Vec<_>>();
+    assert!(orders.iter().any(|order| *order != orders[0]));
+}
+
+#[test]
+fn virtual_time_drives_timers() {
+    let mut executor = DeterministicExecutor::new(1);
+    let clock = executor.clock().clone();
+    let start = clock.now();
+    let real_start = Instant::now();
+
+    let (_tx, mut rx) = oneshot::channel::<()>();
+    let result = executor.run_until(async move {
+        Delay::new(Duration::from_secs(10)).await;
+        rx.recv_timeout(Duration::from_secs(3600)).await
+    });
+    assert!(result.is_err());
+    assert_eq!(clock.now() - start, Duration::from_secs(3610));
+    assert!(real_start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn run_returns_when_stuck() {
+    let mut executor = DeterministicExecutor::new(1);
+    let (tx, rx) = oneshot::channel::<()>();
+    executor.spawner().spawn(async move { rx.await.unwrap() }).unwrap();
+    executor.run();
+    assert_eq!(executor.alive_tasks(), 1);
+
+    tx.send(()).unwrap();
+    executor.run();
+    assert_eq!(executor.alive_tasks(), 0);
+}
@@ -31,3 +31,42 @@ fn dropping_drops_the_future() {
 
     assert_eq!(times_dropped, 1);
 }
+
+#[test]
+fn downcast() {
+    use futures::future::{self, LocalFutureObj, Ready};
+    use futures::executor::block_on;
+
+    let obj = FutureObj::from_boxed(Box::pin(future::ready(1)));
+    assert!(obj.is::<Ready<i32>>());
+    assert!(!obj.is::<Ready<u32>>());
+    let obj = obj.downcast::<future::Pending<i32>>().unwrap_err();
+    let fut = obj.downcast::<Ready<i32>>().unwrap();
+    assert_eq!(block_on(fut), 1);
+
+    let obj: FutureObj<'static, ()> = Box::new(future::ready(())).into();
+    assert_eq!(obj.type_id(), None);
+    assert!(obj.downcast::<Ready<()>>().is_err());
+
+    let obj = LocalFutureObj::from_boxed(Box::pin(async { 2 }));
+    assert!(obj.type_id().is_some());
+    assert_eq!(block_on(obj), 2);
+}
+
+#[test]
+fn config() {
+    use futures::future::LocalFutureObj;
+    use futures::task::SpawnConfig;
+
+    let obj = FutureObj::new(Box::new(async {}));
+    assert!(obj.config().is_none());
+
+    let config = SpawnConfig::new().with_name("task").with_metadata(7u32);
+    let mut obj: LocalFutureObj<'_, ()> = obj.with_config(config).into();
+    let config = obj.config().unwrap();
+    assert_eq!(config.name(), Some("task"));
+    assert_eq!(config.metadata::<u32>(), Some(&7));
+    assert_eq!(config.metadata::<i32>(), None);
+    assert!(obj.take_config().is_some());
+    assert!(obj.config().is_none());
+}
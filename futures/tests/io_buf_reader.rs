@@ -116,6 +116,41 @@ fn test_buffered_reader_seek_relative() {
     assert_eq!(run_fill_buf!(reader).ok(), Some(&[2, 3][..]));
 }
 
+#[test]
+fn test_buffered_reader_seek_relative_future() {
+    let inner: &[u8] = &[5, 6, 7, 0, 1, 2, 3, 4];
+    let mut reader = BufReader::with_capacity(2, Cursor::new(inner));
+
+    block_on(reader.seek_relative(3)).unwrap();
+    assert_eq!(run_fill_buf!(reader).ok(), Some(&[0, 1][..]));
+    assert_eq!(reader.get_ref().position(), 5);
+    block_on(reader.seek_relative(1)).unwrap();
+    assert_eq!(reader.buffer(), [1]);
+    // Moving within the buffer does not touch the underlying reader.
+    block_on(reader.seek_relative(-1)).unwrap();
+    assert_eq!(reader.buffer(), [0, 1]);
+    assert_eq!(reader.get_ref().position(), 5);
+    block_on(reader.seek_relative(-2)).unwrap();
+    assert_eq!(reader.buffer(), []);
+    assert_eq!(reader.get_ref().position(), 1);
+    assert_eq!(run_fill_buf!(reader).ok(), Some(&[6, 7][..]));
+}
+
+#[test]
+fn test_buffered_reader_into_parts() {
+    let inner: &[u8] = &[5, 6, 7, 0, 1, 2, 3, 4];
+    let mut reader = BufReader::with_capacity(4, inner);
+    assert_eq!(reader.capacity(), 4);
+
+    let mut buf = [0];
+    block_on(reader.read_exact(&mut buf)).unwrap();
+    assert_eq!(buf, [5]);
+
+    let (inner, unread) = reader.into_parts();
+    assert_eq!(unread, [6, 7, 0]);
+    assert_eq!(inner, [1, 2, 3, 4]);
+}
+
 #[test]
 fn test_buffered_reader_invalidated_after_read() {
     let inner: &[u8] = &[5, 6, 7, 0, 1, 2, 3, 4];
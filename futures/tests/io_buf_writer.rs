@@ -58,6 +58,16 @@ fn buf_writer_inner_flushes() {
     assert_eq!(w, [0, 1]);
 }
 
+#[test]
+fn buf_writer_into_parts() {
+    let mut w = BufWriter::with_capacity(4, Vec::new());
+    assert!(w.capacity() >= 4);
+    block_on(w.write(&[0, 1])).unwrap();
+    let (inner, unwritten) = w.into_parts();
+    assert_eq!(inner, []);
+    assert_eq!(unwritten, [0, 1]);
+}
+
 #[test]
 fn buf_writer_seek() {
     // FIXME: when https://github.com/rust-lang-nursery/futures-rs/issues/1510 fixed,
@@ -0,0 +1,52 @@
+use futures::executor::block_on;
+use futures::future::{Future, FutureExt};
+use futures::io::{AsyncBufReadExt, BufReader};
+use futures::stream::{StreamExt, TryStreamExt};
+use futures::task::Poll;
+use futures_test::io::AsyncReadTestExt;
+use futures_test::task::noop_context;
+use std::io::Cursor;
+
+fn run<F: Future + Unpin>(mut f: F) -> F::Output {
+    let mut cx = noop_context();
+    loop {
+        if let Poll::Ready(x) = f.poll_unpin(&mut cx) {
+            return x;
+        }
+    }
+}
+
+#[test]
+fn byte_lines() {
+    let reader = Cursor::new(&b"one\ntwo\r\n\n\xff\xfe\r\nlast"[..]);
+    let lines: Vec<Vec<u8>> = block_on(reader.byte_lines().try_collect()).unwrap();
+    assert_eq!(lines, [&b"one"[..], b"two", b"", b"\xff\xfe", b"last"]);
+}
+
+#[test]
+fn byte_lines_trailing_newline() {
+    let reader = Cursor::new(&b"a\r\nb\n"[..]);
+    let lines: Vec<Vec<u8>> = block_on(reader.byte_lines().try_collect()).unwrap();
+    assert_eq!(lines, [&b"a"[..], b"b"]);
+}
+
+#[test]
+fn byte_lines_interleave_pending() {
+    let reader = BufReader::with_capacity(2, Cursor::new(&b"12\r\n345\n6"[..]).interleave_pending());
+    let lines: Vec<Vec<u8>> = run(reader.byte_lines().try_collect()).unwrap();
+    assert_eq!(lines, [&b"12"[..], b"345", b"6"]);
+}
+
+#[test]
+fn byte_lines_recycle() {
+    let mut lines = Cursor::new(&b"first line\nsecond\n"[..]).byte_lines();
+    let first = block_on(lines.next()).unwrap().unwrap();
+    assert_eq!(first, b"first line");
+    let ptr = first.as_ptr();
+    lines.recycle(first);
+
+    let second = block_on(lines.next()).unwrap().unwrap();
+    assert_eq!(second, b"second");
+    assert_eq!(second.as_ptr(), ptr);
+    assert!(block_on(lines.next()).is_none());
+}
@@ -0,0 +1,60 @@
+use futures::executor::block_on;
+use futures::future::{Future, FutureExt};
+use futures::io::{self, AsyncBufReadExt, AsyncReadExt};
+use futures::task::Poll;
+use futures_test::io::AsyncReadTestExt;
+use futures_test::task::noop_context;
+use std::io::Cursor;
+
+fn run<F: Future + Unpin>(mut f: F) -> F::Output {
+    let mut cx = noop_context();
+    loop {
+        if let Poll::Ready(x) = f.poll_unpin(&mut cx) {
+            return x;
+        }
+    }
+}
+
+#[test]
+fn reads_all_in_order() {
+    let parts = vec![&b"ab"[..], b"", b"cde", b"", b"f"];
+    let mut reader = io::chain_all(parts.into_iter().map(Cursor::new));
+    let mut buf = Vec::new();
+    block_on(reader.read_to_end(&mut buf)).unwrap();
+    assert_eq!(buf, b"abcdef");
+
+    let (current, mut rest) = reader.into_inner();
+    assert!(current.is_none());
+    assert!(rest.next().is_none());
+}
+
+#[test]
+fn empty() {
+    let mut reader = io::chain_all(Vec::<Cursor<Vec<u8>>>::new());
+    let mut buf = [0; 4];
+    assert_eq!(block_on(reader.read(&mut buf)).unwrap(), 0);
+}
+
+#[test]
+fn remaining_readers() {
+    let mut reader = io::chain_all(vec![Cursor::new(vec![1, 2]), Cursor::new(vec![3])]);
+    let mut buf = [0; 2];
+    block_on(reader.read_exact(&mut buf)).unwrap();
+    assert_eq!(buf, [1, 2]);
+
+    let (current, rest) = reader.get_ref();
+    assert_eq!(current.unwrap().position(), 2);
+    assert_eq!(rest.len(), 1);
+}
+
+#[test]
+fn buf_read_maybe_pending() {
+    let parts = vec![&b"12"[..], b"", b"3\n45"];
+    let mut reader = io::chain_all(parts.into_iter().map(|p| p.interleave_pending()));
+    let mut line = String::new();
+    assert_eq!(run(reader.read_line(&mut line)).unwrap(), 4);
+    assert_eq!(line, "123\n");
+    line.clear();
+    assert_eq!(run(reader.read_line(&mut line)).unwrap(), 2);
+    assert_eq!(line, "45");
+}
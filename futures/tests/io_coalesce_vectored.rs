@@ -0,0 +1,109 @@
+use futures::executor::block_on;
+use futures::io::{AllowStdIo, AsyncReadExt, AsyncWrite, AsyncWriteExt, IoSlice};
+use futures::task::{Context, Poll};
+use std::io::{self, Cursor};
+use std::pin::Pin;
+
+/// A writer which records every call to `poll_write` and, optionally,
+/// supports vectored writes.
+#[derive(Default)]
+struct Recorder {
+    writes: Vec<Vec<u8>>,
+    vectored: bool,
+}
+
+impl AsyncWrite for Recorder {
+    fn poll_write(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.writes.push(buf.to_vec());
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_write_vectored(self: Pin<&mut Self>, cx: &mut Context<'_>, bufs: &[IoSlice<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        if !self.vectored {
+            return self.poll_write(cx, &bufs[0]);
+        }
+        let data = bufs.iter().flat_map(|b| b.iter().copied()).collect::<Vec<_>>();
+        self.poll_write(cx, &data)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.vectored
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[test]
+fn coalesces_small_buffers() {
+    let mut writer = Recorder::default().coalesce_vectored();
+    assert!(writer.is_write_vectored());
+
+    let bufs = [IoSlice::new(b""), IoSlice::new(b"ab"), IoSlice::new(b"c"), IoSlice::new(b"def")];
+    assert_eq!(block_on(writer.write_vectored(&bufs)).unwrap(), 6);
+    assert_eq!(writer.get_ref().writes, [b"abcdef".to_vec()]);
+}
+
+#[test]
+fn single_buffer_is_not_copied() {
+    let mut writer = Recorder::default().coalesce_vectored();
+    let bufs = [IoSlice::new(b""), IoSlice::new(b"abc"), IoSlice::new(b"")];
+    assert_eq!(block_on(writer.write_vectored(&bufs)).unwrap(), 3);
+    assert_eq!(block_on(writer.write_vectored(&[])).unwrap(), 0);
+    assert_eq!(writer.get_ref().writes, [b"abc".to_vec()]);
+}
+
+#[test]
+fn large_buffers_are_written_directly() {
+    let big = vec![1u8; 64 * 1024];
+    let mut writer = Recorder::default().coalesce_vectored();
+    let bufs = [IoSlice::new(&big), IoSlice::new(b"tail")];
+    assert_eq!(block_on(writer.write_vectored(&bufs)).unwrap(), big.len());
+    assert_eq!(writer.get_ref().writes.len(), 1);
+    assert_eq!(writer.get_ref().writes[0].len(), big.len());
+}
+
+#[test]
+fn coalescing_is_capped_and_write_all_completes() {
+    let chunk = vec![7u8; 3000];
+    let mut writer = Recorder::default().coalesce_vectored();
+    let mut bufs = [IoSlice::new(&chunk), IoSlice::new(&chunk), IoSlice::new(&chunk), IoSlice::new(&chunk)];
+    block_on(writer.write_all_vectored(&mut bufs)).unwrap();
+
+    let writes = &writer.get_ref().writes;
+    assert!(writes.iter().all(|w| w.len() <= 8 * 1024));
+    assert_eq!(writes.iter().map(Vec::len).sum::<usize>(), 12_000);
+    assert!(writes.len() < 4);
+}
+
+#[test]
+fn vectored_writers_are_passed_through() {
+    let inner = Recorder { vectored: true, ..Recorder::default() };
+    let mut writer = inner.coalesce_vectored();
+    let bufs = [IoSlice::new(b"ab"), IoSlice::new(b"cd")];
+    assert_eq!(block_on(writer.write_vectored(&bufs)).unwrap(), 4);
+    assert_eq!(writer.into_inner().writes, [b"abcd".to_vec()]);
+}
+
+#[test]
+fn is_write_vectored_hints() {
+    assert!(Vec::<u8>::new().is_write_vectored());
+    assert!(Box::new(Vec::<u8>::new()).is_write_vectored());
+    assert!(!Recorder::default().is_write_vectored());
+    assert!(futures::io::BufWriter::new(Recorder::default()).is_write_vectored());
+    assert!(AllowStdIo::new(Vec::<u8>::new()).is_write_vectored());
+
+    let (_, writer) = AllowStdIo::new(Cursor::new(Vec::<u8>::new())).split();
+    assert!(writer.is_write_vectored());
+    let (_, writer) = futures::io::duplex(8).0.split();
+    assert!(!writer.is_write_vectored());
+}
@@ -0,0 +1,122 @@
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures::io::{
+    self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufWriter, DuplexStream,
+};
+use futures::task::{Context, Poll};
+use futures_test::task::noop_context;
+use std::pin::Pin;
+
+/// An IO object that reads from a fixed buffer and records everything that is
+/// written to it, along with whether it has been closed.
+struct Endpoint {
+    input: &'static [u8],
+    output: Vec<u8>,
+    closed: bool,
+}
+
+impl Endpoint {
+    fn new(input: &'static [u8]) -> Self {
+        Endpoint { input, output: Vec::new(), closed: false }
+    }
+}
+
+impl AsyncRead for Endpoint {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        Pin::new(&mut self.input).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for Endpoint {
+    fn poll_write(mut self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        assert!(!self.closed, "write after close");
+        self.output.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.closed = true;
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[test]
+fn copies_both_directions() {
+    let mut a = Endpoint::new(b"hello from a");
+    let mut b = Endpoint::new(b"b says hi");
+
+    let (a_to_b, b_to_a) = block_on(io::copy_bidirectional(&mut a, &mut b)).unwrap();
+
+    assert_eq!(a_to_b, 12);
+    assert_eq!(b_to_a, 9);
+    assert_eq!(b.output, b"hello from a");
+    assert_eq!(a.output, b"b says hi");
+    assert!(a.closed);
+    assert!(b.closed);
+}
+
+#[test]
+fn empty_side_is_closed() {
+    let mut a = Endpoint::new(b"");
+    let mut b = Endpoint::new(b"data");
+
+    let (a_to_b, b_to_a) = block_on(io::copy_bidirectional(&mut a, &mut b)).unwrap();
+
+    assert_eq!((a_to_b, b_to_a), (0, 4));
+    assert!(b.output.is_empty());
+    assert_eq!(a.output, b"data");
+    assert!(b.closed);
+}
+
+/// A duplex stream whose writes go through a `BufWriter`.
+struct Buffered(BufWriter<DuplexStream>);
+
+impl AsyncRead for Buffered {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        Pin::new(self.0.get_mut()).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for Buffered {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_close(cx)
+    }
+}
+
+#[test]
+fn flushes_buffered_peer_while_waiting() {
+    let (mut client, mut a) = io::duplex(64);
+    let (b, mut server) = io::duplex(64);
+    let mut b = Buffered(BufWriter::new(b));
+    let mut copy = io::copy_bidirectional(&mut a, &mut b);
+    let mut cx = noop_context();
+
+    block_on(client.write_all(b"ping")).unwrap();
+    assert!(copy.poll_unpin(&mut cx).is_pending());
+
+    // The client has not closed its side, but what it sent so far has been
+    // flushed through to the server.
+    let mut buf = [0; 4];
+    assert_eq!(server.read(&mut buf).now_or_never().unwrap().unwrap(), 4);
+    assert_eq!(&buf, b"ping");
+}
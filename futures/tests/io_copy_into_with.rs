@@ -0,0 +1,37 @@
+use futures::executor::block_on;
+use futures::io::{AsyncReadExt, CopyOptions};
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn defaults_copy_everything() {
+    let reader = Cursor::new(vec![7u8; 20_000]);
+    let mut writer = Vec::new();
+    let n = block_on(reader.copy_into_with(&mut writer, CopyOptions::default())).unwrap();
+    assert_eq!(n, 20_000);
+    assert_eq!(writer, vec![7u8; 20_000]);
+}
+
+#[test]
+fn max_bytes_stops_reading() {
+    let mut reader = Cursor::new(b"0123456789".to_vec());
+    let mut writer = Vec::new();
+    let options = CopyOptions::new().buffer_size(3).max_bytes(4);
+    let n = block_on((&mut reader).copy_into_with(&mut writer, options)).unwrap();
+    assert_eq!(n, 4);
+    assert_eq!(writer, b"0123");
+    assert_eq!(reader.position(), 4);
+}
+
+#[test]
+fn progress_is_reported_per_chunk() {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let reports2 = reports.clone();
+    let reader = Cursor::new(b"abcdefg".to_vec());
+    let mut writer = Vec::new();
+    let options = CopyOptions::new()
+        .buffer_size(3)
+        .progress(move |n| reports2.lock().unwrap().push(n));
+    block_on(reader.copy_into_with(&mut writer, options)).unwrap();
+    assert_eq!(*reports.lock().unwrap(), [3, 6, 7]);
+}
@@ -16,3 +16,21 @@ fn cursor_asyncwrite_asmut() {
     }));
     assert_eq!(cursor.into_inner(), [1, 2, 3, 4, 5]);
 }
+
+#[test]
+fn cursor_stream_position_and_rewind() {
+    use futures::io::{AsyncReadExt, AsyncSeekExt, SeekFrom};
+
+    let mut cursor = Cursor::new(vec![1, 2, 3, 4, 5]);
+    futures::executor::block_on(async {
+        assert_eq!(cursor.stream_position().await.unwrap(), 0);
+        cursor.seek(SeekFrom::End(-2)).await.unwrap();
+        assert_eq!(cursor.stream_position().await.unwrap(), 3);
+
+        cursor.rewind().await.unwrap();
+        assert_eq!(cursor.stream_position().await.unwrap(), 0);
+        let mut buf = [0; 2];
+        cursor.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, [1, 2]);
+    });
+}
@@ -0,0 +1,87 @@
+use futures::executor::block_on;
+use futures::future::join;
+use futures::io::{self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, noop_context};
+use std::pin::Pin;
+use std::task::Context;
+
+#[test]
+fn write_waits_for_capacity() {
+    let (mut a, mut b) = io::duplex(4);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(Pin::new(&mut a).poll_write(&mut cx, b"abcdef").map(Result::unwrap), Poll::Ready(4));
+    assert!(Pin::new(&mut a).poll_write(&mut cx, b"ef").is_pending());
+    assert_eq!(count, 0);
+
+    let mut buf = [0; 3];
+    assert_eq!(block_on(b.read(&mut buf)).unwrap(), 3);
+    assert_eq!(&buf, b"abc");
+    assert_eq!(count, 1);
+    assert_eq!(Pin::new(&mut a).poll_write(&mut cx, b"ef").map(Result::unwrap), Poll::Ready(2));
+}
+
+#[test]
+fn read_waits_for_data() {
+    let (mut a, mut b) = io::duplex(4);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut buf = [0; 4];
+    assert!(Pin::new(&mut b).poll_read(&mut cx, &mut buf).is_pending());
+    block_on(a.write_all(b"hi")).unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(Pin::new(&mut b).poll_read(&mut cx, &mut buf).map(Result::unwrap), Poll::Ready(2));
+    assert_eq!(&buf[..2], b"hi");
+}
+
+#[test]
+fn transfers_more_than_capacity() {
+    let (mut a, mut b) = io::duplex(7);
+    let data: Vec<u8> = (0..255).cycle().take(10_000).collect();
+
+    let write = async {
+        a.write_all(&data).await.unwrap();
+        a.close().await.unwrap();
+    };
+    let mut received = Vec::new();
+    let read = b.read_to_end(&mut received);
+    let ((), n) = block_on(join(write, read));
+    assert_eq!(n.unwrap(), data.len());
+    assert_eq!(received, data);
+}
+
+#[test]
+fn drop_closes_both_directions() {
+    let (mut a, b) = io::duplex(8);
+    block_on(a.write_all(b"left")).unwrap();
+    drop(b);
+
+    let mut cx = noop_context();
+    match Pin::new(&mut a).poll_write(&mut cx, b"more") {
+        Poll::Ready(Err(e)) => assert_eq!(e.kind(), std::io::ErrorKind::BrokenPipe),
+        other => panic!("unexpected {:?}", other),
+    }
+    let mut buf = Vec::new();
+    assert_eq!(block_on(a.read_to_end(&mut buf)).unwrap(), 0);
+}
+
+#[test]
+fn close_delivers_buffered_data_then_eof() {
+    let (mut a, mut b) = io::duplex(8);
+    block_on(a.write_all(b"bye")).unwrap();
+    block_on(a.close()).unwrap();
+    assert!(block_on(a.write(b"x")).is_err());
+
+    let mut buf = Vec::new();
+    assert_eq!(block_on(b.read_to_end(&mut buf)).unwrap(), 3);
+    assert_eq!(buf, b"bye");
+
+    // The other direction is still open.
+    block_on(b.write_all(b"ok")).unwrap();
+    let mut reply = [0; 2];
+    block_on(a.read_exact(&mut reply)).unwrap();
+    assert_eq!(&reply, b"ok");
+}
@@ -0,0 +1,60 @@
+use futures::executor::block_on;
+use futures::future::{Future, FutureExt};
+use futures::io::{AsyncReadExt, AsyncWriteExt, IoSlice};
+use futures::task::Poll;
+use futures_test::io::{AsyncReadTestExt, AsyncWriteTestExt};
+use futures_test::task::noop_context;
+use std::io::Cursor;
+
+fn run<F: Future + Unpin>(mut f: F) -> F::Output {
+    let mut cx = noop_context();
+    loop {
+        if let Poll::Ready(x) = f.poll_unpin(&mut cx) {
+            return x;
+        }
+    }
+}
+
+#[test]
+fn inspect_read_sees_every_chunk() {
+    let mut chunks = Vec::new();
+    let mut reader = Cursor::new(&b"hello world"[..])
+        .interleave_pending()
+        .inspect_read(|buf| chunks.push(buf.to_vec()));
+
+    let mut buf = [0; 4];
+    assert_eq!(run(reader.read(&mut buf)).unwrap(), 4);
+    let mut rest = Vec::new();
+    run(reader.read_to_end(&mut rest)).unwrap();
+    drop(reader);
+
+    assert_eq!(chunks[0], b"hell");
+    assert_eq!(chunks.concat(), b"hello world");
+    assert!(chunks.iter().all(|chunk| !chunk.is_empty()));
+}
+
+#[test]
+fn inspect_write_sees_accepted_bytes() {
+    let mut seen = Vec::new();
+    let mut writer = Cursor::new(vec![0u8; 16])
+        .limited_write(3)
+        .inspect_write(|buf| seen.extend_from_slice(buf));
+
+    assert_eq!(block_on(writer.write(b"hello")).unwrap(), 3);
+    block_on(writer.write_all(b"lo world")).unwrap();
+    drop(writer);
+
+    assert_eq!(seen, b"hello world");
+}
+
+#[test]
+fn inspect_write_vectored() {
+    let mut chunks = Vec::new();
+    let mut writer = Cursor::new(vec![0u8; 16]).inspect_write(|buf| chunks.push(buf.to_vec()));
+
+    let bufs = [IoSlice::new(b"ab"), IoSlice::new(b""), IoSlice::new(b"cde")];
+    assert_eq!(block_on(writer.write_vectored(&bufs)).unwrap(), 5);
+    drop(writer);
+
+    assert_eq!(chunks, [&b"ab"[..], b"cde"]);
+}
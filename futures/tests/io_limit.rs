@@ -0,0 +1,50 @@
+use futures::executor::block_on;
+use futures::future::{Future, FutureExt};
+use futures::io::{AsyncWriteExt, IoSlice, LimitExceeded};
+use futures::task::Poll;
+use futures_test::io::AsyncWriteTestExt;
+use futures_test::task::noop_context;
+
+fn run<F: Future + Unpin>(mut f: F) -> F::Output {
+    let mut cx = noop_context();
+    loop {
+        if let Poll::Ready(x) = f.poll_unpin(&mut cx) {
+            return x;
+        }
+    }
+}
+
+#[test]
+fn shortens_writes_at_limit() {
+    let mut writer = Vec::new().limit(5);
+    assert_eq!(block_on(writer.write(b"abc")).unwrap(), 3);
+    assert_eq!(block_on(writer.write(b"defg")).unwrap(), 2);
+    assert_eq!(writer.bytes_written(), 5);
+    assert_eq!(writer.remaining(), 0);
+
+    let err = block_on(writer.write(b"h")).unwrap_err();
+    let limit = err.get_ref().and_then(|e| e.downcast_ref::<LimitExceeded>());
+    assert_eq!(limit.map(LimitExceeded::limit), Some(5));
+    assert_eq!(block_on(writer.write(b"")).unwrap(), 0);
+
+    writer.set_limit(6);
+    assert_eq!(block_on(writer.write(b"hi")).unwrap(), 1);
+    assert_eq!(writer.into_inner(), b"abcdeh");
+}
+
+#[test]
+fn vectored() {
+    let mut writer = Vec::new().limit(4);
+    let bufs = [IoSlice::new(b"ab"), IoSlice::new(b"c")];
+    assert_eq!(block_on(writer.write_vectored(&bufs)).unwrap(), 3);
+    let bufs = [IoSlice::new(b""), IoSlice::new(b"de")];
+    assert_eq!(block_on(writer.write_vectored(&bufs)).unwrap(), 1);
+    assert_eq!(writer.into_inner(), b"abcd");
+}
+
+#[test]
+fn maybe_pending() {
+    let mut writer = Vec::new().interleave_pending_write().limit(3);
+    assert!(run(writer.write_all(b"abcd")).is_err());
+    assert_eq!(writer.bytes_written(), 3);
+}
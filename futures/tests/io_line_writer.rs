@@ -0,0 +1,94 @@
+use futures::executor::block_on;
+use futures::future::{Future, FutureExt};
+use futures::io::{AsyncWrite, AsyncWriteExt, LineWriter};
+use futures::task::{Context, Poll};
+use futures_test::task::noop_context;
+use std::io;
+use std::pin::Pin;
+
+#[test]
+fn line_writer() {
+    let mut writer = LineWriter::with_capacity(4, Vec::new());
+
+    block_on(writer.write(&[0])).unwrap();
+    assert_eq!(*writer.get_ref(), []);
+    assert_eq!(writer.buffer(), [0]);
+
+    block_on(writer.write(&[1])).unwrap();
+    assert_eq!(*writer.get_ref(), []);
+
+    block_on(writer.flush()).unwrap();
+    assert_eq!(*writer.get_ref(), [0, 1]);
+
+    block_on(writer.write(&[0, b'\n', 1, b'\n', 2])).unwrap();
+    assert_eq!(*writer.get_ref(), [0, 1, 0, b'\n', 1, b'\n']);
+    assert_eq!(writer.buffer(), [2]);
+
+    block_on(writer.flush()).unwrap();
+    assert_eq!(*writer.get_ref(), [0, 1, 0, b'\n', 1, b'\n', 2]);
+
+    block_on(writer.write(&[3, b'\n'])).unwrap();
+    assert_eq!(*writer.get_ref(), [0, 1, 0, b'\n', 1, b'\n', 2, 3, b'\n']);
+}
+
+#[test]
+fn line_writer_long_line() {
+    let mut writer = LineWriter::with_capacity(2, Vec::new());
+    block_on(writer.write_all(b"a long line\nand the rest of the text")).unwrap();
+    assert!(writer.get_ref().starts_with(b"a long line\n"));
+    let mut written = writer.get_ref().clone();
+    written.extend_from_slice(writer.buffer());
+    assert_eq!(written, b"a long line\nand the rest of the text");
+    block_on(writer.close()).unwrap();
+    assert_eq!(*writer.get_ref(), b"a long line\nand the rest of the text");
+}
+
+/// A writer which accepts at most one byte per write and alternates between
+/// returning `Pending` and `Ready`.
+struct Trickle {
+    data: Vec<u8>,
+    ready: bool,
+}
+
+impl AsyncWrite for Trickle {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.ready = !self.ready;
+        if !self.ready {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        self.data.extend_from_slice(&buf[..1]);
+        Poll::Ready(Ok(1))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn run<F: Future + Unpin>(mut f: F) -> F::Output {
+    let mut cx = noop_context();
+    loop {
+        if let Poll::Ready(x) = f.poll_unpin(&mut cx) {
+            return x;
+        }
+    }
+}
+
+#[test]
+fn line_writer_partial_writes() {
+    let mut writer = LineWriter::with_capacity(8, Trickle { data: Vec::new(), ready: false });
+    run(writer.write_all(b"ab\ncd\nef")).unwrap();
+    assert_eq!(writer.get_ref().data, b"ab\ncd\n");
+    assert_eq!(writer.buffer(), b"ef");
+    run(writer.write_all(b"g")).unwrap();
+    assert_eq!(writer.buffer(), b"efg");
+    run(writer.flush()).unwrap();
+    assert_eq!(writer.get_ref().data, b"ab\ncd\nefg");
+}
@@ -0,0 +1,77 @@
+use futures::executor::block_on;
+use futures::future::{Future, FutureExt};
+use futures::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, Metered};
+use futures::task::Poll;
+use futures_test::io::{AsyncReadTestExt, AsyncWriteTestExt};
+use futures_test::task::noop_context;
+use std::io::Cursor;
+use std::thread;
+use std::time::Duration;
+
+fn run<F: Future + Unpin>(mut f: F) -> F::Output {
+    let mut cx = noop_context();
+    loop {
+        if let Poll::Ready(x) = f.poll_unpin(&mut cx) {
+            return x;
+        }
+    }
+}
+
+#[test]
+fn counts_bytes_and_operations() {
+    let mut io = Metered::new(Cursor::new(vec![0; 16]));
+    let meter = io.meter();
+
+    let mut buf = [0; 4];
+    block_on(io.read_exact(&mut buf)).unwrap();
+    block_on(io.read_exact(&mut buf)).unwrap();
+    block_on(io.write_all(&[1, 2, 3])).unwrap();
+
+    let snapshot = meter.snapshot();
+    assert_eq!(snapshot.bytes_read(), 8);
+    assert_eq!(snapshot.reads(), 2);
+    assert_eq!(snapshot.bytes_written(), 3);
+    assert_eq!(snapshot.writes(), 1);
+
+    // The handle outlives the I/O object.
+    drop(io);
+    assert_eq!(meter.snapshot(), snapshot);
+}
+
+#[test]
+fn buf_read_counts_consumed_bytes() {
+    let mut io = Metered::new(Cursor::new(b"line one\nline two\n".to_vec()));
+    let mut line = String::new();
+    block_on(io.read_line(&mut line)).unwrap();
+    assert_eq!(io.meter().snapshot().bytes_read(), 9);
+}
+
+#[test]
+fn measures_pending_time() {
+    let mut io = Metered::new(Vec::new().interleave_pending_write());
+    let meter = io.meter();
+    let mut write = io.write_all(&[1, 2, 3]);
+    assert!(write.poll_unpin(&mut noop_context()).is_pending());
+    thread::sleep(Duration::from_millis(10));
+    run(write).unwrap();
+
+    let snapshot = meter.snapshot();
+    assert!(snapshot.write_pending() >= Duration::from_millis(10));
+    assert_eq!(snapshot.read_pending(), Duration::from_secs(0));
+    assert_eq!(snapshot.bytes_written(), 3);
+
+    let mut reader = Metered::new((&b"abc"[..]).interleave_pending());
+    let mut buf = Vec::new();
+    run(reader.read_to_end(&mut buf)).unwrap();
+    assert_eq!(reader.meter().snapshot().bytes_read(), 3);
+}
+
+#[test]
+fn throughput() {
+    let mut io = Metered::new(Vec::new());
+    block_on(io.write_all(&[0; 1000])).unwrap();
+    let snapshot = io.meter().snapshot();
+    assert_eq!(snapshot.write_throughput(Duration::from_millis(500)), 2000.0);
+    assert_eq!(snapshot.read_throughput(Duration::from_secs(1)), 0.0);
+    assert_eq!(snapshot.write_throughput(Duration::from_secs(0)), 0.0);
+}
@@ -0,0 +1,88 @@
+use futures::executor::block_on;
+use futures::future::join;
+use futures::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, ErrorKind};
+use futures::task::Poll;
+use futures_test::io::Builder;
+use futures_test::task::new_count_waker;
+use std::io;
+use std::pin::Pin;
+use std::task::Context;
+
+#[test]
+fn reads_are_split_by_buffer_size() {
+    let mut mock = Builder::new().read(b"hello world").build();
+    let mut buf = [0; 4];
+    assert_eq!(block_on(mock.read(&mut buf)).unwrap(), 4);
+    assert_eq!(&buf, b"hell");
+
+    let mut rest = String::new();
+    block_on(mock.read_to_string(&mut rest)).unwrap();
+    assert_eq!(rest, "o world");
+}
+
+#[test]
+fn writes_may_be_split() {
+    let mut mock = Builder::new().write(b"hello world").build();
+    block_on(async {
+        mock.write_all(b"hello").await.unwrap();
+        mock.write_all(b" world").await.unwrap();
+    });
+}
+
+#[test]
+fn pending_gap() {
+    let mut mock = Builder::new().pending().read(b"a").build();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut buf = [0; 1];
+
+    assert!(Pin::new(&mut mock).poll_read(&mut cx, &mut buf).is_pending());
+    assert_eq!(count, 1);
+    match Pin::new(&mut mock).poll_read(&mut cx, &mut buf) {
+        Poll::Ready(Ok(1)) => {}
+        poll => panic!("unexpected {:?}", poll),
+    }
+}
+
+#[test]
+fn scripted_errors() {
+    let mut mock = Builder::new()
+        .read_error(io::Error::new(ErrorKind::ConnectionReset, "reset"))
+        .write_error(io::Error::new(ErrorKind::BrokenPipe, "broken"))
+        .build();
+    let mut buf = [0; 1];
+    assert_eq!(block_on(mock.read(&mut buf)).unwrap_err().kind(), ErrorKind::ConnectionReset);
+    assert_eq!(block_on(mock.write(b"a")).unwrap_err().kind(), ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn read_waits_for_write() {
+    let mut builder = Builder::new();
+    builder.write(b"ping").read(b"pong");
+    let (mut reader, mut writer) = builder.build().split();
+
+    let read = async {
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf).await.unwrap();
+        buf
+    };
+    let write = writer.write_all(b"ping");
+    let (buf, res) = block_on(join(read, write));
+    res.unwrap();
+    assert_eq!(&buf, b"pong");
+}
+
+#[test]
+#[should_panic(expected = "other bytes than expected")]
+fn mismatched_write_panics() {
+    let mut mock = Builder::new().write(b"hello").build();
+    let _ = block_on(mock.write_all(b"help"));
+}
+
+#[test]
+#[should_panic(expected = "dropped before performing")]
+fn unperformed_actions_panic_on_drop() {
+    let mut mock = Builder::new().read(b"hello").write(b"bye").build();
+    let mut buf = [0; 5];
+    block_on(mock.read_exact(&mut buf)).unwrap();
+}
@@ -0,0 +1,92 @@
+use futures::executor::block_on;
+use futures::future::{Future, FutureExt};
+use futures::ready;
+use futures::io::{self, AsyncReadAt, AsyncReadAtExt, AsyncWriteAt, AsyncWriteAtExt};
+use futures::task::{Context, Poll};
+use futures_test::task::noop_context;
+use std::cmp;
+use std::pin::Pin;
+
+/// A positional IO object which transfers at most one byte per call and
+/// alternates between returning `Pending` and `Ready`.
+struct Trickle {
+    data: Vec<u8>,
+    ready: bool,
+}
+
+impl Trickle {
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.ready = !self.ready;
+        if self.ready {
+            Poll::Ready(())
+        } else {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+impl AsyncReadAt for Trickle {
+    fn poll_read_at(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8], offset: u64)
+        -> Poll<io::Result<usize>>
+    {
+        ready!(self.poll_ready(cx));
+        let len = cmp::min(buf.len(), 1);
+        Pin::new(&mut self.data).poll_read_at(cx, &mut buf[..len], offset)
+    }
+}
+
+impl AsyncWriteAt for Trickle {
+    fn poll_write_at(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8], offset: u64)
+        -> Poll<io::Result<usize>>
+    {
+        ready!(self.poll_ready(cx));
+        let len = cmp::min(buf.len(), 1);
+        Pin::new(&mut self.data).poll_write_at(cx, &buf[..len], offset)
+    }
+}
+
+fn run<F: Future + Unpin>(mut f: F) -> F::Output {
+    let mut cx = noop_context();
+    loop {
+        if let Poll::Ready(x) = f.poll_unpin(&mut cx) {
+            return x;
+        }
+    }
+}
+
+#[test]
+fn read_at_slice() {
+    let mut data: &[u8] = &[1, 2, 3];
+    let mut buf = [0; 2];
+    assert_eq!(block_on(data.read_at(&mut buf, 0)).unwrap(), 2);
+    assert_eq!(buf, [1, 2]);
+    assert_eq!(block_on(data.read_at(&mut buf, 2)).unwrap(), 1);
+    assert_eq!(buf[0], 3);
+    assert_eq!(block_on(data.read_at(&mut buf, 3)).unwrap(), 0);
+    assert_eq!(block_on(data.read_at(&mut buf, u64::MAX)).unwrap(), 0);
+    // The slice itself is not advanced.
+    assert_eq!(data, [1, 2, 3]);
+}
+
+#[test]
+fn read_exact_at() {
+    let mut io = Trickle { data: vec![1, 2, 3, 4, 5], ready: false };
+    let mut buf = [0; 3];
+    run(io.read_exact_at(&mut buf, 1)).unwrap();
+    assert_eq!(buf, [2, 3, 4]);
+
+    let err = run(io.read_exact_at(&mut buf, 3)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn write_all_at() {
+    let mut io = Trickle { data: vec![1, 2], ready: false };
+    run(io.write_all_at(&[7, 8, 9], 1)).unwrap();
+    assert_eq!(io.data, [1, 7, 8, 9]);
+    run(io.write_all_at(&[5], 6)).unwrap();
+    assert_eq!(io.data, [1, 7, 8, 9, 0, 0, 5]);
+    assert_eq!(run(io.write_at(&[1, 1], 0)).unwrap(), 1);
+    assert_eq!(io.data, [1, 7, 8, 9, 0, 0, 5]);
+}
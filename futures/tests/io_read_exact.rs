@@ -15,3 +15,19 @@ fn read_exact() {
     assert!(res.is_err());
     assert_eq!(reader.len(), 0);
 }
+
+#[test]
+fn read_exact_or_eof() {
+    use futures_test::io::AsyncReadTestExt;
+
+    let mut reader = (&[1, 2, 3, 4, 5u8][..]).limited(2);
+    let mut out = [0u8; 3];
+
+    assert_eq!(block_on(reader.read_exact_or_eof(&mut out)).unwrap(), 3);
+    assert_eq!(out, [1, 2, 3]);
+
+    assert_eq!(block_on(reader.read_exact_or_eof(&mut out)).unwrap(), 2);
+    assert_eq!(out[..2], [4, 5]);
+
+    assert_eq!(block_on(reader.read_exact_or_eof(&mut out)).unwrap(), 0);
+}
@@ -0,0 +1,106 @@
+use futures::executor::block_on;
+use futures::future::{Future, FutureExt};
+use futures::io::{AsyncBufReadExt, ErrorKind, LimitExceeded};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use futures::task::Poll;
+use futures_test::task::noop_context;
+use std::io::{self, Cursor};
+
+fn limit_of(err: &io::Error) -> Option<usize> {
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    err.get_ref()
+        .and_then(|e| e.downcast_ref::<LimitExceeded>())
+        .map(LimitExceeded::limit)
+}
+
+fn run<F: Future + Unpin>(mut f: F) -> F::Output {
+    let mut cx = noop_context();
+    loop {
+        if let Poll::Ready(x) = f.poll_unpin(&mut cx) {
+            return x;
+        }
+    }
+}
+
+#[test]
+fn read_until_within_limit() {
+    let mut buf = Cursor::new(b"123");
+    let mut v = Vec::new();
+    assert_eq!(block_on(buf.read_until_limited(b'3', 3, &mut v)).unwrap(), 3);
+    assert_eq!(v, b"123");
+    v.clear();
+    assert_eq!(block_on(buf.read_until_limited(b'3', 3, &mut v)).unwrap(), 0);
+    assert_eq!(v, []);
+}
+
+#[test]
+fn read_until_exactly_limit_at_eof() {
+    let mut buf = Cursor::new(b"12");
+    let mut v = Vec::new();
+    assert_eq!(block_on(buf.read_until_limited(b'3', 2, &mut v)).unwrap(), 2);
+    assert_eq!(v, b"12");
+}
+
+#[test]
+fn read_until_exceeds_limit() {
+    let mut buf = Cursor::new(b"12345");
+    let mut v = Vec::new();
+    let err = block_on(buf.read_until_limited(b'5', 3, &mut v)).unwrap_err();
+    assert_eq!(limit_of(&err), Some(3));
+    assert_eq!(v, b"123");
+
+    // The remaining bytes have not been consumed.
+    v.clear();
+    assert_eq!(block_on(buf.read_until_limited(b'5', 3, &mut v)).unwrap(), 2);
+    assert_eq!(v, b"45");
+}
+
+#[test]
+fn read_until_zero_limit() {
+    let mut buf = Cursor::new(b"1");
+    let mut v = Vec::new();
+    let err = block_on(buf.read_until_limited(b'1', 0, &mut v)).unwrap_err();
+    assert_eq!(limit_of(&err), Some(0));
+
+    let mut buf = Cursor::new(b"");
+    assert_eq!(block_on(buf.read_until_limited(b'1', 0, &mut v)).unwrap(), 0);
+}
+
+#[test]
+fn read_until_across_chunks() {
+    let mut buf = stream::iter(vec![&b"12"[..], &b"34"[..], &b"5\n"[..]])
+        .map(Ok)
+        .into_async_read();
+    let mut v = Vec::new();
+    let err = run(buf.read_until_limited(b'\n', 4, &mut v)).unwrap_err();
+    assert_eq!(limit_of(&err), Some(4));
+    assert_eq!(v, b"1234");
+}
+
+#[test]
+fn read_line_limited() {
+    let mut buf = Cursor::new(b"12\n3456\n");
+    let mut s = String::new();
+    assert_eq!(block_on(buf.read_line_limited(3, &mut s)).unwrap(), 3);
+    assert_eq!(s, "12\n");
+    s.clear();
+    let err = block_on(buf.read_line_limited(3, &mut s)).unwrap_err();
+    assert_eq!(limit_of(&err), Some(3));
+    assert_eq!(s, "345");
+}
+
+#[test]
+fn read_line_limited_invalid_utf8() {
+    let mut buf = Cursor::new(b"\xff\xff\xff\xff");
+    let mut s = String::new();
+    let err = block_on(buf.read_line_limited(8, &mut s)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(limit_of(&err), None);
+    assert_eq!(s, "");
+
+    // Hitting the limit takes precedence, but the invalid bytes are dropped.
+    let mut buf = Cursor::new(b"\xff\xff\xff\xff");
+    let err = block_on(buf.read_line_limited(2, &mut s)).unwrap_err();
+    assert_eq!(limit_of(&err), Some(2));
+    assert_eq!(s, "");
+}
@@ -0,0 +1,64 @@
+use futures::executor::block_on;
+use futures::future::{Future, FutureExt};
+use futures::io::{AsyncReadExt, ReadToEndOptions};
+use futures::task::Poll;
+use futures_test::io::AsyncReadTestExt;
+use futures_test::task::noop_context;
+use std::io::{Cursor, ErrorKind};
+
+fn run<F: Future + Unpin>(mut f: F) -> F::Output {
+    let mut cx = noop_context();
+    loop {
+        if let Poll::Ready(x) = f.poll_unpin(&mut cx) {
+            return x;
+        }
+    }
+}
+
+#[test]
+fn reserves_size_hint() {
+    let mut buf = Vec::new();
+    let options = ReadToEndOptions::new().size_hint(100);
+    assert_eq!(block_on(Cursor::new([1, 2, 3]).read_to_end_with(&mut buf, options)).unwrap(), 3);
+    assert_eq!(buf, [1, 2, 3]);
+    assert!(buf.capacity() >= 100);
+}
+
+#[test]
+fn size_hint_is_capped_by_max_bytes() {
+    let mut buf = Vec::new();
+    let options = ReadToEndOptions::new().size_hint(usize::MAX).max_bytes(8);
+    assert_eq!(block_on(Cursor::new([1, 2, 3]).read_to_end_with(&mut buf, options)).unwrap(), 3);
+    assert_eq!(buf, [1, 2, 3]);
+}
+
+#[test]
+fn max_bytes_counts_only_new_data() {
+    let mut buf = vec![0; 10];
+    let options = ReadToEndOptions::new().max_bytes(4);
+    let mut reader = Cursor::new([1, 2, 3, 4]).interleave_pending().limited(1);
+    assert_eq!(run(reader.read_to_end_with(&mut buf, options)).unwrap(), 4);
+    assert_eq!(buf[10..], [1, 2, 3, 4]);
+}
+
+#[test]
+fn exceeding_max_bytes_fails() {
+    let mut buf = Vec::new();
+    let options = ReadToEndOptions::new().max_bytes(4);
+    let mut reader = Cursor::new([1, 2, 3, 4, 5, 6]).interleave_pending();
+    let err = run(reader.read_to_end_with(&mut buf, options)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(buf, [1, 2, 3, 4]);
+}
+
+#[test]
+fn zero_max_bytes() {
+    let options = ReadToEndOptions::new().max_bytes(0);
+
+    let mut buf = Vec::new();
+    assert_eq!(block_on(Cursor::new([]).read_to_end_with(&mut buf, options)).unwrap(), 0);
+
+    let err = block_on(Cursor::new([1]).read_to_end_with(&mut buf, options)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(buf.is_empty());
+}
@@ -0,0 +1,65 @@
+use futures::executor::block_on;
+use futures::future::{Future, FutureExt};
+use futures::io::AsyncReadExt;
+use futures::stream::{FusedStream, StreamExt, TryStreamExt};
+use futures::task::Poll;
+use futures_test::io::AsyncReadTestExt;
+use futures_test::task::noop_context;
+use std::io::Cursor;
+
+fn run<F: Future + Unpin>(mut f: F) -> F::Output {
+    let mut cx = noop_context();
+    loop {
+        if let Poll::Ready(x) = f.poll_unpin(&mut cx) {
+            return x;
+        }
+    }
+}
+
+#[test]
+fn yields_chunks_until_eof() {
+    let reader = Cursor::new(&b"0123456789"[..]);
+    let chunks: Vec<Vec<u8>> = block_on(reader.into_stream(3).try_collect()).unwrap();
+    assert_eq!(chunks, [&b"012"[..], b"345", b"678", b"9"]);
+}
+
+#[test]
+fn interleave_pending() {
+    let reader = Cursor::new(&b"0123456789"[..]).interleave_pending();
+    let chunks: Vec<Vec<u8>> = run(reader.into_stream(4).try_collect()).unwrap();
+    assert_eq!(chunks.concat(), b"0123456789");
+    assert!(chunks.iter().all(|chunk| !chunk.is_empty() && chunk.len() <= 4));
+}
+
+#[test]
+fn fused_after_eof() {
+    let mut stream = Cursor::new(&b"ab"[..]).into_stream(8);
+    assert!(!stream.is_terminated());
+    assert_eq!(block_on(stream.next()).unwrap().unwrap(), b"ab");
+    assert!(block_on(stream.next()).is_none());
+    assert!(stream.is_terminated());
+    assert!(block_on(stream.next()).is_none());
+}
+
+#[test]
+fn recycled_buffers_are_reused() {
+    let mut stream = Cursor::new(&b"abcdef"[..]).into_stream(2);
+    let first = block_on(stream.next()).unwrap().unwrap();
+    assert_eq!(first, b"ab");
+    let ptr = first.as_ptr();
+    stream.recycle(first);
+
+    let second = block_on(stream.next()).unwrap().unwrap();
+    assert_eq!(second, b"cd");
+    assert_eq!(second.as_ptr(), ptr);
+
+    // Too small to hold a full chunk, so it is dropped instead.
+    stream.recycle(Vec::with_capacity(1));
+    assert_eq!(block_on(stream.next()).unwrap().unwrap(), b"ef");
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be non-zero")]
+fn zero_chunk_size_panics() {
+    let _ = Cursor::new(&b""[..]).into_stream(0);
+}
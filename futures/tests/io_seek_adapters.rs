@@ -0,0 +1,145 @@
+use futures::executor::block_on;
+use futures::future::{Future, FutureExt};
+use futures::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFrom};
+use futures::task::{Context, Poll};
+use futures_test::task::noop_context;
+use std::io::{self, Cursor};
+use std::pin::Pin;
+
+fn run<F: Future + Unpin>(mut f: F) -> F::Output {
+    let mut cx = noop_context();
+    loop {
+        if let Poll::Ready(x) = f.poll_unpin(&mut cx) {
+            return x;
+        }
+    }
+}
+
+/// Returns `Poll::Pending` before every seek, to check that seeks which are
+/// polled again resume correctly.
+struct PendingSeek<T> {
+    inner: T,
+    pending: bool,
+}
+
+impl<T> PendingSeek<T> {
+    fn new(inner: T) -> Self {
+        Self { inner, pending: false }
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for PendingSeek<T> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<T: AsyncSeek + Unpin> AsyncSeek for PendingSeek<T> {
+    fn poll_seek(mut self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom)
+        -> Poll<io::Result<u64>>
+    {
+        self.pending = !self.pending;
+        if self.pending {
+            return Poll::Pending;
+        }
+        Pin::new(&mut self.inner).poll_seek(cx, pos)
+    }
+}
+
+async fn read_all<R: AsyncRead + Unpin>(reader: &mut R) -> Vec<u8> {
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await.unwrap();
+    buf
+}
+
+#[test]
+fn chain_seek_across_boundary() {
+    let mut chain = Cursor::new(&b"abc"[..]).chain(Cursor::new(&b"defg"[..]));
+    block_on(async {
+        assert_eq!(chain.seek(SeekFrom::Start(2)).await.unwrap(), 2);
+        assert_eq!(read_all(&mut chain).await, b"cdefg");
+
+        assert_eq!(chain.seek(SeekFrom::End(-3)).await.unwrap(), 4);
+        assert_eq!(read_all(&mut chain).await, b"efg");
+
+        assert_eq!(chain.seek(SeekFrom::Start(1)).await.unwrap(), 1);
+        assert_eq!(chain.seek(SeekFrom::Current(3)).await.unwrap(), 4);
+        assert_eq!(chain.seek(SeekFrom::Current(-2)).await.unwrap(), 2);
+        assert_eq!(read_all(&mut chain).await, b"cdefg");
+        assert_eq!(chain.stream_position().await.unwrap(), 7);
+
+        assert!(chain.seek(SeekFrom::Current(-8)).await.is_err());
+        assert_eq!(chain.stream_position().await.unwrap(), 7);
+    });
+}
+
+#[test]
+fn chain_seek_current_after_partial_read() {
+    let mut chain = Cursor::new(&b"abc"[..]).chain(Cursor::new(&b"def"[..]));
+    block_on(async {
+        let mut buf = [0; 2];
+        chain.read_exact(&mut buf).await.unwrap();
+        assert_eq!(chain.stream_position().await.unwrap(), 2);
+        assert_eq!(read_all(&mut chain).await, b"cdef");
+    });
+}
+
+#[test]
+fn chain_seek_resumes_after_pending() {
+    let mut chain = PendingSeek::new(Cursor::new(&b"abc"[..]))
+        .chain(PendingSeek::new(Cursor::new(&b"def"[..])));
+    run(Box::pin(async {
+        let mut buf = [0; 1];
+        chain.read_exact(&mut buf).await.unwrap();
+        assert_eq!(chain.seek(SeekFrom::Current(3)).await.unwrap(), 4);
+        assert_eq!(read_all(&mut chain).await, b"ef");
+        assert_eq!(chain.seek(SeekFrom::Current(-5)).await.unwrap(), 1);
+        assert_eq!(read_all(&mut chain).await, b"bcdef");
+    }));
+}
+
+#[test]
+fn take_seek_within_window() {
+    let mut reader = Cursor::new(&b"0123456789"[..]);
+    block_on(async {
+        reader.seek(SeekFrom::Start(2)).await.unwrap();
+        let mut take = (&mut reader).take(4);
+
+        assert_eq!(take.seek(SeekFrom::Start(1)).await.unwrap(), 1);
+        assert_eq!(take.limit(), 3);
+        assert_eq!(read_all(&mut take).await, b"345");
+
+        assert_eq!(take.seek(SeekFrom::End(-2)).await.unwrap(), 2);
+        assert_eq!(read_all(&mut take).await, b"45");
+
+        assert_eq!(take.seek(SeekFrom::Current(-4)).await.unwrap(), 0);
+        assert_eq!(read_all(&mut take).await, b"2345");
+
+        assert_eq!(take.seek(SeekFrom::End(2)).await.unwrap(), 6);
+        assert_eq!(take.limit(), 0);
+        assert!(read_all(&mut take).await.is_empty());
+
+        assert!(take.seek(SeekFrom::Current(-7)).await.is_err());
+    });
+    assert_eq!(reader.position(), 8);
+}
+
+#[test]
+fn take_seek_past_end_and_back() {
+    let mut reader = Cursor::new(&b"0123456789"[..]);
+    block_on(async {
+        reader.seek(SeekFrom::Start(2)).await.unwrap();
+        let mut take = (&mut reader).take(4);
+
+        assert_eq!(take.seek(SeekFrom::Start(6)).await.unwrap(), 6);
+        assert_eq!(take.seek(SeekFrom::Current(0)).await.unwrap(), 6);
+        assert!(read_all(&mut take).await.is_empty());
+
+        assert_eq!(take.seek(SeekFrom::Start(0)).await.unwrap(), 0);
+        assert_eq!(take.limit(), 4);
+        assert_eq!(read_all(&mut take).await, b"2345");
+    });
+    assert_eq!(reader.position(), 6);
+}
@@ -0,0 +1,80 @@
+use futures::executor::block_on;
+use futures::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, IoSlice, SegmentedBuf};
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+#[test]
+fn vec_deque_is_a_pipe() {
+    let mut pipe = VecDeque::with_capacity(4);
+    block_on(async {
+        pipe.write_all(b"abc").await.unwrap();
+        let mut buf = [0; 2];
+        pipe.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"ab");
+
+        // Wraps around the ring buffer.
+        pipe.write_all(b"def").await.unwrap();
+        let mut rest = Vec::new();
+        pipe.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(rest, b"cdef");
+        assert!(pipe.is_empty());
+    });
+}
+
+#[test]
+fn vec_deque_buf_read() {
+    let mut pipe: VecDeque<u8> = b"one\ntwo\n".iter().copied().collect();
+    let mut line = String::new();
+    block_on(pipe.read_line(&mut line)).unwrap();
+    assert_eq!(line, "one\n");
+    assert_eq!(pipe.len(), 4);
+}
+
+#[test]
+fn segmented_buf_reads_across_segments() {
+    let mut buf = SegmentedBuf::new();
+    buf.push_back(&b"ab"[..]);
+    buf.push_back(&b""[..]);
+    buf.push_back(&b"cde"[..]);
+    assert_eq!(buf.len(), 5);
+    assert_eq!(buf.chunks().collect::<Vec<_>>(), [&b"ab"[..], b"cde"]);
+
+    let mut out = [0; 3];
+    block_on(buf.read_exact(&mut out)).unwrap();
+    assert_eq!(&out, b"abc");
+    assert_eq!(buf.len(), 2);
+    assert_eq!(buf.chunks().collect::<Vec<_>>(), [&b"de"[..]]);
+
+    let (segments, pos) = buf.into_inner();
+    assert_eq!(segments.len(), 1);
+    assert_eq!(pos, 1);
+}
+
+#[test]
+fn segmented_buf_does_not_copy_pushed_segments() {
+    let shared: Rc<[u8]> = Rc::from(&b"shared"[..]);
+    let mut buf = SegmentedBuf::new();
+    buf.push_back(shared.clone());
+    assert_eq!(Rc::strong_count(&shared), 2);
+
+    let mut line = Vec::new();
+    block_on(buf.read_until(b'r', &mut line)).unwrap();
+    assert_eq!(line, b"shar");
+    assert_eq!(buf.chunks().next().unwrap().as_ptr(), shared[4..].as_ptr());
+}
+
+#[test]
+fn segmented_buf_writes_segments() {
+    let mut buf: SegmentedBuf = SegmentedBuf::new();
+    block_on(async {
+        buf.write_all(b"hello").await.unwrap();
+        let bufs = [IoSlice::new(b" "), IoSlice::new(b""), IoSlice::new(b"world")];
+        assert_eq!(buf.write_vectored(&bufs).await.unwrap(), 6);
+    });
+    assert_eq!(buf.chunks().count(), 3);
+
+    let mut out = String::new();
+    block_on(buf.read_to_string(&mut out)).unwrap();
+    assert_eq!(out, "hello world");
+    assert!(buf.is_empty());
+}
@@ -0,0 +1,35 @@
+use futures::executor::block_on;
+use futures::io::{AsyncReadExt, AsyncWriteExt};
+use std::io::Cursor;
+
+#[test]
+fn reunite_matching_halves() {
+    let (mut read, mut write) = Cursor::new(vec![0u8; 4]).split();
+    assert!(read.is_pair_of(&write));
+    assert!(write.is_pair_of(&read));
+
+    block_on(write.write_all(b"ab")).unwrap();
+    let mut buf = [0; 2];
+    block_on(read.read_exact(&mut buf)).unwrap();
+    assert_eq!(buf, [0, 0]);
+
+    let cursor = read.reunite(write).unwrap();
+    assert_eq!(cursor.position(), 4);
+    assert_eq!(cursor.into_inner(), b"ab\0\0");
+}
+
+#[test]
+fn reunite_mismatched_halves_returns_both() {
+    let (read1, write1) = Cursor::new(vec![1u8]).split();
+    let (read2, write2) = Cursor::new(vec![2u8]).split();
+    assert!(!read1.is_pair_of(&write2));
+    assert!(!write1.is_pair_of(&read2));
+
+    let err = write2.reunite(read1).unwrap_err();
+    let (read1, write2) = (err.0, err.1);
+
+    let first = read1.reunite(write1).unwrap();
+    assert_eq!(first.into_inner(), [1]);
+    let second = write2.reunite(read2).unwrap();
+    assert_eq!(second.into_inner(), [2]);
+}
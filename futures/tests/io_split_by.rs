@@ -0,0 +1,93 @@
+use futures::executor::block_on;
+use futures::future::{Future, FutureExt};
+use futures::io::AsyncBufReadExt;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use futures::task::Poll;
+use futures_test::io::AsyncReadTestExt;
+use futures_test::task::noop_context;
+use std::io::Cursor;
+
+fn run<F: Future + Unpin>(mut f: F) -> F::Output {
+    let mut cx = noop_context();
+    loop {
+        if let Poll::Ready(x) = f.poll_unpin(&mut cx) {
+            return x;
+        }
+    }
+}
+
+#[test]
+fn split_by_crlf() {
+    let parts = block_on(
+        Cursor::new(b"a\r\nb\nc\r\r\n\r\nd").split_by(b"\r\n").try_collect::<Vec<_>>()
+    ).unwrap();
+    assert_eq!(parts, vec![&b"a"[..], b"b\nc\r", b"", b"d"]);
+}
+
+#[test]
+fn split_by_single_byte() {
+    let parts = block_on(
+        Cursor::new(b"1,2,,3,").split_by([b',']).try_collect::<Vec<_>>()
+    ).unwrap();
+    assert_eq!(parts, vec![&b"1"[..], b"2", b"", b"3"]);
+}
+
+#[test]
+fn split_by_straddling_chunks() {
+    let reader = stream::iter(vec![&b"ab\r"[..], &b"\ncd-"[..], &b"-"[..], &b"-ef"[..]])
+        .map(Ok)
+        .into_async_read()
+        .interleave_pending();
+    let mut parts = reader.split_by(b"\r\n");
+    assert_eq!(run(parts.next()).unwrap().unwrap(), b"ab");
+    assert_eq!(run(parts.next()).unwrap().unwrap(), b"cd---ef");
+    assert!(run(parts.next()).is_none());
+
+    let reader = stream::iter(vec![&b"ab-"[..], &b"-"[..], &b"-cd"[..]])
+        .map(Ok)
+        .into_async_read();
+    let mut parts = reader.split_by(b"---");
+    assert_eq!(run(parts.next()).unwrap().unwrap(), b"ab");
+    assert_eq!(run(parts.next()).unwrap().unwrap(), b"cd");
+    assert!(run(parts.next()).is_none());
+}
+
+#[test]
+#[should_panic(expected = "split delimiter must not be empty")]
+fn split_by_empty_delimiter() {
+    let _ = Cursor::new(b"").split_by(b"");
+}
+
+#[test]
+fn read_until_any() {
+    let mut buf = Cursor::new(b"a;b,c\nd");
+    let mut v = Vec::new();
+    assert_eq!(block_on(buf.read_until_any(b";,\n", &mut v)).unwrap(), 2);
+    assert_eq!(v, b"a;");
+    v.clear();
+    assert_eq!(block_on(buf.read_until_any(b";,\n", &mut v)).unwrap(), 2);
+    assert_eq!(v, b"b,");
+    v.clear();
+    assert_eq!(block_on(buf.read_until_any(b"\n\t ;,", &mut v)).unwrap(), 2);
+    assert_eq!(v, b"c\n");
+    v.clear();
+    assert_eq!(block_on(buf.read_until_any(b";", &mut v)).unwrap(), 1);
+    assert_eq!(v, b"d");
+    v.clear();
+    assert_eq!(block_on(buf.read_until_any(b";", &mut v)).unwrap(), 0);
+    assert_eq!(v, []);
+}
+
+#[test]
+fn read_until_any_maybe_pending() {
+    let mut buf = stream::iter(vec![&b"12"[..], &b"34"[..], &b"5"[..]])
+        .map(Ok)
+        .into_async_read()
+        .interleave_pending();
+    let mut v = Vec::new();
+    assert_eq!(run(buf.read_until_any(b"46", &mut v)).unwrap(), 4);
+    assert_eq!(v, b"1234");
+    v.clear();
+    assert_eq!(run(buf.read_until_any(b"", &mut v)).unwrap(), 1);
+    assert_eq!(v, b"5");
+}
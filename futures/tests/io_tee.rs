@@ -0,0 +1,67 @@
+use futures::executor::block_on;
+use futures::future::{Future, FutureExt};
+use futures::io::{self, AsyncReadExt, AsyncWrite};
+use futures::task::{Context, Poll};
+use futures_test::io::{AsyncReadTestExt, AsyncWriteTestExt};
+use futures_test::task::noop_context;
+use std::pin::Pin;
+
+fn run<F: Future + Unpin>(mut f: F) -> F::Output {
+    let mut cx = noop_context();
+    loop {
+        if let Poll::Ready(x) = f.poll_unpin(&mut cx) {
+            return x;
+        }
+    }
+}
+
+#[test]
+fn copies_everything_read() {
+    let mut tee = (&b"some data"[..]).tee(Vec::new());
+    let mut buf = [0; 4];
+    assert_eq!(block_on(tee.read(&mut buf)).unwrap(), 4);
+    assert_eq!(tee.get_ref().1, b"some");
+
+    let mut rest = Vec::new();
+    block_on(tee.read_to_end(&mut rest)).unwrap();
+    assert_eq!(rest, b" data");
+    assert_eq!(tee.into_inner().1, b"some data");
+}
+
+#[test]
+fn maybe_pending() {
+    let reader = (&b"0123456789"[..]).interleave_pending();
+    let writer = Vec::new().limited_write(3);
+    let mut tee = reader.tee(writer);
+    let mut buf = Vec::new();
+    run(tee.read_to_end(&mut buf)).unwrap();
+    assert_eq!(buf, b"0123456789");
+    assert_eq!(tee.into_inner().1.into_inner(), b"0123456789");
+}
+
+struct Broken;
+
+impl AsyncWrite for Broken {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, _buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[test]
+fn writer_error_is_reported_by_next_read() {
+    let mut tee = (&b"abcdef"[..]).tee(Broken);
+    let mut buf = [0; 3];
+    assert_eq!(block_on(tee.read(&mut buf)).unwrap(), 3);
+    let err = block_on(tee.read(&mut buf)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+}
@@ -0,0 +1,73 @@
+use futures::executor::block_on;
+use futures::io::{
+    self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, TimeoutReader, TimeoutWriter,
+};
+use futures::task::{Context, Poll};
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+/// An I/O object which never makes progress.
+struct Stalled;
+
+impl AsyncRead for Stalled {
+    fn poll_read(self: Pin<&mut Self>, _cx: &mut Context<'_>, _buf: &mut [u8])
+        -> Poll<io::Result<usize>>
+    {
+        Poll::Pending
+    }
+}
+
+impl AsyncWrite for Stalled {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, _buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        Poll::Pending
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Pending
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Pending
+    }
+}
+
+#[test]
+fn read_times_out() {
+    let mut reader = TimeoutReader::new(Stalled, Duration::from_millis(20));
+    let start = Instant::now();
+    let mut buf = [0; 4];
+    let err = block_on(reader.read(&mut buf)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    assert!(start.elapsed() >= Duration::from_millis(20));
+
+    // The reader can be retried after a timeout.
+    reader.set_timeout(Duration::from_millis(5));
+    let err = block_on(reader.read(&mut buf)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+}
+
+#[test]
+fn ready_reads_pass_through() {
+    let mut reader = TimeoutReader::new(&b"data"[..], Duration::from_millis(1));
+    let mut buf = Vec::new();
+    block_on(reader.read_to_end(&mut buf)).unwrap();
+    assert_eq!(buf, b"data");
+}
+
+#[test]
+fn write_times_out() {
+    let mut writer = TimeoutWriter::new(Stalled, Duration::from_millis(20));
+    let err = block_on(writer.write_all(b"data")).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    let err = block_on(writer.flush()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+}
+
+#[test]
+fn ready_writes_pass_through() {
+    let mut writer = TimeoutWriter::new(Vec::new(), Duration::from_millis(1));
+    block_on(writer.write_all(b"data")).unwrap();
+    assert_eq!(writer.into_inner(), b"data");
+}
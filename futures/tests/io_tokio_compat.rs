@@ -0,0 +1,77 @@
+#![cfg(feature = "tokio-io-compat")]
+
+use futures::executor::block_on;
+use futures::io::{
+    self, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, IoSlice,
+    TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt,
+};
+use futures::task::{Context, Poll};
+use std::pin::Pin;
+
+#[test]
+fn read_from_tokio() {
+    block_on(async {
+        let mut reader = (&b"hello world"[..]).compat_futures();
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf, b"hello world");
+    });
+}
+
+#[test]
+fn read_round_trip() {
+    block_on(async {
+        let mut reader = (&b"hello\nworld"[..]).compat_tokio().compat_futures();
+        let mut line = String::new();
+        assert_eq!(reader.read_line(&mut line).await.unwrap(), 6);
+        assert_eq!(line, "hello\n");
+
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).await.unwrap();
+        assert_eq!(rest, "world");
+    });
+}
+
+#[test]
+fn write_round_trip() {
+    block_on(async {
+        let mut writer = Vec::new().compat_tokio_write().compat_futures_write();
+        writer.write_all(b"hello").await.unwrap();
+
+        // Vectored writes are passed through in both directions.
+        let bufs = [IoSlice::new(b", "), IoSlice::new(b"world")];
+        assert_eq!(writer.write_vectored(&bufs).await.unwrap(), 7);
+
+        writer.close().await.unwrap();
+        assert_eq!(writer.into_inner().into_inner(), b"hello, world");
+    });
+}
+
+/// A writer which does not write vectors efficiently.
+struct Unvectored(Vec<u8>);
+
+impl AsyncWrite for Unvectored {
+    fn poll_write(mut self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.0.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[test]
+fn is_write_vectored_round_trip() {
+    let vectored = Vec::new().compat_tokio_write().compat_futures_write();
+    assert!(vectored.is_write_vectored());
+
+    let unvectored = Unvectored(Vec::new()).compat_tokio_write().compat_futures_write();
+    assert!(!unvectored.is_write_vectored());
+}
@@ -0,0 +1,49 @@
+use futures::executor::block_on;
+use futures::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, SeekFrom, Unblock};
+use std::io::{self, Cursor, Read};
+
+#[test]
+fn write_seek_read() {
+    block_on(async {
+        let mut io = Unblock::with_capacity(4, Cursor::new(Vec::new()));
+        io.write_all(b"hello world").await.unwrap();
+        io.flush().await.unwrap();
+
+        assert_eq!(io.seek(SeekFrom::Start(6)).await.unwrap(), 6);
+        let mut buf = String::new();
+        io.read_to_string(&mut buf).await.unwrap();
+        assert_eq!(buf, "world");
+
+        let cursor = io.into_inner().await;
+        assert_eq!(cursor.into_inner(), b"hello world");
+    });
+}
+
+#[test]
+fn relative_seek() {
+    block_on(async {
+        let mut io = Unblock::new(Cursor::new(b"0123456789".to_vec()));
+        let mut buf = [0; 3];
+        io.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"012");
+        assert_eq!(io.seek(SeekFrom::Current(2)).await.unwrap(), 5);
+        io.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"567");
+    });
+}
+
+struct Failing;
+
+impl Read for Failing {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::InvalidData, "boom"))
+    }
+}
+
+#[test]
+fn errors_are_propagated() {
+    let mut io = Unblock::new(Failing);
+    let mut buf = [0; 4];
+    let err = block_on(io.read(&mut buf)).unwrap_err();
+    assert_eq!(err.to_string(), "boom");
+}
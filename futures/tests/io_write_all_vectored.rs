@@ -0,0 +1,75 @@
+use futures::executor::block_on;
+use futures::io::{self, AsyncWrite, AsyncWriteExt, IoSlice};
+use futures::task::{Context, Poll};
+use std::pin::Pin;
+
+/// A writer which accepts at most `max` bytes per call, to exercise
+/// partial vectored writes.
+struct Trickle {
+    max: usize,
+    data: Vec<u8>,
+}
+
+impl AsyncWrite for Trickle {
+    fn poll_write(mut self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        let n = std::cmp::min(self.max, buf.len());
+        self.data.extend_from_slice(&buf[..n]);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_write_vectored(mut self: Pin<&mut Self>, _cx: &mut Context<'_>, bufs: &[IoSlice<'_>])
+        -> Poll<io::Result<usize>>
+    {
+        let mut written = 0;
+        for buf in bufs {
+            let n = std::cmp::min(self.max - written, buf.len());
+            self.data.extend_from_slice(&buf[..n]);
+            written += n;
+            if written == self.max {
+                break;
+            }
+        }
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[test]
+fn partial_writes_across_slices() {
+    for max in 1..8 {
+        let mut writer = Trickle { max, data: Vec::new() };
+        let bufs = &mut [
+            IoSlice::new(&[]),
+            IoSlice::new(&[1, 2, 3]),
+            IoSlice::new(&[]),
+            IoSlice::new(&[4]),
+            IoSlice::new(&[5, 6, 7, 8, 9]),
+        ];
+        block_on(writer.write_all_vectored(bufs)).unwrap();
+        assert_eq!(writer.data, [1, 2, 3, 4, 5, 6, 7, 8, 9], "max = {}", max);
+    }
+}
+
+#[test]
+fn write_zero_is_an_error() {
+    let mut writer = Trickle { max: 0, data: Vec::new() };
+    let bufs = &mut [IoSlice::new(&[1])];
+    let err = block_on(writer.write_all_vectored(bufs)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn only_empty_slices() {
+    let mut writer = Trickle { max: 0, data: Vec::new() };
+    let bufs = &mut [IoSlice::new(&[]), IoSlice::new(&[])];
+    block_on(writer.write_all_vectored(bufs)).unwrap();
+}
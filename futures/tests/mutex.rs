@@ -1,11 +1,13 @@
 use futures::channel::mpsc;
+use futures::executor::block_on;
 use futures::future::{ready, FutureExt};
-use futures::lock::Mutex;
+use futures::lock::{MappedMutexGuard, Mutex, MutexGuard};
 use futures::stream::StreamExt;
-use futures::task::{Context, SpawnExt};
+use futures::task::{noop_context, Context, SpawnExt};
 use futures_test::future::FutureTestExt;
 use futures_test::task::{panic_context, new_count_waker};
 use std::sync::Arc;
+use std::time::Duration;
 
 #[test]
 fn mutex_acquire_uncontested() {
@@ -33,6 +35,161 @@ fn mutex_wakes_waiters() {
     assert!(waiter.poll_unpin(&mut panic_context()).is_ready());
 }
 
+#[test]
+fn mutex_owned_guard() {
+    let mutex = Arc::new(Mutex::new(0));
+    let (waker, counter) = new_count_waker();
+    let mut guard = mutex.clone().try_lock_owned().unwrap();
+    *guard += 1;
+    let mutex = mutex.try_lock_owned().unwrap_err();
+
+    let mut cx = Context::from_waker(&waker);
+    let mut waiter = mutex.clone().lock_owned();
+    assert!(waiter.poll_unpin(&mut cx).is_pending());
+
+    drop(guard);
+
+    assert_eq!(counter, 1);
+    match waiter.poll_unpin(&mut panic_context()) {
+        std::task::Poll::Ready(guard) => assert_eq!(*guard, 1),
+        std::task::Poll::Pending => panic!("lock not acquired"),
+    }
+}
+
+#[test]
+fn mutex_lock_timeout() {
+    let mutex = Mutex::new(());
+    let guard = mutex.try_lock().unwrap();
+
+    let mut waiter = mutex.lock_timeout(Duration::from_millis(10));
+    assert!(waiter.poll_unpin(&mut noop_context()).is_pending());
+    assert_eq!(mutex.waiters(), 1);
+    assert!(block_on(&mut waiter).is_err());
+    assert_eq!(mutex.waiters(), 0);
+
+    drop(guard);
+    assert!(block_on(mutex.lock_timeout(Duration::from_millis(10))).is_ok());
+}
+
+#[test]
+fn mutex_dropped_waiter_passes_wakeup_on() {
+    let mutex = Mutex::new(());
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let guard = mutex.try_lock().unwrap();
+
+    let mut first = mutex.lock();
+    let mut second = mutex.lock();
+    assert!(first.poll_unpin(&mut cx).is_pending());
+    assert!(second.poll_unpin(&mut cx).is_pending());
+    assert_eq!(mutex.waiters(), 2);
+
+    drop(guard);
+    assert_eq!(counter, 1);
+    drop(first);
+    assert_eq!(counter, 2);
+    assert_eq!(mutex.waiters(), 0);
+    assert!(second.poll_unpin(&mut panic_context()).is_ready());
+}
+
+#[test]
+fn mutex_hands_lock_to_waiter() {
+    let mutex = Mutex::new(());
+    let guard = mutex.try_lock().unwrap();
+    let mut waiter = mutex.lock();
+    assert!(waiter.poll_unpin(&mut noop_context()).is_pending());
+
+    drop(guard);
+
+    // The lock is handed to the waiter, so later callers cannot barge in.
+    assert!(mutex.try_lock().is_none());
+    let mut late = mutex.lock();
+    assert!(late.poll_unpin(&mut noop_context()).is_pending());
+    let guard = match waiter.poll_unpin(&mut panic_context()) {
+        std::task::Poll::Ready(guard) => guard,
+        std::task::Poll::Pending => panic!("lock not handed over"),
+    };
+    drop(guard);
+    assert!(late.poll_unpin(&mut panic_context()).is_ready());
+}
+
+#[test]
+fn mutex_waiters_excludes_handed_over_lock() {
+    let mutex = Mutex::new(());
+    let guard = mutex.try_lock().unwrap();
+    let mut first = mutex.lock();
+    let mut second = mutex.lock();
+    assert!(first.poll_unpin(&mut noop_context()).is_pending());
+    assert!(second.poll_unpin(&mut noop_context()).is_pending());
+    assert_eq!(mutex.waiters(), 2);
+
+    // The first waiter holds the lock now, even though it has not been
+    // polled yet.
+    drop(guard);
+    assert_eq!(mutex.waiters(), 1);
+    let guard = match first.poll_unpin(&mut panic_context()) {
+        std::task::Poll::Ready(guard) => guard,
+        std::task::Poll::Pending => panic!("lock not handed over"),
+    };
+    assert_eq!(mutex.waiters(), 1);
+
+    drop(guard);
+    assert_eq!(mutex.waiters(), 0);
+    assert!(second.poll_unpin(&mut panic_context()).is_ready());
+}
+
+#[test]
+fn mutex_no_starvation() {
+    let (tx, mut rx) = mpsc::unbounded();
+    let mut pool = futures::executor::ThreadPool::builder()
+        .pool_size(8)
+        .create()
+        .unwrap();
+
+    let tx = Arc::new(tx);
+    let mutex = Arc::new(Mutex::new(0usize));
+
+    let num_tasks = 16;
+    let iterations = 100;
+    for _ in 0..num_tasks {
+        let tx = tx.clone();
+        let mutex = mutex.clone();
+        pool.spawn(async move {
+            let mut max_overtaken = 0;
+            for _ in 0..iterations {
+                let before = *mutex.lock().await;
+                let mut lock = mutex.lock().await;
+                // Each acquisition bumps the counter, so this is how many
+                // times the lock was taken while this task was queued.
+                max_overtaken = max_overtaken.max(*lock - before);
+                *lock += 1;
+                ready(()).pending_once().await;
+            }
+            tx.unbounded_send(max_overtaken).unwrap();
+        }).unwrap();
+    }
+
+    pool.run(async {
+        for _ in 0..num_tasks {
+            let max_overtaken = rx.next().await.unwrap();
+            // With FIFO handoff, every other task gets the lock at most
+            // about once before this one, however contended it is.
+            assert!(max_overtaken <= 2 * num_tasks, "starved for {} acquisitions", max_overtaken);
+        }
+    })
+}
+
+#[test]
+fn mutex_mapped_guard() {
+    let mutex = Mutex::new((0, vec![1]));
+    let guard = MutexGuard::map(mutex.try_lock().unwrap(), |v| &mut v.1);
+    let mut first = MappedMutexGuard::map(guard, |v| &mut v[0]);
+    *first += 1;
+    assert!(mutex.try_lock().is_none());
+    drop(first);
+    assert_eq!(mutex.try_lock().unwrap().1, [2]);
+}
+
 #[test]
 fn mutex_contested() {
     let (tx, mut rx) = mpsc::unbounded();
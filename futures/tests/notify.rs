@@ -0,0 +1,79 @@
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures::lock::Notify;
+use futures::task::Context;
+use futures_test::task::{panic_context, new_count_waker};
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+fn notify_one_stores_permit() {
+    let notify = Notify::new();
+    notify.notify_one();
+    notify.notify_one();
+    assert!(notify.notified().now_or_never().is_some());
+    // Only a single permit is stored.
+    assert!(notify.notified().now_or_never().is_none());
+}
+
+#[test]
+fn notify_one_wakes_longest_waiting() {
+    let notify = Notify::new();
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut first = notify.notified();
+    let mut second = notify.notified();
+    assert!(first.poll_unpin(&mut cx).is_pending());
+    assert!(second.poll_unpin(&mut cx).is_pending());
+
+    notify.notify_one();
+    assert_eq!(counter, 1);
+    assert!(first.poll_unpin(&mut panic_context()).is_ready());
+    assert!(second.poll_unpin(&mut cx).is_pending());
+}
+
+#[test]
+fn notify_one_passed_on_when_dropped() {
+    let notify = Notify::new();
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut first = notify.notified();
+    let mut second = notify.notified();
+    assert!(first.poll_unpin(&mut cx).is_pending());
+    assert!(second.poll_unpin(&mut cx).is_pending());
+
+    notify.notify_one();
+    drop(first);
+    assert_eq!(counter, 2);
+    assert!(second.poll_unpin(&mut panic_context()).is_ready());
+}
+
+#[test]
+fn notify_waiters_wakes_all() {
+    let notify = Notify::new();
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut polled = notify.notified();
+    assert!(polled.poll_unpin(&mut cx).is_pending());
+    let mut unpolled = notify.notified();
+
+    notify.notify_waiters();
+    assert_eq!(counter, 1);
+    assert!(polled.poll_unpin(&mut panic_context()).is_ready());
+    assert!(unpolled.poll_unpin(&mut panic_context()).is_ready());
+
+    // No permit is stored.
+    assert!(notify.notified().now_or_never().is_none());
+}
+
+#[test]
+fn notify_across_threads() {
+    let notify = Arc::new(Notify::new());
+    let notify2 = notify.clone();
+    let t = thread::spawn(move || notify2.notify_one());
+    block_on(notify.notified());
+    t.join().unwrap();
+}
@@ -0,0 +1,73 @@
+use futures::channel::oneshot;
+use futures::executor::block_on;
+use futures::future::{self, FutureExt};
+use futures::lock::{Lazy, OnceCell};
+use futures::task::Context;
+use futures_test::task::{panic_context, new_count_waker};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn once_cell_runs_one_initializer() {
+    let cell = OnceCell::new();
+    let runs = AtomicUsize::new(0);
+    let (tx, rx) = oneshot::channel::<()>();
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut first = cell.get_or_init(|| {
+        runs.fetch_add(1, Ordering::SeqCst);
+        rx.map(|_| 1)
+    });
+    let mut second = cell.get_or_init(|| {
+        runs.fetch_add(1, Ordering::SeqCst);
+        future::ready(2)
+    });
+    assert!(first.poll_unpin(&mut cx).is_pending());
+    assert!(second.poll_unpin(&mut cx).is_pending());
+
+    tx.send(()).unwrap();
+    assert_eq!(first.poll_unpin(&mut panic_context()), std::task::Poll::Ready(&1));
+    assert!(counter.get() >= 1);
+    assert_eq!(second.poll_unpin(&mut panic_context()), std::task::Poll::Ready(&1));
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn once_cell_dropped_initializer_hands_over() {
+    let cell = OnceCell::new();
+    let (_tx, rx) = oneshot::channel::<()>();
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut first = cell.get_or_init(|| rx.map(|_| 1));
+    let mut second = cell.get_or_init(|| future::ready(2));
+    assert!(first.poll_unpin(&mut cx).is_pending());
+    assert!(second.poll_unpin(&mut cx).is_pending());
+
+    drop(first);
+    assert_eq!(counter, 1);
+    assert_eq!(second.poll_unpin(&mut panic_context()), std::task::Poll::Ready(&2));
+}
+
+#[test]
+fn once_cell_set() {
+    let cell = OnceCell::new();
+    assert_eq!(cell.get(), None);
+    assert_eq!(cell.set(1), Ok(()));
+    assert_eq!(cell.set(2), Err(2));
+    assert_eq!(block_on(cell.get_or_init(|| future::ready(3))), &1);
+    assert_eq!(cell.into_inner(), Some(1));
+}
+
+#[test]
+fn lazy_forces_once() {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let value = Lazy::new(|| async {
+        RUNS.fetch_add(1, Ordering::SeqCst) + 10
+    });
+
+    assert_eq!(value.get(), None);
+    assert_eq!(*block_on(value.force()), 10);
+    assert_eq!(*block_on(value.force()), 10);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,144 @@
+use futures::channel::mpsc;
+use futures::future::{ready, FutureExt};
+use futures::lock::{MappedRwLockReadGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use futures::stream::StreamExt;
+use futures::task::{Context, SpawnExt};
+use futures_test::future::FutureTestExt;
+use futures_test::task::{panic_context, new_count_waker};
+use std::sync::Arc;
+
+#[test]
+fn rwlock_readers_share() {
+    let lock = RwLock::new(1);
+    let a = lock.read().now_or_never().unwrap();
+    let b = lock.read().now_or_never().unwrap();
+    assert_eq!(*a + *b, 2);
+    assert!(lock.try_write().is_none());
+    drop((a, b));
+    assert!(lock.try_write().is_some());
+}
+
+#[test]
+fn rwlock_writer_excludes() {
+    let lock = RwLock::new(0);
+    let mut w = lock.try_write().unwrap();
+    *w += 1;
+    assert!(lock.try_read().is_none());
+    assert!(lock.try_write().is_none());
+    drop(w);
+    assert_eq!(*lock.try_read().unwrap(), 1);
+}
+
+#[test]
+fn rwlock_waiting_writer_blocks_new_readers() {
+    let lock = RwLock::new(());
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let reader = lock.try_read().unwrap();
+    let mut writer = lock.write();
+    assert!(writer.poll_unpin(&mut cx).is_pending());
+
+    // Readers queue up behind the waiting writer.
+    assert!(lock.try_read().is_none());
+    let mut late_reader = lock.read();
+    assert!(late_reader.poll_unpin(&mut cx).is_pending());
+
+    drop(reader);
+    assert_eq!(counter, 1);
+    let w = match writer.poll_unpin(&mut panic_context()) {
+        std::task::Poll::Ready(w) => w,
+        std::task::Poll::Pending => panic!("writer not granted"),
+    };
+    assert!(late_reader.poll_unpin(&mut cx).is_pending());
+
+    drop(w);
+    assert_eq!(counter, 2);
+    assert!(late_reader.poll_unpin(&mut panic_context()).is_ready());
+}
+
+#[test]
+fn rwlock_dropped_waiter_passes_lock_on() {
+    let lock = RwLock::new(());
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let reader = lock.try_read().unwrap();
+    let mut writer = lock.write();
+    assert!(writer.poll_unpin(&mut cx).is_pending());
+    let mut late_reader = lock.read();
+    assert!(late_reader.poll_unpin(&mut cx).is_pending());
+
+    // The writer giving up lets the reader behind it share the lock.
+    drop(writer);
+    assert_eq!(counter, 1);
+    assert!(late_reader.poll_unpin(&mut panic_context()).is_ready());
+    drop(reader);
+}
+
+#[test]
+fn rwlock_owned_guards() {
+    let lock = Arc::new(RwLock::new(0));
+    let mut w = lock.clone().write_owned().now_or_never().unwrap();
+    *w = 5;
+    assert!(lock.clone().try_read_owned().is_err());
+    drop(w);
+    let r = lock.clone().read_owned().now_or_never().unwrap();
+    assert_eq!(*r, 5);
+    assert!(lock.clone().try_read_owned().is_ok());
+    assert!(lock.try_write_owned().is_err());
+}
+
+#[test]
+fn rwlock_mapped_guards() {
+    let lock = RwLock::new((1, String::from("a")));
+    let mut name = RwLockWriteGuard::map(lock.try_write().unwrap(), |v| &mut v.1);
+    name.push('b');
+    assert!(lock.try_read().is_none());
+    drop(name);
+
+    let name = RwLockReadGuard::map(lock.try_read().unwrap(), |v| &v.1);
+    let name = MappedRwLockReadGuard::map(name, String::as_str);
+    assert_eq!(&*name, "ab");
+    assert!(lock.try_read().is_some());
+    assert!(lock.try_write().is_none());
+    drop(name);
+    assert!(lock.try_write().is_some());
+}
+
+#[test]
+fn rwlock_contested() {
+    let (tx, mut rx) = mpsc::unbounded();
+    let mut pool = futures::executor::ThreadPool::builder()
+        .pool_size(16)
+        .create()
+        .unwrap();
+
+    let tx = Arc::new(tx);
+    let lock = Arc::new(RwLock::new(0));
+
+    let num_tasks = 1000;
+    for i in 0..num_tasks {
+        let tx = tx.clone();
+        let lock = lock.clone();
+        pool.spawn(async move {
+            if i % 2 == 0 {
+                let mut w = lock.write_owned().await;
+                ready(()).pending_once().await;
+                *w += 1;
+            } else {
+                let r = lock.read().await;
+                ready(()).pending_once().await;
+                assert!(*r <= num_tasks / 2);
+            }
+            tx.unbounded_send(()).unwrap();
+        }).unwrap();
+    }
+
+    pool.run(async {
+        for _ in 0..num_tasks {
+            let () = rx.next().await.unwrap();
+        }
+        assert_eq!(num_tasks / 2, *lock.read().await);
+    })
+}
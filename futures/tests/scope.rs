@@ -0,0 +1,68 @@
+use futures::channel::oneshot;
+use futures::executor::block_on;
+use futures::future::{self, FutureExt};
+use futures::task::{scope, yield_now};
+use std::sync::Mutex;
+
+#[test]
+fn tasks_borrow_and_complete_before_scope() {
+    let log = Mutex::new(Vec::new());
+    let out = block_on(scope(|s| {
+        let log = &log;
+        async move {
+            for i in 0..3 {
+                s.spawn(async move {
+                    yield_now().await;
+                    log.lock().unwrap().push(i);
+                });
+            }
+            "body"
+        }
+    }));
+    assert_eq!(out, "body");
+    let mut log = log.into_inner().unwrap();
+    log.sort();
+    assert_eq!(log, [0, 1, 2]);
+}
+
+#[test]
+fn tasks_spawn_tasks() {
+    let count = Mutex::new(0);
+    block_on(scope(|s| {
+        let count = &count;
+        let s2 = s.clone();
+        async move {
+            s.spawn(async move {
+                *count.lock().unwrap() += 1;
+                s2.spawn(async move { *count.lock().unwrap() += 1 });
+            });
+        }
+    }));
+    assert_eq!(count.into_inner().unwrap(), 2);
+}
+
+#[test]
+fn body_waits_on_task() {
+    let (tx, rx) = oneshot::channel();
+    let out = block_on(scope(|s| {
+        s.spawn(async move { tx.send(7).unwrap() });
+        async move { rx.await.unwrap() }
+    }));
+    assert_eq!(out, 7);
+}
+
+#[test]
+fn dropping_scope_drops_tasks() {
+    let (tx, rx) = oneshot::channel::<()>();
+    let fut = scope(|s| {
+        s.spawn(async move {
+            let _tx = tx;
+            future::pending::<()>().await;
+        });
+        async {}
+    });
+    let mut fut = Box::pin(fut);
+    assert!(fut.as_mut().now_or_never().is_none());
+    drop(fut);
+    assert!(block_on(rx).is_err());
+}
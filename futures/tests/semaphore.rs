@@ -0,0 +1,110 @@
+use futures::channel::mpsc;
+use futures::future::{ready, FutureExt};
+use futures::lock::Semaphore;
+use futures::stream::StreamExt;
+use futures::task::{Context, SpawnExt};
+use futures_test::future::FutureTestExt;
+use futures_test::task::{panic_context, new_count_waker};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn semaphore_acquire_uncontested() {
+    let semaphore = Semaphore::new(2);
+    let a = semaphore.acquire(1).now_or_never().unwrap();
+    let b = semaphore.try_acquire(1).unwrap();
+    assert_eq!(semaphore.available_permits(), 0);
+    assert!(semaphore.try_acquire(1).is_none());
+    drop((a, b));
+    assert_eq!(semaphore.available_permits(), 2);
+}
+
+#[test]
+fn semaphore_wakes_waiters_in_order() {
+    let semaphore = Semaphore::new(2);
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let permit = semaphore.try_acquire(2).unwrap();
+    let mut big = semaphore.acquire(2);
+    assert!(big.poll_unpin(&mut cx).is_pending());
+    let mut small = semaphore.acquire(1);
+    assert!(small.poll_unpin(&mut cx).is_pending());
+
+    // One permit is not enough for the waiter at the front, and the waiter
+    // behind it has to wait its turn.
+    semaphore.add_permits(1);
+    assert_eq!(counter, 0);
+    drop(permit);
+    assert_eq!(counter, 2);
+    let big = match big.poll_unpin(&mut panic_context()) {
+        std::task::Poll::Ready(permit) => permit,
+        std::task::Poll::Pending => panic!("permits not granted"),
+    };
+    assert_eq!(big.num_permits(), 2);
+
+    // The extra permit went to the next waiter.
+    assert!(small.poll_unpin(&mut panic_context()).is_ready());
+}
+
+#[test]
+fn semaphore_dropped_waiter_passes_permits_on() {
+    let semaphore = Semaphore::new(1);
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut big = semaphore.acquire(2);
+    assert!(big.poll_unpin(&mut cx).is_pending());
+    let mut small = semaphore.acquire(1);
+    assert!(small.poll_unpin(&mut cx).is_pending());
+
+    drop(big);
+    assert_eq!(counter, 1);
+    assert!(small.poll_unpin(&mut panic_context()).is_ready());
+}
+
+#[test]
+fn semaphore_owned_permits() {
+    let semaphore = Arc::new(Semaphore::new(1));
+    let permit = semaphore.clone().acquire_owned(1).now_or_never().unwrap();
+    assert!(semaphore.clone().try_acquire_owned(1).is_err());
+    permit.forget();
+    assert_eq!(semaphore.available_permits(), 0);
+    semaphore.add_permits(1);
+    assert!(semaphore.try_acquire_owned(1).is_ok());
+}
+
+#[test]
+fn semaphore_limits_concurrency() {
+    let (tx, mut rx) = mpsc::unbounded();
+    let mut pool = futures::executor::ThreadPool::builder()
+        .pool_size(16)
+        .create()
+        .unwrap();
+
+    let tx = Arc::new(tx);
+    let semaphore = Arc::new(Semaphore::new(3));
+    let active = Arc::new(AtomicUsize::new(0));
+
+    let num_tasks = 1000;
+    for _ in 0..num_tasks {
+        let tx = tx.clone();
+        let semaphore = semaphore.clone();
+        let active = active.clone();
+        pool.spawn(async move {
+            let permit = semaphore.acquire_owned(1).await;
+            assert!(active.fetch_add(1, Ordering::SeqCst) < 3);
+            ready(()).pending_once().await;
+            active.fetch_sub(1, Ordering::SeqCst);
+            drop(permit);
+            tx.unbounded_send(()).unwrap();
+        }).unwrap();
+    }
+
+    pool.run(async {
+        for _ in 0..num_tasks {
+            let () = rx.next().await.unwrap();
+        }
+        assert_eq!(semaphore.available_permits(), 3);
+    })
+}
@@ -6,7 +6,9 @@ use futures::ready;
 use futures::sink::{Sink, SinkErrInto, SinkExt};
 use futures::stream::{self, Stream, StreamExt};
 use futures::task::{self, ArcWake, Context, Poll, Waker};
+use futures_test::sink::SinkTestExt;
 use futures_test::task::panic_context;
+use futures_test::{assert_sink_closed, assert_sink_flushed, assert_sink_pending, assert_sink_ready};
 use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::fmt;
@@ -404,6 +406,31 @@ fn buffer() {
     })
 }
 
+#[test]
+fn buffer_holds_items_until_sink_is_ready() {
+    let (sink, allow) = manual_allow::<i32>();
+    let mut sink = sink.buffer(1);
+
+    assert_sink_ready!(sink);
+    Pin::new(&mut sink).start_send(0).unwrap();
+    assert_sink_pending!(sink);
+    assert!(sink.get_ref().data.is_empty());
+
+    allow.start();
+    assert_sink_ready!(sink);
+    assert_sink_flushed!(sink);
+    assert_eq!(sink.get_ref().data, vec![0]);
+    assert_sink_closed!(sink);
+}
+
+#[test]
+fn send_all_to_adversarial_sink() {
+    let mut sink = Box::pin(Vec::new().interleave_pending_sink().assert_unmoved_sink());
+    block_on(sink.send_all(&mut stream::iter(vec![1, 2, 3]))).unwrap();
+    block_on(sink.close()).unwrap();
+    assert_eq!(sink.get_ref().get_ref(), &[1, 2, 3]);
+}
+
 #[test]
 fn fanout_smoke() {
     let sink1 = Vec::new();
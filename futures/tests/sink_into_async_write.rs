@@ -0,0 +1,56 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::io::{AsyncWrite, AsyncWriteExt};
+use futures::sink::SinkExt;
+use futures::stream::StreamExt;
+use futures::task::Poll;
+use futures_test::task::noop_context;
+use std::io;
+use std::pin::Pin;
+
+fn broken_pipe(e: mpsc::SendError) -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, e)
+}
+
+#[test]
+fn writes_are_sent_as_chunks() {
+    let (tx, rx) = mpsc::unbounded::<Vec<u8>>();
+    let mut writer = tx.sink_map_err(broken_pipe).into_async_write();
+
+    block_on(writer.write_all(b"abc")).unwrap();
+    assert_eq!(block_on(writer.write(b"")).unwrap(), 0);
+    block_on(writer.write_all(b"de")).unwrap();
+    block_on(writer.close()).unwrap();
+
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), [&b"abc"[..], b"de"]);
+}
+
+#[test]
+fn write_waits_for_sink_capacity() {
+    let mut cx = noop_context();
+    let (tx, mut rx) = mpsc::channel::<Vec<u8>>(0);
+    let mut writer = tx.sink_map_err(broken_pipe).into_async_write();
+
+    assert_eq!(
+        Pin::new(&mut writer).poll_write(&mut cx, b"one").map(Result::unwrap),
+        Poll::Ready(3),
+    );
+    assert!(Pin::new(&mut writer).poll_write(&mut cx, b"two").is_pending());
+
+    assert_eq!(block_on(rx.next()), Some(b"one".to_vec()));
+    assert_eq!(
+        Pin::new(&mut writer).poll_write(&mut cx, b"two").map(Result::unwrap),
+        Poll::Ready(3),
+    );
+    assert_eq!(block_on(rx.next()), Some(b"two".to_vec()));
+}
+
+#[test]
+fn sink_errors_are_mapped() {
+    let (tx, rx) = mpsc::unbounded::<Vec<u8>>();
+    drop(rx);
+    let mut writer = tx.sink_map_err(broken_pipe).into_async_write();
+
+    let err = block_on(writer.write_all(b"lost")).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+}
@@ -0,0 +1,24 @@
+use futures::task::SpawnError;
+use std::error::Error;
+use std::io;
+
+#[test]
+fn kinds() {
+    let err = SpawnError::shutdown();
+    assert!(err.is_shutdown() && !err.is_retryable());
+    let err = SpawnError::queue_full();
+    assert!(err.is_queue_full() && err.is_retryable());
+    let err = SpawnError::resource_exhausted();
+    assert!(err.is_resource_exhausted() && err.is_retryable());
+    assert!(err.source().is_none());
+}
+
+#[test]
+fn custom_source() {
+    let err = SpawnError::custom(io::Error::other("no threads"));
+    assert!(err.is_custom() && !err.is_retryable() && !err.is_shutdown());
+    assert_eq!(err.to_string(), "no threads");
+    let source = err.source().unwrap().downcast_ref::<io::Error>().unwrap();
+    assert_eq!(source.kind(), io::ErrorKind::Other);
+    assert!(err.get_ref().unwrap().is::<io::Error>());
+}
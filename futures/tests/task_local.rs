@@ -0,0 +1,48 @@
+use futures::channel::oneshot;
+use futures::executor::block_on;
+use futures::future::{self, FutureExt};
+use futures::task::TaskLocalExt;
+use std::panic::{self, AssertUnwindSafe};
+
+futures::task_local! {
+    static NAME: &'static str;
+    static DEPTH: u32;
+}
+
+#[test]
+fn unset_outside_scope() {
+    assert!(NAME.try_with(|_| ()).is_err());
+    let fut = NAME.scope("a", async { NAME.get() });
+    assert!(NAME.try_with(|_| ()).is_err());
+    assert_eq!(block_on(fut), "a");
+    assert!(NAME.try_with(|_| ()).is_err());
+}
+
+#[test]
+fn set_across_pending_polls() {
+    let (tx, rx) = oneshot::channel::<()>();
+    let fut = NAME.scope("a", async move {
+        let before = NAME.get();
+        rx.await.unwrap();
+        (before, NAME.get())
+    });
+    let fut = future::join(fut, async move { tx.send(()).unwrap() });
+    assert_eq!(block_on(fut).0, ("a", "a"));
+}
+
+#[test]
+fn nested_scopes() {
+    let fut = DEPTH.scope(1, async {
+        let inner = DEPTH.scope(2, async { DEPTH.get() }).await;
+        (inner, DEPTH.get())
+    });
+    assert_eq!(block_on(fut), (2, 1));
+}
+
+#[test]
+fn reset_after_panic() {
+    let fut = DEPTH.scope(1, future::lazy(|_| panic!("boom")));
+    let res = panic::catch_unwind(AssertUnwindSafe(|| block_on(fut.map(|()| ()))));
+    assert!(res.is_err());
+    assert!(DEPTH.try_with(|_| ()).is_err());
+}
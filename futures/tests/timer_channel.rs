@@ -0,0 +1,68 @@
+use futures::channel::{mpsc, oneshot};
+use futures::executor::block_on;
+use futures::timer::{RecvTimeoutExt, SendTimeoutExt};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn send_timeout_returns_message() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+    block_on(tx.send_timeout(1, Duration::from_millis(10))).unwrap();
+
+    let start = Instant::now();
+    let err = block_on(tx.send_timeout(2, Duration::from_millis(20))).unwrap_err();
+    assert!(start.elapsed() >= Duration::from_millis(20));
+    assert!(err.is_timeout());
+    assert_eq!(err.into_inner(), 2);
+
+    // Sending succeeds once there is capacity again.
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    block_on(tx.send_timeout(3, Duration::from_millis(10))).unwrap();
+    assert_eq!(rx.try_next().unwrap(), Some(3));
+}
+
+#[test]
+fn send_timeout_waits_for_capacity() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+    tx.try_send(1).unwrap();
+
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(rx.try_next().unwrap(), Some(1));
+        rx
+    });
+    block_on(tx.send_timeout(2, Duration::from_secs(10))).unwrap();
+    let mut rx = t.join().unwrap();
+    assert_eq!(rx.try_next().unwrap(), Some(2));
+}
+
+#[test]
+fn send_timeout_disconnected() {
+    let (mut tx, rx) = mpsc::channel::<i32>(0);
+    drop(rx);
+    let err = block_on(tx.send_timeout(1, Duration::from_secs(10))).unwrap_err();
+    assert!(err.is_disconnected());
+    assert_eq!(err.into_inner(), 1);
+}
+
+#[test]
+fn recv_timeout_mpsc() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(1);
+    assert!(block_on(rx.recv_timeout(Duration::from_millis(10))).is_err());
+
+    tx.try_send(1).unwrap();
+    assert_eq!(block_on(rx.recv_timeout(Duration::from_millis(10))), Ok(Some(1)));
+    drop(tx);
+    assert_eq!(block_on(rx.recv_timeout(Duration::from_millis(10))), Ok(None));
+}
+
+#[test]
+fn recv_timeout_oneshot() {
+    let (tx, mut rx) = oneshot::channel::<i32>();
+    let start = Instant::now();
+    assert!(block_on(rx.recv_timeout(Duration::from_millis(20))).is_err());
+    assert!(start.elapsed() >= Duration::from_millis(20));
+
+    drop(tx);
+    assert_eq!(block_on(rx.recv_timeout(Duration::from_millis(10))), Ok(Err(oneshot::Canceled)));
+}
@@ -0,0 +1,70 @@
+use futures::executor::block_on;
+use futures::future::{self, FutureExt};
+use futures::task::Poll;
+use futures::timer::{Delay, VirtualClock};
+use futures_test::task::noop_context;
+use std::time::{Duration, Instant};
+
+#[test]
+fn completes_after_duration() {
+    let start = Instant::now();
+    block_on(Delay::new(Duration::from_millis(20)));
+    assert!(start.elapsed() >= Duration::from_millis(20));
+}
+
+#[test]
+fn past_deadline_is_ready() {
+    let mut delay = Delay::new_at(Instant::now() - Duration::from_secs(1));
+    assert_eq!(delay.poll_unpin(&mut noop_context()), Poll::Ready(()));
+}
+
+#[test]
+fn reset() {
+    let mut delay = Delay::new(Duration::from_secs(3600));
+    assert_eq!(delay.poll_unpin(&mut noop_context()), Poll::Pending);
+
+    let start = Instant::now();
+    delay.reset(Duration::from_millis(20));
+    block_on(&mut delay);
+    assert!(start.elapsed() >= Duration::from_millis(20));
+    assert!(start.elapsed() < Duration::from_secs(3600));
+
+    // Pushing the deadline back after completion makes it pending again.
+    delay.reset(Duration::from_secs(3600));
+    assert_eq!(delay.poll_unpin(&mut noop_context()), Poll::Pending);
+}
+
+#[test]
+fn many_concurrent() {
+    let start = Instant::now();
+    let delays = (0..50).rev().map(|i| Delay::new(Duration::from_millis(i)));
+    block_on(future::join_all(delays));
+    assert!(start.elapsed() >= Duration::from_millis(49));
+}
+
+#[test]
+fn drop_cancels_wakeup() {
+    let clock = VirtualClock::new();
+    let _guard = clock.enter();
+    let mut delay = Delay::new(Duration::from_secs(60));
+    assert_eq!(delay.poll_unpin(&mut noop_context()), Poll::Pending);
+    assert_eq!(clock.next_deadline(), Some(delay.deadline()));
+
+    drop(delay);
+    assert_eq!(clock.next_deadline(), None);
+}
+
+#[test]
+fn reset_to_earlier_deadline_replaces_wakeup() {
+    let clock = VirtualClock::new();
+    let _guard = clock.enter();
+    let mut delay = Delay::new(Duration::from_secs(60));
+    assert_eq!(delay.poll_unpin(&mut noop_context()), Poll::Pending);
+
+    delay.reset(Duration::from_secs(10));
+    assert_eq!(clock.next_deadline(), Some(delay.deadline()));
+
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(delay.poll_unpin(&mut noop_context()), Poll::Ready(()));
+    assert_eq!(clock.next_deadline(), None);
+}
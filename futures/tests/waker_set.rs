@@ -0,0 +1,54 @@
+use futures::task::{self, WakerSet};
+use std::sync::{Arc, Mutex};
+
+fn recording_waker(woken: &Arc<Mutex<Vec<usize>>>, id: usize) -> task::Waker {
+    let woken = woken.clone();
+    task::waker_fn(move || woken.lock().unwrap().push(id))
+}
+
+#[test]
+fn wake_one_is_fifo() {
+    let woken = Arc::new(Mutex::new(Vec::new()));
+    let set = WakerSet::new();
+    for id in 0..3 {
+        set.insert(&recording_waker(&woken, id));
+    }
+    assert_eq!(set.len(), 3);
+
+    assert!(set.wake_one());
+    assert!(set.wake_one());
+    assert_eq!(*woken.lock().unwrap(), [0, 1]);
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn wake_all() {
+    let woken = Arc::new(Mutex::new(Vec::new()));
+    let set = WakerSet::new();
+    for id in 0..3 {
+        set.insert(&recording_waker(&woken, id));
+    }
+
+    assert_eq!(set.wake_all(), 3);
+    assert_eq!(*woken.lock().unwrap(), [0, 1, 2]);
+    assert!(set.is_empty());
+    assert_eq!(set.wake_all(), 0);
+    assert!(!set.wake_one());
+}
+
+#[test]
+fn update_and_remove() {
+    let woken = Arc::new(Mutex::new(Vec::new()));
+    let set = WakerSet::new();
+    let a = set.insert(&recording_waker(&woken, 0));
+    let b = set.insert(&recording_waker(&woken, 1));
+
+    assert!(set.update(a, &recording_waker(&woken, 2)));
+    assert!(set.remove(b));
+    assert!(!set.remove(b));
+
+    assert!(set.wake_one());
+    assert_eq!(*woken.lock().unwrap(), [2]);
+    assert!(!set.update(a, &recording_waker(&woken, 3)));
+    assert!(!set.remove(a));
+}
@@ -0,0 +1,24 @@
+use futures::executor::LocalPool;
+use futures::task::{yield_now, LocalSpawnExt};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn yield_now_interleaves_tasks() {
+    let mut pool = LocalPool::new();
+    let mut spawn = pool.spawner();
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    for task in 0..2 {
+        let events = events.clone();
+        spawn.spawn_local(async move {
+            for i in 0..2 {
+                events.borrow_mut().push((task, i));
+                yield_now().await;
+            }
+        }).unwrap();
+    }
+    pool.run();
+
+    assert_eq!(*events.borrow(), [(0, 0), (1, 0), (0, 1), (1, 1)]);
+}